@@ -2025,19 +2025,25 @@ fn suggest_using_local_if_applicable(
     ) {
         let used_in_call = matches!(
             explanation,
-            BorrowExplanation::UsedLater(LaterUseKind::Call | LaterUseKind::Other, _call_span, _)
+            BorrowExplanation::UsedLater(
+                LaterUseKind::Call | LaterUseKind::Other,
+                _call_span,
+                _,
+                _
+            )
         );
         if !used_in_call {
             debug!("not later used in call");
             return;
         }
 
-        let use_span =
-            if let BorrowExplanation::UsedLater(LaterUseKind::Other, use_span, _) = explanation {
-                Some(use_span)
-            } else {
-                None
-            };
+        let use_span = if let BorrowExplanation::UsedLater(LaterUseKind::Other, use_span, _, _) =
+            explanation
+        {
+            Some(use_span)
+        } else {
+            None
+        };
 
         let outer_call_loc =
             if let TwoPhaseActivation::ActivatedAt(loc) = issued_borrow.activation_location {
@@ -2888,7 +2894,7 @@ pub(crate) fn report_borrowed_value_does_not_live_long_enough(
             // and `move` will not help here.
             (
                 Some(name),
-                BorrowExplanation::UsedLater(LaterUseKind::ClosureCapture, var_or_use_span, _),
+                BorrowExplanation::UsedLater(LaterUseKind::ClosureCapture, var_or_use_span, _, _),
             ) if borrow_spans.for_coroutine() || borrow_spans.for_closure() => self
                 .report_escaping_closure_capture(
                     borrow_spans,
@@ -6,18 +6,20 @@
 use rustc_errors::{Applicability, Diag};
 use rustc_hir as hir;
 use rustc_hir::intravisit::Visitor;
+use rustc_index::bit_set::BitSet;
 use rustc_index::IndexSlice;
 use rustc_infer::infer::NllRegionVariableOrigin;
 use rustc_middle::middle::resolve_bound_vars::ObjectLifetimeDefault;
 use rustc_middle::mir::{
-    Body, CallSource, CastKind, ConstraintCategory, FakeReadCause, Local, LocalInfo, Location,
-    Operand, Place, Rvalue, Statement, StatementKind, TerminatorKind,
+    Body, CallSource, CastKind, ConstraintCategory, FakeReadCause, FalseEdgeKind, FalseEdgeOrigin,
+    Local, LocalInfo, Location, Operand, Place, Rvalue, Statement, StatementKind, TerminatorKind,
 };
 use rustc_middle::ty::adjustment::PointerCoercion;
 use rustc_middle::ty::{self, RegionVid, Ty, TyCtxt};
 use rustc_span::symbol::{kw, Symbol};
 use rustc_span::{sym, DesugaringKind, Span};
 use rustc_trait_selection::error_reporting::traits::FindExprBySpan;
+use std::collections::VecDeque;
 
 use crate::region_infer::{BlameConstraint, ExtraConstraintInfo};
 use crate::{
@@ -29,7 +31,7 @@
 
 #[derive(Debug)]
 pub(crate) enum BorrowExplanation<'tcx> {
-    UsedLater(LaterUseKind, Span, Option<Span>),
+    UsedLater(LaterUseKind, Span, Option<Span>, Option<FalseEdgeOrigin>),
     UsedLaterInLoop(LaterUseKind, Span, Option<Span>),
     UsedLaterWhenDropped {
         drop_loc: Location,
@@ -98,7 +100,12 @@ pub(crate) fn add_explanation_to_diagnostic(
             }
         }
         match *self {
-            BorrowExplanation::UsedLater(later_use_kind, var_or_use_span, path_span) => {
+            BorrowExplanation::UsedLater(
+                later_use_kind,
+                var_or_use_span,
+                path_span,
+                false_edge_origin,
+            ) => {
                 let message = match later_use_kind {
                     LaterUseKind::TraitCapture => "captured here by trait object",
                     LaterUseKind::ClosureCapture => "captured here by closure",
@@ -129,6 +136,24 @@ pub(crate) fn add_explanation_to_diagnostic(
                         err.span_label(path_span, path_label);
                     }
                 }
+                // The later use is only reachable through the imaginary edge of a `match` arm.
+                if let Some(FalseEdgeOrigin { arm_span: Some(arm_span), kind }) = false_edge_origin
+                {
+                    let note = match kind {
+                        FalseEdgeKind::BeforeBindings => {
+                            "the later arms of this `match` are checked as if they could run \
+                             after the pattern of this arm matched"
+                        }
+                        FalseEdgeKind::GuardFailed => {
+                            "the later arms of this `match` are checked as if they could run \
+                             after the guard of this arm failed"
+                        }
+                        FalseEdgeKind::FalseGuard => {
+                            "this arm is checked as if its `false` guard could succeed"
+                        }
+                    };
+                    err.span_note(arm_span, note);
+                }
             }
             BorrowExplanation::UsedLaterInLoop(later_use_kind, var_or_use_span, path_span) => {
                 let message = match later_use_kind {
@@ -471,7 +496,13 @@ pub(crate) fn explain_why_borrow_contains_point(
                     // message to the `FakeReadCause` it is from: in particular,
                     // the ones inserted in optimized `let var = <expr>` patterns.
                     let later_use = self.later_use_kind(borrow, spans, location);
-                    BorrowExplanation::UsedLater(later_use.0, later_use.1, later_use.2)
+                    let false_edge_origin = self.false_edge_to_later_use(use_location, location);
+                    BorrowExplanation::UsedLater(
+                        later_use.0,
+                        later_use.1,
+                        later_use.2,
+                        false_edge_origin,
+                    )
                 }
             }
 
@@ -519,6 +550,56 @@ pub(crate) fn explain_why_borrow_contains_point(
         }
     }
 
+    /// If `later_use` can only be reached from `location` by taking the imaginary target of a
+    /// `FalseEdge`, returns the origin of the first such edge on a path between them.
+    fn false_edge_to_later_use(
+        &self,
+        location: Location,
+        later_use: Location,
+    ) -> Option<FalseEdgeOrigin> {
+        let body = self.body;
+        if location.block == later_use.block
+            && location.statement_index <= later_use.statement_index
+        {
+            return None;
+        }
+
+        // The blocks reachable from `location` when `FalseEdge`s only go to their real target.
+        let mut reached = BitSet::new_empty(body.basic_blocks.len());
+        let mut imaginary_edges = Vec::new();
+        let mut stack = vec![location.block];
+        while let Some(block) = stack.pop() {
+            let terminator = body[block].terminator();
+            if let TerminatorKind::FalseEdge { real_target, imaginary_target, origin } =
+                terminator.kind
+            {
+                imaginary_edges.push((imaginary_target, origin));
+                if reached.insert(real_target) {
+                    stack.push(real_target);
+                }
+            } else {
+                stack.extend(terminator.successors().filter(|&succ| reached.insert(succ)));
+            }
+        }
+        if reached.contains(later_use.block) {
+            return None;
+        }
+
+        let mut queue: VecDeque<_> =
+            imaginary_edges.into_iter().filter(|&(target, _)| reached.insert(target)).collect();
+        while let Some((block, origin)) = queue.pop_front() {
+            if block == later_use.block {
+                return Some(origin);
+            }
+            for succ in body[block].terminator().successors() {
+                if reached.insert(succ) {
+                    queue.push_back((succ, origin));
+                }
+            }
+        }
+        None
+    }
+
     /// Determine how the borrow was later used.
     /// First span returned points to the location of the conflicting use
     /// Second span if `Some` is returned in the case of closures and points
@@ -446,6 +446,10 @@ pub struct BuiltinAttribute {
         move_size_limit, CrateLevel, template!(NameValueStr: "N"), ErrorFollowing,
         EncodeCrossCrate::No, large_assignments, experimental!(move_size_limit)
     ),
+    gated!(
+        match_size_limit, CrateLevel, template!(NameValueStr: "N"), ErrorFollowing,
+        EncodeCrossCrate::No, large_match_trees, experimental!(match_size_limit)
+    ),
 
     // Entry point:
     ungated!(start, Normal, template!(Word), WarnFollowing, EncodeCrossCrate::No),
@@ -585,6 +589,33 @@ pub struct BuiltinAttribute {
         EncodeCrossCrate::No, derive_smart_pointer, experimental!(pointee)
     ),
 
+    // `#[match_with_partial_eq]` on a constant to compare it with `PartialEq::eq` in patterns
+    gated!(
+        match_with_partial_eq, Normal, template!(Word), WarnFollowing,
+        EncodeCrossCrate::Yes, experimental!(match_with_partial_eq)
+    ),
+
+    // `#[loop_match]` on a `loop` over a `match` and `#[const_continue]` on a `break` in its arms
+    gated!(
+        loop_match, Normal, template!(Word), WarnFollowing,
+        EncodeCrossCrate::No, experimental!(loop_match)
+    ),
+    gated!(
+        const_continue, Normal, template!(Word), WarnFollowing,
+        EncodeCrossCrate::No, loop_match, experimental!(const_continue)
+    ),
+
+    // `#[constant_time_match]` on a function to lower its `match`es without branches
+    gated!(
+        constant_time_match, Normal, template!(Word), WarnFollowing,
+        EncodeCrossCrate::No, experimental!(constant_time_match)
+    ),
+    // `#[branchless_lazy_bool]` on a function to lower its `&&`s and `||`s without branches
+    gated!(
+        branchless_lazy_bool, Normal, template!(Word), WarnFollowing,
+        EncodeCrossCrate::No, experimental!(branchless_lazy_bool)
+    ),
+
     // RFC 3543
     // `#[patchable_function_entry(prefix_nops = m, entry_nops = n)]`
     gated!(
@@ -878,6 +909,12 @@ pub struct BuiltinAttribute {
         EncodeCrossCrate::Yes,
         "#[rustc_never_returns_null_ptr] is used to mark functions returning non-null pointers."
     ),
+    rustc_attr!(
+        rustc_match_guard_fake_borrows, Normal, template!(List: "shallow|deep"), ErrorFollowing,
+        EncodeCrossCrate::No,
+        "#[rustc_match_guard_fake_borrows] overrides how strongly match guards borrow the places \
+        they must not change, and can make matches unsound."
+    ),
     rustc_attr!(
         rustc_coherence_is_core, AttributeType::CrateLevel, template!(Word), ErrorFollowing, EncodeCrossCrate::No,
         "#![rustc_coherence_is_core] allows inherent methods on builtin types, only intended to be used in `core`."
@@ -1129,6 +1166,10 @@ pub struct BuiltinAttribute {
         TEST, rustc_dump_vtable, Normal, template!(Word),
         WarnFollowing, EncodeCrossCrate::Yes
     ),
+    rustc_attr!(
+        TEST, rustc_dump_decision_tree, Normal, template!(Word),
+        WarnFollowing, EncodeCrossCrate::No
+    ),
     rustc_attr!(
         TEST, rustc_dummy, Normal, template!(Word /* doesn't matter*/),
         DuplicatesOk, EncodeCrossCrate::No
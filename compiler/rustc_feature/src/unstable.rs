@@ -363,6 +363,11 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, async_fn_track_caller, "1.73.0", Some(110011)),
     /// Allows `for await` loops.
     (unstable, async_for_loop, "1.77.0", Some(118898)),
+    /// Allows `#[branchless_lazy_bool]` on functions, to evaluate both operands of their `&&`s and
+    /// `||`s without branching when these are cheap and have no side effects.
+    (unstable, branchless_lazy_bool, "CURRENT_RUSTC_VERSION", None),
+    /// Allows byte string literal patterns to match on `[u8; N]` scrutinees.
+    (unstable, byte_string_array_patterns, "CURRENT_RUSTC_VERSION", None),
     /// Allows using C-variadics.
     (unstable, c_variadic, "1.34.0", Some(44930)),
     /// Allows the use of `#[cfg(overflow_checks)` to check if integer overflow behaviour.
@@ -393,6 +398,8 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, closure_track_caller, "1.57.0", Some(87417)),
     /// Allows to use the `#[cmse_nonsecure_entry]` attribute.
     (unstable, cmse_nonsecure_entry, "1.48.0", Some(75835)),
+    /// Allows `#[cold]` on match arms, to move them off the hot path of the match.
+    (unstable, cold_match_arms, "CURRENT_RUSTC_VERSION", None),
     /// Allows `async {}` expressions in const contexts.
     (unstable, const_async_blocks, "1.53.0", Some(85368)),
     /// Allows `const || {}` closures in const contexts.
@@ -415,6 +422,9 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, const_trait_impl, "1.42.0", Some(67792)),
     /// Allows the `?` operator in const contexts.
     (unstable, const_try, "1.56.0", Some(74935)),
+    /// Allows `#[constant_time_match]` on functions, to lower their `match`es without branching on
+    /// the value they match.
+    (incomplete, constant_time_match, "CURRENT_RUSTC_VERSION", None),
     /// Allows coroutines to be cloned.
     (unstable, coroutine_clone, "1.65.0", Some(95360)),
     /// Allows defining coroutines.
@@ -505,6 +515,8 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, intra_doc_pointers, "1.51.0", Some(80896)),
     // Allows setting the threshold for the `large_assignments` lint.
     (unstable, large_assignments, "1.52.0", Some(83518)),
+    /// Allows setting the threshold for the `large_match_trees` lint.
+    (unstable, large_match_trees, "CURRENT_RUSTC_VERSION", None),
     /// Allow to have type alias types for inter-crate use.
     (incomplete, lazy_type_alias, "1.72.0", Some(112792)),
     /// Allows `if/while p && let q = r && ...` chains.
@@ -512,12 +524,18 @@ pub fn internal(&self, feature: Symbol) -> bool {
     /// Allows using `#[link(kind = "link-arg", name = "...")]`
     /// to pass custom arguments to the linker.
     (unstable, link_arg_attribute, "1.76.0", Some(99427)),
+    /// Allows `#[loop_match]` loops over a `match` on a state, in which `#[const_continue]` jumps
+    /// directly to the arm of the next state.
+    (incomplete, loop_match, "CURRENT_RUSTC_VERSION", None),
     /// Give access to additional metadata about declarative macro meta-variables.
     (unstable, macro_metavar_expr, "1.61.0", Some(83527)),
     /// Provides a way to concatenate identifiers using metavariable expressions.
     (unstable, macro_metavar_expr_concat, "CURRENT_RUSTC_VERSION", Some(124225)),
     /// Allows `#[marker]` on certain traits allowing overlapping implementations.
     (unstable, marker_trait_attr, "1.30.0", Some(29864)),
+    /// Allows `#[match_with_partial_eq]` on constants, to use them in patterns even if their
+    /// type does not derive `PartialEq`, comparing them with `PartialEq::eq`.
+    (unstable, match_with_partial_eq, "CURRENT_RUSTC_VERSION", None),
     /// Allows exhaustive pattern matching on types that contain uninhabited types in cases that are
     /// unambiguously sound.
     (unstable, min_exhaustive_patterns, "1.77.0", Some(119612)),
@@ -565,6 +583,9 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, optimize_attribute, "1.34.0", Some(54882)),
     /// Allows specifying nop padding on functions for dynamic patching.
     (unstable, patchable_function_entry, "CURRENT_RUSTC_VERSION", Some(123115)),
+    /// Allows matching raw pointers and function pointers against constants that are not
+    /// derived from integers, by comparing their addresses.
+    (unstable, pointer_const_patterns, "CURRENT_RUSTC_VERSION", None),
     /// Allows postfix match `expr.match { ... }`
     (unstable, postfix_match, "1.79.0", Some(121618)),
     /// Allows `use<'a, 'b, A, B>` in `impl Trait + use<...>` for precise capture of generic args.
@@ -868,6 +868,14 @@ fn walk_irrefutable_pat(
         Ok(())
     }
 
+    /// Whether `pat` is a deref pattern on the `Box` at `place`. MIR building derefs the box
+    /// itself for it, like for a `box` pattern, instead of calling `deref()` and binding through
+    /// the temporary holding the result, so its bindings can move out of the box.
+    fn is_box_deref_pat(&self, place: &PlaceWithHirId<'tcx>, pat: &hir::Pat<'_>) -> bool {
+        matches!(pat.kind, PatKind::Deref(_))
+            && self.cx.try_structurally_resolve_type(pat.span, place.place.ty()).is_box()
+    }
+
     /// The core driver for walking a pattern
     fn walk_pat(
         &self,
@@ -917,7 +925,9 @@ fn walk_pat(
                         }
                     }
                 }
-            } else if let PatKind::Deref(subpattern) = pat.kind {
+            } else if let PatKind::Deref(subpattern) = pat.kind
+                && !self.is_box_deref_pat(place, pat)
+            {
                 // A deref pattern is a bit special: the binding mode of its inner bindings
                 // determines whether to borrow *at the level of the deref pattern* rather than
                 // borrowing the bound place (since that inner place is inside the temporary that
@@ -1729,6 +1739,10 @@ fn cat_pattern<F>(
                 let subplace = self.cat_deref(pat.hir_id, place_with_id)?;
                 self.cat_pattern(subplace, subpat, op)?;
             }
+            PatKind::Deref(subpat) if self.is_box_deref_pat(&place_with_id, pat) => {
+                let subplace = self.cat_deref(pat.hir_id, place_with_id)?;
+                self.cat_pattern(subplace, subpat, op)?;
+            }
             PatKind::Deref(subpat) => {
                 let mutable = self.cx.typeck_results().pat_has_ref_mut_binding(subpat);
                 let mutability = if mutable { hir::Mutability::Mut } else { hir::Mutability::Not };
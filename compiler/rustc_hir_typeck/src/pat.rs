@@ -485,6 +485,19 @@ fn check_pat_lit(
                     .insert(lt.hir_id.local_id);
                 pat_ty =
                     Ty::new_imm_ref(tcx, tcx.lifetimes.re_static, Ty::new_slice(tcx, tcx.types.u8));
+            } else if self.tcx.features().byte_string_array_patterns
+                && let ty::Array(elem_ty, _) = *expected.kind()
+                && elem_ty == self.tcx.types.u8
+                && let ty::Ref(_, array_ty, _) = *ty.kind()
+            {
+                // Match the bytes of the array in place, rather than by comparing a reference to
+                // it with the literal.
+                trace!(?lt.hir_id.local_id, "byte string lit matching an array");
+                self.typeck_results
+                    .borrow_mut()
+                    .treat_byte_string_as_array
+                    .insert(lt.hir_id.local_id);
+                pat_ty = array_ty;
             }
         }
 
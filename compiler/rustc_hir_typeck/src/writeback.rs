@@ -82,6 +82,8 @@ pub fn resolve_type_vars_in_body(
 
         wbcx.typeck_results.treat_byte_string_as_slice =
             mem::take(&mut self.typeck_results.borrow_mut().treat_byte_string_as_slice);
+        wbcx.typeck_results.treat_byte_string_as_array =
+            mem::take(&mut self.typeck_results.borrow_mut().treat_byte_string_as_array);
 
         debug!("writeback: typeck results for {:?} are {:#?}", item_def_id, wbcx.typeck_results);
 
@@ -12,8 +12,8 @@
     InstrumentCoverage, InstrumentXRay, LinkSelfContained, LinkerPluginLto,
 };
 use rustc_session::config::{
-    LocationDetail, LtoCli, NextSolverConfig, OomStrategy, Options, OutFileName, OutputType,
-    OutputTypes, PAuthKey, PacRet, Passes, PatchableFunctionEntry,
+    LocationDetail, LtoCli, MatchLowering, MatchOtherwise, NextSolverConfig, OomStrategy, Options,
+    OutFileName, OutputType, OutputTypes, PAuthKey, PacRet, Passes, PatchableFunctionEntry,
 };
 use rustc_session::config::{
     Polonius, ProcMacroExecutionStrategy, Strip, SwitchWithOptPath, SymbolManglingVersion,
@@ -800,8 +800,20 @@ macro_rules! tracked {
     tracked!(llvm_module_flag, vec![("bar".to_string(), 123, "max".to_string())]);
     tracked!(llvm_plugins, vec![String::from("plugin_name")]);
     tracked!(location_detail, LocationDetail { file: true, line: false, column: false });
+    tracked!(match_binary_search, Some(32));
+    tracked!(match_elide_moved_binding_storage, true);
+    tracked!(match_lowering, MatchLowering::DecisionTree);
+    tracked!(match_lowering_budget, 8);
+    tracked!(match_niche_compare, true);
+    tracked!(match_otherwise, MatchOtherwise::Trap);
+    tracked!(match_pack_bools, true);
+    tracked!(match_perfect_hash, Some(16));
+    tracked!(match_range_switch, true);
+    tracked!(match_repr_switch, true);
+    tracked!(match_size_limit, Some(256));
     tracked!(maximal_hir_to_mir_coverage, true);
     tracked!(merge_functions, Some(MergeFunctions::Disabled));
+    tracked!(mir_emit_fake_borrow_markers, true);
     tracked!(mir_emit_retag, true);
     tracked!(mir_enable_passes, vec![("DestProp".to_string(), false)]);
     tracked!(mir_keep_place_mention, true);
@@ -850,6 +862,7 @@ macro_rules! tracked {
     tracked!(split_lto_unit, Some(true));
     tracked!(src_hash_algorithm, Some(SourceFileHashAlgorithm::Sha1));
     tracked!(stack_protector, StackProtector::All);
+    tracked!(switchint_threshold, Some(4));
     tracked!(teach, true);
     tracked!(thinlto, Some(true));
     tracked!(tiny_const_eval_limit, true);
@@ -40,6 +40,7 @@
         DEPRECATED_SAFE,
         DEPRECATED_WHERE_CLAUSE_LOCATION,
         DUPLICATE_MACRO_ATTRIBUTES,
+        DUPLICATED_GUARD_SIDE_EFFECTS,
         ELIDED_LIFETIMES_IN_ASSOCIATED_CONSTANT,
         ELIDED_LIFETIMES_IN_PATHS,
         EXPORTED_PRIVATE_DEPENDENCIES,
@@ -57,6 +58,7 @@
         INVALID_TYPE_PARAM_DEFAULT,
         IRREFUTABLE_LET_PATTERNS,
         LARGE_ASSIGNMENTS,
+        LARGE_MATCH_TREES,
         LATE_BOUND_LIFETIME_ARGUMENTS,
         LEGACY_DERIVE_HELPERS,
         LONG_RUNNING_CONST_EVAL,
@@ -3179,6 +3181,40 @@
     "detects large moves or copies",
 }
 
+declare_lint! {
+    /// The `large_match_trees` lint detects `match` expressions and other patterns whose decision
+    /// tree, the code that tests the value against the patterns, has more basic blocks than the
+    /// limit set with `#![match_size_limit = "N"]` or `-Zmatch-size-limit=N`.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore (needs a size limit)
+    /// #![feature(large_match_trees)]
+    /// #![match_size_limit = "100"]
+    ///
+    /// fn f(x: (u8, u8, u8, u8)) -> bool {
+    ///     matches!(x, (1 | 2 | 3, 1 | 2 | 3, 1 | 2 | 3, 1 | 2 | 3))
+    /// }
+    /// ```
+    ///
+    /// produces:
+    ///
+    /// ```text
+    /// warning: the decision tree of this pattern is larger than the limit
+    /// ```
+    ///
+    /// ### Explanation
+    ///
+    /// Or-patterns that are followed by other patterns, and slice patterns with several lengths,
+    /// may have the tests of the patterns after them duplicated, which can make the code of a
+    /// `match` grow much larger than its source, in particular when it is generated by a macro.
+    /// The lint points at the or-patterns and slice patterns of the decision tree, so that they
+    /// can be reordered or split into several `match` expressions.
+    pub LARGE_MATCH_TREES,
+    Warn,
+    "detects `match` expressions whose decision tree is larger than the configured limit",
+}
+
 declare_lint! {
     /// The `deprecated_cfg_attr_crate_type_name` lint detects uses of the
     /// `#![cfg_attr(..., crate_type = "...")]` and
@@ -5028,3 +5064,39 @@
         reference: "issue #124535 <https://github.com/rust-lang/rust/issues/124535>",
     };
 }
+
+declare_lint! {
+    /// The `duplicated_guard_side_effects` lint detects match guards with side effects on arms
+    /// whose or-pattern has several alternatives, for which the guard is evaluated separately.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,compile_fail
+    /// #![deny(duplicated_guard_side_effects)]
+    /// fn check(x: u8) -> bool {
+    ///     println!("checking {x}");
+    ///     x > 1
+    /// }
+    ///
+    /// match (1, 2) {
+    ///     (x, _) | (_, x) if check(x) => {}
+    ///     _ => {}
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// A guard runs after the pattern of its arm has matched. When the pattern is an or-pattern
+    /// and the guard fails, the next alternatives of the pattern are tried, and the guard runs
+    /// again for each of them that matches. The guard above prints twice: once for `x == 1`,
+    /// and once for `x == 2`. This is easy to miss when the arm is generated by a macro, and
+    /// side effects such as I/O or counters may then happen more often than intended.
+    ///
+    /// This lint is "allow" by default because running the guard once per alternative is the
+    /// intended behavior of many guards, which only call functions to inspect the bindings.
+    pub DUPLICATED_GUARD_SIDE_EFFECTS,
+    Allow,
+    "detects match guards with side effects that may run once per alternative of an or-pattern"
+}
@@ -1,7 +1,8 @@
 //! Registering limits:
 //! * recursion_limit,
-//! * move_size_limit, and
-//! * type_length_limit
+//! * move_size_limit,
+//! * type_length_limit, and
+//! * match_size_limit
 //!
 //! There are various parts of the compiler that must impose arbitrary limits
 //! on how deeply they recurse to prevent stack overflow. Users can override
@@ -32,6 +33,12 @@ pub fn provide(providers: &mut Providers) {
             sym::type_length_limit,
             2usize.pow(24),
         ),
+        match_size_limit: get_limit(
+            tcx.hir().krate_attrs(),
+            tcx.sess,
+            sym::match_size_limit,
+            tcx.sess.opts.unstable_opts.match_size_limit.unwrap_or(0),
+        ),
     }
 }
 
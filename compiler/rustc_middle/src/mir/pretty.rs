@@ -1395,6 +1395,20 @@ fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
             }
         }
     }
+
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        self.super_terminator(terminator, location);
+        if let TerminatorKind::FalseEdge { origin, .. } = terminator.kind {
+            self.push("false edge");
+            self.push(&format!("+ kind: {:?}", origin.kind));
+            if let Some(arm_span) = origin.arm_span {
+                self.push(&format!(
+                    "+ arm: {}",
+                    self.tcx.sess.source_map().span_to_embeddable_string(arm_span)
+                ));
+            }
+        }
+    }
 }
 
 fn comment(tcx: TyCtxt<'_>, SourceInfo { span, scope }: SourceInfo) -> String {
@@ -840,6 +840,8 @@ pub enum TerminatorKind<'tcx> {
         /// A block control flow could conceptually jump to, but won't in
         /// practice.
         imaginary_target: BasicBlock,
+        /// The candidate of the `match` or `let` whose lowering created this edge.
+        origin: FalseEdgeOrigin,
     },
 
     /// A terminator for blocks that only take one path in reality, but where we reserve the right
@@ -911,6 +913,29 @@ pub const fn name(&self) -> &'static str {
     }
 }
 
+/// Where the lowering of a `match` or `let` created a [`TerminatorKind::FalseEdge`], so that
+/// diagnostics can point at the arm it comes from, and MIR dumps can say what the edge is for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, TyEncodable, TyDecodable, Hash, HashStable)]
+pub struct FalseEdgeOrigin {
+    /// The span of the `match` arm of the candidate, or `None` if the candidate is the pattern of
+    /// a `let` expression or let-else statement.
+    pub arm_span: Option<Span>,
+    pub kind: FalseEdgeKind,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, TyEncodable, TyDecodable, Hash, HashStable)]
+pub enum FalseEdgeKind {
+    /// The edge leaves a candidate that matched, before its bindings are established, towards the
+    /// next candidate. It keeps borrowck from relying on which patterns later candidates test.
+    BeforeBindings,
+    /// The edge leaves a candidate whose guard failed towards the next candidate, which the real
+    /// edge may skip if the tests already done rule it out.
+    GuardFailed,
+    /// The edge enters the arm of a candidate whose guard is the constant `false`, which is not
+    /// evaluated. It keeps borrowck checking that arm like the others.
+    FalseGuard,
+}
+
 #[derive(Debug, Clone, TyEncodable, TyDecodable, Hash, HashStable, PartialEq)]
 pub struct SwitchTargets {
     /// Possible values. The locations to branch to in each case
@@ -446,7 +446,7 @@ pub fn successors(&self) -> Successors<'_> {
                 }
                 InlineAsm { ref targets, unwind: _, .. } => targets.iter().copied().chain(None),
                 SwitchInt { ref targets, .. } => targets.targets.iter().copied().chain(None),
-                FalseEdge { ref real_target, imaginary_target } => {
+                FalseEdge { ref real_target, imaginary_target, origin: _ } => {
                     slice::from_ref(real_target).into_iter().copied().chain(Some(imaginary_target))
                 }
             }
@@ -487,7 +487,7 @@ pub fn successors_mut(&mut self) -> SuccessorsMut<'_> {
                 }
                 InlineAsm { ref mut targets, unwind: _, .. } => targets.iter_mut().chain(None),
                 SwitchInt { ref mut targets, .. } => targets.targets.iter_mut().chain(None),
-                FalseEdge { ref mut real_target, ref mut imaginary_target } => {
+                FalseEdge { ref mut real_target, ref mut imaginary_target, origin: _ } => {
                     slice::from_mut(real_target).into_iter().chain(Some(imaginary_target))
                 }
             }
@@ -628,7 +628,7 @@ pub fn edges(&self) -> TerminatorEdges<'_, 'tcx> {
                 }
             },
 
-            FalseEdge { real_target, imaginary_target } => {
+            FalseEdge { real_target, imaginary_target, origin: _ } => {
                 TerminatorEdges::Double(real_target, imaginary_target)
             }
 
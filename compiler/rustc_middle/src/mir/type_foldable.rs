@@ -10,6 +10,7 @@
     MirPhase,
     SourceInfo,
     FakeReadCause,
+    FalseEdgeOrigin,
     RetagKind,
     SourceScope,
     SourceScopeLocalData,
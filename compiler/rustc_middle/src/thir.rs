@@ -355,6 +355,17 @@ pub enum ExprKind<'tcx> {
         expr: ExprId,
         pat: Box<Pat<'tcx>>,
     },
+    /// A `#[loop_match]` loop, `loop { state = 'blk: { match state { arms } } }`, whose
+    /// `#[const_continue]` breaks to `'blk` may jump directly to the arm of their value.
+    LoopMatch {
+        /// The `state` place, which is both the scrutinee of the `match` and assigned by it.
+        state: ExprId,
+        /// The scope of the labeled block `'blk`.
+        region_scope: region::Scope,
+        arms: Box<[ArmId]>,
+        /// The span of the `match` expression.
+        match_span: Span,
+    },
     /// A `match` expression.
     Match {
         scrutinee: ExprId,
@@ -421,6 +432,11 @@ pub enum ExprKind<'tcx> {
     Continue {
         label: region::Scope,
     },
+    /// A `#[const_continue] break 'blk value` out of the arms of a `#[loop_match]`.
+    ConstContinue {
+        label: region::Scope,
+        value: ExprId,
+    },
     /// A `return` expression.
     Return {
         value: Option<ExprId>,
@@ -538,7 +554,7 @@ pub struct Arm<'tcx> {
     pub span: Span,
 }
 
-#[derive(Copy, Clone, Debug, HashStable)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, HashStable)]
 pub enum LogicalOp {
     /// The `&&` operator.
     And,
@@ -859,10 +875,53 @@ pub struct PatRange<'tcx> {
 }
 
 impl<'tcx> PatRange<'tcx> {
-    /// Whether this range covers the full extent of possible values (best-effort, we ignore floats).
+    /// Whether this range covers the full extent of possible values (best-effort, we ignore floats
+    /// and ends that depend on generic parameters).
     #[inline]
     pub fn is_full_range(&self, tcx: TyCtxt<'tcx>) -> Option<bool> {
-        let (min, max, size, bias) = match *self.ty.kind() {
+        // For performance, it's important to only do the second `try_to_bits` if necessary.
+        Some(self.lo_is_min(tcx)? && self.hi_is_max(tcx)?)
+    }
+
+    /// Whether the lower end of this range is the smallest value of its type, so that every value
+    /// is at least the lower end (best-effort, we ignore floats).
+    #[inline]
+    pub fn lo_is_min(&self, tcx: TyCtxt<'tcx>) -> Option<bool> {
+        let (min, _, size, bias) = self.int_bounds(tcx)?;
+        Some(match self.lo {
+            PatRangeBoundary::NegInfinity => true,
+            PatRangeBoundary::Finite(value) => {
+                let lo = value.try_to_bits(size)? ^ bias;
+                lo <= min
+            }
+            PatRangeBoundary::PosInfinity => false,
+        })
+    }
+
+    /// Whether the upper end of this range is the largest value of its type and is included, so
+    /// that every value is at most the upper end (best-effort, we ignore floats).
+    #[inline]
+    pub fn hi_is_max(&self, tcx: TyCtxt<'tcx>) -> Option<bool> {
+        let (_, max, size, bias) = self.int_bounds(tcx)?;
+        Some(match self.hi {
+            PatRangeBoundary::NegInfinity => false,
+            PatRangeBoundary::Finite(value) => {
+                let hi = value.try_to_bits(size)? ^ bias;
+                hi > max || hi == max && self.end == RangeEnd::Included
+            }
+            PatRangeBoundary::PosInfinity => true,
+        })
+    }
+
+    /// The smallest and largest values of the integer type of this range, its size, and the bias
+    /// to XOR its values with to compare them as unsigned integers.
+    fn int_bounds(&self, tcx: TyCtxt<'tcx>) -> Option<(u128, u128, Size, u128)> {
+        // We want to compare ranges numerically, but the order of the bitwise representation of
+        // signed integers does not match their numeric order. Thus, to correct the ordering, we
+        // need to shift the range of signed integers to correct the comparison. This is achieved by
+        // XORing with a bias (see pattern/deconstruct_pat.rs for another pertinent example of this
+        // pattern).
+        Some(match *self.ty.kind() {
             ty::Char => (0, std::char::MAX as u128, Size::from_bits(32), 0),
             ty::Int(ity) => {
                 let size = Integer::from_int_ty(&tcx, ity).size();
@@ -876,37 +935,7 @@ pub fn is_full_range(&self, tcx: TyCtxt<'tcx>) -> Option<bool> {
                 (0, max, size, 0)
             }
             _ => return None,
-        };
-
-        // We want to compare ranges numerically, but the order of the bitwise representation of
-        // signed integers does not match their numeric order. Thus, to correct the ordering, we
-        // need to shift the range of signed integers to correct the comparison. This is achieved by
-        // XORing with a bias (see pattern/deconstruct_pat.rs for another pertinent example of this
-        // pattern).
-        //
-        // Also, for performance, it's important to only do the second `try_to_bits` if necessary.
-        let lo_is_min = match self.lo {
-            PatRangeBoundary::NegInfinity => true,
-            PatRangeBoundary::Finite(value) => {
-                let lo = value.try_to_bits(size).unwrap() ^ bias;
-                lo <= min
-            }
-            PatRangeBoundary::PosInfinity => false,
-        };
-        if lo_is_min {
-            let hi_is_max = match self.hi {
-                PatRangeBoundary::NegInfinity => false,
-                PatRangeBoundary::Finite(value) => {
-                    let hi = value.try_to_bits(size).unwrap() ^ bias;
-                    hi > max || hi == max && self.end == RangeEnd::Included
-                }
-                PatRangeBoundary::PosInfinity => true,
-            };
-            if hi_is_max {
-                return Some(true);
-            }
-        }
-        Some(false)
+        })
     }
 
     #[inline]
@@ -995,8 +1024,19 @@ pub fn as_finite(self) -> Option<mir::Const<'tcx>> {
         }
     }
     pub fn eval_bits(self, ty: Ty<'tcx>, tcx: TyCtxt<'tcx>, param_env: ty::ParamEnv<'tcx>) -> u128 {
-        match self {
-            Self::Finite(value) => value.eval_bits(tcx, param_env),
+        self.try_eval_bits(ty, tcx, param_env)
+            .unwrap_or_else(|| bug!("expected bits of {self:?}, got generic constant"))
+    }
+    /// Like [`Self::eval_bits`], but returns `None` for a boundary that depends on generic
+    /// parameters, and so can't be evaluated before monomorphization.
+    pub fn try_eval_bits(
+        self,
+        ty: Ty<'tcx>,
+        tcx: TyCtxt<'tcx>,
+        param_env: ty::ParamEnv<'tcx>,
+    ) -> Option<u128> {
+        Some(match self {
+            Self::Finite(value) => value.try_eval_bits(tcx, param_env)?,
             Self::NegInfinity => {
                 // Unwrap is ok because the type is known to be numeric.
                 ty.numeric_min_and_max_as_bits(tcx).unwrap().0
@@ -1005,7 +1045,7 @@ pub fn eval_bits(self, ty: Ty<'tcx>, tcx: TyCtxt<'tcx>, param_env: ty::ParamEnv<
                 // Unwrap is ok because the type is known to be numeric.
                 ty.numeric_min_and_max_as_bits(tcx).unwrap().1
             }
-        }
+        })
     }
 
     #[instrument(skip(tcx, param_env), level = "debug", ret)]
@@ -1043,8 +1083,8 @@ pub fn compare_with(
             _ => {}
         }
 
-        let a = self.eval_bits(ty, tcx, param_env);
-        let b = other.eval_bits(ty, tcx, param_env);
+        let a = self.try_eval_bits(ty, tcx, param_env)?;
+        let b = other.try_eval_bits(ty, tcx, param_env)?;
 
         match ty.kind() {
             ty::Float(ty::FloatTy::F16) => {
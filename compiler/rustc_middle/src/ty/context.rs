@@ -2065,6 +2065,10 @@ pub fn move_size_limit(self) -> Limit {
         self.limits(()).move_size_limit
     }
 
+    pub fn match_size_limit(self) -> Limit {
+        self.limits(()).match_size_limit
+    }
+
     pub fn all_traits(self) -> impl Iterator<Item = DefId> + 'tcx {
         iter::once(LOCAL_CRATE)
             .chain(self.crates(()).iter().copied())
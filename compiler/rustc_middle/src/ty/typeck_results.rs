@@ -211,6 +211,10 @@ pub struct TypeckResults<'tcx> {
     /// like this to allow `const_to_pat` to reliably handle this situation.
     pub treat_byte_string_as_slice: ItemLocalSet,
 
+    /// Likewise, with the `byte_string_array_patterns` feature, byte string literals matched
+    /// against a `[u8; N]` are treated as `[u8; N]` instead of `&[u8; N]`.
+    pub treat_byte_string_as_array: ItemLocalSet,
+
     /// Contains the data for evaluating the effect of feature `capture_disjoint_fields`
     /// on closure size.
     pub closure_size_eval: LocalDefIdMap<ClosureSizeProfileData<'tcx>>,
@@ -247,6 +251,7 @@ pub fn new(hir_owner: OwnerId) -> TypeckResults<'tcx> {
             rvalue_scopes: Default::default(),
             coroutine_stalled_predicates: Default::default(),
             treat_byte_string_as_slice: Default::default(),
+            treat_byte_string_as_array: Default::default(),
             closure_size_eval: Default::default(),
             offset_of_data: Default::default(),
         }
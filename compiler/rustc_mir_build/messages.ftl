@@ -86,11 +86,24 @@ mir_build_call_to_unsafe_fn_requires_unsafe_unsafe_op_in_unsafe_fn_allowed =
 
 mir_build_confused = missing patterns are not covered because `{$variable}` is interpreted as a constant pattern, not a new variable
 
+mir_build_const_continue_bad_label =
+    `#[const_continue]` must break to the labeled block of a `#[loop_match]`
+    .label = not the labeled block of an enclosing `#[loop_match]`
+
+mir_build_const_continue_not_const =
+    the value of a `#[const_continue]` must be a constant
+    .label = not a literal, constant or unit variant
+
 mir_build_const_param_in_pattern = const parameters cannot be referenced in patterns
 
 mir_build_const_pattern_depends_on_generic_parameter =
     constant pattern depends on a generic parameter
 
+mir_build_constant_time_match_unsupported =
+    this `match` can't be lowered without branching
+    .label = this needs a branch
+    .note = in a `#[constant_time_match]` function, the arms of a `match` must have no bindings nor guards, match `bool`s, `char`s and integers with constants and ranges, and evaluate to constant integers or `bool`s
+
 mir_build_could_not_eval_const_pattern = could not evaluate constant pattern
 
 mir_build_deref_raw_pointer_requires_unsafe =
@@ -103,6 +116,10 @@ mir_build_deref_raw_pointer_requires_unsafe_unsafe_op_in_unsafe_fn_allowed =
     .note = raw pointers may be null, dangling or unaligned; they can violate aliasing rules and cause data races: all of these are undefined behavior
     .label = dereference of raw pointer
 
+mir_build_duplicated_guard_side_effects = guard with side effects is evaluated separately for each alternative of the pattern
+    .label = this guard calls functions or assigns to places
+    .pattern_label = if the guard fails, it runs again for the next of these {$alternatives} alternatives that matches
+
 mir_build_exceeds_mcdc_condition_limit = number of conditions in decision ({$num_conditions}) exceeds limit ({$max_conditions}), so MC/DC analysis will not count this expression
 
 mir_build_extern_static_requires_unsafe =
@@ -115,6 +132,13 @@ mir_build_extern_static_requires_unsafe_unsafe_op_in_unsafe_fn_allowed =
     .note = extern statics are not controlled by the Rust type system: invalid data, aliasing violations or data races will cause undefined behavior
     .label = use of extern static
 
+mir_build_guard_fake_borrows_invalid_kind = invalid argument to `#[rustc_match_guard_fake_borrows]`
+    .help = expected `shallow` or `deep`
+
+mir_build_guard_fake_borrows_unsound_shallow = match guards only shallowly borrow the place this deref pattern looks through
+    .label = a guard may change the value behind this pointer
+    .note = `#[rustc_match_guard_fake_borrows(shallow)]` lets a guard change the value that this pattern was tested against, after which the arms below it may be reached with a value the match has ruled out, which is undefined behavior
+
 mir_build_inform_irrefutable = `let` bindings require an "irrefutable pattern", like a `struct` or an `enum` with only one variant
 
 mir_build_initializing_type_with_requires_unsafe =
@@ -181,6 +205,11 @@ mir_build_irrefutable_let_patterns_while_let = irrefutable `while let` {$count -
     } will always match, so the loop will never exit
     .help = consider instead using a `loop {"{"} ... {"}"}` with a `let` inside it
 
+mir_build_large_match_tree = the decision tree of this pattern is larger than the limit
+    .label = the value is tested with {$tests} tests in {$blocks} basic blocks
+    .pattern_label = this pattern multiplies the tests of the patterns after it
+    .note = the limit is {$limit} basic blocks, set with `#![match_size_limit]` or `-Zmatch-size-limit`
+
 mir_build_leading_irrefutable_let_patterns = leading irrefutable {$count ->
         [one] pattern
         *[other] patterns
@@ -198,6 +227,9 @@ mir_build_literal_in_range_out_of_bounds =
     literal out of range for `{$ty}`
     .label = this value does not fit into the type `{$ty}` whose range is `{$min}..={$max}`
 
+mir_build_loop_match_invalid_shape = invalid `#[loop_match]` loop
+    .note = the body of a `#[loop_match]` loop must assign to a variable a labeled block whose only content is a `match` on that variable
+
 mir_build_lower_range_bound_must_be_less_than_or_equal_to_upper =
     lower range bound must be less than or equal to upper
     .label = lower bound larger than upper bound
@@ -289,6 +321,19 @@ mir_build_suggest_let_else = you might want to use `let else` to handle the {$co
         *[other] variants that aren't
     } matched
 
+mir_build_tail_call_mismatched_signature = mismatched signatures
+    .label = `become` requires caller and callee to have matching signatures
+    .note = caller signature: `{$caller}`, callee signature: `{$callee}`
+
+mir_build_tail_call_not_call = `become` requires a function call
+    .label = not a function call
+
+mir_build_tail_call_rust_call = tail calls of closures and other `Fn*` trait implementations are not supported
+    .label = called through an `Fn*` trait
+
+mir_build_tail_call_track_caller = a function marked with `#[track_caller]` cannot be tail-called
+    .label = call of a `#[track_caller]` function
+
 mir_build_trailing_irrefutable_let_patterns = trailing irrefutable {$count ->
         [one] pattern
         *[other] patterns
@@ -85,7 +85,10 @@ fn as_temp_inner(
         match expr.kind {
             // Don't bother with StorageLive and Dead for these temporaries,
             // they are never assigned.
-            ExprKind::Break { .. } | ExprKind::Continue { .. } | ExprKind::Return { .. } => (),
+            ExprKind::Break { .. }
+            | ExprKind::Continue { .. }
+            | ExprKind::ConstContinue { .. }
+            | ExprKind::Return { .. } => (),
             ExprKind::Block { block }
                 if let Block { expr: None, targeted_by_break: false, .. } = this.thir[block]
                     && expr_ty.is_never() => {}
@@ -52,6 +52,19 @@ pub(crate) fn expr_into_dest(
             ExprKind::Block { block: ast_block } => {
                 this.ast_block(destination, block, ast_block, source_info)
             }
+            ExprKind::Match {
+                scrutinee,
+                ref arms,
+                match_source: hir::MatchSource::ForLoopDesugar | hir::MatchSource::TryDesugar(_),
+                ..
+            } => this.desugared_match(
+                destination,
+                block,
+                scrutinee,
+                arms,
+                expr_span,
+                this.thir[scrutinee].span,
+            ),
             ExprKind::Match { scrutinee, ref arms, .. } => this.match_expr(
                 destination,
                 block,
@@ -60,6 +73,9 @@ pub(crate) fn expr_into_dest(
                 expr_span,
                 this.thir[scrutinee].span,
             ),
+            ExprKind::If { .. } if let Some(chain) = this.equality_if_chain(expr_id) => {
+                this.switch_if_chain(destination, block, expr_id, chain)
+            }
             ExprKind::If { cond, then, else_opt, if_then_scope } => {
                 let then_span = this.thir[then].span;
                 let then_source_info = this.source_info(then_span);
@@ -148,6 +164,21 @@ pub(crate) fn expr_into_dest(
                     end_block.unit()
                 }
             }
+            ExprKind::LogicalOp { op, lhs, rhs } if this.lazy_bool_is_branchless(lhs, rhs) => {
+                let lhs = unpack!(block = this.as_local_operand(block, lhs));
+                let rhs = unpack!(block = this.as_local_operand(block, rhs));
+                let op = match op {
+                    LogicalOp::And => BinOp::BitAnd,
+                    LogicalOp::Or => BinOp::BitOr,
+                };
+                this.cfg.push_assign(
+                    block,
+                    source_info,
+                    destination,
+                    Rvalue::BinaryOp(op, Box::new((lhs, rhs))),
+                );
+                block.unit()
+            }
             ExprKind::LogicalOp { op, lhs, rhs } => {
                 let condition_scope = this.local_scope();
                 let source_info = this.source_info(expr.span);
@@ -237,6 +268,51 @@ pub(crate) fn expr_into_dest(
                     None
                 })
             }
+            ExprKind::LoopMatch { state, region_scope, ref arms, match_span } => {
+                // Lowered like `loop { state = 'blk: { match state { arms } } }`, except that the
+                // `#[const_continue]`s in the arms break out of `'blk` into the arm they select,
+                // see `Builder::loop_match_expr`.
+                let loop_block = this.cfg.start_new_block();
+
+                // Start the loop.
+                this.cfg.goto(block, source_info, loop_block);
+
+                this.in_breakable_scope(Some(loop_block), destination, expr_span, move |this| {
+                    let mut body_block = this.cfg.start_new_block();
+                    this.cfg.terminate(
+                        loop_block,
+                        source_info,
+                        TerminatorKind::FalseUnwind {
+                            real_target: body_block,
+                            unwind: UnwindAction::Continue,
+                        },
+                    );
+                    this.diverge_from(loop_block);
+
+                    let state_span = this.thir[state].span;
+                    let state_place = unpack!(body_block = this.as_place(body_block, state));
+                    let block_source_info = this.source_info(match_span);
+                    let body_block_end = unpack!(this.in_scope(
+                        (region_scope, block_source_info),
+                        LintLevel::Inherited,
+                        |this| {
+                            this.in_breakable_scope(None, state_place, match_span, |this| {
+                                Some(this.loop_match_expr(
+                                    state_place,
+                                    body_block,
+                                    arms,
+                                    match_span,
+                                    state_span,
+                                ))
+                            })
+                        }
+                    ));
+                    this.cfg.goto(body_block_end, source_info, loop_block);
+
+                    // Loops are only exited by `break` expressions.
+                    None
+                })
+            }
             ExprKind::Call { ty: _, fun, ref args, from_hir_call, fn_span } => {
                 let fun = unpack!(block = this.as_local_operand(block, fun));
                 let args: Box<[_]> = args
@@ -508,6 +584,7 @@ pub(crate) fn expr_into_dest(
             }
 
             ExprKind::Continue { .. }
+            | ExprKind::ConstContinue { .. }
             | ExprKind::Break { .. }
             | ExprKind::Return { .. }
             | ExprKind::Become { .. } => {
@@ -610,7 +687,62 @@ pub(crate) fn expr_into_dest(
         block_and
     }
 
-    fn is_let(&self, expr: ExprId) -> bool {
+    /// Whether `lhs && rhs` or `lhs || rhs` evaluates both operands and combines them with a
+    /// `BitAnd` or `BitOr`, instead of branching on `lhs`. This is only done in
+    /// `#[branchless_lazy_bool]` functions, and if evaluating `rhs` even when `lhs` decides the
+    /// result can't be observed.
+    pub(crate) fn lazy_bool_is_branchless(&self, lhs: ExprId, rhs: ExprId) -> bool {
+        self.branchless_lazy_bools
+            && self.coverage_info.is_none()
+            && self.is_cheap_and_pure(lhs)
+            && self.is_cheap_and_pure(rhs)
+    }
+
+    /// Whether evaluating `expr` can't panic, have side effects or read invalid values, and only
+    /// costs a few instructions: reads of places that only go through references, literals,
+    /// constants, and comparisons and bitwise operations of these.
+    fn is_cheap_and_pure(&self, expr_id: ExprId) -> bool {
+        let expr = &self.thir[expr_id];
+        match expr.kind {
+            ExprKind::Scope { value: source, .. } | ExprKind::Use { source } => {
+                self.is_cheap_and_pure(source)
+            }
+            ExprKind::VarRef { .. }
+            | ExprKind::UpvarRef { .. }
+            | ExprKind::Literal { .. }
+            | ExprKind::NonHirLiteral { .. }
+            | ExprKind::ZstLiteral { .. }
+            | ExprKind::NamedConst { .. }
+            | ExprKind::ConstParam { .. } => true,
+            // Reading a union field may read a value that is invalid for its type, and a raw
+            // pointer may only be valid to dereference if `lhs` says so.
+            ExprKind::Field { lhs, .. } => {
+                !self.thir[lhs].ty.is_union() && self.is_cheap_and_pure(lhs)
+            }
+            ExprKind::Deref { arg } => self.thir[arg].ty.is_ref() && self.is_cheap_and_pure(arg),
+            ExprKind::Unary { op: UnOp::Not, arg } => self.is_cheap_and_pure(arg),
+            ExprKind::Binary {
+                op:
+                    BinOp::Eq
+                    | BinOp::Ne
+                    | BinOp::Lt
+                    | BinOp::Le
+                    | BinOp::Gt
+                    | BinOp::Ge
+                    | BinOp::BitAnd
+                    | BinOp::BitOr
+                    | BinOp::BitXor,
+                lhs,
+                rhs,
+            }
+            | ExprKind::LogicalOp { lhs, rhs, .. } => {
+                self.is_cheap_and_pure(lhs) && self.is_cheap_and_pure(rhs)
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) fn is_let(&self, expr: ExprId) -> bool {
         match self.thir[expr].kind {
             ExprKind::Let { .. } => true,
             ExprKind::Scope { value, .. } => self.is_let(value),
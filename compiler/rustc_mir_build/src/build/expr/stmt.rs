@@ -90,18 +90,21 @@ pub(crate) fn stmt_expr(
             ExprKind::Break { label, value } => {
                 this.break_scope(block, value, BreakableTarget::Break(label), source_info)
             }
+            ExprKind::ConstContinue { label, value } => {
+                this.break_const_continuable_scope(block, value, label, source_info)
+            }
             ExprKind::Return { value } => {
                 this.break_scope(block, value, BreakableTarget::Return, source_info)
             }
             ExprKind::Become { value } => {
                 let v = &this.thir[value];
                 let ExprKind::Scope { value, lint_level, region_scope } = v.kind else {
-                    span_bug!(v.span, "`thir_check_tail_calls` should have disallowed this {v:?}")
+                    span_bug!(v.span, "`check_tail_calls` should have disallowed this {v:?}")
                 };
 
                 let v = &this.thir[value];
                 let ExprKind::Call { ref args, fun, fn_span, .. } = v.kind else {
-                    span_bug!(v.span, "`thir_check_tail_calls` should have disallowed this {v:?}")
+                    span_bug!(v.span, "`check_tail_calls` should have disallowed this {v:?}")
                 };
 
                 this.in_scope((region_scope, source_info), lint_level, |this| {
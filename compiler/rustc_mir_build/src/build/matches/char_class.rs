@@ -0,0 +1,80 @@
+//! Two-level tables of `char`s, used to test or-patterns of many scattered `char` constants and
+//! ranges.
+//!
+//! A `char` is split into its low [`LEAF_BITS`] bits and the bits above them. The high bits index
+//! a table of buckets, which gives the index of a leaf: a bitmap of the low bits of the `char`s of
+//! the class in that bucket. Buckets without any `char` of the class share the empty leaf, and
+//! buckets with the same `char`s share the same leaf, so the tables of classes made of a few
+//! scripts stay small. Testing a `char` takes a comparison with the number of buckets and two
+//! loads, however many alternatives the or-pattern has.
+
+use std::fmt;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_middle::ty::TyCtxt;
+
+/// How many low bits of a `char` select a bit within a leaf.
+pub(super) const LEAF_BITS: u32 = 8;
+
+/// The size of a leaf, in bytes.
+pub(super) const LEAF_BYTES: usize = (1 << LEAF_BITS) / 8;
+
+/// The set of `char`s matched by an or-pattern, see the module documentation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct CharClass<'tcx> {
+    /// The index of the leaf of each bucket. The class has no `char` past the last bucket.
+    pub(super) buckets: &'tcx [u8],
+    /// The leaves, one after the other, [`LEAF_BYTES`] bytes each. The first one is empty.
+    pub(super) leaves: &'tcx [u8],
+}
+
+impl<'tcx> CharClass<'tcx> {
+    /// Builds the tables of the `char`s in `ranges`, which are inclusive and may be empty or
+    /// overlap. Returns `None` if the class needs more leaves than a byte can index.
+    pub(super) fn new(tcx: TyCtxt<'tcx>, ranges: &[(u32, u32)]) -> Option<Self> {
+        let max = ranges.iter().filter(|&&(lo, hi)| lo <= hi).map(|&(_, hi)| hi).max()?;
+        let mut bitmaps = vec![[0u8; LEAF_BYTES]; (max >> LEAF_BITS) as usize + 1];
+        for &(lo, hi) in ranges {
+            for c in lo..=hi {
+                let low = c & ((1 << LEAF_BITS) - 1);
+                bitmaps[(c >> LEAF_BITS) as usize][(low / 8) as usize] |= 1 << (low % 8);
+            }
+        }
+
+        let mut leaves = vec![[0u8; LEAF_BYTES]];
+        let mut leaf_indices = FxHashMap::from_iter([([0u8; LEAF_BYTES], 0u8)]);
+        let mut buckets = Vec::with_capacity(bitmaps.len());
+        for bitmap in bitmaps {
+            let index = match leaf_indices.get(&bitmap) {
+                Some(&index) => index,
+                None => {
+                    let index = u8::try_from(leaves.len()).ok()?;
+                    leaves.push(bitmap);
+                    leaf_indices.insert(bitmap, index);
+                    index
+                }
+            };
+            buckets.push(index);
+        }
+        Some(CharClass {
+            buckets: tcx.arena.dropless.alloc_slice(&buckets),
+            leaves: tcx.arena.dropless.alloc_slice(&leaves.concat()),
+        })
+    }
+
+    /// Whether the class contains the `char` with the given code point.
+    pub(super) fn contains(&self, c: u32) -> bool {
+        let Some(&leaf) = self.buckets.get((c >> LEAF_BITS) as usize) else {
+            return false;
+        };
+        let low = c & ((1 << LEAF_BITS) - 1);
+        let byte = self.leaves[usize::from(leaf) * LEAF_BYTES + (low / 8) as usize];
+        (byte >> (low % 8)) & 1 != 0
+    }
+}
+
+impl fmt::Display for CharClass<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} buckets, {} leaves", self.buckets.len(), self.leaves.len() / LEAF_BYTES)
+    }
+}
@@ -0,0 +1,346 @@
+//! Lowering of the `match`es of a function marked `#[constant_time_match]`, for code handling
+//! secrets, like cryptographic code, where the time a `match` takes must not depend on the value
+//! it matches:
+//!
+//! ```ignore (illustrative)
+//! #[constant_time_match]
+//! fn hex_class(c: u8) -> u8 {
+//!     match c {
+//!         b'0'..=b'9' => 0,
+//!         b'a'..=b'f' | b'A'..=b'F' => 1,
+//!         _ => 2,
+//!     }
+//! }
+//! ```
+//!
+//! Instead of a decision tree, every arm but the last is tested, with comparisons combined by `&`
+//! and `|` rather than by branches, and the value of the arm is selected with a mask that is all
+//! ones if the arm matched and zero otherwise:
+//! `result = result ^ ((result ^ value) & mask)`. The arms are selected from the last one to the
+//! first one, so that the first arm that matches wins. The last arm, which is reached when no
+//! other arm matched, isn't tested.
+//!
+//! This only works for `match`es on `bool`s, `char`s and integers, or tuples and structs of them,
+//! whose arms have no bindings nor guards and evaluate to constant integers or `bool`s. Any other
+//! `match` in such a function is an error instead of being lowered with branches.
+
+use crate::build::expr::as_constant::as_constant_inner;
+use crate::build::expr::as_place::PlaceBuilder;
+use crate::build::matches::{FlatPat, MatchPair, TestCase};
+use crate::build::{BlockAnd, BlockAndExtension, Builder};
+use crate::errors::ConstantTimeMatchUnsupported;
+use rustc_hir::RangeEnd;
+use rustc_middle::bug;
+use rustc_middle::mir::*;
+use rustc_middle::thir::{ArmId, ExprId, ExprKind};
+use rustc_middle::ty::Ty;
+use rustc_span::Span;
+
+impl<'a, 'tcx> Builder<'a, 'tcx> {
+    /// Lowers a `match` of a `#[constant_time_match]` function without branching on its
+    /// scrutinee, see the module documentation. If that isn't possible, reports an error and
+    /// returns `None`, for the `match` to be lowered as usual.
+    pub(super) fn constant_time_match(
+        &mut self,
+        destination: Place<'tcx>,
+        block: BasicBlock,
+        scrutinee: &PlaceBuilder<'tcx>,
+        arms: &[ArmId],
+        span: Span,
+    ) -> Option<BlockAnd<()>> {
+        // A `match` without arms doesn't select anything.
+        if arms.is_empty() {
+            return None;
+        }
+        let ty = destination.ty(&self.local_decls, self.tcx).ty;
+        match self.constant_time_arms(ty, scrutinee, arms) {
+            Ok(arms) => Some(self.lower_constant_time_arms(destination, block, ty, arms, span)),
+            Err(span) => {
+                self.tcx.dcx().emit_err(ConstantTimeMatchUnsupported { span });
+                None
+            }
+        }
+    }
+
+    /// The patterns and the values of `arms`, or the span of what can't be lowered without
+    /// branching.
+    fn constant_time_arms<'pat>(
+        &mut self,
+        ty: Ty<'tcx>,
+        scrutinee: &PlaceBuilder<'tcx>,
+        arms: &'pat [ArmId],
+    ) -> Result<Vec<(FlatPat<'pat, 'tcx>, ConstOperand<'tcx>)>, Span>
+    where
+        'a: 'pat,
+    {
+        arms.iter()
+            .map(|&arm| {
+                let arm = &self.thir[arm];
+                if let Some(guard) = arm.guard {
+                    return Err(self.thir[guard].span);
+                }
+                let value = self.constant_arm_value(arm.body, ty)?;
+                let flat_pat = FlatPat::new(scrutinee.clone(), &arm.pattern, self);
+                if !is_branchless_pat(&flat_pat) {
+                    return Err(arm.pattern.span);
+                }
+                Ok((flat_pat, value))
+            })
+            .collect()
+    }
+
+    /// The value of the arm `body` of a `match` of type `ty`, if it is a literal or a constant of
+    /// integer or `bool` type.
+    fn constant_arm_value(&self, body: ExprId, ty: Ty<'tcx>) -> Result<ConstOperand<'tcx>, Span> {
+        let mut expr = &self.thir[body];
+        while let ExprKind::Scope { value, .. } = expr.kind {
+            expr = &self.thir[value];
+        }
+        if !matches!(
+            expr.kind,
+            ExprKind::Literal { .. }
+                | ExprKind::NonHirLiteral { .. }
+                | ExprKind::NamedConst { .. }
+                | ExprKind::ConstBlock { .. }
+        ) || expr.ty != ty
+            || !(ty.is_bool() || ty.is_integral())
+        {
+            return Err(self.thir[body].span);
+        }
+        Ok(as_constant_inner(expr, |_| None, self.tcx))
+    }
+
+    fn lower_constant_time_arms(
+        &mut self,
+        destination: Place<'tcx>,
+        block: BasicBlock,
+        ty: Ty<'tcx>,
+        arms: Vec<(FlatPat<'_, 'tcx>, ConstOperand<'tcx>)>,
+        span: Span,
+    ) -> BlockAnd<()> {
+        let source_info = self.source_info(span);
+        let result = self.temp(ty, span);
+        let ((_, last_value), arms) = arms.split_last().unwrap();
+        self.cfg.push_assign_constant(block, source_info, result, last_value.clone());
+        for (flat_pat, value) in arms.iter().rev() {
+            let matched = self.branchless_test(block, source_info, &flat_pat.match_pairs);
+            self.branchless_select(block, source_info, result, ty, matched, value.clone());
+        }
+        self.cfg.push_assign(block, source_info, destination, Rvalue::Use(Operand::Move(result)));
+        block.unit()
+    }
+
+    /// Computes whether all of `match_pairs` match, into a `bool` temporary, without branching.
+    fn branchless_test(
+        &mut self,
+        block: BasicBlock,
+        source_info: SourceInfo,
+        match_pairs: &[MatchPair<'_, 'tcx>],
+    ) -> Place<'tcx> {
+        let mut matched = None;
+        for match_pair in match_pairs {
+            let pair_matched = self.branchless_pair_test(block, source_info, match_pair);
+            matched = Some(match matched {
+                Some(matched) => {
+                    self.push_bool_binop(block, source_info, BinOp::BitAnd, matched, pair_matched)
+                }
+                None => pair_matched,
+            });
+        }
+        matched.unwrap_or_else(|| self.push_bool(block, source_info, true))
+    }
+
+    fn branchless_pair_test(
+        &mut self,
+        block: BasicBlock,
+        source_info: SourceInfo,
+        match_pair: &MatchPair<'_, 'tcx>,
+    ) -> Place<'tcx> {
+        let place = match_pair.place.unwrap();
+        let span = match_pair.pattern.span;
+        match match_pair.test_case {
+            TestCase::Constant { value } => {
+                let value = self.literal_operand(span, value);
+                let op = BinOp::Eq;
+                self.push_bool_binop_operands(block, source_info, op, Operand::Copy(place), value)
+            }
+            TestCase::Range(range) => {
+                // As for `TestKind::Range`, an end that is the smallest or largest value of the
+                // type holds for any value, so it isn't compared.
+                let tcx = self.tcx;
+                let lo = range.lo.as_finite().filter(|_| range.lo_is_min(tcx) != Some(true));
+                let hi = range.hi.as_finite().filter(|_| range.hi_is_max(tcx) != Some(true));
+                let lo = lo.map(|lo| {
+                    let lo = self.literal_operand(span, lo);
+                    let val = Operand::Copy(place);
+                    self.push_bool_binop_operands(block, source_info, BinOp::Le, lo, val)
+                });
+                let hi = hi.map(|hi| {
+                    let hi = self.literal_operand(span, hi);
+                    let op = match range.end {
+                        RangeEnd::Included => BinOp::Le,
+                        RangeEnd::Excluded => BinOp::Lt,
+                    };
+                    self.push_bool_binop_operands(block, source_info, op, Operand::Copy(place), hi)
+                });
+                match (lo, hi) {
+                    (Some(lo), Some(hi)) => {
+                        self.push_bool_binop(block, source_info, BinOp::BitAnd, lo, hi)
+                    }
+                    (Some(matched), None) | (None, Some(matched)) => matched,
+                    (None, None) => self.push_bool(block, source_info, true),
+                }
+            }
+            TestCase::Or { ref pats } => {
+                let mut matched = None;
+                for pat in pats.iter() {
+                    let pat_matched = self.branchless_test(block, source_info, &pat.match_pairs);
+                    matched = Some(match matched {
+                        Some(matched) => self.push_bool_binop(
+                            block,
+                            source_info,
+                            BinOp::BitOr,
+                            matched,
+                            pat_matched,
+                        ),
+                        None => pat_matched,
+                    });
+                }
+                matched.unwrap()
+            }
+            _ => bug!("match pair can't be tested without branching: {match_pair:?}"),
+        }
+    }
+
+    /// `result = if matched { value } else { result }`, without branching on `matched`.
+    fn branchless_select(
+        &mut self,
+        block: BasicBlock,
+        source_info: SourceInfo,
+        result: Place<'tcx>,
+        ty: Ty<'tcx>,
+        matched: Place<'tcx>,
+        value: ConstOperand<'tcx>,
+    ) {
+        let span = source_info.span;
+        let value = Operand::Constant(Box::new(value));
+        let rvalue = if ty.is_bool() {
+            // `result = (matched & value) | (!matched & result)`
+            let selected = self.push_bool_binop_operands(
+                block,
+                source_info,
+                BinOp::BitAnd,
+                Operand::Copy(matched),
+                value,
+            );
+            let not_matched = self.temp(self.tcx.types.bool, span);
+            self.cfg.push_assign(
+                block,
+                source_info,
+                not_matched,
+                Rvalue::UnaryOp(UnOp::Not, Operand::Move(matched)),
+            );
+            let kept = self.push_bool_binop(block, source_info, BinOp::BitAnd, not_matched, result);
+            Rvalue::BinaryOp(BinOp::BitOr, Box::new((Operand::Move(selected), Operand::Move(kept))))
+        } else {
+            // `mask = 0 - (matched as T)`, which wraps around to all ones if `matched`.
+            let bit = self.temp(ty, span);
+            self.cfg.push_assign(
+                block,
+                source_info,
+                bit,
+                Rvalue::Cast(CastKind::IntToInt, Operand::Move(matched), ty),
+            );
+            let zero = self.zero_literal(span, ty);
+            let mask = self.temp(ty, span);
+            self.cfg.push_assign(
+                block,
+                source_info,
+                mask,
+                Rvalue::BinaryOp(BinOp::Sub, Box::new((zero, Operand::Move(bit)))),
+            );
+            // `result = result ^ ((result ^ value) & mask)`
+            let diff = self.temp(ty, span);
+            self.cfg.push_assign(
+                block,
+                source_info,
+                diff,
+                Rvalue::BinaryOp(BinOp::BitXor, Box::new((Operand::Copy(result), value))),
+            );
+            let masked = self.temp(ty, span);
+            self.cfg.push_assign(
+                block,
+                source_info,
+                masked,
+                Rvalue::BinaryOp(
+                    BinOp::BitAnd,
+                    Box::new((Operand::Move(diff), Operand::Move(mask))),
+                ),
+            );
+            Rvalue::BinaryOp(
+                BinOp::BitXor,
+                Box::new((Operand::Copy(result), Operand::Move(masked))),
+            )
+        };
+        self.cfg.push_assign(block, source_info, result, rvalue);
+    }
+
+    fn push_bool(
+        &mut self,
+        block: BasicBlock,
+        source_info: SourceInfo,
+        value: bool,
+    ) -> Place<'tcx> {
+        let temp = self.temp(self.tcx.types.bool, source_info.span);
+        let const_ = Const::from_bool(self.tcx, value);
+        let constant = ConstOperand { span: source_info.span, user_ty: None, const_ };
+        self.cfg.push_assign_constant(block, source_info, temp, constant);
+        temp
+    }
+
+    fn push_bool_binop(
+        &mut self,
+        block: BasicBlock,
+        source_info: SourceInfo,
+        op: BinOp,
+        lhs: Place<'tcx>,
+        rhs: Place<'tcx>,
+    ) -> Place<'tcx> {
+        self.push_bool_binop_operands(
+            block,
+            source_info,
+            op,
+            Operand::Move(lhs),
+            Operand::Move(rhs),
+        )
+    }
+
+    fn push_bool_binop_operands(
+        &mut self,
+        block: BasicBlock,
+        source_info: SourceInfo,
+        op: BinOp,
+        lhs: Operand<'tcx>,
+        rhs: Operand<'tcx>,
+    ) -> Place<'tcx> {
+        let temp = self.temp(self.tcx.types.bool, source_info.span);
+        self.cfg.push_assign(block, source_info, temp, Rvalue::BinaryOp(op, Box::new((lhs, rhs))));
+        temp
+    }
+}
+
+/// Whether `flat_pat` binds nothing and only tests `bool`s, `char`s and integers against
+/// constants and ranges, which can be done without branching.
+fn is_branchless_pat(flat_pat: &FlatPat<'_, '_>) -> bool {
+    flat_pat.extra_data.is_empty()
+        && flat_pat.match_pairs.iter().all(|match_pair| {
+            let ty = match_pair.pattern.ty;
+            match_pair.place.is_some()
+                && match match_pair.test_case {
+                    TestCase::Constant { .. } => ty.is_bool() || ty.is_char() || ty.is_integral(),
+                    TestCase::Range(range) => range.ty.is_char() || range.ty.is_integral(),
+                    TestCase::Or { ref pats } => pats.iter().all(is_branchless_pat),
+                    _ => false,
+                }
+        })
+}
@@ -0,0 +1,245 @@
+//! Lowering of the `match`es that `for` loops and the `?` operator desugar to, and of the `let` of
+//! `while let` loops:
+//!
+//! ```ignore (illustrative)
+//! loop {
+//!     match Iterator::next(&mut iter) {
+//!         None => break,
+//!         Some(<pat>) => <body>,
+//!     }
+//! }
+//!
+//! match Try::branch(<expr>) {
+//!     ControlFlow::Continue(val) => val,
+//!     ControlFlow::Break(residual) => return FromResidual::from_residual(residual),
+//! }
+//!
+//! loop {
+//!     if let Some(x) = iter.next() { <body> } else { break }
+//! }
+//! ```
+//!
+//! Each arm or `let` matches a variant of an enum, and binds its fields by value or ignores them.
+//! When `<pat>` is a binding or `_`, as it is for most loops, the `match` is lowered straight to a
+//! switch on the discriminant with a target for each arm, without building candidates and a match
+//! tree for it, and so is the `let`. This saves build time on code with many loops or uses of `?`,
+//! and gives the MIR optimizations loops and early returns of the same shape every time.
+
+use super::{plain_binding, EmitStorageLive, ScheduleDrops};
+use crate::build::ForGuard::OutsideGuard;
+use crate::build::{BlockAnd, Builder};
+use rustc_middle::bug;
+use rustc_middle::mir::*;
+use rustc_middle::thir::{ArmId, ExprId, Pat, PatKind, Thir};
+use rustc_middle::ty::AdtDef;
+use rustc_span::Span;
+use rustc_target::abi::{FieldIdx, VariantIdx};
+
+/// The arms of a desugared `match` that can take the fast path.
+struct VariantArms<'p, 'tcx> {
+    adt_def: AdtDef<'tcx>,
+    /// The variant matched by each arm, in order.
+    variants: [VariantIdx; 2],
+    /// The pattern of the field of each arm's variant, a binding by value or `_`, if it has one.
+    field_pats: [Option<&'p Pat<'tcx>>; 2],
+}
+
+impl<'a, 'tcx> Builder<'a, 'tcx> {
+    /// Lowers the `match` of a `for` loop or of a `?`, or hands it to [`Builder::match_expr`] if
+    /// its arms don't have the shape of the fast path.
+    pub(crate) fn desugared_match(
+        &mut self,
+        destination: Place<'tcx>,
+        mut block: BasicBlock,
+        scrutinee_id: ExprId,
+        arms: &[ArmId],
+        span: Span,
+        scrutinee_span: Span,
+    ) -> BlockAnd<()> {
+        let thir = self.thir;
+        let Some(VariantArms { adt_def, variants, field_pats }) = variant_arms(thir, arms)
+            .filter(|_| self.may_switch_directly())
+            .filter(|_| !arms.iter().any(|&arm| self.is_cold_arm(&thir[arm])))
+        else {
+            return self.match_expr(destination, block, scrutinee_id, arms, span, scrutinee_span);
+        };
+        let scrutinee_place =
+            unpack!(block = self.lower_scrutinee(block, scrutinee_id, scrutinee_span));
+        let Some(scrutinee) = scrutinee_place.try_to_place(self) else {
+            return self.lower_match(
+                destination,
+                block,
+                scrutinee_place,
+                arms,
+                span,
+                scrutinee_span,
+                false,
+            );
+        };
+
+        let tcx = self.tcx;
+        let source_info = self.source_info(scrutinee_span);
+        let discr = self.read_discriminant(block, scrutinee, scrutinee_span);
+        let arm_blocks = variants.map(|_| self.cfg.start_new_block());
+        let otherwise_block = self
+            .invalid_value_block(source_info)
+            .unwrap_or_else(|| self.unreachable_block(source_info));
+        let values = variants.map(|variant| adt_def.discriminant_for_variant(tcx, variant).val);
+        let targets = SwitchTargets::new(std::iter::zip(values, arm_blocks), otherwise_block);
+        self.cfg.terminate(block, source_info, TerminatorKind::SwitchInt { discr, targets });
+        if self.decision_tree_dump.is_some() {
+            let arm_variants = std::iter::zip(variants, arms)
+                .map(|(variant, &arm)| (vec![variant], thir[arm].pattern.span))
+                .collect::<Vec<_>>();
+            self.dump_variant_switch(scrutinee, adt_def, &arm_variants, scrutinee_span);
+        }
+
+        self.lower_switched_arms(destination, arms, &arm_blocks, span, |this, index, arm| {
+            let scope = this.declare_bindings(
+                None,
+                arm.span,
+                &arm.pattern,
+                None,
+                Some((Some(&scrutinee), scrutinee_span)),
+            );
+            if let Some(source_scope) = scope {
+                this.source_scope = source_scope;
+            }
+            if let Some(field_pat) = field_pats[index] {
+                let variant = tcx.mk_place_downcast(scrutinee, adt_def, variants[index]);
+                let field = tcx.mk_place_field(variant, FieldIdx::ZERO, field_pat.ty);
+                this.bind_field(arm_blocks[index], field, field_pat, EmitStorageLive::Yes);
+            }
+        })
+    }
+
+    /// Lowers the test of the `let` of `while let Some(x) = iter.next()`, or of another `let` whose
+    /// pattern only matches a variant of an enum and binds its fields by value or ignores them,
+    /// like `if let Ok(n) = s.parse()`, to a switch on the discriminant of `scrutinee`, without a
+    /// match tree or false edges. Returns the block where `pat` matched, before its bindings, and
+    /// the one where it didn't, or `None` if `pat` doesn't have this shape.
+    pub(super) fn switch_on_variant(
+        &mut self,
+        block: BasicBlock,
+        scrutinee: Place<'tcx>,
+        scrutinee_span: Span,
+        pat: &Pat<'tcx>,
+    ) -> Option<(BasicBlock, BasicBlock)> {
+        if !self.may_switch_directly() {
+            return None;
+        }
+        let PatKind::Variant { adt_def, variant_index, ref subpatterns, .. } = pat.kind else {
+            return None;
+        };
+        if !subpatterns.iter().all(|field| is_binding_or_wild(&field.pattern)) {
+            return None;
+        }
+
+        let source_info = self.source_info(scrutinee_span);
+        let discr = self.read_discriminant(block, scrutinee, scrutinee_span);
+        let matched_block = self.cfg.start_new_block();
+        let otherwise_block = self.cfg.start_new_block();
+        let value = adt_def.discriminant_for_variant(self.tcx, variant_index).val;
+        let targets = SwitchTargets::static_if(value, matched_block, otherwise_block);
+        self.cfg.terminate(block, source_info, TerminatorKind::SwitchInt { discr, targets });
+        Some((matched_block, otherwise_block))
+    }
+
+    /// Reads the discriminant of `scrutinee` into a temporary, for a switch on it.
+    pub(super) fn read_discriminant(
+        &mut self,
+        block: BasicBlock,
+        scrutinee: Place<'tcx>,
+        scrutinee_span: Span,
+    ) -> Operand<'tcx> {
+        let tcx = self.tcx;
+        let discr_ty = scrutinee.ty(&self.local_decls, tcx).ty.discriminant_ty(tcx);
+        let discr = self.temp(discr_ty, scrutinee_span);
+        let source_info = self.source_info(scrutinee_span);
+        self.cfg.push_assign(block, source_info, discr, Rvalue::Discriminant(scrutinee));
+        Operand::Move(discr)
+    }
+
+    /// Binds the fields of the variant matched by `pat` in `scrutinee`, after
+    /// [`Builder::switch_on_variant`] took `block` to where `pat` matched.
+    pub(super) fn bind_variant_fields(
+        &mut self,
+        block: BasicBlock,
+        scrutinee: Place<'tcx>,
+        pat: &Pat<'tcx>,
+        emit_storage_live: EmitStorageLive,
+    ) {
+        let PatKind::Variant { adt_def, variant_index, ref subpatterns, .. } = pat.kind else {
+            bug!("binding the fields of {pat:?}, which isn't a variant pattern");
+        };
+        let variant = self.tcx.mk_place_downcast(scrutinee, adt_def, variant_index);
+        for field in subpatterns {
+            let place = self.tcx.mk_place_field(variant, field.field, field.pattern.ty);
+            self.bind_field(block, place, &field.pattern, emit_storage_live);
+        }
+    }
+
+    /// Binds `field` to `field_pat` by value, unless `field_pat` is `_`.
+    fn bind_field(
+        &mut self,
+        block: BasicBlock,
+        field: Place<'tcx>,
+        field_pat: &Pat<'tcx>,
+        emit_storage_live: EmitStorageLive,
+    ) {
+        let Some(var) = plain_binding(field_pat, &mut Vec::new()) else { return };
+        let span = field_pat.span;
+        let local = match emit_storage_live {
+            // The storage of the binding is already alive, like for let-else.
+            EmitStorageLive::No => self.var_local_id(var, OutsideGuard).into(),
+            EmitStorageLive::Yes => {
+                self.storage_live_binding(block, var, span, OutsideGuard, ScheduleDrops::Yes)
+            }
+        };
+        self.schedule_drop_for_binding(var, span, OutsideGuard);
+        let rvalue = Rvalue::Use(self.consume_by_copy_or_move(field));
+        self.cfg.push_assign(block, self.source_info(span), local, rvalue);
+    }
+}
+
+/// Whether `pat` is a binding by value, without type ascriptions, or `_`.
+fn is_binding_or_wild(pat: &Pat<'_>) -> bool {
+    let mut annotations = Vec::new();
+    matches!(pat.kind, PatKind::Wild)
+        || plain_binding(pat, &mut annotations).is_some() && annotations.is_empty()
+}
+
+/// Checks that `arms` match each of the two variants of an enum, like `None` and `Some(x)`, without
+/// guards, and bind the field of their variant by value or ignore it.
+fn variant_arms<'p, 'tcx>(thir: &'p Thir<'tcx>, arms: &[ArmId]) -> Option<VariantArms<'p, 'tcx>> {
+    if arms.len() != 2 {
+        return None;
+    }
+    let mut adt_defs = [None; 2];
+    let mut variants = [VariantIdx::ZERO; 2];
+    let mut field_pats = [None; 2];
+    for (index, &arm) in arms.iter().enumerate() {
+        let arm = &thir[arm];
+        let PatKind::Variant { adt_def, variant_index, ref subpatterns, .. } = arm.pattern.kind
+        else {
+            return None;
+        };
+        if arm.guard.is_some() || adt_def.variants().len() != 2 {
+            return None;
+        }
+        match &subpatterns[..] {
+            [] => {}
+            [field] if field.field == FieldIdx::ZERO && is_binding_or_wild(&field.pattern) => {
+                field_pats[index] = Some(&*field.pattern);
+            }
+            _ => return None,
+        }
+        adt_defs[index] = Some(adt_def);
+        variants[index] = variant_index;
+    }
+    let [Some(adt_def), second_adt_def] = adt_defs else { return None };
+    if second_adt_def != Some(adt_def) || variants[0] == variants[1] {
+        return None;
+    }
+    Some(VariantArms { adt_def, variants, field_pats })
+}
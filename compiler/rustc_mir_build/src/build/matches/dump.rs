@@ -0,0 +1,197 @@
+//! Support for `#[rustc_dump_decision_tree]`, a unit-testing mechanism for match lowering.
+//!
+//! When a function carries this attribute, every step taken by the match lowering algorithm for
+//! the patterns in its body is recorded: the tests chosen by [`Builder::pick_test`], the branch
+//! each candidate was sorted into by [`Builder::sort_candidates`], the candidates that end up
+//! matched, and the or-patterns that were expanded or merged. Once the tree for a `match`, `let`
+//! or `if let` is complete, it is reported as an error with one note per step, indented to show
+//! nesting. UI tests can then snapshot the decision tree directly, using ordinary patterns as the
+//! input language, instead of going through the MIR of the whole function.
+//!
+//! Trees that don't perform any test (such as the ones for irrefutable `let`s and function
+//! parameters) are not reported.
+
+use rustc_middle::mir::Place;
+use rustc_middle::ty::{AdtDef, TyCtxt};
+use rustc_span::def_id::LocalDefId;
+use rustc_span::{sym, Span};
+use rustc_target::abi::VariantIdx;
+
+use crate::build::matches::{Candidate, Test, TestBranch, TestKind};
+use crate::build::Builder;
+
+/// The decision tree recorded so far for the match being lowered.
+pub(crate) struct DecisionTreeDump {
+    lines: Vec<String>,
+    /// Nesting level of the next recorded step.
+    depth: usize,
+    /// Whether the tree performs at least one test.
+    has_test: bool,
+}
+
+impl DecisionTreeDump {
+    /// Returns a new dump if the function being built has `#[rustc_dump_decision_tree]`.
+    pub(crate) fn new_if_requested(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Option<Self> {
+        tcx.has_attr(def_id, sym::rustc_dump_decision_tree).then(|| Self {
+            lines: Vec::new(),
+            depth: 0,
+            has_test: false,
+        })
+    }
+}
+
+impl<'a, 'tcx> Builder<'a, 'tcx> {
+    /// Records a step of the decision tree. `describe` is only called if the tree is being dumped.
+    fn dump_decision_tree_step(&mut self, describe: impl FnOnce(&Self) -> String) {
+        if self.decision_tree_dump.is_none() {
+            return;
+        }
+        let line = describe(self);
+        let dump = self.decision_tree_dump.as_mut().unwrap();
+        dump.lines.push(format!("{:indent$}{line}", "", indent = 2 * dump.depth));
+    }
+
+    /// Runs `f`, recording the steps it takes as children of the last recorded step.
+    pub(super) fn nest_decision_tree_dump<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        if let Some(dump) = &mut self.decision_tree_dump {
+            dump.depth += 1;
+        }
+        let result = f(self);
+        if let Some(dump) = &mut self.decision_tree_dump {
+            dump.depth -= 1;
+        }
+        result
+    }
+
+    pub(super) fn dump_decision_tree_test(
+        &mut self,
+        place: Place<'tcx>,
+        test: &Test<'tcx>,
+        implied_branch: Option<TestBranch<'tcx>>,
+    ) {
+        if let Some(dump) = &mut self.decision_tree_dump {
+            dump.has_test = true;
+        }
+        self.dump_decision_tree_step(|this| {
+            let kind = match test.kind {
+                TestKind::Switch { adt_def } => {
+                    format!("Switch({})", this.tcx.item_name(adt_def.did()))
+                }
+                TestKind::SwitchInt => "SwitchInt".to_owned(),
+                TestKind::If => "If".to_owned(),
+                TestKind::Eq { value, .. } => format!("Eq({value})"),
+                TestKind::StrLen => "StrLen".to_owned(),
+                TestKind::StrHash(ref hash) => format!("StrHash({hash})"),
+                TestKind::StrByte { index } => format!("StrByte({index})"),
+                TestKind::Range(ref range) => format!("Range({range})"),
+                TestKind::BitSet { base, mask } => format!("BitSet({base} + {mask:#b})"),
+                TestKind::CharClass(class) => format!("CharClass({class})"),
+                TestKind::Len { len, op } => format!("Len({op:?} {len})"),
+                TestKind::SwitchLen => "SwitchLen".to_owned(),
+                TestKind::Deref { .. } => "Deref".to_owned(),
+                TestKind::Never => "Never".to_owned(),
+            };
+            match implied_branch {
+                Some(branch) => {
+                    format!("test {place:?} with {kind} (known: {})", branch_name(test, branch))
+                }
+                None => format!("test {place:?} with {kind}"),
+            }
+        });
+    }
+
+    pub(super) fn dump_decision_tree_branch(
+        &mut self,
+        test: &Test<'tcx>,
+        branch: TestBranch<'tcx>,
+    ) {
+        self.dump_decision_tree_step(|_| format!("{}:", branch_name(test, branch)));
+    }
+
+    pub(super) fn dump_decision_tree_candidate(
+        &mut self,
+        what: &str,
+        candidate: &Candidate<'_, 'tcx>,
+    ) {
+        self.dump_decision_tree_step(|this| {
+            let guard = if candidate.has_guard { " (with guard)" } else { "" };
+            format!("{what} `{}`{guard}", this.decision_tree_snippet(candidate.extra_data.span))
+        });
+    }
+
+    pub(super) fn dump_decision_tree_or_pattern(&mut self, what: &str, or_span: Span) {
+        self.dump_decision_tree_step(|this| {
+            format!("{what} `{}`", this.decision_tree_snippet(or_span))
+        });
+    }
+
+    /// Records the switch on the discriminant of `place` that a `match` is lowered to without a
+    /// match tree, by [`Builder::single_switch_match`] or [`Builder::desugared_match`], with the
+    /// variants taking each arm, none for a last `_` arm, and the span of its pattern, then
+    /// reports it.
+    pub(super) fn dump_variant_switch(
+        &mut self,
+        place: Place<'tcx>,
+        adt_def: AdtDef<'tcx>,
+        arms: &[(Vec<VariantIdx>, Span)],
+        span: Span,
+    ) {
+        let Some(dump) = &mut self.decision_tree_dump else { return };
+        dump.has_test = true;
+        self.dump_decision_tree_step(|this| {
+            format!("switch on {place:?} with Switch({})", this.tcx.item_name(adt_def.did()))
+        });
+        self.nest_decision_tree_dump(|this| {
+            for (variants, pat_span) in arms {
+                this.dump_decision_tree_step(|_| {
+                    if variants.is_empty() {
+                        return "otherwise:".to_owned();
+                    }
+                    let names: Vec<_> = variants
+                        .iter()
+                        .map(|&variant| adt_def.variant(variant).name.to_string())
+                        .collect();
+                    format!("{}:", names.join(" | "))
+                });
+                this.nest_decision_tree_dump(|this| {
+                    this.dump_decision_tree_step(|this| {
+                        format!("matched `{}`", this.decision_tree_snippet(*pat_span))
+                    })
+                });
+            }
+        });
+        self.emit_decision_tree_dump(span);
+    }
+
+    fn decision_tree_snippet(&self, span: Span) -> String {
+        self.tcx.sess.source_map().span_to_snippet(span).unwrap_or_else(|_| format!("{span:?}"))
+    }
+
+    /// Reports the decision tree recorded since the last call, if it performs any test.
+    pub(super) fn emit_decision_tree_dump(&mut self, span: Span) {
+        let Some(dump) = &mut self.decision_tree_dump else { return };
+        assert_eq!(dump.depth, 0);
+        let lines = std::mem::take(&mut dump.lines);
+        if !std::mem::take(&mut dump.has_test) {
+            return;
+        }
+        let mut diag = self.tcx.dcx().struct_span_err(span, sym::rustc_dump_decision_tree.as_str());
+        for line in lines {
+            diag.note(line);
+        }
+        diag.emit();
+    }
+}
+
+fn branch_name<'tcx>(test: &Test<'tcx>, branch: TestBranch<'tcx>) -> String {
+    match branch {
+        TestBranch::Success => "success".to_owned(),
+        TestBranch::Failure => "failure".to_owned(),
+        TestBranch::Constant(value, _) => format!("{value}"),
+        TestBranch::Range(lo, hi) => format!("{lo}..={hi}"),
+        TestBranch::Variant(variant_index) => match test.kind {
+            TestKind::Switch { adt_def } => adt_def.variant(variant_index).name.to_string(),
+            _ => format!("variant {}", variant_index.as_usize()),
+        },
+    }
+}
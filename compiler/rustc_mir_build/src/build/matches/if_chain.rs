@@ -0,0 +1,185 @@
+//! Lowering of the `if`-`else if` chains that compare the same variable to distinct constants:
+//!
+//! ```ignore (illustrative)
+//! if x == 1 {
+//!     <a>
+//! } else if x == 2 {
+//!     <b>
+//! } else if 3 == x {
+//!     <c>
+//! } else {
+//!     <d>
+//! }
+//! ```
+//!
+//! Like the equivalent `match x { 1 => <a>, 2 => <b>, 3 => <c>, _ => <d> }`, such a chain is
+//! lowered to a single switch on the variable, instead of a comparison and a branch for each
+//! condition. The chain ends at the first `else` that isn't an `if` of this shape, which is then
+//! lowered as usual where the switch matched none of the constants.
+
+use crate::build::expr::as_constant::as_constant_inner;
+use crate::build::{BlockAnd, BlockAndExtension, Builder};
+use rustc_data_structures::fx::FxHashSet;
+use rustc_middle::bug;
+use rustc_middle::mir::*;
+use rustc_middle::thir::{ExprId, ExprKind, LocalVarId, Thir};
+use std::iter;
+
+/// An `if`-`else if` chain lowered to a switch.
+pub(crate) struct EqualityIfChain {
+    /// The variable compared by the first condition of the chain.
+    var: ExprId,
+    /// The constant compared to the variable by each condition of the chain, in order.
+    values: Vec<u128>,
+}
+
+impl<'a, 'tcx> Builder<'a, 'tcx> {
+    /// Returns the chain starting at the `if` expression `expr_id`, if it compares the same
+    /// variable to distinct constants in at least two conditions.
+    pub(crate) fn equality_if_chain(&self, expr_id: ExprId) -> Option<EqualityIfChain> {
+        // Coverage instrumentation maps the spans of the conditions to counters.
+        if self.tcx.sess.instrument_coverage() {
+            return None;
+        }
+
+        let mut chain_var = None;
+        let mut values = Vec::new();
+        let mut seen = FxHashSet::default();
+        let mut expr_id = expr_id;
+        while let ExprKind::If { cond, else_opt, .. } = self.thir[expr_id].kind
+            && let Some((var_expr, var, value)) = self.equality_with_constant(cond)
+            && chain_var.map_or(true, |(_, chain_var)| chain_var == var)
+            && seen.insert(value)
+        {
+            chain_var.get_or_insert((var_expr, var));
+            values.push(value);
+            let Some(else_expr) = else_opt else { break };
+            expr_id = peel_scopes(self.thir, else_expr);
+        }
+
+        let (var, _) = chain_var?;
+        (values.len() >= 2).then_some(EqualityIfChain { var, values })
+    }
+
+    /// If `cond` is `x == C` or `C == x` for a variable `x` of an integer type or `char`, and a
+    /// constant `C`, returns the expression of `x`, `x` itself, and the value of `C`.
+    fn equality_with_constant(&self, cond: ExprId) -> Option<(ExprId, LocalVarId, u128)> {
+        let thir = self.thir;
+        let ExprKind::Binary { op: BinOp::Eq, lhs, rhs } = thir[peel_scopes(thir, cond)].kind
+        else {
+            return None;
+        };
+        let variable = |expr_id| match thir[peel_scopes(thir, expr_id)].kind {
+            ExprKind::VarRef { id } => Some(id),
+            ExprKind::UpvarRef { var_hir_id, .. } => Some(var_hir_id),
+            _ => None,
+        };
+        let (var_expr, var, const_expr) = match (variable(lhs), variable(rhs)) {
+            (Some(var), _) => (lhs, var, rhs),
+            (None, Some(var)) => (rhs, var, lhs),
+            (None, None) => return None,
+        };
+
+        let ty = thir[var_expr].ty;
+        if !ty.is_integral() && !ty.is_char() {
+            return None;
+        }
+        let const_expr = &thir[peel_scopes(thir, const_expr)];
+        if !matches!(
+            const_expr.kind,
+            ExprKind::Literal { .. } | ExprKind::NonHirLiteral { .. } | ExprKind::NamedConst { .. }
+        ) {
+            return None;
+        }
+        let const_ = as_constant_inner(const_expr, |_| None, self.tcx).const_;
+        let value = const_.try_eval_bits(self.tcx, self.param_env)?;
+        Some((var_expr, var, value))
+    }
+
+    /// Lowers the chain starting at the `if` expression `expr_id` into `destination`, with a
+    /// switch on the variable in place of its conditions.
+    pub(crate) fn switch_if_chain(
+        &mut self,
+        destination: Place<'tcx>,
+        mut block: BasicBlock,
+        expr_id: ExprId,
+        chain: EqualityIfChain,
+    ) -> BlockAnd<()> {
+        let var_span = self.thir[chain.var].span;
+        let place = unpack!(block = self.as_place(block, chain.var));
+        let then_blocks: Vec<_> = chain.values.iter().map(|_| self.cfg.start_new_block()).collect();
+        let otherwise_block = self.cfg.start_new_block();
+        let targets = SwitchTargets::new(
+            iter::zip(chain.values, then_blocks.iter().copied()),
+            otherwise_block,
+        );
+        let discr = Operand::Copy(place);
+        self.cfg.terminate(
+            block,
+            self.source_info(var_span),
+            TerminatorKind::SwitchInt { discr, targets },
+        );
+        self.lower_if_chain_branches(destination, expr_id, &then_blocks, otherwise_block)
+    }
+
+    /// Lowers the branches of the `if`s of the chain in `expr_id`, the `then` branch of each `if`
+    /// from the block in `then_blocks` that the switch takes for it, in the same scopes as when
+    /// the conditions are lowered.
+    fn lower_if_chain_branches(
+        &mut self,
+        destination: Place<'tcx>,
+        expr_id: ExprId,
+        then_blocks: &[BasicBlock],
+        otherwise_block: BasicBlock,
+    ) -> BlockAnd<()> {
+        let expr = &self.thir[expr_id];
+        match expr.kind {
+            // The rest of the `else` branches, which aren't part of the chain.
+            _ if then_blocks.is_empty() => {
+                self.expr_into_dest(destination, otherwise_block, expr_id)
+            }
+            ExprKind::Scope { region_scope, lint_level, value } => {
+                let region_scope = (region_scope, self.source_info(expr.span));
+                self.in_scope(region_scope, lint_level, |this| {
+                    this.lower_if_chain_branches(destination, value, then_blocks, otherwise_block)
+                })
+            }
+            ExprKind::If { then, else_opt, if_then_scope, .. } => {
+                let expr_span = expr.span;
+                let then_scope = (if_then_scope, self.source_info(self.thir[then].span));
+                let then_end = unpack!(self.in_scope(then_scope, LintLevel::Inherited, |this| {
+                    this.expr_into_dest(destination, then_blocks[0], then)
+                }));
+                let else_end = match else_opt {
+                    Some(else_expr) => unpack!(self.lower_if_chain_branches(
+                        destination,
+                        else_expr,
+                        &then_blocks[1..],
+                        otherwise_block,
+                    )),
+                    None => {
+                        // The implicit `else {}` of the last `if`.
+                        let source_info = self.source_info(expr_span.shrink_to_hi());
+                        let tcx = self.tcx;
+                        self.cfg.push_assign_unit(otherwise_block, source_info, destination, tcx);
+                        otherwise_block
+                    }
+                };
+
+                let source_info = self.source_info(expr_span);
+                let join_block = self.cfg.start_new_block();
+                self.cfg.goto(then_end, source_info, join_block);
+                self.cfg.goto(else_end, source_info, join_block);
+                join_block.unit()
+            }
+            _ => bug!("expected an `if` of an equality chain, found {:?}", expr.kind),
+        }
+    }
+}
+
+fn peel_scopes(thir: &Thir<'_>, mut expr_id: ExprId) -> ExprId {
+    while let ExprKind::Scope { value, .. } = thir[expr_id].kind {
+        expr_id = value;
+    }
+    expr_id
+}
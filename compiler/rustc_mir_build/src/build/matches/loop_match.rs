@@ -0,0 +1,130 @@
+//! Lowering of the `match` of a `#[loop_match]` loop:
+//!
+//! ```ignore (illustrative)
+//! #[loop_match]
+//! loop {
+//!     state = 'blk: {
+//!         match state {
+//!             State::A => {
+//!                 #[const_continue]
+//!                 break 'blk State::B;
+//!             }
+//!             State::B => break,
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! A `#[const_continue]` assigns its value to `state`, like the `break` it is written as, but
+//! then jumps straight to the arm that the value selects when that arm is known while building
+//! MIR, instead of going back to the loop head and through the tests of the `match` again. This
+//! gives interpreters and parsers written as state machines one jump per transition.
+
+use crate::build::expr::as_constant::as_constant_inner;
+use crate::build::expr::as_place::PlaceBuilder;
+use crate::build::expr::category::Category;
+use crate::build::matches::ArmBodies;
+use crate::build::{BlockAnd, Builder};
+use rustc_middle::mir::{self, BasicBlock, Place};
+use rustc_middle::thir::{ArmId, ExprId, ExprKind, Pat, PatKind};
+use rustc_span::Span;
+use rustc_target::abi::VariantIdx;
+
+/// A `#[const_continue]` value, as far as it is needed to pick the arm it selects.
+enum StateValue<'tcx> {
+    Const(mir::Const<'tcx>),
+    /// A unit variant of an enum.
+    Variant(VariantIdx),
+}
+
+impl<'a, 'tcx> Builder<'a, 'tcx> {
+    /// Lowers the `match state { arms }` of a `#[loop_match]`, writing the next state to
+    /// `state_place`. This is a `match` like any other, except that a `#[const_continue]` in the
+    /// arms may enter another arm directly.
+    pub(crate) fn loop_match_expr(
+        &mut self,
+        state_place: Place<'tcx>,
+        block: BasicBlock,
+        arms: &[ArmId],
+        span: Span,
+        scrutinee_span: Span,
+    ) -> BlockAnd<()> {
+        let source_info = self.source_info(scrutinee_span);
+        self.cfg.push_place_mention(block, source_info, state_place);
+        let scrutinee_place = PlaceBuilder::from(state_place);
+        self.lower_match(
+            ArmBodies::Into(state_place),
+            block,
+            scrutinee_place,
+            arms,
+            span,
+            scrutinee_span,
+            true,
+        )
+    }
+
+    /// The index of the arm among `arms` that a `#[const_continue]` of `value` enters: the first
+    /// arm whose pattern matches `value`. Returns `None` if that isn't known before runtime, for
+    /// this arm or for an arm before it.
+    pub(crate) fn const_continue_target(&self, arms: &[ArmId], value: ExprId) -> Option<usize> {
+        let value = self.state_value(value)?;
+        for (index, &arm) in arms.iter().enumerate() {
+            if self.pat_matches_state(&self.thir[arm].pattern, &value)? {
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    fn state_value(&self, value: ExprId) -> Option<StateValue<'tcx>> {
+        let expr = &self.thir[value];
+        match expr.kind {
+            ExprKind::Scope { value, .. } => self.state_value(value),
+            ExprKind::Adt(ref adt) if adt.adt_def.is_enum() && adt.fields.is_empty() => {
+                Some(StateValue::Variant(adt.variant_index))
+            }
+            _ if Category::of(&expr.kind) == Some(Category::Constant) => {
+                Some(StateValue::Const(as_constant_inner(expr, |_| None, self.tcx).const_))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether `pat` matches `value`, or `None` if that's only known at runtime.
+    fn pat_matches_state(&self, pat: &Pat<'tcx>, value: &StateValue<'tcx>) -> Option<bool> {
+        let (tcx, param_env) = (self.tcx, self.param_env);
+        match (&pat.kind, value) {
+            (PatKind::Wild | PatKind::Binding { subpattern: None, .. }, _) => Some(true),
+            (
+                PatKind::Binding { subpattern: Some(subpattern), .. }
+                | PatKind::AscribeUserType { subpattern, .. }
+                | PatKind::InlineConstant { subpattern, .. },
+                _,
+            ) => self.pat_matches_state(subpattern, value),
+            (PatKind::Constant { value: pat_value }, StateValue::Const(value)) => {
+                let pat_bits = pat_value.try_eval_bits(tcx, param_env)?;
+                Some(pat_bits == value.try_eval_bits(tcx, param_env)?)
+            }
+            (PatKind::Range(range), StateValue::Const(value)) => {
+                range.contains(*value, tcx, param_env)
+            }
+            (PatKind::Variant { variant_index, .. }, StateValue::Variant(value)) => {
+                // The value has no fields, so the subpatterns of its variant match it.
+                Some(variant_index == value)
+            }
+            (PatKind::Leaf { .. }, StateValue::Variant(_)) => Some(true),
+            (PatKind::Or { pats }, _) => {
+                let mut matches = Some(false);
+                for pat in pats.iter() {
+                    match self.pat_matches_state(pat, value) {
+                        Some(true) => return Some(true),
+                        Some(false) => {}
+                        None => matches = None,
+                    }
+                }
+                matches
+            }
+            _ => None,
+        }
+    }
+}
@@ -1,12 +1,59 @@
+use rustc_hir::RangeEnd;
+use rustc_middle::mir::interpret::Allocation;
 use rustc_middle::mir::*;
 use rustc_middle::thir::{self, *};
 use rustc_middle::ty::{self, Ty, TypeVisitableExt};
+use rustc_span::Span;
 
 use crate::build::expr::as_place::{PlaceBase, PlaceBuilder};
+use crate::build::matches::char_class::CharClass;
 use crate::build::matches::{FlatPat, MatchPair, TestCase};
 use crate::build::Builder;
 
+/// The smallest number of consecutive constants in an or-pattern that we test as a range instead.
+/// A range test takes two comparisons, so shorter runs are better left to a `SwitchInt`.
+const MIN_COALESCED_CONSTANTS: usize = 4;
+
+/// The smallest number of constants in an or-pattern that we test as a set of bits instead. A bit
+/// test takes a comparison and a shift, so fewer constants are better left to a `SwitchInt`.
+const MIN_BIT_SET_CONSTANTS: usize = 4;
+
+/// The smallest number of `char` constants and ranges in an or-pattern that we test with a table
+/// of the `char`s they match instead, see [`CharClass`].
+const MIN_CHAR_CLASS_ALTERNATIVES: usize = 16;
+
+/// Or-patterns are tested as a set of bits if their constants are less than this far apart, so that
+/// the mask fits in a `u128`.
+const MAX_BIT_SET_SPAN: u128 = 128;
+
+/// The smallest number of bytes in a slice pattern of `u8` constants that we compare with a single
+/// call to `PartialEq::eq` instead of testing each byte in turn.
+const MIN_COMPARED_BYTES: usize = 2;
+
 impl<'a, 'tcx> Builder<'a, 'tcx> {
+    /// Returns the temporary holding the result of calling `deref()` or `deref_mut()` on `place`
+    /// for a deref pattern.
+    ///
+    /// The deref patterns of the same place in a decision tree share their temporary, so that
+    /// their candidates are sorted into the same `Deref` test and the call happens once per path
+    /// through the tree.
+    fn deref_temp(
+        &mut self,
+        place: Option<Place<'tcx>>,
+        target_ty: Ty<'tcx>,
+        mutability: Mutability,
+        span: Span,
+    ) -> Place<'tcx> {
+        let ty = Ty::new_ref(self.tcx, self.tcx.lifetimes.re_erased, target_ty, mutability);
+        let Some(place) = place else { return self.temp(ty, span) };
+        if let Some(&temp) = self.deref_temps.get(&(place, mutability)) {
+            return temp;
+        }
+        let temp = self.temp(ty, span);
+        self.deref_temps.insert((place, mutability), temp);
+        temp
+    }
+
     /// Builds and returns [`MatchPair`] trees, one for each pattern in
     /// `subpatterns`, representing the fields of a [`PatKind::Variant`] or
     /// [`PatKind::Leaf`].
@@ -76,6 +123,145 @@ fn prefix_slice_suffix<'pat>(
             MatchPair::new(place, subpattern, self)
         }));
     }
+
+    /// Returns the values of the alternatives of an or-pattern of type `ty` if they are all integer
+    /// or `char` constants, sorted and without duplicates. Each value comes with its bits, whose
+    /// sign bit is flipped for signed integers so that they are ordered like the values.
+    fn or_pattern_constants(
+        &self,
+        ty: Ty<'tcx>,
+        pats: &[Box<Pat<'tcx>>],
+    ) -> Option<Vec<(u128, Const<'tcx>)>> {
+        let size = ty.primitive_size(self.tcx);
+        let sign_bit = if ty.is_signed() { 1 << (size.bits() - 1) } else { 0 };
+        let mut values = pats
+            .iter()
+            .map(|pat| match pat.kind {
+                PatKind::Constant { value } => {
+                    Some((value.try_eval_bits(self.tcx, self.param_env)? ^ sign_bit, value))
+                }
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        values.sort_unstable_by_key(|&(bits, _)| bits);
+        values.dedup_by_key(|(bits, _)| *bits);
+        Some(values)
+    }
+
+    /// If the alternatives of an or-pattern of type `ty` are integer or `char` constants that form
+    /// a run of at least [`MIN_COALESCED_CONSTANTS`] consecutive values, returns the range they
+    /// cover. For example `0 | 1 | 2 | 3` is tested like `0..=3`, which keeps `SwitchInt`s small
+    /// and avoids expanding the or-pattern into one subcandidate per value.
+    ///
+    /// Used internally by [`MatchPair::new`].
+    fn coalesce_constants(&self, ty: Ty<'tcx>, pats: &[Box<Pat<'tcx>>]) -> Option<PatRange<'tcx>> {
+        if !(ty.is_integral() || ty.is_char()) || pats.len() < MIN_COALESCED_CONSTANTS {
+            return None;
+        }
+        let values = self.or_pattern_constants(ty, pats)?;
+        if values.len() < MIN_COALESCED_CONSTANTS
+            || values.windows(2).any(|pair| pair[0].0 + 1 != pair[1].0)
+        {
+            return None;
+        }
+        Some(PatRange {
+            lo: PatRangeBoundary::Finite(values[0].1),
+            hi: PatRangeBoundary::Finite(values[values.len() - 1].1),
+            end: RangeEnd::Included,
+            ty,
+        })
+    }
+
+    /// If the alternatives of an or-pattern of type `char` are at least
+    /// [`MIN_CHAR_CLASS_ALTERNATIVES`] constants and ranges, returns a table of the `char`s they
+    /// match. Character classes from lexer generators, which list dozens of scattered `char`s and
+    /// ranges, are then tested with two lookups instead of a `SwitchInt` with a target per `char`.
+    /// Not in `#[constant_time_match]` functions, where the lookups would load from an address
+    /// that depends on the `char`.
+    ///
+    /// Used internally by [`MatchPair::new`].
+    fn char_class(&self, ty: Ty<'tcx>, pats: &[Box<Pat<'tcx>>]) -> Option<CharClass<'tcx>> {
+        if !ty.is_char() || pats.len() < MIN_CHAR_CLASS_ALTERNATIVES || self.constant_time_matches {
+            return None;
+        }
+        let code_point = |value: Const<'tcx>| value.try_eval_bits(self.tcx, self.param_env);
+        let ranges = pats
+            .iter()
+            .map(|pat| match pat.kind {
+                PatKind::Constant { value } => {
+                    let c = code_point(value)? as u32;
+                    Some((c, c))
+                }
+                PatKind::Range(ref range) => {
+                    let lo = code_point(range.lo.as_finite()?)? as u32;
+                    let hi = code_point(range.hi.as_finite()?)? as u32;
+                    match range.end {
+                        RangeEnd::Included => Some((lo, hi)),
+                        RangeEnd::Excluded => Some((lo, hi.checked_sub(1)?)),
+                    }
+                }
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        CharClass::new(self.tcx, &ranges)
+    }
+
+    /// If the alternatives of an or-pattern of integer type `ty` are at least
+    /// [`MIN_BIT_SET_CONSTANTS`] constants that differ by less than [`MAX_BIT_SET_SPAN`], returns
+    /// the smallest of them and the mask of their offsets from it. For example `1 | 3 | 5 | 7 | 9`
+    /// is tested by looking up bit `x - 1` of `0b101010101`, instead of with a `SwitchInt` that has
+    /// a target for each value. Not in `#[constant_time_match]` functions, where the lookup is
+    /// guarded by a branch on whether `x - 1` is in the mask.
+    ///
+    /// Used internally by [`MatchPair::new`].
+    fn constant_bit_set(
+        &self,
+        ty: Ty<'tcx>,
+        pats: &[Box<Pat<'tcx>>],
+    ) -> Option<(Const<'tcx>, u128)> {
+        if !ty.is_integral() || pats.len() < MIN_BIT_SET_CONSTANTS || self.constant_time_matches {
+            return None;
+        }
+        let values = self.or_pattern_constants(ty, pats)?;
+        let (base_bits, base) = values[0];
+        if values.len() < MIN_BIT_SET_CONSTANTS
+            || values[values.len() - 1].0 - base_bits >= MAX_BIT_SET_SPAN
+        {
+            return None;
+        }
+        let mask = values.iter().fold(0, |mask, &(bits, _)| mask | (1 << (bits - base_bits)));
+        Some((base, mask))
+    }
+
+    /// If `prefix` is a list of at least [`MIN_COMPARED_BYTES`] `u8` constants making up a slice
+    /// pattern of type `ty`, such as `b"GET"` used on a `&[u8]`, returns them as a single `&[u8]`
+    /// constant. Once the length of the slice has been tested, it is compared with this constant
+    /// all at once, which `<[u8]>::eq` does with `memcmp`.
+    ///
+    /// Const contexts, where `PartialEq::eq` can't be called, keep testing each byte.
+    ///
+    /// Used internally by [`MatchPair::new`].
+    fn byte_slice_constant(&self, ty: Ty<'tcx>, prefix: &[Box<Pat<'tcx>>]) -> Option<Const<'tcx>> {
+        let tcx = self.tcx;
+        if !matches!(*ty.kind(), ty::Slice(elem_ty) if elem_ty == tcx.types.u8)
+            || prefix.len() < MIN_COMPARED_BYTES
+            || tcx.hir().body_const_context(self.def_id).is_some()
+        {
+            return None;
+        }
+        let bytes = prefix
+            .iter()
+            .map(|pat| match pat.kind {
+                PatKind::Constant { value } => {
+                    Some(value.try_eval_bits(tcx, self.param_env)? as u8)
+                }
+                _ => None,
+            })
+            .collect::<Option<Vec<u8>>>()?;
+        let allocation = tcx.mk_const_alloc(Allocation::from_bytes_byte_aligned_immutable(bytes));
+        let value = ConstValue::Slice { data: allocation, meta: prefix.len() as u64 };
+        Some(Const::Val(value, Ty::new_imm_ref(tcx, tcx.lifetimes.re_static, ty)))
+    }
 }
 
 impl<'pat, 'tcx> MatchPair<'pat, 'tcx> {
@@ -112,8 +298,21 @@ pub(in crate::build) fn new(
         let test_case = match pattern.kind {
             PatKind::Wild | PatKind::Error(_) => default_irrefutable(),
 
-            PatKind::Or { ref pats } => TestCase::Or {
-                pats: pats.iter().map(|pat| FlatPat::new(place_builder.clone(), pat, cx)).collect(),
+            PatKind::Or { ref pats } => match cx.coalesce_constants(pattern.ty, pats) {
+                Some(range) if range.is_full_range(cx.tcx) == Some(true) => default_irrefutable(),
+                Some(range) => TestCase::Range(cx.tcx.arena.dropless.alloc(range)),
+                None if let Some((base, mask)) = cx.constant_bit_set(pattern.ty, pats) => {
+                    TestCase::BitSet { base, mask }
+                }
+                None if let Some(class) = cx.char_class(pattern.ty, pats) => {
+                    TestCase::CharClass(class)
+                }
+                None => TestCase::Or {
+                    pats: pats
+                        .iter()
+                        .map(|pat| FlatPat::new(place_builder.clone(), pat, cx))
+                        .collect(),
+                },
             },
 
             PatKind::Range(ref range) => {
@@ -191,7 +390,16 @@ pub(in crate::build) fn new(
                 default_irrefutable()
             }
             PatKind::Slice { ref prefix, ref slice, ref suffix } => {
-                cx.prefix_slice_suffix(&mut subpairs, &place_builder, prefix, slice, suffix);
+                if place.is_some()
+                    && slice.is_none()
+                    && suffix.is_empty()
+                    && let Some(value) = cx.byte_slice_constant(pattern.ty, prefix)
+                {
+                    let test_case = TestCase::Constant { value };
+                    subpairs.push(MatchPair { place, test_case, subpairs: Vec::new(), pattern });
+                } else {
+                    cx.prefix_slice_suffix(&mut subpairs, &place_builder, prefix, slice, suffix);
+                }
 
                 if prefix.is_empty() && slice.is_some() && suffix.is_empty() {
                     default_irrefutable()
@@ -207,17 +415,11 @@ pub(in crate::build) fn new(
                 let downcast_place = place_builder.downcast(adt_def, variant_index); // `(x as Variant)`
                 subpairs = cx.field_match_pairs(downcast_place, subpatterns);
 
-                let irrefutable = adt_def.variants().iter_enumerated().all(|(i, v)| {
-                    i == variant_index || {
-                        (cx.tcx.features().exhaustive_patterns
-                            || cx.tcx.features().min_exhaustive_patterns)
-                            && !v
-                                .inhabited_predicate(cx.tcx, adt_def)
-                                .instantiate(cx.tcx, args)
-                                .apply_ignore_module(cx.tcx, cx.param_env)
-                    }
-                }) && (adt_def.did().is_local()
-                    || !adt_def.is_variant_list_non_exhaustive());
+                let irrefutable = adt_def
+                    .variants()
+                    .indices()
+                    .all(|i| i == variant_index || cx.is_variant_uninhabited(adt_def, args, i))
+                    && (adt_def.did().is_local() || !adt_def.is_variant_list_non_exhaustive());
                 if irrefutable {
                     default_irrefutable()
                 } else {
@@ -236,12 +438,7 @@ pub(in crate::build) fn new(
             }
 
             PatKind::DerefPattern { ref subpattern, mutability } => {
-                // Create a new temporary for each deref pattern.
-                // FIXME(deref_patterns): dedup temporaries to avoid multiple `deref()` calls?
-                let temp = cx.temp(
-                    Ty::new_ref(cx.tcx, cx.tcx.lifetimes.re_erased, subpattern.ty, mutability),
-                    pattern.span,
-                );
+                let temp = cx.deref_temp(place, subpattern.ty, mutability, pattern.span);
                 subpairs.push(MatchPair::new(PlaceBuilder::from(temp).deref(), subpattern, cx));
                 TestCase::Deref { temp, mutability }
             }
@@ -5,32 +5,70 @@
 //! This also includes code for pattern bindings in `let` statements and
 //! function parameters.
 
+use crate::build::expr::as_constant::as_constant_inner;
 use crate::build::expr::as_place::PlaceBuilder;
+use crate::build::expr::category::Category;
 use crate::build::scope::DropKind;
 use crate::build::ForGuard::{self, OutsideGuard, RefWithinGuard};
-use crate::build::{BlockAnd, BlockAndExtension, Builder};
+use crate::build::{BlockAnd, BlockAndExtension, BlockFrame, Builder};
 use crate::build::{GuardFrame, GuardFrameLocal, LocalsForNode};
-use rustc_data_structures::{fx::FxIndexMap, stack::ensure_sufficient_stack};
-use rustc_hir::{BindingMode, ByRef};
+use crate::errors::{DuplicatedGuardSideEffects, LargeMatchTree};
+use char_class::CharClass;
+use perfect_hash::PerfectHash;
+use rustc_ast::LitKind;
+use rustc_data_structures::{
+    fx::{FxIndexMap, FxIndexSet},
+    stack::ensure_sufficient_stack,
+};
+use rustc_hir::{BindingMode, ByRef, LangItem, RangeEnd};
+use rustc_index::IndexVec;
 use rustc_middle::bug;
 use rustc_middle::middle::region;
+use rustc_middle::mir::interpret::Allocation;
 use rustc_middle::mir::{self, *};
 use rustc_middle::thir::{self, *};
 use rustc_middle::ty::{self, CanonicalUserTypeAnnotation, Ty};
-use rustc_span::symbol::Symbol;
+use rustc_session::config::{MatchLowering, MatchOtherwise, OptLevel};
+use rustc_session::lint::builtin::{DUPLICATED_GUARD_SIDE_EFFECTS, LARGE_MATCH_TREES};
+use rustc_span::source_map::Spanned;
+use rustc_span::symbol::{sym, Symbol};
 use rustc_span::{BytePos, Pos, Span};
 use rustc_target::abi::VariantIdx;
 use tracing::{debug, instrument};
 use util::visit_bindings;
 
 // helper functions, broken out by category:
+mod char_class;
+mod constant_time;
+mod desugared;
+mod dump;
+mod if_chain;
+mod loop_match;
 mod match_pair;
+mod packed_bools;
+mod perfect_hash;
 mod simplify;
+mod single_switch;
 mod test;
 mod util;
 
+pub(crate) use dump::DecisionTreeDump;
+pub(crate) use util::forced_guard_fake_borrow_kind;
+
+/// The smallest number of variants of a fieldless enum for which a `match` mapping each variant to
+/// its discriminant is lowered to a cast, see [`Builder::is_discriminant_cast`].
+const MIN_DISCRIMINANT_CAST_VARIANTS: usize = 5;
+
+/// The smallest number of alternatives of an or-pattern with a guard for which the guard is lowered
+/// once for all alternatives, see [`Builder::bind_and_guard_or_candidate`]. With fewer
+/// alternatives, duplicating the guard is cheaper than switching on the matched alternative.
+/// `if let` guards are always lowered once, so that their `let` bindings are bound only once, and
+/// so are all guards with `-Copt-level=z`, where the smaller code is preferred.
+const MIN_SHARED_GUARD_ALTERNATIVES: usize = 3;
+
 use std::assert_matches::assert_matches;
 use std::borrow::Borrow;
+use std::iter;
 use std::mem;
 
 /// Arguments to [`Builder::then_else_break_inner`] that are usually forwarded
@@ -47,6 +85,16 @@ struct ThenElseArgs {
     declare_let_bindings: DeclareLetBindings,
 }
 
+/// What the arms of a lowered `match` do with the values of their bodies.
+#[derive(Clone, Copy)]
+enum ArmBodies<'tcx> {
+    /// Write them to the destination of the `match`.
+    Into(Place<'tcx>),
+    /// Branch on them, for a `match` in condition position; see
+    /// [`Builder::then_else_break_branch`].
+    Branch(ThenElseArgs),
+}
+
 /// Should lowering a `let` expression also declare its bindings?
 ///
 /// Used by [`Builder::lower_let_expr`] when lowering [`ExprKind::Let`].
@@ -120,13 +168,18 @@ fn then_else_break_inner(
         let expr_span = expr.span;
 
         match expr.kind {
-            ExprKind::LogicalOp { op: op @ LogicalOp::And, lhs, rhs } => {
+            // An `&&` or `||` lowered without branches is evaluated like any other condition.
+            ExprKind::LogicalOp { op: op @ LogicalOp::And, lhs, rhs }
+                if !this.lazy_bool_is_branchless(lhs, rhs) =>
+            {
                 this.visit_coverage_branch_operation(op, expr_span);
                 let lhs_then_block = unpack!(this.then_else_break_inner(block, lhs, args));
                 let rhs_then_block = unpack!(this.then_else_break_inner(lhs_then_block, rhs, args));
                 rhs_then_block.unit()
             }
-            ExprKind::LogicalOp { op: op @ LogicalOp::Or, lhs, rhs } => {
+            ExprKind::LogicalOp { op: op @ LogicalOp::Or, lhs, rhs }
+                if !this.lazy_bool_is_branchless(lhs, rhs) =>
+            {
                 this.visit_coverage_branch_operation(op, expr_span);
                 let local_scope = this.local_scope();
                 let (lhs_success_block, failure_block) =
@@ -192,6 +245,72 @@ fn then_else_break_inner(
                 })
             }
             ExprKind::Use { source } => this.then_else_break_inner(block, source, args),
+            // An `if` or a `match` branches through its branches or arms, which branch to the
+            // targets of the condition directly instead of evaluating it into a temporary.
+            ExprKind::If { cond, then, else_opt: Some(else_expr), if_then_scope }
+                if this.coverage_info.is_none() =>
+            {
+                let then_span = this.thir[then].span;
+                let then_source_info = this.source_info(then_span);
+                let condition_scope = this.local_scope();
+                let branch_args = ThenElseArgs { temp_scope_override: None, ..args };
+
+                let then_and_else_blocks = this.in_scope(
+                    (if_then_scope, then_source_info),
+                    LintLevel::Inherited,
+                    |this| {
+                        let source_info = if this.is_let(cond) {
+                            let variable_scope =
+                                this.new_source_scope(then_span, LintLevel::Inherited);
+                            this.source_scope = variable_scope;
+                            SourceInfo { span: then_span, scope: variable_scope }
+                        } else {
+                            this.source_info(then_span)
+                        };
+                        let (then_block, else_block) =
+                            this.in_if_then_scope(condition_scope, then_span, |this| {
+                                this.then_else_break(
+                                    block,
+                                    cond,
+                                    Some(condition_scope),
+                                    source_info,
+                                    DeclareLetBindings::Yes,
+                                )
+                            });
+
+                        // The `else` paths of the `then` branch leave the whole condition, so
+                        // it's lowered outside of the if-then scope of the inner condition.
+                        let then_block =
+                            unpack!(this.then_else_break_branch(then_block, then, branch_args));
+                        then_block.and(else_block)
+                    },
+                );
+                let (then_block, else_block);
+                else_block = unpack!(then_block = then_and_else_blocks);
+                let else_block =
+                    unpack!(this.then_else_break_branch(else_block, else_expr, branch_args));
+
+                let success_block = this.cfg.start_new_block();
+                this.cfg.goto(then_block, args.variable_source_info, success_block);
+                this.cfg.goto(else_block, args.variable_source_info, success_block);
+                success_block.unit()
+            }
+            ExprKind::Match { scrutinee, ref arms, .. } if this.coverage_info.is_none() => {
+                let scrutinee_span = this.thir[scrutinee].span;
+                let mut block = block;
+                let scrutinee_place =
+                    unpack!(block = this.lower_scrutinee(block, scrutinee, scrutinee_span));
+                let branch_args = ThenElseArgs { temp_scope_override: None, ..args };
+                this.lower_match(
+                    ArmBodies::Branch(branch_args),
+                    block,
+                    scrutinee_place,
+                    arms,
+                    expr_span,
+                    scrutinee_span,
+                    false,
+                )
+            }
             ExprKind::Let { expr, ref pat } => this.lower_let_expr(
                 block,
                 expr,
@@ -232,6 +351,48 @@ fn then_else_break_inner(
         }
     }
 
+    /// Lowers a branch of an `if` or an arm of a `match` in condition position, like the `true`
+    /// and `flag` of `if match k { A => true, _ => flag } { ... }`. A literal `true` continues to
+    /// the returned block and a literal `false` breaks to the `else` target, without a test.
+    fn then_else_break_branch(
+        &mut self,
+        block: BasicBlock,
+        expr_id: ExprId,
+        args: ThenElseArgs,
+    ) -> BlockAnd<()> {
+        let expr = &self.thir[expr_id];
+        match expr.kind {
+            ExprKind::Scope { region_scope, lint_level, value } => {
+                let region_scope = (region_scope, self.source_info(expr.span));
+                self.in_scope(region_scope, lint_level, |this| {
+                    this.then_else_break_branch(block, value, args)
+                })
+            }
+            ExprKind::Block { block: ast_block } => {
+                let Block { region_scope, ref stmts, expr: tail, targeted_by_break, .. } =
+                    self.thir[ast_block];
+                match tail {
+                    Some(tail) if stmts.is_empty() && !targeted_by_break => {
+                        let region_scope = (region_scope, self.source_info(expr.span));
+                        self.in_scope(region_scope, LintLevel::Inherited, |this| {
+                            this.then_else_break_branch(block, tail, args)
+                        })
+                    }
+                    _ => self.then_else_break_inner(block, expr_id, args),
+                }
+            }
+            ExprKind::Literal { lit, neg: false } if let LitKind::Bool(value) = lit.node => {
+                if value {
+                    block.unit()
+                } else {
+                    self.break_for_else(block, self.source_info(expr.span));
+                    self.cfg.start_new_block().unit()
+                }
+            }
+            _ => self.then_else_break_inner(block, expr_id, args),
+        }
+    }
+
     /// Generates MIR for a `match` expression.
     ///
     /// The MIR that we generate for a match looks like this.
@@ -269,8 +430,8 @@ fn then_else_break_inner(
     ///
     /// 1. Evaluate the scrutinee and add the PlaceMention of it ([Builder::lower_scrutinee]).
     /// 2. Create the decision tree ([Builder::lower_match_tree]).
-    /// 3. Determine the fake borrows that are needed from the places that were
-    ///    matched against and create the required temporaries for them
+    /// 3. Determine the fake borrows that each guard needs from the places that
+    ///    are matched against and create the required temporaries for them
     ///    ([util::collect_fake_borrows]).
     /// 4. Create everything else: the guards and the arms ([Builder::lower_match_arms]).
     ///
@@ -360,22 +521,254 @@ pub(crate) fn match_expr(
         let scrutinee_place =
             unpack!(block = self.lower_scrutinee(block, scrutinee_id, scrutinee_span));
 
+        if self.scrutinee_diverges(scrutinee_id) {
+            // The arms can't be reached, so there is no need for a match tree, only for the
+            // evaluation of the scrutinee.
+            let source_info = self.source_info(scrutinee_span);
+            self.cfg.terminate(block, source_info, TerminatorKind::Unreachable);
+            return self.cfg.start_new_block().unit();
+        }
+
+        if let Some(scrutinee) = scrutinee_place.try_to_place(self)
+            && self.is_discriminant_cast(scrutinee, destination, arms)
+        {
+            let tcx = self.tcx;
+            let discr_ty = scrutinee.ty(&self.local_decls, tcx).ty.discriminant_ty(tcx);
+            let discr = self.temp(discr_ty, scrutinee_span);
+            let source_info = self.source_info(scrutinee_span);
+            self.cfg.push_assign(block, source_info, discr, Rvalue::Discriminant(scrutinee));
+            let destination_ty = destination.ty(&self.local_decls, tcx).ty;
+            self.cfg.push_assign(
+                block,
+                self.source_info(span),
+                destination,
+                Rvalue::Cast(CastKind::IntToInt, Operand::Move(discr), destination_ty),
+            );
+            return block.unit();
+        }
+
+        let match_facts = self.match_facts.len();
+        if let Some(fact) = self.constant_scrutinee_fact(scrutinee_id, &scrutinee_place) {
+            self.match_facts.push(fact);
+        }
+        let end = if self.constant_time_matches
+            && let Some(end) =
+                self.constant_time_match(destination, block, &scrutinee_place, arms, span)
+        {
+            end
+        } else if let Some(if_matched) = self.bool_match(arms) {
+            let pat = &self.thir[arms[0]].pattern;
+            self.lower_pattern_into_bool(
+                destination,
+                block,
+                &scrutinee_place,
+                scrutinee_span,
+                pat,
+                if_matched,
+                span,
+            )
+        } else if let Some(end) = self.single_switch_match(
+            destination,
+            block,
+            &scrutinee_place,
+            arms,
+            span,
+            scrutinee_span,
+        ) {
+            end
+        } else if let Some(end) = self.packed_bools_match(
+            destination,
+            block,
+            &scrutinee_place,
+            arms,
+            span,
+            scrutinee_span,
+        ) {
+            end
+        } else {
+            self.lower_match(
+                ArmBodies::Into(destination),
+                block,
+                scrutinee_place,
+                arms,
+                span,
+                scrutinee_span,
+                false,
+            )
+        };
+        self.match_facts.truncate(match_facts);
+        end
+    }
+
+    /// If the scrutinee is a literal or a constant, like `match 3 { ... }` or the
+    /// `match cfg!(unix) { ... }` of code that depends on the target, and has a type tested by a
+    /// `SwitchInt` or an `If`, returns the outcome of these tests. The tests of the scrutinee then
+    /// branch directly to the candidates that match (see [`Builder::implied_test_branch`]), and
+    /// the decision tree has no path to the other arms.
+    fn constant_scrutinee_fact(
+        &mut self,
+        scrutinee_id: ExprId,
+        scrutinee: &PlaceBuilder<'tcx>,
+    ) -> Option<MatchFact<'tcx>> {
+        let mut expr = &self.thir[scrutinee_id];
+        while let ExprKind::Scope { value, .. } = expr.kind {
+            expr = &self.thir[value];
+        }
+        if !matches!(
+            expr.kind,
+            ExprKind::Literal { .. }
+                | ExprKind::NonHirLiteral { .. }
+                | ExprKind::NamedConst { .. }
+                | ExprKind::ConstBlock { .. }
+        ) || !(expr.ty.is_bool() || expr.ty.is_char() || expr.ty.is_integral())
+        {
+            return None;
+        }
+        let place = scrutinee.try_to_place(self)?;
+        // Constants that depend on generic parameters can't be evaluated yet.
+        let bits = self.as_constant(expr).const_.try_eval_bits(self.tcx, self.param_env)?;
+        let (kind, branch) = if expr.ty.is_bool() {
+            (TestKind::If, if bits != 0 { TestBranch::Success } else { TestBranch::Failure })
+        } else {
+            let value = Const::from_bits(self.tcx, bits, self.param_env.and(expr.ty));
+            (TestKind::SwitchInt, TestBranch::Constant(value, bits))
+        };
+        Some(MatchFact { place, kind, branch })
+    }
+
+    /// If `arms` are those of a `match` like the one `matches!` expands to, `pat => true` and
+    /// `_ => false` with no guard, returns the value of the first arm, which may be `false` if
+    /// the second one is `true`. The `match` then only needs the match tree of `pat`, ending in
+    /// blocks that write the `bool`, and no arm blocks.
+    ///
+    /// `pat` must have no bindings, nor type ascriptions, which would need an arm to check them
+    /// in.
+    fn bool_match(&self, arms: &[ArmId]) -> Option<bool> {
+        let &[first, second] = arms else { return None };
+        let (first, second) = (&self.thir[first], &self.thir[second]);
+        if first.guard.is_some()
+            || second.guard.is_some()
+            || !matches!(second.pattern.kind, PatKind::Wild)
+            // Coverage instrumentation counts each arm.
+            || self.tcx.sess.instrument_coverage()
+        {
+            return None;
+        }
+        let mut needs_arm = false;
+        first.pattern.walk_always(|pat| {
+            needs_arm |=
+                matches!(pat.kind, PatKind::Binding { .. } | PatKind::AscribeUserType { .. })
+        });
+        let if_matched = self.bool_literal(first.body)?;
+        (!needs_arm && self.bool_literal(second.body)? != if_matched).then_some(if_matched)
+    }
+
+    fn bool_literal(&self, expr: ExprId) -> Option<bool> {
+        match self.thir[expr].kind {
+            ExprKind::Scope { value, .. } => self.bool_literal(value),
+            ExprKind::Literal { lit, neg: false } => match lit.node {
+                LitKind::Bool(value) => Some(value),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The value of `guard` if it is a literal or a constant, like the `if cfg!(...)` or
+    /// `if DEBUG` of arms expanded from macros. Such guards are not lowered: the arm is entered
+    /// directly if the guard is `true`, and never if it is `false`, see
+    /// [`Builder::bind_and_guard_matched_candidate`].
+    fn constant_guard(&self, guard: ExprId) -> Option<bool> {
+        // Coverage reports the branches of each guard.
+        if self.coverage_info.is_some() {
+            return None;
+        }
+        let mut expr = &self.thir[guard];
+        while let ExprKind::Scope { value, .. } = expr.kind {
+            expr = &self.thir[value];
+        }
+        if !matches!(
+            expr.kind,
+            ExprKind::Literal { .. }
+                | ExprKind::NonHirLiteral { .. }
+                | ExprKind::NamedConst { .. }
+                | ExprKind::ConstBlock { .. }
+        ) {
+            return None;
+        }
+        let const_ = as_constant_inner(expr, |_| None, self.tcx).const_;
+        Some(const_.try_eval_bits(self.tcx, self.param_env)? != 0)
+    }
+
+    /// Lowers a `match` on the already lowered `scrutinee_place`, from the match tree to the
+    /// arms, whose bodies are lowered as `bodies` says. If `const_continuable`, the `match` is
+    /// the one of a `#[loop_match]`, whose arms a `#[const_continue]` may jump to; see
+    /// [`Builder::loop_match_expr`].
+    fn lower_match(
+        &mut self,
+        bodies: ArmBodies<'tcx>,
+        mut block: BasicBlock,
+        scrutinee_place: PlaceBuilder<'tcx>,
+        arms: &[ArmId],
+        span: Span,
+        scrutinee_span: Span,
+        const_continuable: bool,
+    ) -> BlockAnd<()> {
+        block = self.hoist_shared_guards(block, arms);
+
         let mut arm_candidates = self.create_match_candidates(&scrutinee_place, arms);
 
+        // A single arm without a guard whose pattern needs no test, like the `match` of many macro
+        // expansions, binds right where the match starts, without a match tree.
+        if let [(arm, candidate)] = &mut arm_candidates[..]
+            && arm.guard.is_none()
+            && candidate.match_pairs.is_empty()
+            && candidate.subcandidates.is_empty()
+            && !const_continuable
+            && self.decision_tree_dump.is_none()
+        {
+            candidate.pre_binding_block = Some(block);
+            return self.lower_match_arms(
+                bodies,
+                scrutinee_place,
+                scrutinee_span,
+                arm_candidates,
+                self.source_info(span),
+                vec![Vec::new()],
+            );
+        }
+
         let match_has_guard = arm_candidates.iter().any(|(_, candidate)| candidate.has_guard);
         let mut candidates =
             arm_candidates.iter_mut().map(|(_, candidate)| candidate).collect::<Vec<_>>();
 
         let match_start_span = span.shrink_to_lo().to(scrutinee_span);
 
-        // The set of places that we are creating fake borrows of. If there are no match guards then
-        // we don't need any fake borrows, so don't track them.
-        let fake_borrow_temps: Vec<(Place<'tcx>, Local, FakeBorrowKind)> = if match_has_guard {
+        // The set of places that we are creating fake borrows of, and the subset of them that each
+        // guard borrows. If there are no match guards then we don't need any fake borrows, so don't
+        // track them.
+        let (fake_borrow_temps, guard_fake_borrows) = if match_has_guard {
             util::collect_fake_borrows(self, &candidates, scrutinee_span, scrutinee_place.base())
         } else {
-            Vec::new()
+            (Vec::new(), vec![Vec::new(); candidates.len()])
         };
 
+        // A guard may be left early, e.g. through `?` in `Some(x) if x > y? => ...`. On that edge,
+        // the fake borrows are as dead as they are after the match, so we give them storage scoped
+        // to the match. Their `StorageDead` then gets emitted on the exit edges, together with the
+        // drops of the guard bindings.
+        if arms.iter().any(|&arm| {
+            self.thir[arm].guard.is_some_and(|guard| util::guard_may_exit_early(self.thir, guard))
+        }) {
+            let source_info = self.source_info(scrutinee_span);
+            let match_scope = self.local_scope();
+            for &(_, temp, _) in &fake_borrow_temps {
+                self.cfg
+                    .push(block, Statement { source_info, kind: StatementKind::StorageLive(temp) });
+                self.schedule_drop(scrutinee_span, match_scope, temp, DropKind::Storage);
+            }
+        }
+
         self.lower_match_tree(
             block,
             scrutinee_span,
@@ -385,13 +778,41 @@ pub(crate) fn match_expr(
             false,
         );
 
+        if const_continuable {
+            let ArmBodies::Into(state_place) = bodies else {
+                bug!("the arms of a `#[loop_match]` write the next state")
+            };
+            // An arm can only be entered directly where its pattern matched if that's a single
+            // block, and if it has no guard that could send the `match` on to later arms.
+            let arm_blocks = arm_candidates
+                .iter()
+                .map(|(arm, candidate)| {
+                    if arm.guard.is_none() && candidate.subcandidates.is_empty() {
+                        candidate.pre_binding_block
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            return self.in_const_continuable_scope(state_place, arms, arm_blocks, span, |this| {
+                this.lower_match_arms(
+                    bodies,
+                    scrutinee_place,
+                    scrutinee_span,
+                    arm_candidates,
+                    this.source_info(span),
+                    guard_fake_borrows,
+                )
+            });
+        }
+
         self.lower_match_arms(
-            destination,
+            bodies,
             scrutinee_place,
             scrutinee_span,
             arm_candidates,
             self.source_info(span),
-            fake_borrow_temps,
+            guard_fake_borrows,
         )
     }
 
@@ -411,6 +832,342 @@ fn lower_scrutinee(
         block.and(scrutinee_place_builder)
     }
 
+    /// Whether evaluating the scrutinee never finishes, like `loop {}` or a call of a function
+    /// returning `!`. A place of type `!` is not evaluated by the `match`, so it doesn't count.
+    fn scrutinee_diverges(&self, scrutinee_id: ExprId) -> bool {
+        let mut scrutinee = &self.thir[scrutinee_id];
+        while let ExprKind::Scope { value, .. } = scrutinee.kind {
+            scrutinee = &self.thir[value];
+        }
+        scrutinee.ty.is_never()
+            && matches!(Category::of(&scrutinee.kind), Some(Category::Rvalue(_)))
+    }
+
+    /// Whether the crate is built with `-Copt-level=z`, for which match lowering prefers smaller
+    /// code to faster code: it makes no copies of candidates, tests small switches with chains of
+    /// comparisons (see [`Builder::terminate_switch`]), and lowers the guard of an or-pattern once
+    /// for all its alternatives.
+    fn optimizes_for_size(&self) -> bool {
+        self.tcx.sess.opts.optimize == OptLevel::SizeMin
+    }
+
+    /// The block terminated by `Unreachable` that the edges of the match tree which can't be
+    /// taken point to. It is created the first time it is needed, with `source_info`, and shared
+    /// by all the matches of the body, so that each of them doesn't add its own.
+    fn unreachable_block(&mut self, source_info: SourceInfo) -> BasicBlock {
+        *self.unreachable_block.get_or_insert_with(|| {
+            let block = self.cfg.start_new_block();
+            self.cfg.terminate(block, source_info, TerminatorKind::Unreachable);
+            block
+        })
+    }
+
+    /// The block that the edges of the match tree which only values that are invalid for their
+    /// type can take, like the otherwise edge of a switch with a target for each variant of an
+    /// enum, point to. With `-Zmatch-otherwise=trap` or `-Zmatch-otherwise=abort`, it calls the
+    /// `match_otherwise_trap` lang item or `panic_nounwind`, so that such values handed out by
+    /// unsafe code or FFI are caught, and it is shared by all the matches of the body. Returns
+    /// `None` by default, when these edges are unreachable.
+    ///
+    /// `const` bodies can't call these functions, so their edges stay unreachable. Evaluating them
+    /// at compile time reports invalid values anyway. Other bodies report an error if the lang
+    /// item is missing, rather than silently leaving the edges unreachable.
+    fn invalid_value_block(&mut self, source_info: SourceInfo) -> Option<BasicBlock> {
+        if self.invalid_value_block.is_some() {
+            return self.invalid_value_block;
+        }
+        let tcx = self.tcx;
+        let span = source_info.span;
+        let (item, args) = match tcx.sess.opts.unstable_opts.match_otherwise {
+            MatchOtherwise::Unreachable => return None,
+            MatchOtherwise::Trap => (LangItem::MatchOtherwiseTrap, [].into()),
+            MatchOtherwise::Abort => {
+                let message = "invalid value reached the otherwise block of an exhaustive `match`";
+                let allocation = Allocation::from_bytes_byte_aligned_immutable(message.as_bytes());
+                let allocation = tcx.mk_const_alloc(allocation);
+                let value = ConstValue::Slice { data: allocation, meta: message.len() as u64 };
+                let message =
+                    self.literal_operand(span, Const::Val(value, Ty::new_static_str(tcx)));
+                (LangItem::PanicNounwind, [Spanned { node: message, span }].into())
+            }
+        };
+        if tcx.hir().body_const_context(self.def_id).is_some() {
+            return None;
+        }
+        let func = tcx.require_lang_item(item, Some(span));
+
+        let block = self.cfg.start_new_block();
+        let destination = self.temp(tcx.types.never, span);
+        self.cfg.terminate(
+            block,
+            source_info,
+            TerminatorKind::Call {
+                func: Operand::function_handle(tcx, func, [], span),
+                args,
+                destination,
+                target: None,
+                unwind: UnwindAction::Unreachable,
+                call_source: CallSource::Misc,
+                fn_span: span,
+            },
+        );
+        self.invalid_value_block = Some(block);
+        Some(block)
+    }
+
+    /// Whether the arms of a `match` on `scrutinee` map each variant of a fieldless enum to its
+    /// discriminant, converted to the integer type of `destination`, like
+    /// `match e { A => 0, B => 1, C => 2 }`. Derives of serialization traits write such matches
+    /// often. The `match` is then lowered to a read of the discriminant and a cast.
+    ///
+    /// Enums with fewer than [`MIN_DISCRIMINANT_CAST_VARIANTS`] variants are left alone, since
+    /// their switch is small and `MatchBranchSimplification` turns it into the same cast.
+    fn is_discriminant_cast(
+        &self,
+        scrutinee: Place<'tcx>,
+        destination: Place<'tcx>,
+        arms: &[ArmId],
+    ) -> bool {
+        let tcx = self.tcx;
+        let ty::Adt(adt_def, _) = *scrutinee.ty(&self.local_decls, tcx).ty.kind() else {
+            return false;
+        };
+        let destination_ty = destination.ty(&self.local_decls, tcx).ty;
+        if !adt_def.is_enum()
+            || adt_def.variants().len() < MIN_DISCRIMINANT_CAST_VARIANTS
+            || adt_def.variants().iter().any(|variant| !variant.fields.is_empty())
+            || !destination_ty.is_integral()
+            // Coverage instrumentation counts each arm.
+            || tcx.sess.instrument_coverage()
+        {
+            return false;
+        }
+
+        let mut values =
+            IndexVec::<VariantIdx, Option<u128>>::from_elem_n(None, adt_def.variants().len());
+        for &arm in arms {
+            let arm = &self.thir[arm];
+            if arm.guard.is_some() {
+                return false;
+            }
+            let mut body = &self.thir[arm.body];
+            while let ExprKind::Scope { value, .. } = body.kind {
+                body = &self.thir[value];
+            }
+            if body.ty != destination_ty
+                || !matches!(body.kind, ExprKind::Literal { .. } | ExprKind::NonHirLiteral { .. })
+            {
+                return false;
+            }
+            let Some(value) =
+                as_constant_inner(body, |_| None, tcx).const_.try_eval_bits(tcx, self.param_env)
+            else {
+                return false;
+            };
+            // Later arms for the same variant are unreachable.
+            let all_variants = unit_variants(&arm.pattern, &mut |variant_index| {
+                values[variant_index].get_or_insert(value);
+            });
+            if !all_variants {
+                return false;
+            }
+        }
+
+        let destination_size = destination_ty.primitive_size(tcx);
+        adt_def.discriminants(tcx).all(|(variant_index, discr)| {
+            let discr_size = discr.ty.primitive_size(tcx);
+            let bits =
+                if discr.ty.is_signed() { discr_size.sign_extend(discr.val) } else { discr.val };
+            values[variant_index] == Some(destination_size.truncate(bits))
+        })
+    }
+
+    /// Evaluates once, before the decision tree, the guards that consecutive arms without bindings
+    /// share, e.g. the `DEBUG` in several `_ if DEBUG => ...` arms expanded from a macro. These
+    /// guards are then lowered to a switch on the temporary holding their value.
+    ///
+    /// Only guards that can neither panic nor have side effects are hoisted (see
+    /// [`util::same_pure_expr`]), so that evaluating them earlier, or when none of the arms
+    /// matches, is not observable. Nothing that runs between the guards of consecutive arms can
+    /// change their value either, since it only runs the tests of the patterns and the guards
+    /// themselves.
+    fn hoist_shared_guards(&mut self, mut block: BasicBlock, arms: &[ArmId]) -> BasicBlock {
+        // Coverage reports the branches of each guard separately.
+        if self.coverage_info.is_some() {
+            return block;
+        }
+        let thir = self.thir;
+        let shareable_guard = |arm: ArmId| {
+            let arm = &thir[arm];
+            let mut has_bindings = false;
+            arm.pattern.walk_always(|pat| {
+                has_bindings |= matches!(pat.kind, PatKind::Binding { .. });
+            });
+            arm.guard.filter(|_| !has_bindings)
+        };
+        for pair in arms.windows(2) {
+            if let (Some(first), Some(second)) =
+                (shareable_guard(pair[0]), shareable_guard(pair[1]))
+                && util::same_pure_expr(thir, first, second)
+                && self.constant_guard(first).is_none()
+            {
+                let value = match self.hoisted_guards.get(&first) {
+                    Some(&value) => value,
+                    None => {
+                        let value = self.temp(self.tcx.types.bool, thir[first].span);
+                        unpack!(block = self.expr_into_dest(value, block, first));
+                        self.hoisted_guards.insert(first, value);
+                        value
+                    }
+                };
+                self.hoisted_guards.insert(second, value);
+            }
+        }
+        block
+    }
+
+    /// Finds the arms whose bodies are the same as the body of another arm (see
+    /// [`util::same_arm_body`]), as is common in `match`es expanded from macros, so that these
+    /// bodies are only lowered once. For each arm, this returns the index in `arms` of the first
+    /// arm with the same body, if there are several.
+    ///
+    /// Only arms that bind no variables, in their pattern or in an `if let` guard, share their
+    /// body, so that nothing is left to drop in the scope of an arm once it jumps to the body of
+    /// another one. The shared body is lowered in the lint scope of the first arm, so arms with
+    /// lint level attributes of their own keep their body too.
+    fn shared_arm_bodies(&self, arms: &[&Arm<'tcx>]) -> Vec<Option<usize>> {
+        let mut shared = vec![None; arms.len()];
+        // Coverage counts the executions of each arm body.
+        if self.coverage_info.is_some() {
+            return shared;
+        }
+        let mut first_arms: Vec<usize> = Vec::new();
+        for (index, arm) in arms.iter().enumerate() {
+            let mut has_bindings =
+                arm.guard.is_some_and(|guard| util::is_if_let_guard(self.thir, guard));
+            arm.pattern.walk_always(|pat| {
+                has_bindings |= matches!(pat.kind, PatKind::Binding { .. });
+            });
+            if has_bindings || util::has_lint_level_attrs(self.tcx, arm.lint_level) {
+                continue;
+            }
+            match first_arms
+                .iter()
+                .find(|&&first| util::same_arm_body(self.thir, arms[first].body, arm.body))
+            {
+                Some(&first) => {
+                    shared[first] = Some(first);
+                    shared[index] = Some(first);
+                }
+                None => first_arms.push(index),
+            }
+        }
+        shared
+    }
+
+    /// Finds the arms whose bodies only call the `panic` lang item with a string literal (see
+    /// [`util::const_panic_call`]), like the `unreachable!()` arms of `match`es validating an
+    /// enum, so that they store their message and branch to a single call lowered by
+    /// [`Builder::lower_panic_trap`]. For each arm, this returns the call and its message.
+    ///
+    /// `panic` reports the location of its caller, so this is only done with
+    /// `-Zlocation-detail=none`, which hides it. Like for [`Builder::shared_arm_bodies`], the arms
+    /// must bind no variables, and arms that already share their body are left alone.
+    fn panic_arms(
+        &self,
+        arms: &[&Arm<'tcx>],
+        shared_bodies: &[Option<usize>],
+    ) -> Vec<Option<(ExprId, ExprId)>> {
+        let mut panic_arms = vec![None; arms.len()];
+        let location_detail = self.tcx.sess.opts.unstable_opts.location_detail;
+        if self.coverage_info.is_some()
+            || location_detail.file
+            || location_detail.line
+            || location_detail.column
+        {
+            return panic_arms;
+        }
+        for (index, arm) in arms.iter().enumerate() {
+            if shared_bodies[index].is_some()
+                || arm.guard.is_some_and(|guard| util::is_if_let_guard(self.thir, guard))
+            {
+                continue;
+            }
+            let mut has_bindings = false;
+            arm.pattern.walk_always(|pat| {
+                has_bindings |= matches!(pat.kind, PatKind::Binding { .. });
+            });
+            if !has_bindings {
+                panic_arms[index] = util::const_panic_call(self.tcx, self.thir, arm.body);
+            }
+        }
+        // A single arm has nothing to share its call with.
+        if panic_arms.iter().flatten().count() < 2 {
+            panic_arms.fill(None);
+        }
+        panic_arms
+    }
+
+    /// Lowers the block that the arms found by [`Builder::panic_arms`] branch to, which calls the
+    /// `panic` function of `call` with the message stored in the returned place.
+    ///
+    /// This is done in the scope of the `match` rather than of any of the arms, and the `panic`
+    /// function is referred to directly, rather than by lowering the callee of one of the arms.
+    fn lower_panic_trap(&mut self, call: ExprId, message: ExprId) -> (Place<'tcx>, BasicBlock) {
+        let call_expr = &self.thir[call];
+        let ExprKind::Call { fun, fn_span, .. } = call_expr.kind else {
+            bug!("expected a call to `panic`")
+        };
+        let &ty::FnDef(def_id, args) = self.thir[fun].ty.kind() else {
+            bug!("expected a call to `panic`")
+        };
+        let source_info = self.source_info(call_expr.span);
+        let destination = self.temp(call_expr.ty, call_expr.span);
+        let message_span = self.thir[message].span;
+        let message_place = self.temp(self.thir[message].ty, message_span);
+
+        let block = self.cfg.start_new_block();
+        let func = Operand::function_handle(self.tcx, def_id, args, self.thir[fun].span);
+        let arg = Spanned { node: Operand::Move(message_place), span: message_span };
+        self.cfg.terminate(
+            block,
+            source_info,
+            TerminatorKind::Call {
+                func,
+                args: Box::new([arg]),
+                destination,
+                target: None,
+                unwind: UnwindAction::Continue,
+                call_source: CallSource::Normal,
+                fn_span,
+            },
+        );
+        self.diverge_from(block);
+        (message_place, block)
+    }
+
+    /// With `-Zmatch-elide-moved-binding-storage`, records the immutable by-value bindings of `arm`
+    /// that the first statement of its body moves out of (see [`util::vars_moved_first`]), so that
+    /// [`Builder::storage_live_binding`] emits neither `StorageLive` nor `StorageDead` for them.
+    /// This removes statements from the MIR, for tools that consume it unoptimized.
+    ///
+    /// Such a binding can't be used again once it is moved from, nor be assigned a new value, so
+    /// its local being live for the whole body instead makes no difference to borrowck.
+    fn elide_moved_binding_storage(&mut self, arm: &Arm<'tcx>) {
+        let moved = util::vars_moved_first(self.thir, arm.body);
+        if moved.is_empty() {
+            return;
+        }
+        arm.pattern.walk_always(|pat| {
+            if let PatKind::Binding { mode: BindingMode::NONE, var, ty, .. } = pat.kind
+                && moved.contains(&var)
+                && !ty.is_copy_modulo_regions(self.tcx, self.param_env)
+            {
+                self.storage_elided_bindings.insert(var);
+            }
+        });
+    }
+
     /// Create the initial `Candidate`s for a `match` expression.
     fn create_match_candidates<'pat>(
         &mut self,
@@ -430,37 +1187,97 @@ fn create_match_candidates<'pat>(
             .map(|arm| {
                 let arm = &self.thir[arm];
                 let arm_has_guard = arm.guard.is_some();
-                let arm_candidate =
-                    Candidate::new(scrutinee.clone(), &arm.pattern, arm_has_guard, self);
+                let arm_is_cold = self.is_cold_arm(arm);
+                let arm_candidate = Candidate::new(
+                    scrutinee.clone(),
+                    &arm.pattern,
+                    arm_has_guard,
+                    arm_is_cold,
+                    self,
+                );
                 (arm, arm_candidate)
             })
             .collect()
     }
 
+    /// Whether `arm` is marked `#[cold]`, which is only honored with
+    /// `#![feature(cold_match_arms)]`.
+    fn is_cold_arm(&self, arm: &Arm<'tcx>) -> bool {
+        // The lint level of an arm is always explicit, and gives us its attributes.
+        let LintLevel::Explicit(hir_id) = arm.lint_level else { return false };
+        self.tcx.features().cold_match_arms
+            && self.tcx.hir().attrs(hir_id).iter().any(|attr| attr.has_name(sym::cold))
+    }
+
+    /// Lints the guard of `arm` if it has side effects, since it is lowered separately for each
+    /// alternative of the or-pattern of `candidate`, and may run once for each of them.
+    fn lint_duplicated_guard(
+        &self,
+        arm: &Arm<'tcx>,
+        guard: ExprId,
+        candidate: &Candidate<'_, 'tcx>,
+    ) {
+        let alternatives = candidate.count_leaves();
+        // The lint level of an arm is always explicit.
+        let LintLevel::Explicit(hir_id) = arm.lint_level else { return };
+        if alternatives > 1 && util::guard_has_side_effects(self.thir, guard) {
+            let span = self.thir[guard].span;
+            self.tcx.emit_node_span_lint(
+                DUPLICATED_GUARD_SIDE_EFFECTS,
+                hir_id,
+                span,
+                DuplicatedGuardSideEffects { guard: span, pattern: arm.pattern.span, alternatives },
+            );
+        }
+    }
+
     /// Lower the bindings, guards and arm bodies of a `match` expression.
     ///
     /// The decision tree should have already been created
     /// (by [Builder::lower_match_tree]).
     ///
-    /// `outer_source_info` is the SourceInfo for the whole match.
+    /// `outer_source_info` is the SourceInfo for the whole match, and `guard_fake_borrows` holds
+    /// the fake borrows taken by the guard of each arm.
     fn lower_match_arms(
         &mut self,
-        destination: Place<'tcx>,
+        bodies: ArmBodies<'tcx>,
         scrutinee_place_builder: PlaceBuilder<'tcx>,
         scrutinee_span: Span,
         arm_candidates: Vec<(&'_ Arm<'tcx>, Candidate<'_, 'tcx>)>,
         outer_source_info: SourceInfo,
-        fake_borrow_temps: Vec<(Place<'tcx>, Local, FakeBorrowKind)>,
+        guard_fake_borrows: Vec<Vec<(Place<'tcx>, Local, FakeBorrowKind)>>,
     ) -> BlockAnd<()> {
+        let mut arm_candidates: Vec<_> =
+            arm_candidates.into_iter().zip(guard_fake_borrows).collect();
+        // Lower the arms marked `#[cold]` last, so that their blocks come after the others.
+        arm_candidates.sort_by_key(|((_, candidate), _)| candidate.is_cold);
+
+        let shared_bodies = self.shared_arm_bodies(
+            &arm_candidates.iter().map(|((arm, _), _)| *arm).collect::<Vec<_>>(),
+        );
+        // The block each arm body that is shared with later arms is lowered from.
+        let mut body_blocks = vec![None; arm_candidates.len()];
+        let panic_arms = self.panic_arms(
+            &arm_candidates.iter().map(|((arm, _), _)| *arm).collect::<Vec<_>>(),
+            &shared_bodies,
+        );
+        // The place holding the message of the panic arms, and the block calling `panic` with it.
+        let panic_trap = panic_arms
+            .iter()
+            .flatten()
+            .next()
+            .map(|&(call, message)| self.lower_panic_trap(call, message));
+
         let arm_end_blocks: Vec<_> = arm_candidates
             .into_iter()
-            .map(|(arm, candidate)| {
+            .enumerate()
+            .filter_map(|(index, ((arm, candidate), fake_borrow_temps))| {
                 debug!("lowering arm {:?}\ncandidate = {:?}", arm, candidate);
 
                 let arm_source_info = self.source_info(arm.span);
                 let arm_scope = (arm.scope, arm_source_info);
                 let match_scope = self.local_scope();
-                self.in_scope(arm_scope, arm.lint_level, |this| {
+                let arm_end = self.in_scope(arm_scope, arm.lint_level, |this| {
                     let old_dedup_scope =
                         mem::replace(&mut this.fixed_temps_scope, Some(arm.scope));
 
@@ -475,6 +1292,9 @@ fn lower_match_arms(
                     //    match foo { _ => () };
                     // };
                     // ```
+                    if this.tcx.sess.opts.unstable_opts.match_elide_moved_binding_storage {
+                        this.elide_moved_binding_storage(arm);
+                    }
                     let scrutinee_place = scrutinee_place_builder.try_to_place(this);
                     let opt_scrutinee_place =
                         scrutinee_place.as_ref().map(|place| (Some(place), scrutinee_span));
@@ -486,7 +1306,7 @@ fn lower_match_arms(
                         opt_scrutinee_place,
                     );
 
-                    let arm_block = this.bind_pattern(
+                    let mut arm_block = this.bind_pattern(
                         outer_source_info,
                         candidate,
                         &fake_borrow_temps,
@@ -501,8 +1321,59 @@ fn lower_match_arms(
                         this.source_scope = source_scope;
                     }
 
-                    this.expr_into_dest(destination, arm_block, arm.body)
-                })
+                    if let Some((_, message)) = panic_arms[index] {
+                        let (message_place, trap_block) = panic_trap.unwrap();
+                        let message = &this.thir[message];
+                        let source_info = this.source_info(message.span);
+                        let message = this.as_constant(message);
+                        this.cfg.push_assign_constant(
+                            arm_block,
+                            source_info,
+                            message_place,
+                            message,
+                        );
+                        this.cfg.goto(arm_block, arm_source_info, trap_block);
+                        return this.cfg.start_new_block().and(false);
+                    }
+
+                    match shared_bodies[index] {
+                        // The body was already lowered for an earlier arm. This arm has no
+                        // bindings, so there is nothing left in its scope to drop, and the
+                        // block after it can't be reached.
+                        Some(shared) if shared != index => {
+                            let body_block = body_blocks[shared].unwrap();
+                            this.cfg.goto(arm_block, arm_source_info, body_block);
+                            return this.cfg.start_new_block().and(false);
+                        }
+                        Some(_) => {
+                            let body_block = this.cfg.start_new_block();
+                            this.cfg.goto(arm_block, arm_source_info, body_block);
+                            body_blocks[index] = Some(body_block);
+                            arm_block = body_block;
+                        }
+                        None => {}
+                    }
+
+                    unpack!(
+                        arm_block = match bodies {
+                            ArmBodies::Into(destination) => {
+                                this.expr_into_dest(destination, arm_block, arm.body)
+                            }
+                            ArmBodies::Branch(args) => {
+                                this.then_else_break_branch(arm_block, arm.body, args)
+                            }
+                        }
+                    );
+                    arm_block.and(true)
+                });
+                let arm_end_block;
+                let lowered_body = unpack!(arm_end_block = arm_end);
+                if !lowered_body {
+                    // The arm branched away before the end of its scope, and nothing reaches the
+                    // block popping it.
+                    self.cfg.terminate(arm_end_block, arm_source_info, TerminatorKind::Unreachable);
+                }
+                lowered_body.then_some(arm_end_block)
             })
             .collect();
 
@@ -513,10 +1384,10 @@ fn lower_match_arms(
             outer_source_info.span.with_lo(outer_source_info.span.hi() - BytePos::from_usize(1)),
         );
         for arm_block in arm_end_blocks {
-            let block = &self.cfg.basic_blocks[arm_block.0];
+            let block = &self.cfg.basic_blocks[arm_block];
             let last_location = block.statements.last().map(|s| s.source_info);
 
-            self.cfg.goto(unpack!(arm_block), last_location.unwrap_or(end_brace), end_block);
+            self.cfg.goto(arm_block, last_location.unwrap_or(end_brace), end_block);
         }
 
         self.source_scope = outer_source_info.scope;
@@ -550,8 +1421,32 @@ fn bind_pattern(
                 arm_match_scope,
                 ScheduleDrops::Yes,
                 emit_storage_live,
+                &mut Vec::new(),
+            )
+        } else if let Some((arm, match_scope)) = arm_match_scope
+            && let Some(guard) = arm.guard
+            && self.constant_guard(guard).is_none()
+            && (candidate.count_leaves() >= MIN_SHARED_GUARD_ALTERNATIVES
+                || util::is_if_let_guard(self.thir, guard)
+                || self.optimizes_for_size())
+        {
+            self.bind_and_guard_or_candidate(
+                outer_source_info,
+                candidate,
+                fake_borrow_temps,
+                scrutinee_span,
+                arm,
+                guard,
+                match_scope,
+                emit_storage_live,
             )
         } else {
+            if let Some((arm, _)) = arm_match_scope
+                && let Some(guard) = arm.guard
+            {
+                self.lint_duplicated_guard(arm, guard, &candidate);
+            }
+
             // It's helpful to avoid scheduling drops multiple times to save
             // drop elaboration from having to clean up the extra drops.
             //
@@ -569,6 +1464,8 @@ fn bind_pattern(
             let target_block = self.cfg.start_new_block();
             let mut schedule_drops = ScheduleDrops::Yes;
             let arm = arm_match_scope.unzip().0;
+            let has_guard = arm.is_some_and(|arm| arm.guard.is_some());
+            let mut ascribed = Vec::new();
             // We keep a stack of all of the bindings and type ascriptions
             // from the parent candidates that we visit, that also need to
             // be bound for each candidate.
@@ -576,6 +1473,19 @@ fn bind_pattern(
                 candidate,
                 &mut Vec::new(),
                 &mut |leaf_candidate, parent_data| {
+                    if leaf_candidate.binds_nothing(parent_data, has_guard) {
+                        self.false_edges(
+                            leaf_candidate.pre_binding_block.unwrap(),
+                            target_block,
+                            leaf_candidate.next_candidate_start_block,
+                            self.source_info(leaf_candidate.extra_data.span),
+                            FalseEdgeOrigin {
+                                arm_span: arm.map(|arm| arm.span),
+                                kind: FalseEdgeKind::BeforeBindings,
+                            },
+                        );
+                        return;
+                    }
                     if let Some(arm) = arm {
                         self.clear_top_scope(arm.scope);
                     }
@@ -587,6 +1497,7 @@ fn bind_pattern(
                         arm_match_scope,
                         schedule_drops,
                         emit_storage_live,
+                        &mut ascribed,
                     );
                     if arm.is_none() {
                         schedule_drops = ScheduleDrops::No;
@@ -612,101 +1523,89 @@ pub(super) fn expr_into_pattern(
         irrefutable_pat: &Pat<'tcx>,
         initializer_id: ExprId,
     ) -> BlockAnd<()> {
-        match irrefutable_pat.kind {
-            // Optimize the case of `let x = ...` to write directly into `x`
-            PatKind::Binding { mode: BindingMode(ByRef::No, _), var, subpattern: None, .. } => {
-                let place = self.storage_live_binding(
-                    block,
-                    var,
-                    irrefutable_pat.span,
-                    OutsideGuard,
-                    ScheduleDrops::Yes,
-                );
-                unpack!(block = self.expr_into_dest(place, block, initializer_id));
-
-                // Inject a fake read, see comments on `FakeReadCause::ForLet`.
-                let source_info = self.source_info(irrefutable_pat.span);
-                self.cfg.push_fake_read(block, source_info, FakeReadCause::ForLet(None), place);
-
-                self.schedule_drop_for_binding(var, irrefutable_pat.span, OutsideGuard);
+        // Optimize the case of `let (a, b) = (x, y)`, with a plain binding for each field, to write
+        // each field directly into its binding instead of into a temporary tuple that is then
+        // moved out of. Other tuple patterns move the fields out of the initializer's temporary.
+        let thir = self.thir;
+        if let PatKind::Leaf { ref subpatterns } = irrefutable_pat.kind
+            && let ExprKind::Scope { region_scope, lint_level, value } = thir[initializer_id].kind
+            && let ExprKind::Tuple { ref fields } = thir[value].kind
+            && subpatterns.len() == fields.len()
+            && subpatterns.iter().enumerate().all(|(i, subpattern)| {
+                subpattern.field.index() == i
+                    && plain_binding(&subpattern.pattern, &mut Vec::new()).is_some()
+            })
+        {
+            let source_info = self.source_info(thir[initializer_id].span);
+            return self.in_scope((region_scope, source_info), lint_level, |this| {
+                this.block_context.push(BlockFrame::SubExpr);
+                for (subpattern, &field) in subpatterns.iter().zip(fields.iter()) {
+                    unpack!(block = this.expr_into_pattern(block, &subpattern.pattern, field));
+                }
+                this.block_context.pop();
                 block.unit()
-            }
+            });
+        }
 
-            // Optimize the case of `let x: T = ...` to write directly
-            // into `x` and then require that `T == typeof(x)`.
-            //
-            // Weirdly, this is needed to prevent the
-            // `intrinsic-move-val.rs` test case from crashing. That
-            // test works with uninitialized values in a rather
-            // dubious way, so it may be that the test is kind of
-            // broken.
-            PatKind::AscribeUserType {
-                subpattern:
-                    box Pat {
-                        kind:
-                            PatKind::Binding {
-                                mode: BindingMode(ByRef::No, _),
-                                var,
-                                subpattern: None,
-                                ..
-                            },
-                        ..
-                    },
-                ascription: thir::Ascription { ref annotation, variance: _ },
-            } => {
-                let place = self.storage_live_binding(
-                    block,
-                    var,
-                    irrefutable_pat.span,
-                    OutsideGuard,
-                    ScheduleDrops::Yes,
-                );
-                unpack!(block = self.expr_into_dest(place, block, initializer_id));
-
-                // Inject a fake read, see comments on `FakeReadCause::ForLet`.
-                let pattern_source_info = self.source_info(irrefutable_pat.span);
-                let cause_let = FakeReadCause::ForLet(None);
-                self.cfg.push_fake_read(block, pattern_source_info, cause_let, place);
+        // Optimize the case of `let x = ...`, `let x: T = ...` or `let x @ _ = ...` to write
+        // directly into `x`, so that a `match` or block initializer builds its result in `x`
+        // instead of in a temporary that is then moved, and require that `T == typeof(x)`.
+        //
+        // Weirdly, the latter is needed to prevent the `intrinsic-move-val.rs` test case from
+        // crashing. That test works with uninitialized values in a rather dubious way, so it may be
+        // that the test is kind of broken.
+        let mut annotations = Vec::new();
+        let Some(var) = plain_binding(irrefutable_pat, &mut annotations) else {
+            let initializer = &self.thir[initializer_id];
+            let place_builder =
+                unpack!(block = self.lower_scrutinee(block, initializer_id, initializer.span));
+            return self.place_into_pattern(block, irrefutable_pat, place_builder, true);
+        };
 
-                let ty_source_info = self.source_info(annotation.span);
+        let place = self.storage_live_binding(
+            block,
+            var,
+            irrefutable_pat.span,
+            OutsideGuard,
+            ScheduleDrops::Yes,
+        );
+        unpack!(block = self.expr_into_dest(place, block, initializer_id));
 
-                let base = self.canonical_user_type_annotations.push(annotation.clone());
-                self.cfg.push(
-                    block,
-                    Statement {
-                        source_info: ty_source_info,
-                        kind: StatementKind::AscribeUserType(
-                            Box::new((place, UserTypeProjection { base, projs: Vec::new() })),
-                            // We always use invariant as the variance here. This is because the
-                            // variance field from the ascription refers to the variance to use
-                            // when applying the type to the value being matched, but this
-                            // ascription applies rather to the type of the binding. e.g., in this
-                            // example:
-                            //
-                            // ```
-                            // let x: T = <expr>
-                            // ```
-                            //
-                            // We are creating an ascription that defines the type of `x` to be
-                            // exactly `T` (i.e., with invariance). The variance field, in
-                            // contrast, is intended to be used to relate `T` to the type of
-                            // `<expr>`.
-                            ty::Invariant,
-                        ),
-                    },
-                );
+        // Inject a fake read, see comments on `FakeReadCause::ForLet`.
+        let pattern_source_info = self.source_info(irrefutable_pat.span);
+        let cause_let = FakeReadCause::ForLet(None);
+        self.cfg.push_fake_read(block, pattern_source_info, cause_let, place);
 
-                self.schedule_drop_for_binding(var, irrefutable_pat.span, OutsideGuard);
-                block.unit()
-            }
+        for annotation in annotations {
+            let ty_source_info = self.source_info(annotation.span);
 
-            _ => {
-                let initializer = &self.thir[initializer_id];
-                let place_builder =
-                    unpack!(block = self.lower_scrutinee(block, initializer_id, initializer.span));
-                self.place_into_pattern(block, irrefutable_pat, place_builder, true)
-            }
+            let base = self.canonical_user_type_annotations.push(annotation.clone());
+            self.cfg.push(
+                block,
+                Statement {
+                    source_info: ty_source_info,
+                    kind: StatementKind::AscribeUserType(
+                        Box::new((place, UserTypeProjection { base, projs: Vec::new() })),
+                        // We always use invariant as the variance here. This is because the
+                        // variance field from the ascription refers to the variance to use when
+                        // applying the type to the value being matched, but this ascription
+                        // applies rather to the type of the binding. e.g., in this example:
+                        //
+                        // ```
+                        // let x: T = <expr>
+                        // ```
+                        //
+                        // We are creating an ascription that defines the type of `x` to be exactly
+                        // `T` (i.e., with invariance). The variance field, in contrast, is
+                        // intended to be used to relate `T` to the type of `<expr>`.
+                        ty::Invariant,
+                    ),
+                },
+            );
         }
+
+        self.schedule_drop_for_binding(var, irrefutable_pat.span, OutsideGuard);
+        block.unit()
     }
 
     pub(crate) fn place_into_pattern(
@@ -716,7 +1615,8 @@ pub(crate) fn place_into_pattern(
         initializer: PlaceBuilder<'tcx>,
         set_match_place: bool,
     ) -> BlockAnd<()> {
-        let mut candidate = Candidate::new(initializer.clone(), irrefutable_pat, false, self);
+        let mut candidate =
+            Candidate::new(initializer.clone(), irrefutable_pat, false, false, self);
 
         // For matches and function arguments, the place that is being matched
         // can be set when creating the variables. But the place for
@@ -853,6 +1753,9 @@ pub(crate) fn storage_live_binding(
         schedule_drop: ScheduleDrops,
     ) -> Place<'tcx> {
         let local_id = self.var_local_id(var, for_guard);
+        if for_guard == OutsideGuard && self.storage_elided_bindings.contains(&var) {
+            return Place::from(local_id);
+        }
         let source_info = self.source_info(span);
         self.cfg.push(block, Statement { source_info, kind: StatementKind::StorageLive(local_id) });
         // Although there is almost always scope for given variable in corner cases
@@ -880,6 +1783,11 @@ pub(crate) fn schedule_drop_for_binding(
     /// Visit all of the primary bindings in a patterns, that is, visit the
     /// leftmost occurrence of each variable bound in a pattern. A variable
     /// will occur more than once in an or-pattern.
+    ///
+    /// Since Rust 2024, the occurrences of a variable in the alternatives of an or-pattern may
+    /// differ in whether they bind by reference. The binding mode given to `f` is then only that
+    /// of the leftmost occurrence: each occurrence is still bound in its own mode, see
+    /// `bind_matched_candidate_for_arm_body`.
     pub(super) fn visit_primary_bindings(
         &mut self,
         pattern: &Pat<'tcx>,
@@ -996,7 +1904,8 @@ pub(super) fn visit_primary_bindings(
 }
 
 /// Data extracted from a pattern that doesn't affect which branch is taken. Collected during
-/// pattern simplification and not mutated later.
+/// pattern simplification and only extended later when merging or-pattern alternatives that bind
+/// the same variables (see [`Builder::merge_trivial_subcandidates`]).
 #[derive(Debug, Clone)]
 struct PatternExtraData<'tcx> {
     /// [`Span`] of the original pattern.
@@ -1016,6 +1925,18 @@ impl<'tcx> PatternExtraData<'tcx> {
     fn is_empty(&self) -> bool {
         self.bindings.is_empty() && self.ascriptions.is_empty()
     }
+
+    /// Whether `self` and `other` establish the same bindings from the same places and ascribe the
+    /// same types to the same places, up to spans. This holds for the alternatives of `(x, 0) |
+    /// (x, 1)`.
+    fn binds_like(&self, other: &Self) -> bool {
+        self.bindings.len() == other.bindings.len()
+            && self.ascriptions.len() == other.ascriptions.len()
+            && iter::zip(&self.bindings, &other.bindings).all(|(a, b)| {
+                a.source == b.source && a.var_id == b.var_id && a.binding_mode == b.binding_mode
+            })
+            && iter::zip(&self.ascriptions, &other.ascriptions).all(|(a, b)| a.same_as(b))
+    }
 }
 
 /// A pattern in a form suitable for generating code.
@@ -1076,10 +1997,25 @@ struct Candidate<'pat, 'tcx> {
     /// If the candidate matches, bindings and ascriptions must be established.
     extra_data: PatternExtraData<'tcx>,
 
+    /// Whether this candidate comes from a match arm marked `#[cold]`. The branches of a test
+    /// that only lead to cold candidates are built after the others and hinted as unlikely.
+    is_cold: bool,
+
+    /// Whether this is the last candidate of an irrefutable match tree. Once its match pairs are
+    /// satisfied, no later candidate remains to be tested, so without a guard nothing falls
+    /// through and it doesn't need an otherwise block.
+    ends_irrefutable_tree: bool,
+
     /// If we filled `self.subcandidate`, we store here the span of the or-pattern they came from.
     // Invariant: it is `None` iff `subcandidates.is_empty()`.
     or_span: Option<Span>,
 
+    /// Whether there is a guard and no value can match more than one of `self.subcandidates`. The
+    /// guard then runs at most once whichever subcandidate matches, and no later candidate is
+    /// tested together with them (see [`Builder::expand_and_match_or_candidates`]), which lets
+    /// [`Builder::merge_trivial_subcandidates`] merge them despite the guard.
+    disjoint_subcandidates: bool,
+
     /// The block before the `bindings` have been established.
     pre_binding_block: Option<BasicBlock>,
 
@@ -1098,21 +2034,25 @@ fn new(
         place: PlaceBuilder<'tcx>,
         pattern: &'pat Pat<'tcx>,
         has_guard: bool,
+        is_cold: bool,
         cx: &mut Builder<'_, 'tcx>,
     ) -> Self {
         // Use `FlatPat` to build simplified match pairs, then immediately
         // incorporate them into a new candidate.
-        Self::from_flat_pat(FlatPat::new(place, pattern, cx), has_guard)
+        Self::from_flat_pat(FlatPat::new(place, pattern, cx), has_guard, is_cold)
     }
 
     /// Incorporates an already-simplified [`FlatPat`] into a new candidate.
-    fn from_flat_pat(flat_pat: FlatPat<'pat, 'tcx>, has_guard: bool) -> Self {
+    fn from_flat_pat(flat_pat: FlatPat<'pat, 'tcx>, has_guard: bool, is_cold: bool) -> Self {
         Candidate {
             match_pairs: flat_pat.match_pairs,
             extra_data: flat_pat.extra_data,
             has_guard,
+            is_cold,
+            ends_irrefutable_tree: false,
             subcandidates: Vec::new(),
             or_span: None,
+            disjoint_subcandidates: false,
             otherwise_block: None,
             pre_binding_block: None,
             false_edge_start_block: None,
@@ -1136,6 +2076,67 @@ fn visit_leaves<'a>(&'a mut self, mut visit_leaf: impl FnMut(&'a mut Self)) {
             |_| {},
         );
     }
+
+    /// Returns the number of leaves of this candidate tree.
+    fn count_leaves(&self) -> usize {
+        let mut leaves = 0;
+        traverse_candidate(
+            self,
+            &mut (),
+            &mut |_, _| leaves += 1,
+            |c, _| c.subcandidates.iter(),
+            |_| {},
+        );
+        leaves
+    }
+
+    /// Whether this leaf candidate, whose parents have the given `parent_data`, has nothing to do
+    /// once it matched: no guard to run, and no bindings or type ascriptions to establish. The
+    /// false edge of such a leaf can lead straight to the block its alternatives converge to,
+    /// instead of going through an empty block of its own.
+    fn binds_nothing(&self, parent_data: &[PatternExtraData<'tcx>], has_guard: bool) -> bool {
+        !has_guard
+            && iter::once(&self.extra_data)
+                .chain(parent_data)
+                .all(|extra_data| extra_data.is_empty() && !extra_data.is_never)
+    }
+}
+
+/// Calls `f` with the variant matched by `pat`, or by each alternative of `pat`, and returns `true`
+/// if they are all unit variant patterns.
+fn unit_variants(pat: &Pat<'_>, f: &mut impl FnMut(VariantIdx)) -> bool {
+    match pat.kind {
+        PatKind::Variant { variant_index, ref subpatterns, .. } if subpatterns.is_empty() => {
+            f(variant_index);
+            true
+        }
+        PatKind::Or { ref pats } => pats.iter().all(|pat| unit_variants(pat, f)),
+        _ => false,
+    }
+}
+
+/// Returns the variable bound by value by `pat` if it is a plain binding, possibly under type
+/// ascriptions and with a wildcard subpattern, like `x`, `mut x: T` or `x @ _`, and pushes the
+/// annotations of the ascriptions to `annotations`, outermost first.
+fn plain_binding<'p, 'tcx>(
+    pat: &'p Pat<'tcx>,
+    annotations: &mut Vec<&'p CanonicalUserTypeAnnotation<'tcx>>,
+) -> Option<LocalVarId> {
+    match pat.kind {
+        PatKind::AscribeUserType {
+            ref subpattern,
+            ascription: thir::Ascription { ref annotation, .. },
+        } => {
+            annotations.push(annotation);
+            plain_binding(subpattern, annotations)
+        }
+        PatKind::Binding { mode: BindingMode(ByRef::No, _), var, ref subpattern, .. }
+            if subpattern.as_ref().map_or(true, |pat| matches!(pat.kind, PatKind::Wild)) =>
+        {
+            Some(var)
+        }
+        _ => None,
+    }
 }
 
 /// A depth-first traversal of the `Candidate` and all of its recursive
@@ -1178,12 +2179,25 @@ struct Ascription<'tcx> {
     variance: ty::Variance,
 }
 
+impl<'tcx> Ascription<'tcx> {
+    /// Whether `self` and `other` ascribe the same type to the same place, up to spans.
+    fn same_as(&self, other: &Self) -> bool {
+        self.source == other.source
+            && self.variance == other.variance
+            && self.annotation.user_ty == other.annotation.user_ty
+            && self.annotation.inferred_ty == other.annotation.inferred_ty
+    }
+}
+
 #[derive(Debug, Clone)]
 enum TestCase<'pat, 'tcx> {
     Irrefutable { binding: Option<Binding<'tcx>>, ascription: Option<Ascription<'tcx>> },
     Variant { adt_def: ty::AdtDef<'tcx>, variant_index: VariantIdx },
     Constant { value: mir::Const<'tcx> },
+    StrBytes { bytes: &'tcx [u8], tested: usize },
     Range(&'pat PatRange<'tcx>),
+    BitSet { base: mir::Const<'tcx>, mask: u128 },
+    CharClass(CharClass<'tcx>),
     Slice { len: usize, variable_length: bool },
     Deref { temp: Place<'tcx>, mutability: Mutability },
     Never,
@@ -1235,7 +2249,11 @@ enum TestKind<'tcx> {
         adt_def: ty::AdtDef<'tcx>,
     },
 
-    /// Test what value an integer or `char` has.
+    /// Test what value an integer or `char` has. On types with few values, this also tests range
+    /// patterns, with one target per value in the range. With `-Zmatch-binary-search`, a switch on
+    /// many sparse values is lowered to a binary search over them. With `-Zmatch-range-switch`,
+    /// range patterns are tested this way on every type, and lowered to a binary search over the
+    /// ranges on types with many values.
     SwitchInt,
 
     /// Test what value a `bool` has.
@@ -1247,16 +2265,46 @@ enum TestKind<'tcx> {
         value: Const<'tcx>,
         // Integer types are handled by `SwitchInt`, and constants with ADT
         // types are converted back into patterns, so this can only be `&str`,
-        // `&[T]`, `f32` or `f64`.
+        // `&[T]`, a float type, a raw or function pointer, or `[u8]` for slice
+        // patterns of constant bytes (see `Builder::byte_slice_constant`).
+        // Pointers are compared by address, and floats are compared with
+        // `BinOp::Eq`, so `-0.0` matches `0.0` and NaN constants are rejected
+        // before lowering.
         ty: Ty<'tcx>,
     },
 
+    /// Test what length a `&str` has, as the first step of matching many string constants one
+    /// byte at a time instead of comparing them one after the other.
+    StrLen,
+
+    /// Test what value a perfect hash of a `&str` has, as the first step of matching many string
+    /// constants with a single comparison each. Enabled with `-Zmatch-perfect-hash`.
+    StrHash(Box<PerfectHash>),
+
+    /// Test what value the byte at `index` of a `&str` has, after a [`TestKind::StrLen`] found it
+    /// to be longer than `index`. The string constants matched this way are represented by
+    /// [`TestCase::StrBytes`] once their length is known, which records how many of their bytes
+    /// were tested so far.
+    StrByte { index: usize },
+
     /// Test whether the value falls within an inclusive or exclusive range.
     Range(Box<PatRange<'tcx>>),
 
+    /// Test whether an integer is one of the constants of an or-pattern, by checking that bit
+    /// `value - base` of `mask` is set (see `Builder::constant_bit_set`).
+    BitSet { base: Const<'tcx>, mask: u128 },
+
+    /// Test whether a `char` is one of those matched by an or-pattern of many `char` constants and
+    /// ranges, by looking it up in a two-level table (see `Builder::char_class`).
+    CharClass(CharClass<'tcx>),
+
     /// Test that the length of the slice is equal to `len`.
     Len { len: u64, op: BinOp },
 
+    /// Test what length a slice has, when several slice patterns of fixed length are tested on
+    /// it.
+    SwitchLen,
+
     /// Call `Deref::deref[_mut]` on the value.
     Deref {
         /// Temporary to store the result of `deref()`/`deref_mut()`.
@@ -1278,6 +2326,26 @@ pub(crate) struct Test<'tcx> {
     kind: TestKind<'tcx>,
 }
 
+/// A place whose test is known to have taken a given branch, on the current path of the decision
+/// tree. Later tests of that place can then branch directly, see
+/// [`Builder::implied_test_branch`].
+#[derive(Debug)]
+pub(crate) struct MatchFact<'tcx> {
+    place: Place<'tcx>,
+    kind: TestKind<'tcx>,
+    branch: TestBranch<'tcx>,
+}
+
+/// What makes the decision tree being built large, see [`Builder::lint_large_match_tree`].
+#[derive(Debug, Default)]
+pub(crate) struct MatchTreeStats {
+    /// The number of tests that the decision tree performs.
+    tests: usize,
+    /// The or-patterns expanded before other patterns, and the slice patterns tested for their
+    /// length, which duplicate the tests of the patterns after them.
+    patterns: FxIndexSet<Span>,
+}
+
 /// The branch to be taken after a test.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum TestBranch<'tcx> {
@@ -1285,16 +2353,90 @@ enum TestBranch<'tcx> {
     Success,
     /// Branch corresponding to this constant.
     Constant(Const<'tcx>, u128),
+    /// Branch corresponding to all the values between these two constants, inclusive. Only used
+    /// for [`TestKind::SwitchInt`], see `is_range_switch_ty`.
+    Range(Const<'tcx>, Const<'tcx>),
     /// Branch corresponding to this variant.
     Variant(VariantIdx),
     /// Failure branch for tests with two possible outcomes, and "otherwise" branch for other tests.
     Failure,
 }
 
+/// How expensive a test is, from cheapest to most expensive. When the first candidate has match
+/// pairs on several places, the cheapest test is performed first (see `Builder::pick_test`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum TestCost {
+    /// A switch or comparison on a value that is already available.
+    Cheap,
+    /// A call to `PartialEq::eq`, to compare a string or slice with a constant.
+    Compare,
+    /// A call to `Deref::deref` or `DerefMut::deref_mut`, whose result must then be tested too.
+    Deref,
+}
+
+impl<'tcx> Test<'tcx> {
+    /// Returns every branch this test can take, if they are known before sorting any candidate.
+    /// This isn't the case for [`TestKind::SwitchInt`], whose branches are the constants (and
+    /// ranges) found in the candidates.
+    fn all_branches(&self) -> Option<Vec<TestBranch<'tcx>>> {
+        match self.kind {
+            TestKind::Switch { adt_def } => {
+                Some(adt_def.variants().indices().map(TestBranch::Variant).collect())
+            }
+            TestKind::If
+            | TestKind::Eq { .. }
+            | TestKind::Range(_)
+            | TestKind::BitSet { .. }
+            | TestKind::CharClass(_)
+            | TestKind::Len { .. } => Some(vec![TestBranch::Success, TestBranch::Failure]),
+            TestKind::SwitchInt
+            | TestKind::SwitchLen
+            | TestKind::StrLen
+            | TestKind::StrHash(_)
+            | TestKind::StrByte { .. }
+            | TestKind::Deref { .. }
+            | TestKind::Never => None,
+        }
+    }
+
+    /// How expensive this test is, see [`TestCost`].
+    fn cost(&self) -> TestCost {
+        match self.kind {
+            TestKind::Eq { ty, .. } if !ty.is_floating_point() => TestCost::Compare,
+            TestKind::Deref { .. } => TestCost::Deref,
+            TestKind::Switch { .. }
+            | TestKind::SwitchInt
+            | TestKind::If
+            | TestKind::Eq { .. }
+            | TestKind::StrLen
+            | TestKind::StrHash(_)
+            | TestKind::StrByte { .. }
+            | TestKind::Range(_)
+            | TestKind::BitSet { .. }
+            | TestKind::CharClass(_)
+            | TestKind::Len { .. }
+            | TestKind::SwitchLen
+            | TestKind::Never => TestCost::Cheap,
+        }
+    }
+}
+
 impl<'tcx> TestBranch<'tcx> {
     fn as_constant(&self) -> Option<&Const<'tcx>> {
         if let Self::Constant(v, _) = self { Some(v) } else { None }
     }
+    fn as_range(&self) -> Option<PatRange<'tcx>> {
+        if let Self::Range(lo, hi) = *self {
+            Some(PatRange {
+                lo: PatRangeBoundary::Finite(lo),
+                hi: PatRangeBoundary::Finite(hi),
+                end: RangeEnd::Included,
+                ty: lo.ty(),
+            })
+        } else {
+            None
+        }
+    }
 }
 
 /// `ArmHasGuard` is a wrapper around a boolean flag. It indicates whether
@@ -1314,7 +2456,8 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
     ///
     /// `refutable` indicates whether the candidate list is refutable (for `if let` and `let else`)
     /// or not (for `let` and `match`). In the refutable case we return the block to which we branch
-    /// on failure.
+    /// on failure. In the irrefutable case that block is unreachable, and we return `None` when
+    /// no path leads to it.
     fn lower_match_tree<'pat>(
         &mut self,
         block: BasicBlock,
@@ -1323,11 +2466,38 @@ fn lower_match_tree<'pat>(
         match_start_span: Span,
         candidates: &mut [&mut Candidate<'pat, 'tcx>],
         refutable: bool,
-    ) -> BasicBlock {
+    ) -> Option<BasicBlock> {
+        self.match_duplication_budget = match self.tcx.sess.opts.unstable_opts.match_lowering {
+            MatchLowering::Backtracking => None,
+            // Copies of candidates grow the code of each arm they reach, see `-Zmatch-lowering`.
+            MatchLowering::DecisionTree if self.optimizes_for_size() => Some(0),
+            MatchLowering::DecisionTree => {
+                Some(self.tcx.sess.opts.unstable_opts.match_lowering_budget)
+            }
+        };
+
+        if !refutable && let Some(last) = candidates.last_mut() {
+            last.ends_irrefutable_tree = true;
+        }
+
         // This will generate code to test scrutinee_place and branch to the appropriate arm block.
         // See the doc comment on `match_candidates` for why we have an otherwise block.
+        let first_block = self.cfg.basic_blocks.next_index();
         let otherwise_block =
             self.match_candidates(match_start_span, scrutinee_span, block, candidates);
+        self.emit_decision_tree_dump(scrutinee_span);
+        self.lint_large_match_tree(match_start_span, first_block);
+
+        // The branches that fall through to candidates that don't start with a test, see
+        // `test_candidates`, go to these candidates.
+        let source_info = self.source_info(match_start_span);
+        for (start_block, fallthroughs) in mem::take(&mut self.match_fallthroughs) {
+            for (block, _) in fallthroughs {
+                self.cfg.goto(block, source_info, start_block);
+            }
+        }
+        self.deref_temps.clear();
+        let exhaustive_switch_blocks = mem::take(&mut self.exhaustive_switch_blocks);
 
         // Link each leaf candidate to the `false_edge_start_block` of the next one.
         let mut previous_candidate: Option<&mut Candidate<'_, '_>> = None;
@@ -1341,11 +2511,35 @@ fn lower_match_tree<'pat>(
             });
         }
 
+        // The otherwise edge of a switch on the variants of an enum that has a target for each
+        // variant, or on a scalar that has a target for each of its valid values, can't be taken,
+        // but it usually leads to the tests of the next candidates. Point such edges to an
+        // unreachable block instead, so that codegen can emit a jump table without a default case.
+        if !exhaustive_switch_blocks.is_empty() {
+            let unreachable_block = match otherwise_block {
+                // In irrefutable cases `otherwise_block` is unreachable, see below.
+                Some(otherwise_block) if !refutable => otherwise_block,
+                _ => {
+                    let source_info = self.source_info(scrutinee_span);
+                    self.invalid_value_block(source_info)
+                        .unwrap_or_else(|| self.unreachable_block(source_info))
+                }
+            };
+            for block in exhaustive_switch_blocks {
+                let TerminatorKind::SwitchInt { targets, .. } =
+                    &mut self.cfg.block_data_mut(block).terminator_mut().kind
+                else {
+                    bug!("expected an exhaustive switch")
+                };
+                *targets.all_targets_mut().last_mut().unwrap() = unreachable_block;
+            }
+        }
+
         if refutable {
             // In refutable cases there's always at least one candidate, and we want a false edge to
             // the failure block.
-            previous_candidate.as_mut().unwrap().next_candidate_start_block = Some(otherwise_block)
-        } else {
+            previous_candidate.as_mut().unwrap().next_candidate_start_block = otherwise_block
+        } else if let Some(otherwise_block) = otherwise_block {
             // Match checking ensures `otherwise_block` is actually unreachable in irrefutable
             // cases.
             let source_info = self.source_info(scrutinee_span);
@@ -1372,12 +2566,47 @@ fn lower_match_tree<'pat>(
                 );
             }
 
-            self.cfg.terminate(otherwise_block, source_info, TerminatorKind::Unreachable);
+            match self.invalid_value_block(source_info) {
+                Some(invalid_value_block) => {
+                    self.cfg.goto(otherwise_block, source_info, invalid_value_block)
+                }
+                None => {
+                    self.cfg.terminate(otherwise_block, source_info, TerminatorKind::Unreachable)
+                }
+            }
         }
 
         otherwise_block
     }
 
+    /// Lints the decision tree just built, whose blocks start at `first_block`, if it has more
+    /// blocks than `#![match_size_limit]` allows. By default there is no limit.
+    fn lint_large_match_tree(&mut self, span: Span, first_block: BasicBlock) {
+        let stats = mem::take(&mut self.match_tree_stats);
+        let limit = self.tcx.match_size_limit();
+        let blocks = self.cfg.basic_blocks.len() - first_block.as_usize();
+        if limit.0 == 0 || limit.value_within_limit(blocks) {
+            return;
+        }
+        let lint_root = self.source_scopes[self.source_scope]
+            .local_data
+            .as_ref()
+            .assert_crate_local()
+            .lint_root;
+        self.tcx.emit_node_span_lint(
+            LARGE_MATCH_TREES,
+            lint_root,
+            span,
+            LargeMatchTree {
+                span,
+                patterns: stats.patterns.into_iter().collect(),
+                blocks,
+                tests: stats.tests,
+                limit: limit.0,
+            },
+        );
+    }
+
     /// The main match algorithm. It begins with a set of candidates `candidates` and has the job of
     /// generating code that branches to an appropriate block if the scrutinee matches one of these
     /// candidates. The
@@ -1386,7 +2615,9 @@ fn lower_match_tree<'pat>(
     /// the value, we will set and generate a branch to the appropriate
     /// pre-binding block.
     ///
-    /// If none of the candidates apply, we continue to the returned `otherwise_block`.
+    /// If none of the candidates apply, we continue to the returned `otherwise_block`. There is
+    /// none when the last candidate of an irrefutable match tree is matched without a guard, see
+    /// [`Candidate::ends_irrefutable_tree`].
     ///
     /// It might be surprising that the input can be non-exhaustive.
     /// Indeed, for matches, initially, it is not, because all matches are
@@ -1446,9 +2677,14 @@ fn match_candidates(
         scrutinee_span: Span,
         start_block: BasicBlock,
         candidates: &mut [&mut Candidate<'_, 'tcx>],
-    ) -> BasicBlock {
+    ) -> Option<BasicBlock> {
         ensure_sufficient_stack(|| {
-            self.match_candidates_inner(span, scrutinee_span, start_block, candidates)
+            // The discriminants read while matching `candidates` are only known on the paths that
+            // go through these reads, which don't include the blocks of the following candidates.
+            let cached_discriminants = self.cached_discriminants.len();
+            let block = self.match_candidates_inner(span, scrutinee_span, start_block, candidates);
+            self.cached_discriminants.truncate(cached_discriminants);
+            block
         })
     }
 
@@ -1460,7 +2696,7 @@ fn match_candidates_inner(
         scrutinee_span: Span,
         mut start_block: BasicBlock,
         candidates: &mut [&mut Candidate<'_, 'tcx>],
-    ) -> BasicBlock {
+    ) -> Option<BasicBlock> {
         if let [first, ..] = candidates {
             if first.false_edge_start_block.is_none() {
                 first.false_edge_start_block = Some(start_block);
@@ -1471,18 +2707,18 @@ fn match_candidates_inner(
         let rest = match candidates {
             [] => {
                 // If there are no candidates that still need testing, we're done.
-                return start_block;
+                return Some(start_block);
             }
             [first, remaining @ ..] if first.match_pairs.is_empty() => {
                 // The first candidate has satisfied all its match pairs; we link it up and continue
                 // with the remaining candidates.
-                let remainder_start = self.select_matched_candidate(first, start_block);
+                let remainder_start = self.select_matched_candidate(first, start_block)?;
                 remainder_start.and(remaining)
             }
             candidates if candidates.iter().any(|candidate| candidate.starts_with_or_pattern()) => {
                 // If any candidate starts with an or-pattern, we have to expand the or-pattern before we
                 // can proceed further.
-                self.expand_and_match_or_candidates(span, scrutinee_span, start_block, candidates)
+                self.expand_and_match_or_candidates(span, scrutinee_span, start_block, candidates)?
             }
             candidates => {
                 // The first candidate has some unsatisfied match pairs; we proceed to do more tests.
@@ -1518,35 +2754,43 @@ fn match_candidates_inner(
     /// pre-binding block of the next candidate in the original set of
     /// candidates.
     ///
+    /// The last candidate of an irrefutable match tree gets no otherwise block if it has no guard,
+    /// since nothing can branch to it, and we return `None`.
+    ///
     /// [pre-binding block]: Candidate::pre_binding_block
     /// [otherwise block]: Candidate::otherwise_block
     fn select_matched_candidate(
         &mut self,
         candidate: &mut Candidate<'_, 'tcx>,
         start_block: BasicBlock,
-    ) -> BasicBlock {
+    ) -> Option<BasicBlock> {
         assert!(candidate.otherwise_block.is_none());
         assert!(candidate.pre_binding_block.is_none());
         assert!(candidate.subcandidates.is_empty());
+        self.dump_decision_tree_candidate("matched", candidate);
 
         candidate.pre_binding_block = Some(start_block);
+        if candidate.ends_irrefutable_tree && !candidate.has_guard {
+            return None;
+        }
         let otherwise_block = self.cfg.start_new_block();
         // Create the otherwise block for this candidate, which is the
         // pre-binding block for the next candidate.
         candidate.otherwise_block = Some(otherwise_block);
-        otherwise_block
+        Some(otherwise_block)
     }
 
     /// Takes a list of candidates such that some of the candidates' first match pairs are
     /// or-patterns. This expands as many or-patterns as possible and processes the resulting
-    /// candidates. Returns the unprocessed candidates if any.
+    /// candidates. Returns the unprocessed candidates if any, or `None` if the expanded candidates
+    /// end the match tree and nothing falls through them.
     fn expand_and_match_or_candidates<'pat, 'b, 'c>(
         &mut self,
         span: Span,
         scrutinee_span: Span,
         start_block: BasicBlock,
         candidates: &'b mut [&'c mut Candidate<'pat, 'tcx>],
-    ) -> BlockAnd<&'b mut [&'c mut Candidate<'pat, 'tcx>]> {
+    ) -> Option<BlockAnd<&'b mut [&'c mut Candidate<'pat, 'tcx>]>> {
         // We can't expand or-patterns freely. The rule is:
         // - If a candidate doesn't start with an or-pattern, we include it in
         //   the expansion list as-is (i.e. it "expands" to itself).
@@ -1554,6 +2798,10 @@ fn expand_and_match_or_candidates<'pat, 'b, 'c>(
         //   we can expand it.
         // - If it starts with an or-pattern but also has other match pairs,
         //   we can expand it, but we can't process more candidates after it.
+        // - Likewise if it has a guard and the alternatives of its or-pattern are disjoint, so
+        //   that its subcandidates can be merged (see `merge_trivial_subcandidates`): when the
+        //   guard fails, we then continue with the candidates after it, whichever alternative
+        //   matched.
         //
         // If we didn't stop, the `otherwise` cases could get mixed up. E.g. in the
         // following, or-pattern simplification (in `merge_trivial_subcandidates`) makes it
@@ -1577,7 +2825,8 @@ fn expand_and_match_or_candidates<'pat, 'b, 'c>(
             .position(|candidate| {
                 // If a candidate starts with an or-pattern and has more match pairs,
                 // we can expand it, but we must stop expanding _after_ it.
-                candidate.match_pairs.len() > 1 && candidate.starts_with_or_pattern()
+                candidate.starts_with_or_pattern()
+                    && (candidate.match_pairs.len() > 1 || self.has_disjoint_or_pattern(candidate))
             })
             .map(|pos| pos + 1) // Stop _after_ the found candidate
             .unwrap_or(candidates.len()); // Otherwise, include all candidates
@@ -1588,6 +2837,10 @@ fn expand_and_match_or_candidates<'pat, 'b, 'c>(
         for candidate in candidates_to_expand.iter_mut() {
             if candidate.starts_with_or_pattern() {
                 let or_match_pair = candidate.match_pairs.remove(0);
+                self.dump_decision_tree_or_pattern("expand", or_match_pair.pattern.span);
+                if !candidate.match_pairs.is_empty() {
+                    self.match_tree_stats.patterns.insert(or_match_pair.pattern.span);
+                }
                 // Expand the or-pattern into subcandidates.
                 self.create_or_subcandidates(candidate, or_match_pair);
                 // Collect the newly created subcandidates.
@@ -1616,7 +2869,7 @@ fn expand_and_match_or_candidates<'pat, 'b, 'c>(
             }
         }
 
-        remainder_start.and(remaining_candidates)
+        Some(remainder_start?.and(remaining_candidates))
     }
 
     /// Given a match-pair that corresponds to an or-pattern, expand each subpattern into a new
@@ -1630,10 +2883,38 @@ fn create_or_subcandidates<'pat>(
         let TestCase::Or { pats } = match_pair.test_case else { bug!() };
         debug!("expanding or-pattern: candidate={:#?}\npats={:#?}", candidate, pats);
         candidate.or_span = Some(match_pair.pattern.span);
+        candidate.disjoint_subcandidates = candidate.has_guard && self.are_disjoint(&pats);
         candidate.subcandidates = pats
             .into_vec()
             .into_iter()
-            .map(|flat_pat| Candidate::from_flat_pat(flat_pat, candidate.has_guard))
+            .map(|flat_pat| {
+                Candidate::from_flat_pat(flat_pat, candidate.has_guard, candidate.is_cold)
+            })
+            .collect();
+        candidate.subcandidates[0].false_edge_start_block = candidate.false_edge_start_block;
+    }
+
+    /// Copy a candidate that a test says nothing about into `count` subcandidates, one for each
+    /// branch of the test. This is how `-Zmatch-lowering=decision-tree` avoids testing the same
+    /// place again after the test. As with or-patterns, the bindings stay on the parent candidate,
+    /// so that they are established by whichever copy matches.
+    fn create_copy_subcandidates(&mut self, candidate: &mut Candidate<'_, 'tcx>, count: usize) {
+        assert!(candidate.subcandidates.is_empty() && !candidate.extra_data.is_never);
+        debug!("copying candidate into {count} branches: candidate={:#?}", candidate);
+        let match_pairs = mem::take(&mut candidate.match_pairs);
+        let extra_data = PatternExtraData {
+            span: candidate.extra_data.span,
+            bindings: Vec::new(),
+            ascriptions: Vec::new(),
+            is_never: false,
+        };
+        candidate.or_span = Some(candidate.extra_data.span);
+        candidate.subcandidates = (0..count)
+            .map(|_| {
+                let flat_pat =
+                    FlatPat { match_pairs: match_pairs.clone(), extra_data: extra_data.clone() };
+                Candidate::from_flat_pat(flat_pat, candidate.has_guard, candidate.is_cold)
+            })
             .collect();
         candidate.subcandidates[0].false_edge_start_block = candidate.false_edge_start_block;
     }
@@ -1691,6 +2972,11 @@ fn create_or_subcandidates<'pat>(
     ///      |
     ///     ...
     /// ```
+    ///
+    /// * Otherwise, if there isn't a match guard, `R | S` is still only matched once: `P` and `Q`
+    ///   record which of them matched before jumping to it, and `R` and `S` each switch on that
+    ///   record to bind the variables of the right alternatives (see
+    ///   [`Builder::match_shared_or_suffix`]).
     fn finalize_or_candidate(
         &mut self,
         span: Span,
@@ -1715,12 +3001,32 @@ fn finalize_or_candidate(
                 last_otherwise = leaf_candidate.otherwise_block;
             });
             let remaining_match_pairs = mem::take(&mut candidate.match_pairs);
+            if !candidate.has_guard && candidate.count_leaves() > 1 {
+                self.match_shared_or_suffix(
+                    span,
+                    scrutinee_span,
+                    candidate,
+                    remaining_match_pairs,
+                    last_otherwise.unwrap(),
+                    source_info,
+                );
+                return;
+            }
             candidate.visit_leaves(|leaf_candidate| {
                 assert!(leaf_candidate.match_pairs.is_empty());
                 leaf_candidate.match_pairs.extend(remaining_match_pairs.iter().cloned());
                 let or_start = leaf_candidate.pre_binding_block.unwrap();
-                let otherwise =
-                    self.match_candidates(span, scrutinee_span, or_start, &mut [leaf_candidate]);
+                self.dump_decision_tree_candidate("after", leaf_candidate);
+                let otherwise = self
+                    .nest_decision_tree_dump(|this| {
+                        this.match_candidates(
+                            span,
+                            scrutinee_span,
+                            or_start,
+                            &mut [&mut *leaf_candidate],
+                        )
+                    })
+                    .unwrap();
                 // In a case like `(P | Q, R | S)`, if `P` succeeds and `R | S` fails, we know `(Q,
                 // R | S)` will fail too. If there is no guard, we skip testing of `Q` by branching
                 // directly to `last_otherwise`. If there is a guard,
@@ -1736,23 +3042,161 @@ fn finalize_or_candidate(
         }
     }
 
+    /// Tests the match pairs that remain after the or-pattern of `candidate` once for all of its
+    /// leaves, instead of once per leaf. Each leaf records its index in a temporary and jumps to
+    /// the shared tests. Each way these tests can succeed then switches on that index to a new
+    /// subcandidate of the leaf that matched, which establishes the bindings of both. This keeps
+    /// the CFG of patterns like `(Ok(x) | Err(x), 1 | 2, Ok(y) | Err(y))` linear in the number of
+    /// or-patterns.
+    ///
+    /// This is only done without a guard: with a guard, a leaf whose remaining tests fail must
+    /// continue with the next leaf, which the shared tests can't tell apart.
+    fn match_shared_or_suffix<'pat>(
+        &mut self,
+        span: Span,
+        scrutinee_span: Span,
+        candidate: &mut Candidate<'pat, 'tcx>,
+        match_pairs: Vec<MatchPair<'pat, 'tcx>>,
+        otherwise_block: BasicBlock,
+        source_info: SourceInfo,
+    ) {
+        let tcx = self.tcx;
+        let alternative = self.temp(tcx.types.usize, source_info.span);
+        let suffix_start = self.cfg.start_new_block();
+        let mut index = 0;
+        candidate.visit_leaves(|leaf_candidate| {
+            let block = leaf_candidate.pre_binding_block.unwrap();
+            let value = self.literal_operand(source_info.span, Const::from_usize(tcx, index));
+            self.cfg.push_assign(block, source_info, alternative, Rvalue::Use(value));
+            self.cfg.goto(block, source_info, suffix_start);
+            index += 1;
+        });
+
+        let extra_data = PatternExtraData {
+            span: source_info.span,
+            bindings: Vec::new(),
+            ascriptions: Vec::new(),
+            is_never: false,
+        };
+        let mut suffix =
+            Candidate::from_flat_pat(FlatPat { match_pairs, extra_data }, false, candidate.is_cold);
+        self.dump_decision_tree_candidate("after", &suffix);
+        let suffix_otherwise = self
+            .nest_decision_tree_dump(|this| {
+                this.match_candidates(span, scrutinee_span, suffix_start, &mut [&mut suffix])
+            })
+            .unwrap();
+        self.cfg.goto(suffix_otherwise, source_info, otherwise_block);
+
+        // Collect each leaf of the shared tests with the bindings and type ascriptions of its
+        // parents.
+        let mut suffix_leaves = Vec::new();
+        traverse_candidate(
+            suffix,
+            &mut Vec::new(),
+            &mut |leaf, parent_data| {
+                let extra_data = PatternExtraData {
+                    span: leaf.extra_data.span,
+                    bindings: parent_data
+                        .iter()
+                        .flat_map(|d| &d.bindings)
+                        .chain(&leaf.extra_data.bindings)
+                        .cloned()
+                        .collect(),
+                    ascriptions: parent_data
+                        .iter()
+                        .flat_map(|d| &d.ascriptions)
+                        .chain(&leaf.extra_data.ascriptions)
+                        .cloned()
+                        .collect(),
+                    is_never: false,
+                };
+                let blocks = (leaf.pre_binding_block.unwrap(), leaf.false_edge_start_block);
+                suffix_leaves.push((blocks, extra_data));
+            },
+            |inner_candidate, parent_data| {
+                parent_data.push(inner_candidate.extra_data);
+                inner_candidate.subcandidates.into_iter()
+            },
+            |parent_data| {
+                parent_data.pop();
+            },
+        );
+
+        // Give each leaf of `candidate` a subcandidate for each leaf of the shared tests.
+        let mut targets = vec![Vec::new(); suffix_leaves.len()];
+        let mut index = 0;
+        candidate.visit_leaves(|leaf_candidate| {
+            let false_edge_start_block = leaf_candidate.false_edge_start_block;
+            let is_cold = leaf_candidate.is_cold;
+            leaf_candidate.or_span = Some(source_info.span);
+            leaf_candidate.subcandidates = iter::zip(&suffix_leaves, &mut targets)
+                .enumerate()
+                .map(|(i, (&((_, suffix_start_block), ref extra_data), targets))| {
+                    let block = self.cfg.start_new_block();
+                    targets.push((index, block));
+                    let flat_pat =
+                        FlatPat { match_pairs: Vec::new(), extra_data: extra_data.clone() };
+                    let mut subcandidate = Candidate::from_flat_pat(flat_pat, false, is_cold);
+                    subcandidate.pre_binding_block = Some(block);
+                    subcandidate.otherwise_block = Some(otherwise_block);
+                    subcandidate.false_edge_start_block =
+                        if i == 0 { false_edge_start_block } else { suffix_start_block };
+                    subcandidate
+                })
+                .collect();
+            index += 1;
+        });
+
+        let unreachable = self.unreachable_block(source_info);
+        for (((block, _), _), targets) in iter::zip(suffix_leaves, targets) {
+            self.cfg.terminate(
+                block,
+                source_info,
+                TerminatorKind::SwitchInt {
+                    discr: Operand::Copy(alternative),
+                    targets: SwitchTargets::new(targets.into_iter(), unreachable),
+                },
+            );
+        }
+    }
+
     /// Try to merge all of the subcandidates of the given candidate into one. This avoids
     /// exponentially large CFGs in cases like `(1 | 2, 3 | 4, ...)`. The candidate should have been
     /// expanded with `create_or_subcandidates`.
     fn merge_trivial_subcandidates(&mut self, candidate: &mut Candidate<'_, 'tcx>) {
-        if candidate.subcandidates.is_empty() || candidate.has_guard {
-            // FIXME(or_patterns; matthewjasper) Don't give up if we have a guard.
+        if candidate.subcandidates.is_empty() {
+            return;
+        }
+        if candidate.has_guard && !candidate.disjoint_subcandidates {
+            // A value that matches several subcandidates runs the guard once for each of them
+            // until it succeeds, which merging them would change.
             return;
         }
 
-        // FIXME(or_patterns; matthewjasper) Try to be more aggressive here.
+        // Subcandidates that bind the same variables from the same places, like those of
+        // `(x, 0) | (x, 1)`, can be merged too, establishing their bindings and ascriptions once
+        // after the merged block. We only do this when more match pairs remain to be tested after
+        // the or-pattern: those would otherwise be tested once per subcandidate, whereas a trailing
+        // or-pattern only duplicates the bindings.
+        let first_extra_data = &candidate.subcandidates[0].extra_data;
+        let merge_bindings = !candidate.match_pairs.is_empty();
         let can_merge = candidate.subcandidates.iter().all(|subcandidate| {
-            subcandidate.subcandidates.is_empty() && subcandidate.extra_data.is_empty()
+            subcandidate.subcandidates.is_empty()
+                && if merge_bindings {
+                    subcandidate.extra_data.binds_like(first_extra_data)
+                } else {
+                    subcandidate.extra_data.is_empty()
+                }
         });
         if can_merge {
+            let extra_data = candidate.subcandidates[0].extra_data.clone();
+            candidate.extra_data.bindings.extend(extra_data.bindings);
+            candidate.extra_data.ascriptions.extend(extra_data.ascriptions);
             let mut last_otherwise = None;
             let any_matches = self.cfg.start_new_block();
             let or_span = candidate.or_span.take().unwrap();
+            self.dump_decision_tree_or_pattern("merge", or_span);
             let source_info = self.source_info(or_span);
             if candidate.false_edge_start_block.is_none() {
                 candidate.false_edge_start_block =
@@ -1790,12 +3234,82 @@ fn merge_trivial_subcandidates(&mut self, candidate: &mut Candidate<'_, 'tcx>) {
         }
     }
 
+    /// Whether `candidate` has a guard and starts with an or-pattern whose alternatives are
+    /// disjoint (see [`Builder::are_disjoint`]).
+    fn has_disjoint_or_pattern(&self, candidate: &Candidate<'_, 'tcx>) -> bool {
+        match candidate.match_pairs.first() {
+            Some(MatchPair { test_case: TestCase::Or { pats }, .. }) => {
+                candidate.has_guard && self.are_disjoint(pats)
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether no value can match more than one of the alternatives `pats` of an or-pattern. This
+    /// is conservative: alternatives are only found to be disjoint if they test some place for
+    /// different variants, constants or lengths, or for ranges that don't overlap.
+    fn are_disjoint(&self, pats: &[FlatPat<'_, 'tcx>]) -> bool {
+        pats.iter().enumerate().all(|(i, a)| {
+            pats[i + 1..]
+                .iter()
+                .all(|b| self.are_disjoint_match_pairs(&a.match_pairs, &b.match_pairs))
+        })
+    }
+
+    fn are_disjoint_match_pairs(
+        &self,
+        a: &[MatchPair<'_, 'tcx>],
+        b: &[MatchPair<'_, 'tcx>],
+    ) -> bool {
+        a.iter().any(|a| {
+            b.iter().any(|b| {
+                a.place.is_some() && a.place == b.place && self.is_disjoint_match_pair(a, b)
+            })
+        })
+    }
+
+    fn is_disjoint_match_pair(&self, a: &MatchPair<'_, 'tcx>, b: &MatchPair<'_, 'tcx>) -> bool {
+        let (tcx, param_env) = (self.tcx, self.param_env);
+        let is_disjoint = match (&a.test_case, &b.test_case) {
+            (
+                &TestCase::Variant { variant_index: a, .. },
+                &TestCase::Variant { variant_index: b, .. },
+            ) => a != b,
+            (&TestCase::Constant { value: a }, &TestCase::Constant { value: b }) => {
+                let (a, b) = (a.try_eval_bits(tcx, param_env), b.try_eval_bits(tcx, param_env));
+                a.is_some() && b.is_some() && a != b
+            }
+            (&TestCase::Constant { value }, TestCase::Range(range))
+            | (TestCase::Range(range), &TestCase::Constant { value }) => {
+                range.contains(value, tcx, param_env) == Some(false)
+            }
+            (TestCase::Range(a), TestCase::Range(b)) => {
+                a.overlaps(b, tcx, param_env) == Some(false)
+            }
+            (
+                &TestCase::Slice { len: a, variable_length: a_variable },
+                &TestCase::Slice { len: b, variable_length: b_variable },
+            ) => match (a_variable, b_variable) {
+                (false, false) => a != b,
+                (false, true) => a < b,
+                (true, false) => b < a,
+                (true, true) => false,
+            },
+            _ => false,
+        };
+        is_disjoint || self.are_disjoint_match_pairs(&a.subpairs, &b.subpairs)
+    }
+
     /// Pick a test to run. Which test doesn't matter as long as it is guaranteed to fully match at
-    /// least one match pair. We currently simply pick the test corresponding to the first match
-    /// pair of the first candidate in the list.
+    /// least one match pair. We pick the cheapest test among the match pairs of the first
+    /// candidate in the list (see [`TestCost`]), preferring earlier match pairs on ties.
+    ///
+    /// Since the first candidate has to pass all of its tests anyway, this only changes their
+    /// order: a `deref()` call or a string comparison is postponed until cheaper tests on other
+    /// places have ruled out the candidates they can.
     ///
-    /// *Note:* taking the first match pair is somewhat arbitrary, and we might do better here by
-    /// choosing more carefully what to test.
+    /// *Note:* this is still somewhat arbitrary, and we might do better here by choosing more
+    /// carefully what to test.
     ///
     /// For example, consider the following possible match-pairs:
     ///
@@ -1804,15 +3318,48 @@ fn merge_trivial_subcandidates(&mut self, candidate: &mut Candidate<'_, 'tcx>) {
     /// 3. `x @ 3..5` -- we will do a [`Range`] test to decide what range `x` falls in
     /// 4. etc.
     ///
+    /// The exceptions are tests that are worth widening to the match pairs of the following
+    /// candidates on the same place:
+    /// - a string constant followed by enough other string constants starts a [`StrLen`] or
+    ///   [`StrHash`] test instead of an [`Eq`] test;
+    /// - slice patterns of fixed but different lengths are tested by a single [`SwitchLen`]
+    ///   instead of one [`Len`] test per length.
+    ///
     /// [`Switch`]: TestKind::Switch
     /// [`SwitchInt`]: TestKind::SwitchInt
     /// [`Range`]: TestKind::Range
+    /// [`StrLen`]: TestKind::StrLen
+    /// [`StrHash`]: TestKind::StrHash
+    /// [`Eq`]: TestKind::Eq
+    /// [`SwitchLen`]: TestKind::SwitchLen
+    /// [`Len`]: TestKind::Len
     fn pick_test(&mut self, candidates: &[&mut Candidate<'_, 'tcx>]) -> (Place<'tcx>, Test<'tcx>) {
-        // Extract the match-pair from the highest priority candidate
-        let match_pair = &candidates.first().unwrap().match_pairs[0];
-        let test = self.test(match_pair);
+        // Extract the cheapest match-pair from the highest priority candidate. Or-patterns are
+        // expanded once they come first, so it has at least one other match-pair.
+        let (match_pair, mut test) = candidates
+            .first()
+            .unwrap()
+            .match_pairs
+            .iter()
+            .filter(|match_pair| !matches!(match_pair.test_case, TestCase::Or { .. }))
+            .map(|match_pair| (match_pair, self.test(match_pair)))
+            .min_by_key(|(_, test)| test.cost())
+            .unwrap();
         // Unwrap is ok after simplification.
         let match_place = match_pair.place.unwrap();
+        match test.kind {
+            TestKind::Eq { value, .. }
+                if let Some(kind) = self.str_column_test(match_place, value, candidates) =>
+            {
+                test.kind = kind;
+            }
+            TestKind::Len { op: BinOp::Eq, .. }
+                if self.is_len_switch_column(match_place, candidates) =>
+            {
+                test.kind = TestKind::SwitchLen;
+            }
+            _ => {}
+        }
         debug!(?test, ?match_pair);
 
         (match_place, test)
@@ -1820,7 +3367,8 @@ fn pick_test(&mut self, candidates: &[&mut Candidate<'_, 'tcx>]) -> (Place<'tcx>
 
     /// Given a test, we sort the input candidates into several buckets. If a candidate only matches
     /// in one of the branches of `test`, we move it there. If it could match in more than one of
-    /// the branches of `test`, we stop sorting candidates.
+    /// the branches of `test`, we stop sorting candidates, unless we are building a decision tree
+    /// (see below).
     ///
     /// This returns a pair of
     /// - the candidates that weren't sorted;
@@ -1851,6 +3399,11 @@ fn pick_test(&mut self, candidates: &[&mut Candidate<'_, 'tcx>]) -> (Place<'tcx>
     /// The sorted candidates are transformed:
     /// - candidate 0 becomes `[z @ true]` since we know that `x` was `true`;
     /// - candidate 1 becomes `[y @ false]` since we know that `x` was `false`.
+    ///
+    /// With `-Zmatch-lowering=decision-tree`, candidate 2 is instead copied into both outcomes,
+    /// which lets us sort candidate 3 into outcome `x == true` as well. This is only done for tests
+    /// whose branches are all known in advance, and only while the duplication budget of the
+    /// current match lasts.
     fn sort_candidates<'b, 'c, 'pat>(
         &mut self,
         match_place: Place<'tcx>,
@@ -1866,18 +3419,36 @@ fn sort_candidates<'b, 'c, 'pat>(
 
         let total_candidate_count = candidates.len();
 
+        // When building a decision tree, the branches a candidate gets copied into.
+        let all_branches =
+            if self.match_duplication_budget.is_some() { test.all_branches() } else { None };
+
         // Sort the candidates into the appropriate vector in `target_candidates`. Note that at some
         // point we may encounter a candidate where the test is not relevant; at that point, we stop
-        // sorting.
+        // sorting, unless we can afford to copy that candidate into every branch. Never patterns
+        // are not copied, because they must not end up in subcandidates.
         while let Some(candidate) = candidates.first_mut() {
-            let Some(branch) =
+            if let Some(branch) =
                 self.sort_candidate(match_place, test, candidate, &target_candidates)
-            else {
+            {
+                let (candidate, rest) = candidates.split_first_mut().unwrap();
+                target_candidates.entry(branch).or_insert_with(Vec::new).push(candidate);
+                candidates = rest;
+            } else if let Some(branches) = &all_branches
+                && !candidate.extra_data.is_never
+                && let Some(budget) = &mut self.match_duplication_budget
+                && let Some(remaining_budget) = budget.checked_sub(branches.len() - 1)
+            {
+                *budget = remaining_budget;
+                let (candidate, rest) = candidates.split_first_mut().unwrap();
+                self.create_copy_subcandidates(candidate, branches.len());
+                for (&branch, copy) in iter::zip(branches, &mut candidate.subcandidates) {
+                    target_candidates.entry(branch).or_insert_with(Vec::new).push(copy);
+                }
+                candidates = rest;
+            } else {
                 break;
-            };
-            let (candidate, rest) = candidates.split_first_mut().unwrap();
-            target_candidates.entry(branch).or_insert_with(Vec::new).push(candidate);
-            candidates = rest;
+            }
         }
 
         // At least the first candidate ought to be tested
@@ -1999,36 +3570,99 @@ fn test_candidates<'pat, 'b, 'c>(
 
         // For each of the N possible test outcomes, build the vector of candidates that applies if
         // the test has that particular outcome.
-        let (remaining_candidates, target_candidates) =
+        let (remaining_candidates, mut target_candidates) =
             self.sort_candidates(match_place, &test, candidates);
 
+        // Build the branches that only lead to arms marked `#[cold]` last, and remember them so
+        // that the test can hint that it is unlikely to take them.
+        let is_cold = |candidates: &[&mut Candidate<'_, '_>]| {
+            !candidates.is_empty() && candidates.iter().all(|candidate| candidate.is_cold)
+        };
+        target_candidates.sort_by(|_, first, _, second| is_cold(first).cmp(&is_cold(second)));
+
+        // An enclosing branch may already tell us the outcome of the test.
+        let implied_branch = self.implied_test_branch(match_place, &test, &target_candidates);
+        self.dump_decision_tree_test(match_place, &test, implied_branch);
+
         // The block that we should branch to if none of the
         // `target_candidates` match.
         let remainder_start = self.cfg.start_new_block();
+        if is_cold(remaining_candidates) {
+            self.cold_match_blocks.insert(remainder_start);
+        }
 
         // For each outcome of test, process the candidates that still apply.
         let target_blocks: FxIndexMap<_, _> = target_candidates
             .into_iter()
             .map(|(branch, mut candidates)| {
                 let branch_start = self.cfg.start_new_block();
-                let branch_otherwise =
-                    self.match_candidates(span, scrutinee_span, branch_start, &mut *candidates);
-                let source_info = self.source_info(span);
-                self.cfg.goto(branch_otherwise, source_info, remainder_start);
+                if is_cold(&candidates) {
+                    self.cold_match_blocks.insert(branch_start);
+                }
+                // Within the branch, we know the outcome of the test. This doesn't hold in the
+                // remainder, which is also reached from the other branches.
+                self.match_facts.push(MatchFact {
+                    place: match_place,
+                    kind: test.kind.clone(),
+                    branch,
+                });
+                let branch_otherwise = self.nest_decision_tree_dump(|this| {
+                    this.dump_decision_tree_branch(&test, branch);
+                    this.nest_decision_tree_dump(|this| {
+                        this.match_candidates(span, scrutinee_span, branch_start, &mut *candidates)
+                    })
+                });
+                // The outcome is still known when the candidates of the branch fall through to
+                // the remainder, e.g. after a guard failed. We link the branch up to the
+                // remainder once we know the test it starts with, see below.
+                let fact = self.match_facts.pop().unwrap();
+                if let Some(branch_otherwise) = branch_otherwise {
+                    self.match_fallthroughs
+                        .entry(remainder_start)
+                        .or_default()
+                        .push((branch_otherwise, fact));
+                }
                 (branch, branch_start)
             })
             .collect();
 
-        // Perform the test, branching to one of N blocks.
-        self.perform_test(
-            span,
-            scrutinee_span,
-            start_block,
-            remainder_start,
-            match_place,
-            &test,
-            target_blocks,
-        );
+        // Perform the test, branching to one of N blocks, unless we know where it branches to.
+        let cached_discriminants = self.cached_discriminants.len();
+        if let Some(branch) = implied_branch {
+            let target = target_blocks.get(&branch).copied().unwrap_or(remainder_start);
+            self.cfg.goto(start_block, self.source_info(span), target);
+        } else {
+            self.match_tree_stats.tests += 1;
+            if let TestKind::Len { .. } | TestKind::SwitchLen = test.kind {
+                self.match_tree_stats.patterns.insert(test.span);
+            }
+            self.perform_test(
+                span,
+                scrutinee_span,
+                start_block,
+                remainder_start,
+                match_place,
+                &test,
+                &target_blocks,
+            );
+        }
+
+        // The branches of earlier tests that fall through to this one may know its outcome, and
+        // then skip it, unless it reads a discriminant that the tests after it reuse.
+        let reads_discriminant = self.cached_discriminants.len() > cached_discriminants;
+        let source_info = self.source_info(span);
+        for (block, fact) in self.match_fallthroughs.swap_remove(&start_block).unwrap_or_default() {
+            let implied = Self::branch_implied_by(&fact, match_place, &test, &target_blocks)
+                .filter(|_| !reads_discriminant);
+            match implied.map(|branch| target_blocks.get(&branch)) {
+                Some(Some(&target)) => self.cfg.goto(block, source_info, target),
+                // The fact still holds in the remainder of this test.
+                Some(None) => {
+                    self.match_fallthroughs.entry(remainder_start).or_default().push((block, fact))
+                }
+                None => self.cfg.goto(block, source_info, start_block),
+            }
+        }
 
         remainder_start.and(remaining_candidates)
     }
@@ -2058,21 +3692,81 @@ pub(crate) fn lower_let_expr(
     ) -> BlockAnd<()> {
         let expr_span = self.thir[expr_id].span;
         let scrutinee = unpack!(block = self.lower_scrutinee(block, expr_id, expr_span));
-        let mut candidate = Candidate::new(scrutinee.clone(), pat, false, self);
-        let otherwise_block = self.lower_match_tree(
-            block,
+        let expr_place = scrutinee.try_to_place(self);
+
+        // The `let` of `while let Some(x) = iter.next()` and the like only needs a switch on the
+        // discriminant of the scrutinee.
+        if let Some(place) = expr_place
+            && let Some((matched_block, otherwise_block)) =
+                self.switch_on_variant(block, place, expr_span, pat)
+        {
+            self.break_for_else(otherwise_block, self.source_info(expr_span));
+            self.declare_let_expr_bindings(
+                declare_let_bindings,
+                source_scope,
+                scope_span,
+                pat,
+                expr_place,
+                expr_span,
+            );
+            self.bind_variant_fields(matched_block, place, pat, emit_storage_live);
+            self.visit_coverage_conditional_let(pat, matched_block, otherwise_block);
+            return matched_block.unit();
+        }
+
+        let mut candidate = Candidate::new(scrutinee.clone(), pat, false, false, self);
+        let otherwise_block = self
+            .lower_match_tree(block, expr_span, &scrutinee, pat.span, &mut [&mut candidate], true)
+            .unwrap();
+
+        self.break_for_else(otherwise_block, self.source_info(expr_span));
+        self.declare_let_expr_bindings(
+            declare_let_bindings,
+            source_scope,
+            scope_span,
+            pat,
+            expr_place,
             expr_span,
-            &scrutinee,
-            pat.span,
-            &mut [&mut candidate],
-            true,
         );
 
-        self.break_for_else(otherwise_block, self.source_info(expr_span));
+        let success = if candidate.subcandidates.is_empty() {
+            self.bind_pattern(
+                self.source_info(pat.span),
+                candidate,
+                &[],
+                expr_span,
+                None,
+                emit_storage_live,
+            )
+        } else {
+            self.bind_let_or_candidate(
+                self.source_info(pat.span),
+                candidate,
+                otherwise_block,
+                expr_span,
+                emit_storage_live,
+            )
+        };
+
+        // If branch coverage is enabled, record this branch.
+        self.visit_coverage_conditional_let(pat, success, otherwise_block);
+
+        success.unit()
+    }
 
+    /// Declares the bindings of the pattern of a `let` expression or let-else statement, if
+    /// `declare_let_bindings` says so.
+    fn declare_let_expr_bindings(
+        &mut self,
+        declare_let_bindings: DeclareLetBindings,
+        source_scope: Option<SourceScope>,
+        scope_span: Span,
+        pat: &Pat<'tcx>,
+        expr_place: Option<Place<'tcx>>,
+        expr_span: Span,
+    ) {
         match declare_let_bindings {
             DeclareLetBindings::Yes => {
-                let expr_place = scrutinee.try_to_place(self);
                 let opt_expr_place = expr_place.as_ref().map(|place| (Some(place), expr_span));
                 self.declare_bindings(
                     source_scope,
@@ -2087,20 +3781,112 @@ pub(crate) fn lower_let_expr(
                 self.tcx.dcx().span_bug(expr_span, "let expression not expected in this context")
             }
         }
+    }
 
-        let success = self.bind_pattern(
-            self.source_info(pat.span),
-            candidate,
-            &[],
-            expr_span,
-            None,
-            emit_storage_live,
-        );
+    /// Writes `if_matched` to `destination` if `pat` matches `scrutinee`, and `!if_matched`
+    /// otherwise, right from the blocks where the match tree of `pat` ends up. The bindings of
+    /// `pat`, if any, are not bound.
+    fn lower_pattern_into_bool(
+        &mut self,
+        destination: Place<'tcx>,
+        block: BasicBlock,
+        scrutinee: &PlaceBuilder<'tcx>,
+        scrutinee_span: Span,
+        pat: &Pat<'tcx>,
+        if_matched: bool,
+        span: Span,
+    ) -> BlockAnd<()> {
+        let mut candidate = Candidate::new(scrutinee.clone(), pat, false, false, self);
+        let otherwise_block = self
+            .lower_match_tree(
+                block,
+                scrutinee_span,
+                scrutinee,
+                pat.span,
+                &mut [&mut candidate],
+                true,
+            )
+            .unwrap();
 
-        // If branch coverage is enabled, record this branch.
-        self.visit_coverage_conditional_let(pat, success, otherwise_block);
+        let source_info = self.source_info(span);
+        let join_block = self.cfg.start_new_block();
+        let assign_bool = |this: &mut Self, block: BasicBlock, value: bool| {
+            let const_ = Const::from_bool(this.tcx, value);
+            let constant = ConstOperand { span, user_ty: None, const_ };
+            this.cfg.push_assign_constant(block, source_info, destination, constant);
+            this.cfg.goto(block, source_info, join_block);
+        };
+        candidate.visit_leaves(|leaf_candidate| {
+            assign_bool(self, leaf_candidate.pre_binding_block.unwrap(), if_matched);
+        });
+        assign_bool(self, otherwise_block, !if_matched);
+        join_block.unit()
+    }
 
-        success.unit()
+    /// Binds the alternatives of the or-patterns of a `let` expression or let-else statement, and
+    /// returns the block that all the alternatives that match continue to.
+    ///
+    /// There is no guard to fail after an alternative matched, so instead of chaining false edges
+    /// through the later alternatives, each alternative has a false edge straight to
+    /// `otherwise_block`, the only block from which the `let` fails. With coverage enabled, the
+    /// bindings of each alternative are attributed to its span, so that reports show which of them
+    /// matched.
+    fn bind_let_or_candidate(
+        &mut self,
+        outer_source_info: SourceInfo,
+        candidate: Candidate<'_, 'tcx>,
+        otherwise_block: BasicBlock,
+        scrutinee_span: Span,
+        emit_storage_live: EmitStorageLive,
+    ) -> BasicBlock {
+        let target_block = self.cfg.start_new_block();
+        let mut schedule_drops = ScheduleDrops::Yes;
+        let mut ascribed = Vec::new();
+        traverse_candidate(
+            candidate,
+            &mut Vec::new(),
+            &mut |mut leaf_candidate, parent_data| {
+                leaf_candidate.next_candidate_start_block = Some(otherwise_block);
+                let alternative_source_info = self.source_info(leaf_candidate.extra_data.span);
+                if leaf_candidate.binds_nothing(parent_data, false) {
+                    let block = leaf_candidate.pre_binding_block.unwrap();
+                    if self.tcx.sess.instrument_coverage() {
+                        self.cfg.push_coverage_span_marker(block, alternative_source_info);
+                    }
+                    self.false_edges(
+                        block,
+                        target_block,
+                        Some(otherwise_block),
+                        alternative_source_info,
+                        FalseEdgeOrigin { arm_span: None, kind: FalseEdgeKind::BeforeBindings },
+                    );
+                    return;
+                }
+                let binding_end = self.bind_and_guard_matched_candidate(
+                    leaf_candidate,
+                    parent_data,
+                    &[],
+                    scrutinee_span,
+                    None,
+                    schedule_drops,
+                    emit_storage_live,
+                    &mut ascribed,
+                );
+                if self.tcx.sess.instrument_coverage() {
+                    self.cfg.push_coverage_span_marker(binding_end, alternative_source_info);
+                }
+                schedule_drops = ScheduleDrops::No;
+                self.cfg.goto(binding_end, outer_source_info, target_block);
+            },
+            |inner_candidate, parent_data| {
+                parent_data.push(inner_candidate.extra_data);
+                inner_candidate.subcandidates.into_iter()
+            },
+            |parent_data| {
+                parent_data.pop();
+            },
+        );
+        target_block
     }
 
     /// Initializes each of the bindings from the candidate by
@@ -2120,6 +3906,7 @@ fn bind_and_guard_matched_candidate<'pat>(
         arm_match_scope: Option<(&Arm<'tcx>, region::Scope)>,
         schedule_drops: ScheduleDrops,
         emit_storage_live: EmitStorageLive,
+        ascribed: &mut Vec<Ascription<'tcx>>,
     ) -> BasicBlock {
         debug!("bind_and_guard_matched_candidate(candidate={:?})", candidate);
 
@@ -2136,6 +3923,10 @@ fn bind_and_guard_matched_candidate<'pat>(
                 fresh_block,
                 candidate.next_candidate_start_block,
                 candidate_source_info,
+                FalseEdgeOrigin {
+                    arm_span: arm_match_scope.map(|(arm, _)| arm.span),
+                    kind: FalseEdgeKind::BeforeBindings,
+                },
             );
             block = fresh_block;
         }
@@ -2156,7 +3947,7 @@ fn bind_and_guard_matched_candidate<'pat>(
         let bindings =
             parent_data.iter().flat_map(|d| &d.bindings).chain(&candidate.extra_data.bindings);
 
-        self.ascribe_types(block, ascriptions);
+        self.ascribe_types(block, ascriptions, ascribed);
 
         // rust-lang/rust#27282: The `autoref` business deserves some
         // explanation here.
@@ -2239,59 +4030,78 @@ fn bind_and_guard_matched_candidate<'pat>(
         //      the reference that we create for the arm.
         //    * So we eagerly create the reference for the arm and then take a
         //      reference to that.
-        if let Some((arm, match_scope)) = arm_match_scope
+        //
+        // If the guard refers to none of the bindings, `tmp1` is never used: it only has to keep
+        // the guard from mutating the bound places, which a fake borrow does as well. Those are
+        // removed after borrowck, together with the fake reads of `tmp1`.
+        //
+        // None of this is needed for a constant guard, which is not lowered.
+        let constant_guard = arm_match_scope.and_then(|(arm, _)| self.constant_guard(arm.guard?));
+        if let Some((arm, _)) = arm_match_scope
+            && constant_guard == Some(false)
+        {
+            // The arm is only entered through a false edge, so that borrowck still checks it.
+            let arm_block = self.cfg.start_new_block();
+            let guard_failed_block = self.cfg.start_new_block();
+            self.cfg.terminate(
+                block,
+                candidate_source_info,
+                TerminatorKind::FalseEdge {
+                    real_target: guard_failed_block,
+                    imaginary_target: arm_block,
+                    origin: FalseEdgeOrigin {
+                        arm_span: Some(arm.span),
+                        kind: FalseEdgeKind::FalseGuard,
+                    },
+                },
+            );
+            let otherwise_block = candidate
+                .otherwise_block
+                .unwrap_or_else(|| self.unreachable_block(candidate_source_info));
+            self.false_edges(
+                guard_failed_block,
+                otherwise_block,
+                candidate.next_candidate_start_block,
+                candidate_source_info,
+                FalseEdgeOrigin { arm_span: Some(arm.span), kind: FalseEdgeKind::GuardFailed },
+            );
+            self.bind_matched_candidate_for_arm_body(
+                arm_block,
+                schedule_drops,
+                bindings,
+                emit_storage_live,
+            );
+            arm_block
+        } else if let Some((arm, match_scope)) = arm_match_scope
             && let Some(guard) = arm.guard
+            && constant_guard.is_none()
         {
-            let tcx = self.tcx;
-
-            self.bind_matched_candidate_for_guard(block, schedule_drops, bindings.clone());
-            let guard_frame = GuardFrame {
-                locals: bindings.clone().map(|b| GuardFrameLocal::new(b.var_id)).collect(),
-            };
-            debug!("entering guard building context: {:?}", guard_frame);
-            self.guard_context.push(guard_frame);
-
-            let re_erased = tcx.lifetimes.re_erased;
-            let scrutinee_source_info = self.source_info(scrutinee_span);
-            for &(place, temp, kind) in fake_borrows {
-                let borrow = Rvalue::Ref(re_erased, BorrowKind::Fake(kind), place);
-                self.cfg.push_assign(block, scrutinee_source_info, Place::from(temp), borrow);
-            }
-
-            let mut guard_span = rustc_span::DUMMY_SP;
-
-            let (post_guard_block, otherwise_post_guard_block) =
-                self.in_if_then_scope(match_scope, guard_span, |this| {
-                    guard_span = this.thir[guard].span;
-                    this.then_else_break(
-                        block,
-                        guard,
-                        None, // Use `self.local_scope()` as the temp scope
-                        this.source_info(arm.span),
-                        DeclareLetBindings::No, // For guards, `let` bindings are declared separately
-                    )
-                });
-
-            let source_info = self.source_info(guard_span);
-            let guard_end = self.source_info(tcx.sess.source_map().end_point(guard_span));
-            let guard_frame = self.guard_context.pop().unwrap();
-            debug!("Exiting guard building context with locals: {:?}", guard_frame);
-
-            for &(_, temp, _) in fake_borrows {
-                let cause = FakeReadCause::ForMatchGuard;
-                self.cfg.push_fake_read(post_guard_block, guard_end, cause, Place::from(temp));
-            }
+            let guard_borrow = self.guard_borrow_kind(guard, bindings.clone());
+            self.bind_matched_candidate_for_guard(
+                block,
+                schedule_drops,
+                guard_borrow,
+                bindings.clone(),
+            );
+            let (post_guard_block, otherwise_post_guard_block, source_info) = self
+                .lower_match_guard(
+                    block,
+                    arm,
+                    guard,
+                    match_scope,
+                    fake_borrows,
+                    scrutinee_span,
+                    bindings.clone(),
+                );
 
-            let otherwise_block = candidate.otherwise_block.unwrap_or_else(|| {
-                let unreachable = self.cfg.start_new_block();
-                self.cfg.terminate(unreachable, source_info, TerminatorKind::Unreachable);
-                unreachable
-            });
+            let otherwise_block =
+                candidate.otherwise_block.unwrap_or_else(|| self.unreachable_block(source_info));
             self.false_edges(
                 otherwise_post_guard_block,
                 otherwise_block,
                 candidate.next_candidate_start_block,
                 source_info,
+                FalseEdgeOrigin { arm_span: Some(arm.span), kind: FalseEdgeKind::GuardFailed },
             );
 
             // We want to ensure that the matched candidates are bound
@@ -2322,13 +4132,6 @@ fn bind_and_guard_matched_candidate<'pat>(
             // and that is clearly not correct.
             let by_value_bindings =
                 bindings.filter(|binding| matches!(binding.binding_mode.0, ByRef::No));
-            // Read all of the by reference bindings to ensure that the
-            // place they refer to can't be modified by the guard.
-            for binding in by_value_bindings.clone() {
-                let local_id = self.var_local_id(binding.var_id, RefWithinGuard);
-                let cause = FakeReadCause::ForGuardBinding;
-                self.cfg.push_fake_read(post_guard_block, guard_end, cause, Place::from(local_id));
-            }
             assert_matches!(
                 schedule_drops,
                 ScheduleDrops::Yes,
@@ -2356,14 +4159,249 @@ fn bind_and_guard_matched_candidate<'pat>(
         }
     }
 
+    /// Binds the leaves of an or-pattern candidate whose arm has a guard, lowering the guard only
+    /// once for all of them.
+    ///
+    /// Each leaf establishes the references the guard uses for its bindings, records its index in
+    /// a temporary and jumps to the shared guard. Since the alternatives of an or-pattern bind the
+    /// same variables, the guard sees the same locals whichever leaf matched. After the guard, a
+    /// switch on the recorded index continues with the otherwise block of the leaf when the guard
+    /// fails, or binds the by-value bindings of the leaf for the arm body when it succeeds.
+    fn bind_and_guard_or_candidate(
+        &mut self,
+        outer_source_info: SourceInfo,
+        candidate: Candidate<'_, 'tcx>,
+        fake_borrows: &[(Place<'tcx>, Local, FakeBorrowKind)],
+        scrutinee_span: Span,
+        arm: &Arm<'tcx>,
+        guard: ExprId,
+        match_scope: region::Scope,
+        emit_storage_live: EmitStorageLive,
+    ) -> BasicBlock {
+        let tcx = self.tcx;
+
+        // Collect each leaf with the bindings and type ascriptions of its parents.
+        let mut leaves = Vec::new();
+        traverse_candidate(
+            candidate,
+            &mut Vec::new(),
+            &mut |leaf, parent_data| {
+                let bindings: Vec<_> = parent_data
+                    .iter()
+                    .flat_map(|d| &d.bindings)
+                    .chain(&leaf.extra_data.bindings)
+                    .cloned()
+                    .collect();
+                let ascriptions: Vec<_> = parent_data
+                    .iter()
+                    .flat_map(|d| &d.ascriptions)
+                    .chain(&leaf.extra_data.ascriptions)
+                    .cloned()
+                    .collect();
+                leaves.push((leaf, bindings, ascriptions));
+            },
+            |inner_candidate, parent_data| {
+                parent_data.push(inner_candidate.extra_data);
+                inner_candidate.subcandidates.into_iter()
+            },
+            |parent_data| {
+                parent_data.pop();
+            },
+        );
+
+        // All leaves bind the same variables.
+        let guard_borrow = self.guard_borrow_kind(guard, &leaves[0].1);
+        let alternative = self.temp(tcx.types.usize, arm.span);
+        let guard_block = self.cfg.start_new_block();
+        self.clear_top_scope(arm.scope);
+        // All leaves bind the same locals, so their drops only need to be scheduled once.
+        let mut schedule_drops = ScheduleDrops::Yes;
+        let mut ascribed = Vec::new();
+        for (index, (leaf, bindings, ascriptions)) in leaves.iter().enumerate() {
+            let source_info = self.source_info(leaf.extra_data.span);
+            let mut block = leaf.pre_binding_block.unwrap();
+            if leaf.next_candidate_start_block.is_some() {
+                let fresh_block = self.cfg.start_new_block();
+                self.false_edges(
+                    block,
+                    fresh_block,
+                    leaf.next_candidate_start_block,
+                    source_info,
+                    FalseEdgeOrigin {
+                        arm_span: Some(arm.span),
+                        kind: FalseEdgeKind::BeforeBindings,
+                    },
+                );
+                block = fresh_block;
+            }
+            self.ascribe_types(block, ascriptions.iter().cloned(), &mut ascribed);
+            self.bind_matched_candidate_for_guard(block, schedule_drops, guard_borrow, bindings);
+            let index =
+                self.literal_operand(source_info.span, Const::from_usize(tcx, index as u64));
+            self.cfg.push_assign(block, source_info, alternative, Rvalue::Use(index));
+            self.cfg.goto(block, source_info, guard_block);
+            schedule_drops = ScheduleDrops::No;
+        }
+
+        let (post_guard_block, otherwise_post_guard_block, source_info) = self.lower_match_guard(
+            guard_block,
+            arm,
+            guard,
+            match_scope,
+            fake_borrows,
+            scrutinee_span,
+            leaves[0].1.iter(),
+        );
+
+        let unreachable = self.unreachable_block(source_info);
+        let target_block = self.cfg.start_new_block();
+        let mut otherwise_targets = Vec::with_capacity(leaves.len());
+        let mut binding_targets = Vec::with_capacity(leaves.len());
+        let mut schedule_drops = ScheduleDrops::Yes;
+        for (index, (leaf, bindings, _)) in leaves.iter().enumerate() {
+            let otherwise_block = self.cfg.start_new_block();
+            self.false_edges(
+                otherwise_block,
+                leaf.otherwise_block.unwrap_or(unreachable),
+                leaf.next_candidate_start_block,
+                source_info,
+                FalseEdgeOrigin { arm_span: Some(arm.span), kind: FalseEdgeKind::GuardFailed },
+            );
+            otherwise_targets.push((index as u128, otherwise_block));
+
+            // As in `bind_and_guard_matched_candidate`, by-value bindings are only bound once the
+            // guard succeeded.
+            let binding_block = self.cfg.start_new_block();
+            let by_value_bindings =
+                bindings.iter().filter(|binding| matches!(binding.binding_mode.0, ByRef::No));
+            self.bind_matched_candidate_for_arm_body(
+                binding_block,
+                schedule_drops,
+                by_value_bindings,
+                emit_storage_live,
+            );
+            self.cfg.goto(binding_block, outer_source_info, target_block);
+            binding_targets.push((index as u128, binding_block));
+            schedule_drops = ScheduleDrops::No;
+        }
+
+        for (block, targets) in
+            [(otherwise_post_guard_block, otherwise_targets), (post_guard_block, binding_targets)]
+        {
+            self.cfg.terminate(
+                block,
+                source_info,
+                TerminatorKind::SwitchInt {
+                    discr: Operand::Copy(alternative),
+                    targets: SwitchTargets::new(targets.into_iter(), unreachable),
+                },
+            );
+        }
+
+        target_block
+    }
+
+    /// Lowers the guard of `arm` starting at `block`, where the references the guard uses for the
+    /// given bindings have already been established. Returns the blocks reached when the guard
+    /// succeeds and when it fails, and the source info of the guard.
+    fn lower_match_guard<'b>(
+        &mut self,
+        block: BasicBlock,
+        arm: &Arm<'tcx>,
+        guard: ExprId,
+        match_scope: region::Scope,
+        fake_borrows: &[(Place<'tcx>, Local, FakeBorrowKind)],
+        scrutinee_span: Span,
+        bindings: impl Iterator<Item = &'b Binding<'tcx>> + Clone,
+    ) -> (BasicBlock, BasicBlock, SourceInfo)
+    where
+        'tcx: 'b,
+    {
+        let tcx = self.tcx;
+
+        let guard_frame = GuardFrame {
+            locals: bindings.clone().map(|b| GuardFrameLocal::new(b.var_id)).collect(),
+        };
+        debug!("entering guard building context: {:?}", guard_frame);
+        self.guard_context.push(guard_frame);
+
+        let re_erased = tcx.lifetimes.re_erased;
+        let scrutinee_source_info = self.source_info(scrutinee_span);
+        for &(place, temp, kind) in fake_borrows {
+            let borrow = Rvalue::Ref(re_erased, BorrowKind::Fake(kind), place);
+            self.cfg.push_assign(block, scrutinee_source_info, Place::from(temp), borrow);
+        }
+        let fake_borrow_markers =
+            self.push_fake_borrow_markers(block, scrutinee_source_info, fake_borrows);
+
+        let mut guard_span = rustc_span::DUMMY_SP;
+
+        let (post_guard_block, otherwise_post_guard_block) =
+            self.in_if_then_scope(match_scope, guard_span, |this| {
+                guard_span = this.thir[guard].span;
+                if let Some(&value) = this.hoisted_guards.get(&guard) {
+                    let source_info = this.source_info(guard_span);
+                    let then_block = this.cfg.start_new_block();
+                    let else_block = this.cfg.start_new_block();
+                    let term = TerminatorKind::if_(Operand::Copy(value), then_block, else_block);
+                    this.cfg.terminate(block, source_info, term);
+                    this.break_for_else(else_block, source_info);
+                    then_block.unit()
+                } else {
+                    this.then_else_break(
+                        block,
+                        guard,
+                        None, // Use `self.local_scope()` as the temp scope
+                        this.source_info(arm.span),
+                        // The `let` bindings of guards are declared with the bindings of the arm,
+                        // which can use them.
+                        DeclareLetBindings::No,
+                    )
+                }
+            });
+
+        let source_info = self.source_info(guard_span);
+        let guard_end = self.source_info(tcx.sess.source_map().end_point(guard_span));
+        let guard_frame = self.guard_context.pop().unwrap();
+        debug!("Exiting guard building context with locals: {:?}", guard_frame);
+
+        for &(_, temp, _) in fake_borrows {
+            let cause = FakeReadCause::ForMatchGuard;
+            self.cfg.push_fake_read(post_guard_block, guard_end, cause, Place::from(temp));
+        }
+        // The places must be unchanged whether the guard succeeded or not: on failure, later
+        // candidates rely on the tests that were already performed.
+        self.check_fake_borrow_markers(post_guard_block, guard_end, &fake_borrow_markers);
+        self.check_fake_borrow_markers(otherwise_post_guard_block, guard_end, &fake_borrow_markers);
+
+        // Read all of the by reference bindings to ensure that the
+        // place they refer to can't be modified by the guard.
+        for binding in bindings.filter(|binding| matches!(binding.binding_mode.0, ByRef::No)) {
+            let local_id = self.var_local_id(binding.var_id, RefWithinGuard);
+            let cause = FakeReadCause::ForGuardBinding;
+            self.cfg.push_fake_read(post_guard_block, guard_end, cause, Place::from(local_id));
+        }
+
+        (post_guard_block, otherwise_post_guard_block, source_info)
+    }
+
     /// Append `AscribeUserType` statements onto the end of `block`
-    /// for each ascription
+    /// for each ascription that is not in `ascribed` yet, and add them to it.
+    ///
+    /// The alternatives of an or-pattern share the ascriptions of the patterns around it, and may
+    /// make the same ascriptions themselves. Borrowck relates the types of an ascription wherever
+    /// it is, so each of them is only made for the first alternative that has it.
     fn ascribe_types(
         &mut self,
         block: BasicBlock,
         ascriptions: impl IntoIterator<Item = Ascription<'tcx>>,
+        ascribed: &mut Vec<Ascription<'tcx>>,
     ) {
         for ascription in ascriptions {
+            if ascribed.iter().any(|other| other.same_as(&ascription)) {
+                continue;
+            }
+            ascribed.push(ascription.clone());
             let source_info = self.source_info(ascription.annotation.span);
 
             let base = self.canonical_user_type_annotations.push(ascription.annotation);
@@ -2383,10 +4421,29 @@ fn ascribe_types(
         }
     }
 
+    /// Returns the kind of borrow the references used by `guard` for `bindings` should take: a
+    /// shared borrow if the guard refers to any of the bindings, or a fake borrow otherwise.
+    fn guard_borrow_kind<'b>(
+        &self,
+        guard: ExprId,
+        bindings: impl IntoIterator<Item = &'b Binding<'tcx>>,
+    ) -> BorrowKind
+    where
+        'tcx: 'b,
+    {
+        let vars: Vec<_> = bindings.into_iter().map(|binding| binding.var_id).collect();
+        if util::guard_uses_variables(self.thir, guard, &vars) {
+            BorrowKind::Shared
+        } else {
+            BorrowKind::Fake(FakeBorrowKind::Deep)
+        }
+    }
+
     fn bind_matched_candidate_for_guard<'b>(
         &mut self,
         block: BasicBlock,
         schedule_drops: ScheduleDrops,
+        guard_borrow: BorrowKind,
         bindings: impl IntoIterator<Item = &'b Binding<'tcx>>,
     ) where
         'tcx: 'b,
@@ -2414,7 +4471,7 @@ fn bind_matched_candidate_for_guard<'b>(
             );
             match binding.binding_mode.0 {
                 ByRef::No => {
-                    let rvalue = Rvalue::Ref(re_erased, BorrowKind::Shared, binding.source);
+                    let rvalue = Rvalue::Ref(re_erased, guard_borrow, binding.source);
                     self.cfg.push_assign(block, source_info, ref_for_guard, rvalue);
                 }
                 ByRef::Yes(mutbl) => {
@@ -2429,7 +4486,7 @@ fn bind_matched_candidate_for_guard<'b>(
                     let rvalue =
                         Rvalue::Ref(re_erased, util::ref_pat_borrow_kind(mutbl), binding.source);
                     self.cfg.push_assign(block, source_info, value_for_arm, rvalue);
-                    let rvalue = Rvalue::Ref(re_erased, BorrowKind::Shared, value_for_arm);
+                    let rvalue = Rvalue::Ref(re_erased, guard_borrow, value_for_arm);
                     self.cfg.push_assign(block, source_info, ref_for_guard, rvalue);
                 }
             }
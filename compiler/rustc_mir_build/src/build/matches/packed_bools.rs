@@ -0,0 +1,274 @@
+//! Lowering of the `match`es on tuples of `bool`s with `-Zmatch-pack-bools`, like the decision
+//! tables of the code generated by parser generators:
+//!
+//! ```ignore (illustrative)
+//! match (a, b, c) {
+//!     (true, false, _) => <x>,
+//!     (_, true, true) | (false, false, _) => <y>,
+//!     _ => <z>,
+//! }
+//! ```
+//!
+//! The `bool`s that the arms test are packed into an integer, here
+//! `a as u8 | (b as u8) << 1 | (c as u8) << 2`, and each alternative of an arm matches the values
+//! of that integer that have its constants under a mask of the bits it tests. With at most
+//! [`MAX_TABLE_BITS`] packed `bool`s, the first arm matching each value is known, and a single
+//! `SwitchInt` on the packed value takes it there. With more, the alternatives are tested in
+//! order, each with a mask and a switch on the masked value. The match tree would instead test the
+//! `bool`s one at a time, and copy the tests of the later arms into each branch.
+
+use crate::build::expr::as_place::PlaceBuilder;
+use crate::build::{BlockAnd, Builder};
+use rustc_middle::bug;
+use rustc_middle::mir::*;
+use rustc_middle::thir::{ArmId, Pat, PatKind};
+use rustc_middle::ty::{self, Ty};
+use rustc_span::Span;
+use rustc_target::abi::FieldIdx;
+
+/// The most `bool`s packed into a value that a single `SwitchInt` dispatches on, for a table of
+/// `2^MAX_TABLE_BITS` values.
+const MAX_TABLE_BITS: usize = 8;
+
+/// An alternative of the pattern of an arm: the bits of the packed value that it tests, and the
+/// values of these bits that it matches.
+#[derive(Clone, Copy)]
+struct BitPattern {
+    mask: u128,
+    value: u128,
+}
+
+impl BitPattern {
+    fn matches(self, packed: u128) -> bool {
+        packed & self.mask == self.value
+    }
+}
+
+impl<'a, 'tcx> Builder<'a, 'tcx> {
+    /// Lowers a `match` on a tuple of `bool`s whose arms have no guards, and only test its fields
+    /// with `true`, `false` and `_`, by dispatching on the tested fields packed into an integer.
+    /// Returns `None` if the `match` doesn't have this shape, or without `-Zmatch-pack-bools`.
+    pub(super) fn packed_bools_match(
+        &mut self,
+        destination: Place<'tcx>,
+        block: BasicBlock,
+        scrutinee: &PlaceBuilder<'tcx>,
+        arms: &[ArmId],
+        span: Span,
+        scrutinee_span: Span,
+    ) -> Option<BlockAnd<()>> {
+        if !self.tcx.sess.opts.unstable_opts.match_pack_bools || self.decision_tree_dump.is_some() {
+            return None;
+        }
+        let tcx = self.tcx;
+        let thir = self.thir;
+        let scrutinee_place = scrutinee.try_to_place(self)?;
+        let ty::Tuple(field_tys) = scrutinee_place.ty(&self.local_decls, tcx).ty.kind() else {
+            return None;
+        };
+        if !field_tys.iter().all(|ty| ty.is_bool()) {
+            return None;
+        }
+
+        // The fields tested by each alternative of each arm, with the values they match.
+        let mut arm_alternatives = Vec::with_capacity(arms.len());
+        for &arm in arms {
+            let arm = &thir[arm];
+            if arm.guard.is_some() {
+                return None;
+            }
+            let mut alternatives = Vec::new();
+            self.bool_tuple_alternatives(&arm.pattern, &mut alternatives)?;
+            arm_alternatives.push(alternatives);
+        }
+        let mut fields: Vec<FieldIdx> =
+            arm_alternatives.iter().flatten().flatten().map(|&(field, _)| field).collect();
+        fields.sort_unstable();
+        fields.dedup();
+        if fields.is_empty() || fields.len() > 128 {
+            return None;
+        }
+        let patterns: Vec<Vec<BitPattern>> = arm_alternatives
+            .iter()
+            .map(|alternatives| {
+                alternatives
+                    .iter()
+                    .map(|tests| {
+                        let mut pattern = BitPattern { mask: 0, value: 0 };
+                        for &(field, value) in tests {
+                            let bit = 1 << fields.binary_search(&field).unwrap();
+                            pattern.mask |= bit;
+                            if value {
+                                pattern.value |= bit;
+                            }
+                        }
+                        pattern
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let packed_ty = match fields.len() {
+            ..=8 => tcx.types.u8,
+            ..=16 => tcx.types.u16,
+            ..=32 => tcx.types.u32,
+            ..=64 => tcx.types.u64,
+            _ => tcx.types.u128,
+        };
+        let source_info = self.source_info(scrutinee_span);
+        let packed = self.pack_bools(block, source_info, scrutinee_place, &fields, packed_ty);
+
+        let arm_blocks: Vec<_> = arms.iter().map(|_| self.cfg.start_new_block()).collect();
+        if fields.len() <= MAX_TABLE_BITS {
+            // The first arm matching each packed value. The `match` is exhaustive, so there is
+            // one for every value.
+            let value_arms: Vec<usize> = (0..1u128 << fields.len())
+                .map(|packed| {
+                    patterns
+                        .iter()
+                        .position(|alternatives| {
+                            alternatives.iter().any(|pattern| pattern.matches(packed))
+                        })
+                        .unwrap_or_else(|| bug!("no arm matches the packed `bool`s {packed:#b}"))
+                })
+                .collect();
+            // The arm matching the most values is the otherwise target.
+            let mut arm_values = vec![0; arms.len()];
+            for &arm in &value_arms {
+                arm_values[arm] += 1;
+            }
+            let most_matched = (0..arms.len()).max_by_key(|&arm| arm_values[arm]).unwrap();
+            let values = value_arms.iter().enumerate().filter_map(|(packed, &arm)| {
+                (arm != most_matched).then_some((packed as u128, arm_blocks[arm]))
+            });
+            let targets = SwitchTargets::new(values, arm_blocks[most_matched]);
+            let discr = Operand::Move(packed);
+            self.cfg.terminate(block, source_info, TerminatorKind::SwitchInt { discr, targets });
+        } else {
+            // The block testing the next alternative, unless an earlier one matches everything.
+            let mut next_test = Some(block);
+            'arms: for (alternatives, &arm_block) in patterns.iter().zip(&arm_blocks) {
+                for pattern in alternatives {
+                    let Some(block) = next_test else { break 'arms };
+                    if pattern.mask == 0 {
+                        self.cfg.goto(block, source_info, arm_block);
+                        next_test = None;
+                        break 'arms;
+                    }
+                    let masked = self.temp(packed_ty, scrutinee_span);
+                    let mask = self.packed_literal(scrutinee_span, packed_ty, pattern.mask);
+                    self.cfg.push_assign(
+                        block,
+                        source_info,
+                        masked,
+                        Rvalue::BinaryOp(BinOp::BitAnd, Box::new((Operand::Copy(packed), mask))),
+                    );
+                    let next_block = self.cfg.start_new_block();
+                    let targets = SwitchTargets::static_if(pattern.value, arm_block, next_block);
+                    let discr = Operand::Move(masked);
+                    let kind = TerminatorKind::SwitchInt { discr, targets };
+                    self.cfg.terminate(block, source_info, kind);
+                    next_test = Some(next_block);
+                }
+            }
+            if let Some(block) = next_test {
+                let otherwise_block = self
+                    .invalid_value_block(source_info)
+                    .unwrap_or_else(|| self.unreachable_block(source_info));
+                self.cfg.goto(block, source_info, otherwise_block);
+            }
+        }
+
+        // The arms have no bindings to declare.
+        Some(self.lower_switched_arms(destination, arms, &arm_blocks, span, |_, _, _| {}))
+    }
+
+    /// Pushes the alternatives of `pat`, a pattern on a tuple of `bool`s, to `alternatives`, as the
+    /// fields each of them tests with the values it matches. Returns `None` if `pat` tests the
+    /// fields with anything else than `true`, `false` and `_`, or binds them.
+    fn bool_tuple_alternatives(
+        &self,
+        pat: &Pat<'tcx>,
+        alternatives: &mut Vec<Vec<(FieldIdx, bool)>>,
+    ) -> Option<()> {
+        match pat.kind {
+            PatKind::Wild => alternatives.push(Vec::new()),
+            PatKind::Or { ref pats } => {
+                for pat in pats.iter() {
+                    self.bool_tuple_alternatives(pat, alternatives)?;
+                }
+            }
+            PatKind::Leaf { ref subpatterns } => {
+                let mut tests = Vec::with_capacity(subpatterns.len());
+                for field in subpatterns {
+                    if let Some(value) = self.bool_test(&field.pattern)? {
+                        tests.push((field.field, value));
+                    }
+                }
+                alternatives.push(tests);
+            }
+            _ => return None,
+        }
+        Some(())
+    }
+
+    /// The value that `pat`, a pattern on a `bool`, matches: `Some(None)` for `_`.
+    fn bool_test(&self, pat: &Pat<'tcx>) -> Option<Option<bool>> {
+        match pat.kind {
+            PatKind::Wild => Some(None),
+            PatKind::Constant { value } => {
+                Some(Some(value.try_eval_bool(self.tcx, self.param_env)?))
+            }
+            PatKind::InlineConstant { ref subpattern, .. } => self.bool_test(subpattern),
+            _ => None,
+        }
+    }
+
+    /// Packs the `bool`s in `fields` of `tuple` into a temporary of type `packed_ty`, the first
+    /// field in the lowest bit.
+    fn pack_bools(
+        &mut self,
+        block: BasicBlock,
+        source_info: SourceInfo,
+        tuple: Place<'tcx>,
+        fields: &[FieldIdx],
+        packed_ty: Ty<'tcx>,
+    ) -> Place<'tcx> {
+        let tcx = self.tcx;
+        let span = source_info.span;
+        let packed = self.temp(packed_ty, span);
+        for (bit, &field) in fields.iter().enumerate() {
+            let field = tcx.mk_place_field(tuple, field, tcx.types.bool);
+            let cast = Rvalue::Cast(CastKind::IntToInt, Operand::Copy(field), packed_ty);
+            if bit == 0 {
+                self.cfg.push_assign(block, source_info, packed, cast);
+                continue;
+            }
+            let field_bit = self.temp(packed_ty, span);
+            self.cfg.push_assign(block, source_info, field_bit, cast);
+            let shift = self.packed_literal(span, packed_ty, bit as u128);
+            let shifted = self.temp(packed_ty, span);
+            self.cfg.push_assign(
+                block,
+                source_info,
+                shifted,
+                Rvalue::BinaryOp(BinOp::Shl, Box::new((Operand::Move(field_bit), shift))),
+            );
+            self.cfg.push_assign(
+                block,
+                source_info,
+                packed,
+                Rvalue::BinaryOp(
+                    BinOp::BitOr,
+                    Box::new((Operand::Copy(packed), Operand::Move(shifted))),
+                ),
+            );
+        }
+        packed
+    }
+
+    fn packed_literal(&mut self, span: Span, packed_ty: Ty<'tcx>, bits: u128) -> Operand<'tcx> {
+        let const_ = Const::from_bits(self.tcx, bits, ty::ParamEnv::empty().and(packed_ty));
+        self.literal_operand(span, const_)
+    }
+}
@@ -0,0 +1,152 @@
+//! Perfect hashes of string constants, used with `-Zmatch-perfect-hash`.
+//!
+//! A column of many distinct string constants can be matched by computing a hash of the
+//! scrutinee that is different for each of the constants, switching on it, and comparing the
+//! scrutinee with the single constant that has this hash. The hash is a weighted sum of the length
+//! of the string and of at most two of its bytes, masked to a small power of two: it only takes a
+//! few instructions to compute, and the switch on it can become a jump table. Its weights and bytes
+//! are searched for at compile time, which fails if the constants don't differ enough in their
+//! length or in the bytes we look at.
+//!
+//! The hash is computed with wrapping arithmetic on `usize`. Since the low bits of a wrapping sum
+//! or product only depend on the low bits of its operands, the masked hash is the same whatever
+//! the width of `usize` on the target, and we can compute it for the constants with `u64`.
+
+use std::{fmt, mem};
+
+use rustc_middle::mir::*;
+
+/// The weights tried for the length of the string and each of the bytes the hash uses.
+const WEIGHTS: [u64; 6] = [1, 3, 7, 13, 31, 131];
+
+/// How many leading bytes of the strings are considered for the hash, besides their last byte.
+const MAX_LEADING_BYTES: usize = 4;
+
+/// How many additional bits the table of hashes may have, on top of the bits needed to give each
+/// constant a different hash.
+const MAX_EXTRA_TABLE_BITS: u32 = 2;
+
+/// A byte of a string used by a [`PerfectHash`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum HashedByte {
+    /// The byte at this index.
+    Leading(usize),
+    /// The last byte.
+    Last,
+}
+
+impl HashedByte {
+    fn get(self, bytes: &[u8]) -> u8 {
+        match self {
+            HashedByte::Leading(index) => bytes[index],
+            HashedByte::Last => bytes[bytes.len() - 1],
+        }
+    }
+
+    /// The length a string must have for this byte to exist.
+    fn min_len(self) -> usize {
+        match self {
+            HashedByte::Leading(index) => index + 1,
+            HashedByte::Last => 1,
+        }
+    }
+
+    /// The projection of this byte out of a slice of at least `min_length` bytes.
+    pub(super) fn projection<'tcx>(self, min_length: u64) -> PlaceElem<'tcx> {
+        match self {
+            HashedByte::Leading(index) => {
+                ProjectionElem::ConstantIndex { offset: index as u64, min_length, from_end: false }
+            }
+            HashedByte::Last => {
+                ProjectionElem::ConstantIndex { offset: 1, min_length, from_end: true }
+            }
+        }
+    }
+}
+
+/// A hash that is different for each of the string constants it was found for, see the module
+/// documentation.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct PerfectHash {
+    pub(super) len_weight: u64,
+    pub(super) bytes: Vec<(HashedByte, u64)>,
+    pub(super) mask: u64,
+}
+
+impl PerfectHash {
+    /// Looks for a hash that is different for each of `keys`, which must be distinct. Smaller
+    /// tables are preferred, then hashes that use fewer bytes.
+    pub(super) fn find(keys: &[&[u8]]) -> Option<Self> {
+        let min_len = keys.iter().map(|key| key.len()).min()?;
+        let mut candidate_bytes: Vec<_> =
+            (0..min_len.min(MAX_LEADING_BYTES)).map(HashedByte::Leading).collect();
+        if min_len > 0 {
+            candidate_bytes.push(HashedByte::Last);
+        }
+        let mut byte_sets = vec![vec![]];
+        for &byte in &candidate_bytes {
+            byte_sets.push(vec![byte]);
+        }
+        for (i, &first) in candidate_bytes.iter().enumerate() {
+            for &second in &candidate_bytes[i + 1..] {
+                byte_sets.push(vec![first, second]);
+            }
+        }
+
+        let table_bits = keys.len().next_power_of_two().trailing_zeros();
+        let mut used = Vec::new();
+        for bits in table_bits..=table_bits + MAX_EXTRA_TABLE_BITS {
+            let mask = (1 << bits) - 1;
+            for byte_set in &byte_sets {
+                let weight_choices = WEIGHTS.len().pow(1 + byte_set.len() as u32);
+                for mut choice in 0..weight_choices {
+                    let mut next_weight = || {
+                        let weight = WEIGHTS[choice % WEIGHTS.len()];
+                        choice /= WEIGHTS.len();
+                        weight
+                    };
+                    let len_weight = next_weight();
+                    let bytes = byte_set.iter().map(|&byte| (byte, next_weight())).collect();
+                    let hash = PerfectHash { len_weight, bytes, mask };
+
+                    used.clear();
+                    used.resize(1 << bits, false);
+                    if keys
+                        .iter()
+                        .all(|key| !mem::replace(&mut used[hash.hash(key) as usize], true))
+                    {
+                        return Some(hash);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Computes the hash of a string constant, which must be at least [`Self::min_len`] long.
+    pub(super) fn hash(&self, key: &[u8]) -> u64 {
+        let mut hash = (key.len() as u64).wrapping_mul(self.len_weight);
+        for &(byte, weight) in &self.bytes {
+            hash = hash.wrapping_add(u64::from(byte.get(key)).wrapping_mul(weight));
+        }
+        hash & self.mask
+    }
+
+    /// The length a string must have for all the bytes the hash uses to exist.
+    pub(super) fn min_len(&self) -> usize {
+        self.bytes.iter().map(|&(byte, _)| byte.min_len()).max().unwrap_or(0)
+    }
+}
+
+impl fmt::Display for PerfectHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(len * {}", self.len_weight)?;
+        for &(byte, weight) in &self.bytes {
+            match byte {
+                HashedByte::Leading(index) => write!(f, " + s[{index}] * {weight}")?,
+                HashedByte::Last => write!(f, " + s[len - 1] * {weight}")?,
+            }
+        }
+        write!(f, ") & {}", self.mask)
+    }
+}
@@ -0,0 +1,262 @@
+//! Lowering of the `match`es that only need one switch on the discriminant of an enum, without
+//! building candidates and a match tree for them, like the ones that derives of `Debug` or `Hash`
+//! expand to:
+//!
+//! ```ignore (illustrative)
+//! match self {
+//!     Shape::Circle(__self_0) => Formatter::debug_tuple_field1_finish(f, "Circle", &__self_0),
+//!     Shape::Square => Formatter::write_str(f, "Square"),
+//! }
+//! ```
+//!
+//! Each arm matches variants of the same enum, possibly behind references, and binds their fields
+//! or ignores them, and there are no guards. A last arm may be `_`. This extends the fast path of
+//! the `match`es of `for` loops and `?` (see the `desugared` module) to other `match`es, which
+//! saves build time on code with many derives.
+
+use super::{util, EmitStorageLive, ScheduleDrops};
+use crate::build::expr::as_place::PlaceBuilder;
+use crate::build::ForGuard::OutsideGuard;
+use crate::build::{BlockAnd, BlockAndExtension, Builder};
+use rustc_hir::{BindingMode, ByRef};
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::*;
+use rustc_middle::thir::{Arm, ArmId, FieldPat, LocalVarId, Pat, PatKind};
+use rustc_middle::ty::AdtDef;
+use rustc_span::Span;
+use rustc_target::abi::VariantIdx;
+use std::iter;
+
+/// A pattern that a switch on the discriminant of an enum decides.
+struct SwitchPattern<'p, 'tcx> {
+    adt_def: AdtDef<'tcx>,
+    /// The number of references that the pattern matches through before the enum.
+    derefs: usize,
+    /// The variant matched by each alternative of the pattern, with the patterns of its fields.
+    /// These are bindings or `_`, and only `_` if there are several alternatives.
+    variants: Vec<(VariantIdx, &'p [FieldPat<'tcx>])>,
+}
+
+impl<'a, 'tcx> Builder<'a, 'tcx> {
+    /// Lowers a `match` whose arms only need a switch on the discriminant of `scrutinee`, with a
+    /// target for each arm. Returns `None` if the arms don't have this shape.
+    pub(super) fn single_switch_match(
+        &mut self,
+        destination: Place<'tcx>,
+        block: BasicBlock,
+        scrutinee: &PlaceBuilder<'tcx>,
+        arms: &[ArmId],
+        span: Span,
+        scrutinee_span: Span,
+    ) -> Option<BlockAnd<()>> {
+        if !self.may_switch_directly() {
+            return None;
+        }
+
+        let thir = self.thir;
+        let mut patterns = Vec::with_capacity(arms.len());
+        for (index, &arm) in arms.iter().enumerate() {
+            let arm = &thir[arm];
+            if arm.guard.is_some() || self.is_cold_arm(arm) {
+                return None;
+            }
+            if index == arms.len() - 1 && matches!(arm.pattern.kind, PatKind::Wild) {
+                patterns.push(None);
+            } else {
+                patterns.push(Some(switch_pattern(&arm.pattern)?));
+            }
+        }
+        let first = patterns.first()?.as_ref()?;
+        let (adt_def, derefs) = (first.adt_def, first.derefs);
+        let mut matched = BitSet::new_empty(adt_def.variants().len());
+        for pattern in patterns.iter().flatten() {
+            if pattern.adt_def != adt_def || pattern.derefs != derefs {
+                return None;
+            }
+            if !pattern.variants.iter().all(|&(variant, _)| matched.insert(variant)) {
+                return None;
+            }
+        }
+        let scrutinee_place = scrutinee.try_to_place(self)?;
+        let place = self.deref_place(scrutinee_place, derefs);
+
+        let tcx = self.tcx;
+        let source_info = self.source_info(scrutinee_span);
+        let discr = self.read_discriminant(block, place, scrutinee_span);
+        let arm_blocks: Vec<_> = arms.iter().map(|_| self.cfg.start_new_block()).collect();
+        let otherwise_block = match patterns.last() {
+            Some(None) => *arm_blocks.last().unwrap(),
+            _ => self
+                .invalid_value_block(source_info)
+                .unwrap_or_else(|| self.unreachable_block(source_info)),
+        };
+        let values = patterns.iter().zip(&arm_blocks).flat_map(|(pattern, &arm_block)| {
+            pattern.iter().flat_map(move |pattern| {
+                pattern.variants.iter().map(move |&(variant, _)| {
+                    (adt_def.discriminant_for_variant(tcx, variant).val, arm_block)
+                })
+            })
+        });
+        let targets = SwitchTargets::new(values, otherwise_block);
+        self.cfg.terminate(block, source_info, TerminatorKind::SwitchInt { discr, targets });
+        if self.decision_tree_dump.is_some() {
+            let arm_variants: Vec<_> = iter::zip(arms, &patterns)
+                .map(|(&arm, pattern)| {
+                    let variants = pattern.iter().flat_map(|pattern| &pattern.variants);
+                    (variants.map(|&(variant, _)| variant).collect(), thir[arm].pattern.span)
+                })
+                .collect();
+            self.dump_variant_switch(place, adt_def, &arm_variants, scrutinee_span);
+        }
+
+        Some(self.lower_switched_arms(destination, arms, &arm_blocks, span, |this, index, arm| {
+            let scope = this.declare_bindings(
+                None,
+                arm.span,
+                &arm.pattern,
+                None,
+                Some((Some(&scrutinee_place), scrutinee_span)),
+            );
+            if let Some(source_scope) = scope {
+                this.source_scope = source_scope;
+            }
+            if let Some(pattern) = &patterns[index] {
+                this.bind_switch_pattern(arm_blocks[index], place, pattern, EmitStorageLive::Yes);
+            }
+        }))
+    }
+
+    /// Lowers the bodies of `arms` into `destination`, each from its block in `arm_blocks` and in
+    /// the scope of its arm, once `bind_arm` has declared and bound the variables of the arm with
+    /// the given index. Returns the block that all the arms end in.
+    pub(super) fn lower_switched_arms(
+        &mut self,
+        destination: Place<'tcx>,
+        arms: &[ArmId],
+        arm_blocks: &[BasicBlock],
+        span: Span,
+        mut bind_arm: impl FnMut(&mut Self, usize, &Arm<'tcx>),
+    ) -> BlockAnd<()> {
+        let thir = self.thir;
+        let outer_source_info = self.source_info(span);
+        let end_block = self.cfg.start_new_block();
+        for (index, (&arm, &arm_block)) in iter::zip(arms, arm_blocks).enumerate() {
+            let arm = &thir[arm];
+            let arm_source_info = self.source_info(arm.span);
+            let arm_end = self.in_scope((arm.scope, arm_source_info), arm.lint_level, |this| {
+                bind_arm(this, index, arm);
+                this.expr_into_dest(destination, arm_block, arm.body)
+            });
+            let arm_end_block = unpack!(arm_end);
+            self.cfg.goto(arm_end_block, outer_source_info, end_block);
+        }
+
+        self.source_scope = outer_source_info.scope;
+        end_block.unit()
+    }
+
+    /// Binds the fields of the variant matched by `pattern` in `place`, the enum behind the
+    /// references that `pattern` matches through, if `pattern` has a single alternative.
+    fn bind_switch_pattern(
+        &mut self,
+        block: BasicBlock,
+        place: Place<'tcx>,
+        pattern: &SwitchPattern<'_, 'tcx>,
+        emit_storage_live: EmitStorageLive,
+    ) {
+        let &[(variant_index, fields)] = &pattern.variants[..] else { return };
+        let tcx = self.tcx;
+        let variant = tcx.mk_place_downcast(place, pattern.adt_def, variant_index);
+        for field in fields {
+            let Some((var, by_ref)) = field_binding(&field.pattern) else { continue };
+            let source = tcx.mk_place_field(variant, field.field, field.pattern.ty);
+            let span = field.pattern.span;
+            let local = match emit_storage_live {
+                // The storage of the binding is already alive, like for let-else.
+                EmitStorageLive::No => self.var_local_id(var, OutsideGuard).into(),
+                EmitStorageLive::Yes => {
+                    self.storage_live_binding(block, var, span, OutsideGuard, ScheduleDrops::Yes)
+                }
+            };
+            self.schedule_drop_for_binding(var, span, OutsideGuard);
+            let rvalue = match by_ref {
+                ByRef::No => Rvalue::Use(self.consume_by_copy_or_move(source)),
+                ByRef::Yes(mutbl) => {
+                    let re_erased = tcx.lifetimes.re_erased;
+                    Rvalue::Ref(re_erased, util::ref_pat_borrow_kind(mutbl), source)
+                }
+            };
+            self.cfg.push_assign(block, self.source_info(span), local, rvalue);
+        }
+    }
+
+    /// Whether variant tests may be lowered to a switch on the discriminant without a match tree.
+    /// The options that change how a match tree reads the discriminant, lowers its switches, or
+    /// binds the variables of the arms, and the discriminants that it already read, only apply to
+    /// match trees, so they need one.
+    pub(super) fn may_switch_directly(&self) -> bool {
+        let opts = &self.tcx.sess.opts.unstable_opts;
+        !opts.match_repr_switch
+            && !opts.match_niche_compare
+            && opts.match_binary_search.is_none()
+            && opts.switchint_threshold.is_none()
+            && !opts.match_elide_moved_binding_storage
+            && !self.optimizes_for_size()
+            && self.cached_discriminants.is_empty()
+    }
+
+    fn deref_place(&self, mut place: Place<'tcx>, derefs: usize) -> Place<'tcx> {
+        for _ in 0..derefs {
+            place = self.tcx.mk_place_deref(place);
+        }
+        place
+    }
+}
+
+/// Returns how a switch on the discriminant of an enum decides `pat`, if it does.
+fn switch_pattern<'p, 'tcx>(mut pat: &'p Pat<'tcx>) -> Option<SwitchPattern<'p, 'tcx>> {
+    let mut derefs = 0;
+    while let PatKind::Deref { ref subpattern } = pat.kind {
+        derefs += 1;
+        pat = &**subpattern;
+    }
+    let alternatives: Vec<&'p Pat<'tcx>> = match pat.kind {
+        PatKind::Or { ref pats } => pats.iter().map(|pat| &**pat).collect(),
+        _ => vec![pat],
+    };
+
+    let mut adt_def = None;
+    let mut variants = Vec::with_capacity(alternatives.len());
+    for &alternative in &alternatives {
+        let PatKind::Variant { adt_def: def, variant_index, ref subpatterns, .. } =
+            alternative.kind
+        else {
+            return None;
+        };
+        if adt_def.is_some_and(|adt_def| adt_def != def) {
+            return None;
+        }
+        // The alternatives of an or-pattern would need to bind the same variables.
+        let fields_fit = subpatterns.iter().all(|field| match field.pattern.kind {
+            PatKind::Wild => true,
+            _ => alternatives.len() == 1 && field_binding(&field.pattern).is_some(),
+        });
+        if !fields_fit {
+            return None;
+        }
+        adt_def = Some(def);
+        variants.push((variant_index, &subpatterns[..]));
+    }
+    Some(SwitchPattern { adt_def: adt_def?, derefs, variants })
+}
+
+/// The variable bound by `pat` and whether it's bound by reference, if `pat` is a binding without
+/// a subpattern.
+fn field_binding(pat: &Pat<'_>) -> Option<(LocalVarId, ByRef)> {
+    match pat.kind {
+        PatKind::Binding { mode: BindingMode(by_ref, _), var, subpattern: None, .. } => {
+            Some((var, by_ref))
+        }
+        _ => None,
+    }
+}
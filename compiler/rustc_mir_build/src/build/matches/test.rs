@@ -5,11 +5,18 @@
 // identify what tests are needed, perform the tests, and then filter
 // the candidates based on the result.
 
-use crate::build::matches::{Candidate, MatchPair, Test, TestBranch, TestCase, TestKind};
+use crate::build::matches::char_class::{LEAF_BITS, LEAF_BYTES};
+use crate::build::matches::perfect_hash::PerfectHash;
+use crate::build::matches::{
+    Candidate, MatchFact, MatchPair, Test, TestBranch, TestCase, TestKind,
+};
 use crate::build::Builder;
-use rustc_data_structures::fx::FxIndexMap;
+use rustc_data_structures::fx::{FxHashSet, FxIndexMap, FxIndexSet};
 use rustc_hir::{LangItem, RangeEnd};
+use rustc_middle::mir::interpret::Allocation;
 use rustc_middle::mir::*;
+use rustc_middle::thir::PatRange;
+use rustc_middle::ty::layout::IntegerExt;
 use rustc_middle::ty::util::IntTypeExt;
 use rustc_middle::ty::GenericArg;
 use rustc_middle::ty::{self, adjustment::PointerCoercion, Ty, TyCtxt};
@@ -18,9 +25,32 @@
 use rustc_span::source_map::Spanned;
 use rustc_span::symbol::{sym, Symbol};
 use rustc_span::{Span, DUMMY_SP};
+use rustc_target::abi::{Abi, Primitive, Size, TagEncoding, VariantIdx, Variants};
 use tracing::{debug, instrument};
 
 use std::cmp::Ordering;
+use std::ops::Bound;
+
+/// The smallest number of distinct string constants on a place that we match by switching on
+/// their length and bytes, see [`TestKind::StrLen`]. Fewer constants are compared one by one.
+const MIN_STR_TRIE_CONSTANTS: usize = 4;
+
+/// The largest number of constants that a binary search (see `-Zmatch-binary-search`) leaves to a
+/// `SwitchInt` instead of splitting them further.
+const MAX_BINARY_SEARCH_LEAF: usize = 4;
+
+/// The `-Zswitchint-threshold` used by default with `-Copt-level=z`: on fewer values than this, the
+/// jump table a `SwitchInt` may become takes more space than a chain of comparisons.
+const OPT_SIZE_SWITCHINT_THRESHOLD: usize = 6;
+
+/// A switch whose constants take up at least this share, in percent, of the values between the
+/// smallest and the largest of them is dense enough for LLVM to lower it to a jump table, so it
+/// isn't lowered to a binary search.
+const MIN_JUMP_TABLE_DENSITY: u128 = 40;
+
+/// The longest string constant whose bytes an equality test compares one by one, rather than all
+/// at once with the `match_bytes_eq` lang item.
+const MAX_UNROLLED_STR_EQ_BYTES: usize = 8;
 
 impl<'a, 'tcx> Builder<'a, 'tcx> {
     /// Identifies what test is needed to decide if `match_pair` is applicable.
@@ -30,13 +60,26 @@ pub(super) fn test<'pat>(&mut self, match_pair: &MatchPair<'pat, 'tcx>) -> Test<
         let kind = match match_pair.test_case {
             TestCase::Variant { adt_def, variant_index: _ } => TestKind::Switch { adt_def },
 
+            // A constant that depends on generic parameters, like an inline constant using them,
+            // is only known after monomorphization, so it can't be one of the values of a switch.
+            TestCase::Constant { value }
+                if value.try_eval_bits(self.tcx, self.param_env).is_none() =>
+            {
+                TestKind::Eq { value, ty: match_pair.pattern.ty }
+            }
             TestCase::Constant { .. } if match_pair.pattern.ty.is_bool() => TestKind::If,
             TestCase::Constant { .. } if is_switch_ty(match_pair.pattern.ty) => TestKind::SwitchInt,
             TestCase::Constant { value } => TestKind::Eq { value, ty: match_pair.pattern.ty },
 
+            TestCase::StrBytes { tested, .. } => TestKind::StrByte { index: tested },
+
             TestCase::Range(range) => {
                 assert_eq!(range.ty, match_pair.pattern.ty);
-                TestKind::Range(Box::new(range.clone()))
+                if self.is_range_switch(range) {
+                    TestKind::SwitchInt
+                } else {
+                    TestKind::Range(Box::new(range.clone()))
+                }
             }
 
             TestCase::Slice { len, variable_length } => {
@@ -44,6 +87,10 @@ pub(super) fn test<'pat>(&mut self, match_pair: &MatchPair<'pat, 'tcx>) -> Test<
                 TestKind::Len { len: len as u64, op }
             }
 
+            TestCase::BitSet { base, mask } => TestKind::BitSet { base, mask },
+
+            TestCase::CharClass(class) => TestKind::CharClass(class),
+
             TestCase::Deref { temp, mutability } => TestKind::Deref { temp, mutability },
 
             TestCase::Never => TestKind::Never,
@@ -69,7 +116,7 @@ pub(super) fn perform_test(
         otherwise_block: BasicBlock,
         place: Place<'tcx>,
         test: &Test<'tcx>,
-        target_blocks: FxIndexMap<TestBranch<'tcx>, BasicBlock>,
+        target_blocks: &FxIndexMap<TestBranch<'tcx>, BasicBlock>,
     ) {
         let place_ty = place.ty(&self.local_decls, self.tcx);
         debug!(?place, ?place_ty);
@@ -77,40 +124,303 @@ pub(super) fn perform_test(
 
         let source_info = self.source_info(test.span);
         match test.kind {
+            TestKind::Switch { .. }
+                if let Some((scalar_ty, niche, niche_variant, untagged_variant)) =
+                    self.niche_compare(place_ty.ty) =>
+            {
+                let otherwise_block = target_block(TestBranch::Failure);
+                let variant_block = |idx| {
+                    target_blocks.get(&TestBranch::Variant(idx)).copied().unwrap_or(otherwise_block)
+                };
+                let scalar = self.temp(scalar_ty, test.span);
+                self.cfg.push_assign(
+                    block,
+                    self.source_info(scrutinee_span),
+                    scalar,
+                    Rvalue::Cast(CastKind::Transmute, Operand::Copy(place), scalar_ty),
+                );
+                let niche = Const::from_bits(self.tcx, niche, self.param_env.and(scalar_ty));
+                let niche = self.literal_operand(test.span, niche);
+                self.compare(
+                    block,
+                    variant_block(niche_variant),
+                    variant_block(untagged_variant),
+                    self.source_info(match_start_span),
+                    BinOp::Eq,
+                    Operand::Move(scalar),
+                    niche,
+                );
+            }
+
             TestKind::Switch { adt_def } => {
                 let otherwise_block = target_block(TestBranch::Failure);
+                let discr_ty = adt_def.repr().discr_type().to_ty(self.tcx);
+                let size = discr_ty.primitive_size(self.tcx);
+                let affine = affine_discriminants(self.tcx, adt_def);
                 let switch_targets = SwitchTargets::new(
                     adt_def.discriminants(self.tcx).filter_map(|(idx, discr)| {
-                        if let Some(&block) = target_blocks.get(&TestBranch::Variant(idx)) {
-                            Some((discr.val, block))
+                        let &block = target_blocks.get(&TestBranch::Variant(idx))?;
+                        match affine {
+                            Some((base, shift)) => {
+                                Some((size.truncate(discr.val.wrapping_sub(base)) >> shift, block))
+                            }
+                            None => Some((discr.val, block)),
+                        }
+                    }),
+                    otherwise_block,
+                );
+                debug!("num_enum_variants: {}", adt_def.variants().len());
+                // The discriminant may have been read by an earlier test on every path to this one,
+                // which must then leave it in place for us.
+                let cached_discr = self.cached_discriminants.iter().find(|&&(p, ..)| p == place);
+                let (discr, is_cached) = match cached_discr {
+                    Some(&(_, discr, read_block)) => {
+                        let terminator = self.cfg.block_data_mut(read_block).terminator_mut();
+                        if let TerminatorKind::SwitchInt { discr: operand, .. } =
+                            &mut terminator.kind
+                            && *operand == Operand::Move(discr)
+                        {
+                            *operand = Operand::Copy(discr);
+                        }
+                        (discr, true)
+                    }
+                    None => {
+                        let discr = self.temp(discr_ty, test.span);
+                        let rvalue = if self.is_repr_switch(adt_def, place_ty.ty) {
+                            Rvalue::Cast(CastKind::Transmute, Operand::Copy(place), discr_ty)
+                        } else {
+                            Rvalue::Discriminant(place)
+                        };
+                        let source_info = self.source_info(scrutinee_span);
+                        self.cfg.push_assign(block, source_info, discr, rvalue);
+                        self.cached_discriminants.push((place, discr, block));
+                        (discr, false)
+                    }
+                };
+                let source_info = self.source_info(match_start_span);
+                let discr_operand = match affine {
+                    Some((base, shift)) => {
+                        self.normalize_discriminant(block, source_info, discr, base, shift)
+                    }
+                    None if is_cached => Operand::Copy(discr),
+                    None => Operand::Move(discr),
+                };
+                let is_switch =
+                    self.terminate_switch(block, source_info, discr_operand, switch_targets);
+                // If every variant that can exist has a target, the otherwise edge can't be taken.
+                // We can't point it to an unreachable block yet, see `lower_match_tree`.
+                let ty::Adt(_, args) = place_ty.ty.kind() else { bug!() };
+                let is_exhaustive = adt_def.variants().indices().all(|idx| {
+                    target_blocks.contains_key(&TestBranch::Variant(idx))
+                        || self.is_variant_uninhabited(adt_def, args, idx)
+                });
+                if is_exhaustive && is_switch {
+                    self.exhaustive_switch_blocks.push(block);
+                }
+            }
+
+            TestKind::SwitchInt
+                if !is_small_switch_ty(self.tcx, place_ty.ty)
+                    && target_blocks.keys().any(|branch| branch.as_range().is_some()) =>
+            {
+                // Listing every value of the ranges would take too many targets, so we search for
+                // the range or constant containing the value among all of them instead.
+                let (tcx, param_env) = (self.tcx, self.param_env);
+                let sign_bit = sign_bit(tcx, place_ty.ty);
+                let mut ranges: Vec<_> = target_blocks
+                    .iter()
+                    .filter_map(|(&branch, &block)| {
+                        let (lo, hi) = match branch {
+                            TestBranch::Constant(_, bits) => (bits, bits),
+                            TestBranch::Range(lo, hi) => {
+                                (lo.eval_bits(tcx, param_env), hi.eval_bits(tcx, param_env))
+                            }
+                            _ => return None,
+                        };
+                        Some((lo ^ sign_bit, hi ^ sign_bit, block))
+                    })
+                    .collect();
+                ranges.sort_unstable_by_key(|&(lo, ..)| lo);
+                let source_info = self.source_info(match_start_span);
+                let bounds = (0, place_ty.ty.primitive_size(tcx).unsigned_int_max());
+                let otherwise_block = target_block(TestBranch::Failure);
+                self.range_search(block, source_info, place, &ranges, bounds, otherwise_block);
+            }
+
+            TestKind::SwitchInt => {
+                // The switch may be inexhaustive so we have a catch-all block
+                let otherwise_block = target_block(TestBranch::Failure);
+                let (tcx, param_env) = (self.tcx, self.param_env);
+                let size = place_ty.ty.primitive_size(tcx);
+                let switch_targets = SwitchTargets::new(
+                    target_blocks
+                        .iter()
+                        .filter_map(|(&branch, &block)| {
+                            let (lo, hi) = match branch {
+                                TestBranch::Constant(_, bits) => (bits, bits),
+                                TestBranch::Range(lo, hi) => {
+                                    (lo.eval_bits(tcx, param_env), hi.eval_bits(tcx, param_env))
+                                }
+                                _ => return None,
+                            };
+                            // The bits of a range of signed integers may wrap around.
+                            let count = size.truncate(hi.wrapping_sub(lo));
+                            let values =
+                                (0..=count).map(move |i| size.truncate(lo.wrapping_add(i)));
+                            Some(values.map(move |value| (value, block)))
+                        })
+                        .flatten(),
+                    otherwise_block,
+                );
+                // A switch on a single value is a comparison, which can be hinted if one of its
+                // sides only leads to cold arms.
+                let mut values = switch_targets.iter();
+                if let (Some((value, target)), None) = (values.next(), values.next())
+                    && (self.cold_match_blocks.contains(&target)
+                        || self.cold_match_blocks.contains(&otherwise_block))
+                {
+                    let value = Const::from_bits(tcx, value, param_env.and(place_ty.ty));
+                    let expected = self.literal_operand(test.span, value);
+                    self.compare(
+                        block,
+                        target,
+                        otherwise_block,
+                        self.source_info(match_start_span),
+                        BinOp::Eq,
+                        Operand::Copy(place),
+                        expected,
+                    );
+                } else if let Some(min_constants) =
+                    self.tcx.sess.opts.unstable_opts.match_binary_search
+                    && let Some(targets) =
+                        sparse_switch_targets(tcx, place_ty.ty, &switch_targets, min_constants)
+                {
+                    let source_info = self.source_info(match_start_span);
+                    self.binary_search_switch(block, source_info, place, &targets, otherwise_block);
+                } else {
+                    let covers_valid_range = self.covers_valid_range(place, &switch_targets);
+                    let source_info = self.source_info(match_start_span);
+                    let is_switch = self.terminate_switch(
+                        block,
+                        source_info,
+                        Operand::Copy(place),
+                        switch_targets,
+                    );
+                    if is_switch && covers_valid_range {
+                        self.exhaustive_switch_blocks.push(block);
+                    }
+                }
+            }
+
+            TestKind::SwitchLen | TestKind::StrLen | TestKind::StrByte { .. } => {
+                let otherwise_block = target_block(TestBranch::Failure);
+                let discr = match test.kind {
+                    TestKind::StrByte { index } => {
+                        let bytes = self.str_bytes(block, source_info, place);
+                        // `(*bytes)[index]`, which is in bounds since the length was tested first.
+                        let offset = index as u64;
+                        let elem = ProjectionElem::ConstantIndex {
+                            offset,
+                            min_length: offset + 1,
+                            from_end: false,
+                        };
+                        self.tcx.mk_place_elem(bytes, elem)
+                    }
+                    _ => {
+                        let slice = if matches!(test.kind, TestKind::StrLen) {
+                            self.str_bytes(block, source_info, place)
+                        } else {
+                            place
+                        };
+                        let len = self.temp(self.tcx.types.usize, test.span);
+                        self.cfg.push_assign(block, source_info, len, Rvalue::Len(slice));
+                        len
+                    }
+                };
+                let switch_targets = SwitchTargets::new(
+                    target_blocks.iter().filter_map(|(&branch, &block)| {
+                        if let TestBranch::Constant(_, bits) = branch {
+                            Some((bits, block))
                         } else {
                             None
                         }
                     }),
                     otherwise_block,
                 );
-                debug!("num_enum_variants: {}", adt_def.variants().len());
-                let discr_ty = adt_def.repr().discr_type().to_ty(self.tcx);
-                let discr = self.temp(discr_ty, test.span);
-                self.cfg.push_assign(
+                let source_info = self.source_info(match_start_span);
+                self.terminate_switch(block, source_info, Operand::Copy(discr), switch_targets);
+            }
+
+            TestKind::StrHash(ref hash) => {
+                let tcx = self.tcx;
+                let usize_ty = tcx.types.usize;
+                let otherwise_block = target_block(TestBranch::Failure);
+                let bytes = self.str_bytes(block, source_info, place);
+                let len = self.temp(usize_ty, test.span);
+                self.cfg.push_assign(block, source_info, len, Rvalue::Len(bytes));
+
+                // The bytes used by the hash must exist: `if len < min_len { goto otherwise }`.
+                let mut block = block;
+                let min_len = hash.min_len() as u64;
+                if min_len > 0 {
+                    let expected = self.push_usize(block, source_info, min_len);
+                    let hash_block = self.cfg.start_new_block();
+                    self.compare(
+                        block,
+                        hash_block,
+                        otherwise_block,
+                        source_info,
+                        BinOp::Ge,
+                        Operand::Copy(len),
+                        Operand::Move(expected),
+                    );
+                    block = hash_block;
+                }
+
+                // `hash = (len * len_weight + (bytes[i] as usize) * weight + ...) & mask`
+                let mut sum = self.push_usize_binop(
                     block,
-                    self.source_info(scrutinee_span),
-                    discr,
-                    Rvalue::Discriminant(place),
+                    source_info,
+                    BinOp::Mul,
+                    Operand::Copy(len),
+                    hash.len_weight,
                 );
-                self.cfg.terminate(
+                for &(byte, weight) in &hash.bytes {
+                    let byte_place = tcx.mk_place_elem(bytes, byte.projection(min_len));
+                    let byte = self.temp(usize_ty, test.span);
+                    self.cfg.push_assign(
+                        block,
+                        source_info,
+                        byte,
+                        Rvalue::Cast(CastKind::IntToInt, Operand::Copy(byte_place), usize_ty),
+                    );
+                    let term = self.push_usize_binop(
+                        block,
+                        source_info,
+                        BinOp::Mul,
+                        Operand::Move(byte),
+                        weight,
+                    );
+                    let new_sum = self.temp(usize_ty, test.span);
+                    self.cfg.push_assign(
+                        block,
+                        source_info,
+                        new_sum,
+                        Rvalue::BinaryOp(
+                            BinOp::Add,
+                            Box::new((Operand::Move(sum), Operand::Move(term))),
+                        ),
+                    );
+                    sum = new_sum;
+                }
+                let hash = self.push_usize_binop(
                     block,
-                    self.source_info(match_start_span),
-                    TerminatorKind::SwitchInt {
-                        discr: Operand::Move(discr),
-                        targets: switch_targets,
-                    },
+                    source_info,
+                    BinOp::BitAnd,
+                    Operand::Move(sum),
+                    hash.mask,
                 );
-            }
 
-            TestKind::SwitchInt => {
-                // The switch may be inexhaustive so we have a catch-all block
-                let otherwise_block = target_block(TestBranch::Failure);
                 let switch_targets = SwitchTargets::new(
                     target_blocks.iter().filter_map(|(&branch, &block)| {
                         if let TestBranch::Constant(_, bits) = branch {
@@ -121,27 +431,31 @@ pub(super) fn perform_test(
                     }),
                     otherwise_block,
                 );
-                let terminator = TerminatorKind::SwitchInt {
-                    discr: Operand::Copy(place),
-                    targets: switch_targets,
-                };
-                self.cfg.terminate(block, self.source_info(match_start_span), terminator);
+                let source_info = self.source_info(match_start_span);
+                self.terminate_switch(block, source_info, Operand::Move(hash), switch_targets);
             }
 
             TestKind::If => {
                 let success_block = target_block(TestBranch::Success);
                 let fail_block = target_block(TestBranch::Failure);
-                let terminator =
-                    TerminatorKind::if_(Operand::Copy(place), success_block, fail_block);
-                self.cfg.terminate(block, self.source_info(match_start_span), terminator);
+                self.terminate_if(
+                    block,
+                    self.source_info(match_start_span),
+                    Operand::Copy(place),
+                    success_block,
+                    fail_block,
+                );
             }
 
             TestKind::Eq { value, ty } => {
                 let tcx = self.tcx;
                 let success_block = target_block(TestBranch::Success);
                 let fail_block = target_block(TestBranch::Failure);
+                // A string literal matched against a `String`, rather than a `String` constant
+                // compared with `PartialEq::eq` because of `#[match_with_partial_eq]`.
                 if let ty::Adt(def, _) = ty.kind()
                     && tcx.is_lang_item(def.did(), LangItem::String)
+                    && value.ty() != ty
                 {
                     if !tcx.features().string_deref_patterns {
                         bug!(
@@ -162,7 +476,7 @@ pub(super) fn perform_test(
                         ref_str,
                         test.span,
                     );
-                    self.non_scalar_compare(
+                    self.str_compare(
                         eq_block,
                         success_block,
                         fail_block,
@@ -171,6 +485,67 @@ pub(super) fn perform_test(
                         ref_str,
                         ref_str_ty,
                     );
+                } else if ty.is_slice() {
+                    // A slice of constant bytes, which is compared all at once after its length
+                    // was tested.
+                    // `let slice: &[u8] = &place;`
+                    let re_erased = tcx.lifetimes.re_erased;
+                    let ref_slice_ty = Ty::new_imm_ref(tcx, re_erased, ty);
+                    let ref_slice = self.temp(ref_slice_ty, test.span);
+                    self.cfg.push_assign(
+                        block,
+                        source_info,
+                        ref_slice,
+                        Rvalue::Ref(re_erased, BorrowKind::Shared, place),
+                    );
+                    self.non_scalar_compare(
+                        block,
+                        success_block,
+                        fail_block,
+                        source_info,
+                        value,
+                        ref_slice,
+                        ref_slice_ty,
+                    );
+                } else if let ty::Ref(_, inner_ty, _) = *ty.kind()
+                    && let ty::Adt(def, _) = *inner_ty.kind()
+                    && tcx.is_lang_item(def.did(), LangItem::CStr)
+                {
+                    // A C string constant, whose bytes are compared with those of the C string,
+                    // including the NUL terminator, so that `<[u8]>::eq` first compares their
+                    // lengths. `CStr` is layout-compatible with `[u8]`, which `std` relies on too.
+                    // `let bytes: &[u8] = transmute::<&CStr, &[u8]>(place);`
+                    let re_erased = tcx.lifetimes.re_erased;
+                    let ref_bytes_ty =
+                        Ty::new_imm_ref(tcx, re_erased, Ty::new_slice(tcx, tcx.types.u8));
+                    let ref_bytes = self.temp(ref_bytes_ty, test.span);
+                    self.cfg.push_assign(
+                        block,
+                        source_info,
+                        ref_bytes,
+                        Rvalue::Cast(CastKind::Transmute, Operand::Copy(place), ref_bytes_ty),
+                    );
+                    self.non_scalar_compare(
+                        block,
+                        success_block,
+                        fail_block,
+                        source_info,
+                        constant_as_bytes(tcx, value),
+                        ref_bytes,
+                        ref_bytes_ty,
+                    );
+                } else if let ty::Ref(_, inner_ty, _) = *ty.kind()
+                    && inner_ty.is_str()
+                {
+                    self.str_compare(
+                        block,
+                        success_block,
+                        fail_block,
+                        source_info,
+                        value,
+                        place,
+                        ty,
+                    );
                 } else if !ty.is_scalar() {
                     // Use `PartialEq::eq` instead of `BinOp::Eq`
                     // (the binop can only handle primitives)
@@ -199,21 +574,66 @@ pub(super) fn perform_test(
                 }
             }
 
+            TestKind::Range(ref range)
+                if let Some((lo, width)) = self.range_offset_bounds(range, place_ty.ty) =>
+            {
+                let tcx = self.tcx;
+                let success = target_block(TestBranch::Success);
+                let fail = target_block(TestBranch::Failure);
+                // Test `val` with the single comparison `(val - lo) as uN <= hi - lo`, like
+                // optimizing backends do: below `lo`, the offset wraps around to a value above
+                // `hi - lo`. `char`s are compared as `u32`s.
+                let ty = place_ty.ty;
+                let (int_ty, val) = if ty.is_char() {
+                    let u32_ty = tcx.types.u32;
+                    let val = self.temp(u32_ty, test.span);
+                    self.cfg.push_assign(
+                        block,
+                        source_info,
+                        val,
+                        Rvalue::Cast(CastKind::IntToInt, Operand::Copy(place), u32_ty),
+                    );
+                    (u32_ty, Operand::Move(val))
+                } else {
+                    (ty, Operand::Copy(place))
+                };
+                let lo = Const::from_bits(tcx, lo, self.param_env.and(int_ty));
+                let lo = self.literal_operand(test.span, lo);
+                let offset = self.push_offset(block, source_info, int_ty, val, lo);
+                let unsigned_ty = unsigned_ty(tcx, int_ty);
+                let width = Const::from_bits(tcx, width, self.param_env.and(unsigned_ty));
+                let width = self.literal_operand(test.span, width);
+                let op = match range.end {
+                    RangeEnd::Included => BinOp::Le,
+                    RangeEnd::Excluded => BinOp::Lt,
+                };
+                self.compare(block, success, fail, source_info, op, Operand::Move(offset), width);
+            }
+
             TestKind::Range(ref range) => {
                 let success = target_block(TestBranch::Success);
                 let fail = target_block(TestBranch::Failure);
                 // Test `val` by computing `lo <= val && val <= hi`, using primitive comparisons.
+                // An end that is the smallest or largest value of the type holds for any `val`, so
+                // it isn't compared, e.g. `0..=hi` on an unsigned type only tests `val <= hi`.
                 let val = Operand::Copy(place);
+                let lo = range.lo.as_finite().filter(|_| range.lo_is_min(self.tcx) != Some(true));
+                let hi = range.hi.as_finite().filter(|_| range.hi_is_max(self.tcx) != Some(true));
+
+                if lo.is_none() && hi.is_none() {
+                    // A full range is normally irrefutable, and so isn't tested.
+                    self.cfg.goto(block, source_info, success);
+                }
 
-                let intermediate_block = if !range.lo.is_finite() {
+                let intermediate_block = if lo.is_none() {
                     block
-                } else if !range.hi.is_finite() {
+                } else if hi.is_none() {
                     success
                 } else {
                     self.cfg.start_new_block()
                 };
 
-                if let Some(lo) = range.lo.as_finite() {
+                if let Some(lo) = lo {
                     let lo = self.literal_operand(test.span, lo);
                     self.compare(
                         block,
@@ -226,7 +646,7 @@ pub(super) fn perform_test(
                     );
                 };
 
-                if let Some(hi) = range.hi.as_finite() {
+                if let Some(hi) = hi {
                     let hi = self.literal_operand(test.span, hi);
                     let op = match range.end {
                         RangeEnd::Included => BinOp::Le,
@@ -236,6 +656,201 @@ pub(super) fn perform_test(
                 }
             }
 
+            TestKind::BitSet { base, mask } => {
+                let tcx = self.tcx;
+                let success = target_block(TestBranch::Success);
+                let fail = target_block(TestBranch::Failure);
+                let ty = place_ty.ty;
+                let unsigned_ty = unsigned_ty(tcx, ty);
+                let mask_ty = match u128::BITS - mask.leading_zeros() {
+                    0..=32 => tcx.types.u32,
+                    33..=64 => tcx.types.u64,
+                    _ => tcx.types.u128,
+                };
+
+                let base = self.literal_operand(test.span, base);
+                let offset = self.push_offset(block, source_info, ty, Operand::Copy(place), base);
+
+                // The offset must be in the mask before shifting by it.
+                let max_offset = u128::from(u128::BITS - 1 - mask.leading_zeros());
+                let max_offset = Const::from_bits(tcx, max_offset, self.param_env.and(unsigned_ty));
+                let max_offset = self.literal_operand(test.span, max_offset);
+                let lookup_block = self.cfg.start_new_block();
+                self.compare(
+                    block,
+                    lookup_block,
+                    fail,
+                    source_info,
+                    BinOp::Le,
+                    Operand::Copy(offset),
+                    max_offset,
+                );
+
+                // `bit = (mask >> (offset as uM)) & 1`
+                let shift = self.temp(mask_ty, test.span);
+                self.cfg.push_assign(
+                    lookup_block,
+                    source_info,
+                    shift,
+                    Rvalue::Cast(CastKind::IntToInt, Operand::Move(offset), mask_ty),
+                );
+                let mask = Const::from_bits(tcx, mask, self.param_env.and(mask_ty));
+                let mask = self.literal_operand(test.span, mask);
+                let shifted = self.temp(mask_ty, test.span);
+                self.cfg.push_assign(
+                    lookup_block,
+                    source_info,
+                    shifted,
+                    Rvalue::BinaryOp(BinOp::Shr, Box::new((mask, Operand::Move(shift)))),
+                );
+                let one = Const::from_bits(tcx, 1, self.param_env.and(mask_ty));
+                let one = self.literal_operand(test.span, one);
+                let bit = self.temp(mask_ty, test.span);
+                self.cfg.push_assign(
+                    lookup_block,
+                    source_info,
+                    bit,
+                    Rvalue::BinaryOp(BinOp::BitAnd, Box::new((Operand::Move(shifted), one))),
+                );
+                let zero = Const::from_bits(tcx, 0, self.param_env.and(mask_ty));
+                let zero = self.literal_operand(test.span, zero);
+                self.compare(
+                    lookup_block,
+                    success,
+                    fail,
+                    source_info,
+                    BinOp::Ne,
+                    Operand::Move(bit),
+                    zero,
+                );
+            }
+
+            TestKind::CharClass(class) => {
+                let tcx = self.tcx;
+                let success = target_block(TestBranch::Success);
+                let fail = target_block(TestBranch::Failure);
+                let (u8_ty, usize_ty) = (tcx.types.u8, tcx.types.usize);
+
+                // `code = val as usize`
+                let code = self.temp(usize_ty, test.span);
+                self.cfg.push_assign(
+                    block,
+                    source_info,
+                    code,
+                    Rvalue::Cast(CastKind::IntToInt, Operand::Copy(place), usize_ty),
+                );
+
+                // `if bucket >= buckets.len() { goto fail }`
+                let bucket = self.push_usize_binop(
+                    block,
+                    source_info,
+                    BinOp::Shr,
+                    Operand::Copy(code),
+                    LEAF_BITS.into(),
+                );
+                let bucket_count = self.push_usize(block, source_info, class.buckets.len() as u64);
+                let lookup_block = self.cfg.start_new_block();
+                self.compare(
+                    block,
+                    lookup_block,
+                    fail,
+                    source_info,
+                    BinOp::Lt,
+                    Operand::Copy(bucket),
+                    Operand::Move(bucket_count),
+                );
+                let block = lookup_block;
+
+                // `leaf = buckets[bucket] as usize * LEAF_BYTES`
+                let leaf = self.temp(u8_ty, test.span);
+                let buckets = self.byte_table(block, source_info, class.buckets);
+                let leaf_place = tcx.mk_place_index(tcx.mk_place_deref(buckets), bucket.local);
+                self.cfg.push_assign(
+                    block,
+                    source_info,
+                    leaf,
+                    Rvalue::Use(Operand::Copy(leaf_place)),
+                );
+                let leaf_usize = self.temp(usize_ty, test.span);
+                self.cfg.push_assign(
+                    block,
+                    source_info,
+                    leaf_usize,
+                    Rvalue::Cast(CastKind::IntToInt, Operand::Move(leaf), usize_ty),
+                );
+                let leaf_start = self.push_usize_binop(
+                    block,
+                    source_info,
+                    BinOp::Mul,
+                    Operand::Move(leaf_usize),
+                    LEAF_BYTES as u64,
+                );
+
+                // `byte = leaves[leaf + low / 8]`, with `low = code % (1 << LEAF_BITS)`
+                let low = self.push_usize_binop(
+                    block,
+                    source_info,
+                    BinOp::BitAnd,
+                    Operand::Move(code),
+                    (1 << LEAF_BITS) - 1,
+                );
+                let low_byte =
+                    self.push_usize_binop(block, source_info, BinOp::Shr, Operand::Copy(low), 3);
+                let index = self.temp(usize_ty, test.span);
+                self.cfg.push_assign(
+                    block,
+                    source_info,
+                    index,
+                    Rvalue::BinaryOp(
+                        BinOp::Add,
+                        Box::new((Operand::Move(leaf_start), Operand::Move(low_byte))),
+                    ),
+                );
+                let byte = self.temp(u8_ty, test.span);
+                let leaves = self.byte_table(block, source_info, class.leaves);
+                let byte_place = tcx.mk_place_index(tcx.mk_place_deref(leaves), index.local);
+                self.cfg.push_assign(
+                    block,
+                    source_info,
+                    byte,
+                    Rvalue::Use(Operand::Copy(byte_place)),
+                );
+
+                // `if (byte >> (low % 8)) & 1 != 0 { goto success }`
+                let low_bit =
+                    self.push_usize_binop(block, source_info, BinOp::BitAnd, Operand::Move(low), 7);
+                let shifted = self.temp(u8_ty, test.span);
+                self.cfg.push_assign(
+                    block,
+                    source_info,
+                    shifted,
+                    Rvalue::BinaryOp(
+                        BinOp::Shr,
+                        Box::new((Operand::Move(byte), Operand::Move(low_bit))),
+                    ),
+                );
+                let one = Const::from_bits(tcx, 1, self.param_env.and(u8_ty));
+                let one = self.literal_operand(test.span, one);
+                let bit = self.temp(u8_ty, test.span);
+                self.cfg.push_assign(
+                    block,
+                    source_info,
+                    bit,
+                    Rvalue::BinaryOp(BinOp::BitAnd, Box::new((Operand::Move(shifted), one))),
+                );
+                let zero = Const::from_bits(tcx, 0, self.param_env.and(u8_ty));
+                let zero = self.literal_operand(test.span, zero);
+                self.compare(
+                    block,
+                    success,
+                    fail,
+                    source_info,
+                    BinOp::Ne,
+                    Operand::Move(bit),
+                    zero,
+                );
+            }
+
             TestKind::Len { len, op } => {
                 let usize_ty = self.tcx.types.usize;
                 let actual = self.temp(usize_ty, test.span);
@@ -283,6 +898,345 @@ pub(super) fn perform_test(
         }
     }
 
+    /// Returns a place for the bytes of the `&str` at `place`, by pushing
+    /// `let bytes = &raw const *place as *const [u8];` to `block`.
+    fn str_bytes(
+        &mut self,
+        block: BasicBlock,
+        source_info: SourceInfo,
+        place: Place<'tcx>,
+    ) -> Place<'tcx> {
+        let tcx = self.tcx;
+        let str_ptr_ty = Ty::new_imm_ptr(tcx, tcx.types.str_);
+        let str_ptr = self.temp(str_ptr_ty, source_info.span);
+        self.cfg.push_assign(
+            block,
+            source_info,
+            str_ptr,
+            Rvalue::AddressOf(Mutability::Not, tcx.mk_place_deref(place)),
+        );
+        let bytes_ty = Ty::new_imm_ptr(tcx, Ty::new_slice(tcx, tcx.types.u8));
+        let bytes = self.temp(bytes_ty, source_info.span);
+        self.cfg.push_assign(
+            block,
+            source_info,
+            bytes,
+            Rvalue::Cast(CastKind::PtrToPtr, Operand::Move(str_ptr), bytes_ty),
+        );
+        tcx.mk_place_deref(bytes)
+    }
+
+    /// Terminates `block` with a `SwitchInt` on `discr`. With `-Zswitchint-threshold=N`, a switch
+    /// on fewer than `N` values is instead lowered to a chain of comparisons with each of them, for
+    /// targets without efficient indirect jumps, and so is a switch on fewer than
+    /// [`OPT_SIZE_SWITCHINT_THRESHOLD`] values with `-Copt-level=z`. Returns whether `block` ends
+    /// with the `SwitchInt`.
+    fn terminate_switch(
+        &mut self,
+        mut block: BasicBlock,
+        source_info: SourceInfo,
+        discr: Operand<'tcx>,
+        targets: SwitchTargets,
+    ) -> bool {
+        let default_threshold = self.optimizes_for_size().then_some(OPT_SIZE_SWITCHINT_THRESHOLD);
+        let threshold = self.tcx.sess.opts.unstable_opts.switchint_threshold.or(default_threshold);
+        if threshold.map_or(true, |threshold| targets.iter().len() >= threshold) {
+            self.cfg.terminate(block, source_info, TerminatorKind::SwitchInt { discr, targets });
+            return true;
+        }
+
+        // The discriminant is compared with each value in turn, so it can't be moved.
+        let discr_ty = discr.ty(&self.local_decls, self.tcx);
+        let discr = match discr {
+            Operand::Copy(place) | Operand::Move(place) => Operand::Copy(place),
+            Operand::Constant(_) => discr,
+        };
+        for (value, target) in targets.iter() {
+            let value = Const::from_bits(self.tcx, value, self.param_env.and(discr_ty));
+            let value = self.literal_operand(source_info.span, value);
+            let next_block = self.cfg.start_new_block();
+            self.compare(block, target, next_block, source_info, BinOp::Eq, discr.clone(), value);
+            block = next_block;
+        }
+        self.cfg.goto(block, source_info, targets.otherwise());
+        false
+    }
+
+    /// Pushes the computation of `((discr as uN) - base) >> shift` to `block`, which numbers the
+    /// variants of an enum with affine discriminants from zero (see [`affine_discriminants`]), and
+    /// returns it.
+    fn normalize_discriminant(
+        &mut self,
+        block: BasicBlock,
+        source_info: SourceInfo,
+        discr: Place<'tcx>,
+        base: u128,
+        shift: u32,
+    ) -> Operand<'tcx> {
+        let tcx = self.tcx;
+        let span = source_info.span;
+        let discr_ty = discr.ty(&self.local_decls, tcx).ty;
+        let unsigned_ty = unsigned_ty(tcx, discr_ty);
+        let unsigned = if unsigned_ty == discr_ty {
+            Operand::Copy(discr)
+        } else {
+            let unsigned = self.temp(unsigned_ty, span);
+            self.cfg.push_assign(
+                block,
+                source_info,
+                unsigned,
+                Rvalue::Cast(CastKind::IntToInt, Operand::Copy(discr), unsigned_ty),
+            );
+            Operand::Move(unsigned)
+        };
+        let base = Const::from_bits(tcx, base, self.param_env.and(unsigned_ty));
+        let base = self.literal_operand(span, base);
+        let offset = self.temp(unsigned_ty, span);
+        self.cfg.push_assign(
+            block,
+            source_info,
+            offset,
+            Rvalue::BinaryOp(BinOp::Sub, Box::new((unsigned, base))),
+        );
+        if shift == 0 {
+            return Operand::Move(offset);
+        }
+        let shift = Const::from_bits(tcx, shift.into(), self.param_env.and(tcx.types.u32));
+        let shift = self.literal_operand(span, shift);
+        let normalized = self.temp(unsigned_ty, span);
+        self.cfg.push_assign(
+            block,
+            source_info,
+            normalized,
+            Rvalue::BinaryOp(BinOp::Shr, Box::new((Operand::Move(offset), shift))),
+        );
+        Operand::Move(normalized)
+    }
+
+    /// Branches from `block` to the target of the value of `place` in `targets`, which are sorted
+    /// by value, or to `otherwise_block` if it isn't there. This compares `place` with the middle
+    /// value and searches the half on its side, until few enough values are left for a `SwitchInt`.
+    fn binary_search_switch(
+        &mut self,
+        block: BasicBlock,
+        source_info: SourceInfo,
+        place: Place<'tcx>,
+        targets: &[(u128, BasicBlock)],
+        otherwise_block: BasicBlock,
+    ) {
+        if targets.len() <= MAX_BINARY_SEARCH_LEAF {
+            let targets = SwitchTargets::new(targets.iter().copied(), otherwise_block);
+            self.terminate_switch(block, source_info, Operand::Copy(place), targets);
+            return;
+        }
+        let (below, above) = targets.split_at(targets.len() / 2);
+        let place_ty = place.ty(&self.local_decls, self.tcx).ty;
+        let pivot = Const::from_bits(self.tcx, above[0].0, self.param_env.and(place_ty));
+        let pivot = self.literal_operand(source_info.span, pivot);
+        let below_block = self.cfg.start_new_block();
+        let above_block = self.cfg.start_new_block();
+        self.compare(
+            block,
+            below_block,
+            above_block,
+            source_info,
+            BinOp::Lt,
+            Operand::Copy(place),
+            pivot,
+        );
+        self.binary_search_switch(below_block, source_info, place, below, otherwise_block);
+        self.binary_search_switch(above_block, source_info, place, above, otherwise_block);
+    }
+
+    /// Whether `targets` has a target for every valid value of `place`, when `place` is the field
+    /// of a struct whose valid range is restricted by `#[rustc_layout_scalar_valid_range_start]`
+    /// or `#[rustc_layout_scalar_valid_range_end]`, like the integer in a `NonZero`. The otherwise
+    /// edge of a switch on `place` can't be taken then.
+    fn covers_valid_range(&self, place: Place<'tcx>, targets: &SwitchTargets) -> bool {
+        let Some((base, ProjectionElem::Field(..))) = place.as_ref().last_projection() else {
+            return false;
+        };
+        let ty::Adt(adt_def, _) = *base.ty(&self.local_decls, self.tcx).ty.kind() else {
+            return false;
+        };
+        let size = place.ty(&self.local_decls, self.tcx).ty.primitive_size(self.tcx);
+        let (start, end) = match self.tcx.layout_scalar_valid_range(adt_def.did()) {
+            (Bound::Unbounded, Bound::Unbounded) => return false,
+            (start, end) => {
+                let bound = |bound, default| match bound {
+                    Bound::Included(value) => value,
+                    _ => default,
+                };
+                (bound(start, 0), bound(end, size.unsigned_int_max()))
+            }
+        };
+        // The valid range wraps around if `start > end`.
+        let is_valid = |value| {
+            if start <= end {
+                start <= value && value <= end
+            } else {
+                value >= start || value <= end
+            }
+        };
+        let valid_targets = targets.iter().filter(|&(value, _)| is_valid(value)).count() as u128;
+        valid_targets > 0 && valid_targets - 1 == size.truncate(end.wrapping_sub(start))
+    }
+
+    /// With `-Zmatch-niche-compare`, if `ty` is an enum with two variants whose tag is a niche that
+    /// makes up its whole value, like `Option<&T>` or `Option<NonZeroU32>`, returns the unsigned
+    /// integer type that this value can be transmuted to, the value of the niche, the variant it
+    /// encodes and the other variant. Testing the variant is then a single comparison with the
+    /// niche, which mir-opts can see through more easily than a switch on the discriminant.
+    ///
+    /// This isn't done in const contexts, where pointers can't be transmuted to integers, nor for
+    /// enums that aren't `Copy`, like `Option<Box<T>>`, since the value is copied out of the place.
+    fn niche_compare(&self, ty: Ty<'tcx>) -> Option<(Ty<'tcx>, u128, VariantIdx, VariantIdx)> {
+        let tcx = self.tcx;
+        if !tcx.sess.opts.unstable_opts.match_niche_compare
+            || tcx.hir().body_const_context(self.def_id).is_some()
+            || !ty.is_copy_modulo_regions(tcx, self.param_env)
+        {
+            return None;
+        }
+        let layout = tcx.layout_of(self.param_env.and(ty)).ok()?;
+        let Abi::Scalar(scalar) = layout.abi else { return None };
+        let Variants::Multiple {
+            tag_encoding: TagEncoding::Niche { untagged_variant, ref niche_variants, niche_start },
+            ref variants,
+            ..
+        } = layout.variants
+        else {
+            return None;
+        };
+        if variants.len() != 2 || niche_variants.start() != niche_variants.end() {
+            return None;
+        }
+        let scalar_ty = match scalar.primitive() {
+            Primitive::Int(int, _) => int.to_ty(tcx, false),
+            Primitive::Pointer(_) => tcx.types.usize,
+            Primitive::Float(_) => return None,
+        };
+        Some((scalar_ty, niche_start, *niche_variants.start(), untagged_variant))
+    }
+
+    /// With `-Zmatch-repr-switch`, whether `ty` is an enum without fields and with an explicit
+    /// integer `repr`, like `#[repr(u8)] enum E { A = 1, B = 4 }`. The value of such an enum is its
+    /// discriminant, so the switch on its variant can read it as an integer of the `repr` type,
+    /// which mir-opts can combine with the integer logic around the `match`.
+    fn is_repr_switch(&self, adt_def: ty::AdtDef<'tcx>, ty: Ty<'tcx>) -> bool {
+        self.tcx.sess.opts.unstable_opts.match_repr_switch
+            && adt_def.repr().int.is_some()
+            && !adt_def.variants().is_empty()
+            && adt_def.variants().iter().all(|variant| variant.fields.is_empty())
+            // The value is copied out of the scrutinee, which needs a `Copy` type.
+            && ty.is_copy_modulo_regions(self.tcx, self.param_env)
+    }
+
+    /// Branches from `block` to the target of the range that contains the value of `place`, with a
+    /// binary search over the disjoint `ranges`, or to `otherwise` if none does. The ranges are
+    /// sorted, and their ends, like `bounds`, have their sign bit flipped (see [`sign_bit`]).
+    /// `bounds` are those of the values that `place` can still have, which spares comparisons that
+    /// are known to succeed.
+    fn range_search(
+        &mut self,
+        block: BasicBlock,
+        source_info: SourceInfo,
+        place: Place<'tcx>,
+        ranges: &[(u128, u128, BasicBlock)],
+        bounds: (u128, u128),
+        otherwise: BasicBlock,
+    ) {
+        let (min, max) = bounds;
+        let place_ty = place.ty(&self.local_decls, self.tcx).ty;
+        let sign_bit = sign_bit(self.tcx, place_ty);
+        let literal = |this: &mut Self, bits: u128| {
+            let value = Const::from_bits(this.tcx, bits ^ sign_bit, this.param_env.and(place_ty));
+            this.literal_operand(source_info.span, value)
+        };
+        let &[(lo, hi, target)] = ranges else {
+            let (below, above) = ranges.split_at(ranges.len() / 2);
+            let pivot = above[0].0;
+            let below_block = self.cfg.start_new_block();
+            let above_block = self.cfg.start_new_block();
+            let pivot_operand = literal(self, pivot);
+            self.compare(
+                block,
+                below_block,
+                above_block,
+                source_info,
+                BinOp::Lt,
+                Operand::Copy(place),
+                pivot_operand,
+            );
+            self.range_search(below_block, source_info, place, below, (min, pivot - 1), otherwise);
+            self.range_search(above_block, source_info, place, above, (pivot, max), otherwise);
+            return;
+        };
+        let (op, bound, success_block) = match (min >= lo, max <= hi) {
+            (true, true) => {
+                self.cfg.goto(block, source_info, target);
+                return;
+            }
+            (true, false) => (BinOp::Le, hi, target),
+            (false, true) => (BinOp::Ge, lo, target),
+            (false, false) if lo == hi => (BinOp::Eq, lo, target),
+            // Test the lower end first, then the upper one.
+            (false, false) => (BinOp::Ge, lo, self.cfg.start_new_block()),
+        };
+        let bound_operand = literal(self, bound);
+        self.compare(
+            block,
+            success_block,
+            otherwise,
+            source_info,
+            op,
+            Operand::Copy(place),
+            bound_operand,
+        );
+        if success_block != target {
+            self.range_search(success_block, source_info, place, ranges, (lo, max), otherwise);
+        }
+    }
+
+    /// Pushes `let table: &[u8] = <bytes>;` to `block` and returns `table`.
+    fn byte_table(
+        &mut self,
+        block: BasicBlock,
+        source_info: SourceInfo,
+        bytes: &[u8],
+    ) -> Place<'tcx> {
+        let tcx = self.tcx;
+        let table_ty =
+            Ty::new_imm_ref(tcx, tcx.lifetimes.re_static, Ty::new_slice(tcx, tcx.types.u8));
+        let allocation = tcx.mk_const_alloc(Allocation::from_bytes_byte_aligned_immutable(bytes));
+        let value = ConstValue::Slice { data: allocation, meta: bytes.len() as u64 };
+        let table = self.temp(table_ty, source_info.span);
+        let value = self.literal_operand(source_info.span, Const::Val(value, table_ty));
+        self.cfg.push_assign(block, source_info, table, Rvalue::Use(value));
+        table
+    }
+
+    /// Pushes `let result = left <op> right;` to `block` for a `usize` operand `left` and a
+    /// constant `right`, and returns `result`.
+    fn push_usize_binop(
+        &mut self,
+        block: BasicBlock,
+        source_info: SourceInfo,
+        op: BinOp,
+        left: Operand<'tcx>,
+        right: u64,
+    ) -> Place<'tcx> {
+        let right = self.literal_operand(source_info.span, Const::from_usize(self.tcx, right));
+        let result = self.temp(self.tcx.types.usize, source_info.span);
+        self.cfg.push_assign(
+            block,
+            source_info,
+            result,
+            Rvalue::BinaryOp(op, Box::new((left, right))),
+        );
+        result
+    }
+
     /// Perform `let temp = <ty as Deref>::deref(&place)`.
     /// or `let temp = <ty as DerefMut>::deref_mut(&mut place)`.
     pub(super) fn call_deref(
@@ -334,6 +1288,163 @@ pub(super) fn call_deref(
         );
     }
 
+    /// Returns the branch that `test` of `place` takes, if its outcome is implied by the outcome
+    /// of a test of an enclosing branch of the decision tree. This happens when candidates that
+    /// couldn't be sorted by that test, e.g. because of an or-pattern, have been copied into its
+    /// branches with `-Zmatch-lowering=decision-tree`. The scrutinee being a constant also gives
+    /// the outcome of its tests, see [`Builder::constant_scrutinee_fact`].
+    pub(super) fn implied_test_branch<T>(
+        &self,
+        place: Place<'tcx>,
+        test: &Test<'tcx>,
+        target_candidates: &FxIndexMap<TestBranch<'tcx>, T>,
+    ) -> Option<TestBranch<'tcx>> {
+        self.match_facts.iter().rev().find_map(|fact| {
+            Self::branch_implied_by(fact, place, test, target_candidates)
+                .or_else(|| self.branch_implied_by_value(fact, place, test, target_candidates))
+        })
+    }
+
+    /// Returns the branch that a test of `place` with ranges of values takes, if `fact` gives the
+    /// value of `place`.
+    fn branch_implied_by_value<T>(
+        &self,
+        fact: &MatchFact<'tcx>,
+        place: Place<'tcx>,
+        test: &Test<'tcx>,
+        target_candidates: &FxIndexMap<TestBranch<'tcx>, T>,
+    ) -> Option<TestBranch<'tcx>> {
+        let (TestKind::SwitchInt, TestBranch::Constant(value, bits)) = (&fact.kind, fact.branch)
+        else {
+            return None;
+        };
+        if fact.place != place {
+            return None;
+        }
+        match test.kind {
+            TestKind::Range(ref range) => {
+                let contains = range.contains(value, self.tcx, self.param_env)?;
+                Some(if contains { TestBranch::Success } else { TestBranch::Failure })
+            }
+            TestKind::SwitchInt => {
+                for &branch in target_candidates.keys() {
+                    let taken = match branch.as_range() {
+                        Some(range) => range.contains(value, self.tcx, self.param_env)?,
+                        None => matches!(branch, TestBranch::Constant(_, b) if b == bits),
+                    };
+                    if taken {
+                        return Some(branch);
+                    }
+                }
+                Some(TestBranch::Failure)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the branch that `test` of `place` takes on the paths where `fact` holds, if `fact`
+    /// determines it.
+    pub(super) fn branch_implied_by<T>(
+        fact: &MatchFact<'tcx>,
+        place: Place<'tcx>,
+        test: &Test<'tcx>,
+        target_candidates: &FxIndexMap<TestBranch<'tcx>, T>,
+    ) -> Option<TestBranch<'tcx>> {
+        if fact.place != place {
+            return None;
+        }
+        match (&fact.kind, fact.branch) {
+            // A test with two outcomes has the same outcome as an identical test.
+            (
+                TestKind::If
+                | TestKind::Eq { .. }
+                | TestKind::Range(_)
+                | TestKind::BitSet { .. }
+                | TestKind::CharClass(_)
+                | TestKind::Len { .. },
+                branch,
+            ) if fact.kind == test.kind => Some(branch),
+            // The variant of the enum is known. If no candidate has a branch for it, the
+            // switch takes its "otherwise" branch.
+            (TestKind::Switch { .. }, branch @ TestBranch::Variant(_))
+                if matches!(test.kind, TestKind::Switch { .. }) =>
+            {
+                Some(if target_candidates.contains_key(&branch) {
+                    branch
+                } else {
+                    TestBranch::Failure
+                })
+            }
+            // The value of the integer, length or hash is known, unless the test has ranges
+            // of values that we would have to look into.
+            (
+                TestKind::SwitchInt
+                | TestKind::SwitchLen
+                | TestKind::StrLen
+                | TestKind::StrHash(_)
+                | TestKind::StrByte { .. },
+                TestBranch::Constant(_, bits),
+            ) if fact.kind == test.kind
+                && !target_candidates.keys().any(|branch| branch.as_range().is_some()) =>
+            {
+                let branch = target_candidates
+                    .keys()
+                    .find(|branch| matches!(**branch, TestBranch::Constant(_, b) if b == bits));
+                Some(branch.copied().unwrap_or(TestBranch::Failure))
+            }
+            _ => None,
+        }
+    }
+
+    /// Pushes `offset = (val - base) as uN` to `block`, where `uN` is the unsigned type of the size
+    /// of the integer type `ty`, and returns `offset`. The offset wraps around to a large value for
+    /// values below `base`.
+    fn push_offset(
+        &mut self,
+        block: BasicBlock,
+        source_info: SourceInfo,
+        ty: Ty<'tcx>,
+        val: Operand<'tcx>,
+        base: Operand<'tcx>,
+    ) -> Place<'tcx> {
+        let span = source_info.span;
+        let diff = self.temp(ty, span);
+        self.cfg.push_assign(
+            block,
+            source_info,
+            diff,
+            Rvalue::BinaryOp(BinOp::Sub, Box::new((val, base))),
+        );
+        let unsigned_ty = unsigned_ty(self.tcx, ty);
+        if unsigned_ty == ty {
+            return diff;
+        }
+        let offset = self.temp(unsigned_ty, span);
+        self.cfg.push_assign(
+            block,
+            source_info,
+            offset,
+            Rvalue::Cast(CastKind::IntToInt, Operand::Move(diff), unsigned_ty),
+        );
+        offset
+    }
+
+    /// The bits of the start of `range`, a range of integers or `char`s, and the difference
+    /// between its end and its start, if it's tested by comparing the offset from its start with
+    /// that difference: when both ends are known and need a comparison.
+    fn range_offset_bounds(&self, range: &PatRange<'tcx>, ty: Ty<'tcx>) -> Option<(u128, u128)> {
+        if !is_switch_ty(ty)
+            || range.lo_is_min(self.tcx) != Some(false)
+            || range.hi_is_max(self.tcx) != Some(false)
+        {
+            return None;
+        }
+        let lo = range.lo.try_eval_bits(ty, self.tcx, self.param_env)?;
+        let hi = range.hi.try_eval_bits(ty, self.tcx, self.param_env)?;
+        let size = if ty.is_char() { Size::from_bits(32) } else { ty.primitive_size(self.tcx) };
+        Some((lo, size.truncate(hi.wrapping_sub(lo))))
+    }
+
     /// Compare using the provided built-in comparison operator
     fn compare(
         &mut self,
@@ -357,11 +1468,7 @@ fn compare(
         );
 
         // branch based on result
-        self.cfg.terminate(
-            block,
-            source_info,
-            TerminatorKind::if_(Operand::Move(result), success_block, fail_block),
-        );
+        self.terminate_if(block, source_info, Operand::Move(result), success_block, fail_block);
     }
 
     /// Compare two values using `<T as std::compare::PartialEq>::eq`.
@@ -501,10 +1608,198 @@ fn non_scalar_compare(
         self.diverge_from(block);
 
         // check the result
+        self.terminate_if(
+            eq_block,
+            source_info,
+            Operand::Move(eq_result),
+            success_block,
+            fail_block,
+        );
+    }
+
+    /// Compares the `&str` at `place` with the `&str` constant `value` by testing its length, then
+    /// its bytes, rather than with `<str as PartialEq>::eq`. The bytes of constants of at most
+    /// [`MAX_UNROLLED_STR_EQ_BYTES`] bytes are compared one by one, and those of longer constants
+    /// all at once with the `match_bytes_eq` lang item, a `memcmp`. `core` compares longer
+    /// constants with `PartialEq::eq` before defining it. `const` bodies, which can't call it,
+    /// compare all constants with `PartialEq::eq`, so that whether they compile doesn't depend on
+    /// the length of the constants.
+    fn str_compare(
+        &mut self,
+        block: BasicBlock,
+        success_block: BasicBlock,
+        fail_block: BasicBlock,
+        source_info: SourceInfo,
+        value: Const<'tcx>,
+        place: Place<'tcx>,
+        ty: Ty<'tcx>,
+    ) {
+        let tcx = self.tcx;
+        let expected = str_constant_bytes(tcx, value)
+            .filter(|_| tcx.hir().body_const_context(self.def_id).is_none());
+        let (expected, bytes_eq) = match expected {
+            Some(expected) if expected.len() <= MAX_UNROLLED_STR_EQ_BYTES => (expected, None),
+            Some(expected) if let Some(bytes_eq) = tcx.lang_items().get(LangItem::MatchBytesEq) => {
+                (expected, Some(bytes_eq))
+            }
+            _ => {
+                return self.non_scalar_compare(
+                    block,
+                    success_block,
+                    fail_block,
+                    source_info,
+                    value,
+                    place,
+                    ty,
+                );
+            }
+        };
+        let span = source_info.span;
+
+        // `if Len(*bytes) != <N> { goto fail_block }`
+        let bytes = self.str_bytes(block, source_info, place);
+        let len = self.temp(tcx.types.usize, span);
+        self.cfg.push_assign(block, source_info, len, Rvalue::Len(bytes));
+        let expected_len = self.push_usize(block, source_info, expected.len() as u64);
+        let bytes_block = self.cfg.start_new_block();
+        self.compare(
+            block,
+            bytes_block,
+            fail_block,
+            source_info,
+            BinOp::Eq,
+            Operand::Move(len),
+            Operand::Move(expected_len),
+        );
+        let block = bytes_block;
+
+        let bool_ty = tcx.types.bool;
+        let eq_result = self.temp(bool_ty, span);
+        let Some(bytes_eq) = bytes_eq else {
+            if expected.is_empty() {
+                self.cfg.goto(block, source_info, success_block);
+                return;
+            }
+            // `eq_result = (*bytes)[0] == <B0> & .. & (*bytes)[N - 1] == <BN-1>`, in bounds since
+            // the length was tested first. LLVM merges these loads into wider ones.
+            let min_length = expected.len() as u64;
+            for (index, &byte) in expected.iter().enumerate() {
+                let offset = index as u64;
+                let elem = ProjectionElem::ConstantIndex { offset, min_length, from_end: false };
+                let actual = Operand::Copy(tcx.mk_place_elem(bytes, elem));
+                let byte =
+                    Const::from_bits(tcx, byte.into(), ty::ParamEnv::empty().and(tcx.types.u8));
+                let byte = self.literal_operand(span, byte);
+                let byte_eq = Rvalue::BinaryOp(BinOp::Eq, Box::new((actual, byte)));
+                if index == 0 {
+                    self.cfg.push_assign(block, source_info, eq_result, byte_eq);
+                    continue;
+                }
+                let byte_eq_result = self.temp(bool_ty, span);
+                self.cfg.push_assign(block, source_info, byte_eq_result, byte_eq);
+                self.cfg.push_assign(
+                    block,
+                    source_info,
+                    eq_result,
+                    Rvalue::BinaryOp(
+                        BinOp::BitAnd,
+                        Box::new((Operand::Copy(eq_result), Operand::Move(byte_eq_result))),
+                    ),
+                );
+            }
+            self.terminate_if(
+                block,
+                source_info,
+                Operand::Move(eq_result),
+                success_block,
+                fail_block,
+            );
+            return;
+        };
+
+        // `eq_result = match_bytes_eq(&*bytes, <value as &[u8]>)`
+        let re_erased = tcx.lifetimes.re_erased;
+        let ref_bytes_ty = Ty::new_imm_ref(tcx, re_erased, Ty::new_slice(tcx, tcx.types.u8));
+        let ref_bytes = self.temp(ref_bytes_ty, span);
+        self.cfg.push_assign(
+            block,
+            source_info,
+            ref_bytes,
+            Rvalue::Ref(re_erased, BorrowKind::Shared, bytes),
+        );
+        let expect = self.literal_operand(span, constant_as_bytes(tcx, value));
+        let eq_block = self.cfg.start_new_block();
         self.cfg.terminate(
+            block,
+            source_info,
+            TerminatorKind::Call {
+                func: Operand::function_handle(tcx, bytes_eq, [], span),
+                args: [
+                    Spanned { node: Operand::Move(ref_bytes), span: DUMMY_SP },
+                    Spanned { node: expect, span: DUMMY_SP },
+                ]
+                .into(),
+                destination: eq_result,
+                target: Some(eq_block),
+                unwind: UnwindAction::Unreachable,
+                call_source: CallSource::MatchCmp,
+                fn_span: span,
+            },
+        );
+        self.terminate_if(
             eq_block,
             source_info,
-            TerminatorKind::if_(Operand::Move(eq_result), success_block, fail_block),
+            Operand::Move(eq_result),
+            success_block,
+            fail_block,
+        );
+    }
+
+    /// Branches to `success_block` if `cond` is true, and to `fail_block` otherwise. If one of
+    /// them only leads to match arms marked `#[cold]`, `cond` is first passed through the `likely`
+    /// or `unlikely` intrinsic, so that the other one becomes the fall-through path.
+    fn terminate_if(
+        &mut self,
+        mut block: BasicBlock,
+        source_info: SourceInfo,
+        mut cond: Operand<'tcx>,
+        success_block: BasicBlock,
+        fail_block: BasicBlock,
+    ) {
+        let hint = if self.cold_match_blocks.contains(&success_block) {
+            Some(LangItem::Unlikely)
+        } else if self.cold_match_blocks.contains(&fail_block) {
+            Some(LangItem::Likely)
+        } else {
+            None
+        };
+        // The hints are unstable to call in const contexts, and pointless there anyway.
+        if let Some(hint) = hint
+            && let Some(hint_fn) = self.tcx.lang_items().get(hint)
+            && self.tcx.hir().body_const_context(self.def_id).is_none()
+        {
+            let hinted = self.temp(self.tcx.types.bool, source_info.span);
+            let hinted_block = self.cfg.start_new_block();
+            self.cfg.terminate(
+                block,
+                source_info,
+                TerminatorKind::Call {
+                    func: Operand::function_handle(self.tcx, hint_fn, [], source_info.span),
+                    args: [Spanned { node: cond, span: source_info.span }].into(),
+                    destination: hinted,
+                    target: Some(hinted_block),
+                    unwind: UnwindAction::Unreachable,
+                    call_source: CallSource::Misc,
+                    fn_span: source_info.span,
+                },
+            );
+            block = hinted_block;
+            cond = Operand::Move(hinted);
+        }
+        self.cfg.terminate(
+            block,
+            source_info,
+            TerminatorKind::if_(cond, success_block, fail_block),
         );
     }
 
@@ -571,7 +1866,8 @@ pub(super) fn sort_candidate(
             // FIXME(#29623) we could use PatKind::Range to rule
             // things out here, in some cases.
             (TestKind::SwitchInt, &TestCase::Constant { value })
-                if is_switch_ty(match_pair.pattern.ty) =>
+                if is_switch_ty(match_pair.pattern.ty)
+                    && value.try_eval_bits(self.tcx, self.param_env).is_some() =>
             {
                 // Beware: there might be some ranges sorted into the failure case; we must not add
                 // a success case that could be matched by one of these ranges.
@@ -586,9 +1882,16 @@ pub(super) fn sort_candidate(
                         .iter()
                         .any(|mp| mp.place == Some(test_place) && is_covering_range(&mp.test_case))
                 };
-                if sorted_candidates
-                    .get(&TestBranch::Failure)
-                    .is_some_and(|candidates| candidates.iter().any(is_conflicting_candidate))
+                // Likewise, the value must not already lead to the branch of a range.
+                let is_in_range_branch = sorted_candidates.keys().any(|branch| {
+                    branch.as_range().is_some_and(|range| {
+                        matches!(range.contains(value, self.tcx, self.param_env), None | Some(true))
+                    })
+                });
+                if is_in_range_branch
+                    || sorted_candidates
+                        .get(&TestBranch::Failure)
+                        .is_some_and(|candidates| candidates.iter().any(is_conflicting_candidate))
                 {
                     fully_matched = false;
                     None
@@ -598,6 +1901,28 @@ pub(super) fn sort_candidate(
                     Some(TestBranch::Constant(value, bits))
                 }
             }
+            (TestKind::SwitchInt, TestCase::Range(range)) if self.is_range_switch(range) => {
+                // The range gets a branch of its own, unless some of its values already lead to
+                // another branch.
+                let overlaps_branch = |branch: &TestBranch<'tcx>| match *branch {
+                    TestBranch::Constant(value, _) => {
+                        !matches!(range.contains(value, self.tcx, self.param_env), Some(false))
+                    }
+                    TestBranch::Range(..) => !matches!(
+                        branch.as_range().unwrap().overlaps(range, self.tcx, self.param_env),
+                        Some(false)
+                    ),
+                    TestBranch::Success | TestBranch::Variant(_) | TestBranch::Failure => true,
+                };
+                if sorted_candidates.keys().any(overlaps_branch) {
+                    fully_matched = false;
+                    None
+                } else {
+                    fully_matched = true;
+                    let (lo, hi) = self.range_switch_bounds(range);
+                    Some(TestBranch::Range(lo, hi))
+                }
+            }
             (TestKind::SwitchInt, TestCase::Range(range)) => {
                 fully_matched = false;
                 let not_contained =
@@ -613,11 +1938,10 @@ pub(super) fn sort_candidate(
             }
 
             (TestKind::If, TestCase::Constant { value }) => {
-                fully_matched = true;
-                let value = value.try_eval_bool(self.tcx, self.param_env).unwrap_or_else(|| {
-                    span_bug!(test.span, "expected boolean value but got {value:?}")
-                });
-                Some(if value { TestBranch::Success } else { TestBranch::Failure })
+                // A constant that depends on generic parameters may lead to either branch.
+                let value = value.try_eval_bool(self.tcx, self.param_env);
+                fully_matched = value.is_some();
+                value.map(|value| if value { TestBranch::Success } else { TestBranch::Failure })
             }
 
             (
@@ -712,16 +2036,110 @@ pub(super) fn sort_candidate(
                 }
             }
 
+            (
+                &TestKind::BitSet { base, mask },
+                &TestCase::BitSet { base: case_base, mask: case_mask },
+            ) if base == case_base && mask == case_mask => {
+                fully_matched = true;
+                Some(TestBranch::Success)
+            }
+            (&TestKind::BitSet { base, mask }, &TestCase::Constant { value }) => {
+                fully_matched = false;
+                let size = base.ty().primitive_size(self.tcx);
+                let base = base.try_eval_bits(self.tcx, self.param_env)?;
+                let value = value.try_eval_bits(self.tcx, self.param_env)?;
+                let offset = size.truncate(value.wrapping_sub(base));
+                if offset >= u128::from(u128::BITS) || (mask >> offset) & 1 == 0 {
+                    // `value` is not in the set, so it can be matched only if this test fails.
+                    Some(TestBranch::Failure)
+                } else {
+                    None
+                }
+            }
+
+            (TestKind::CharClass(class), TestCase::CharClass(case_class))
+                if class == case_class =>
+            {
+                fully_matched = true;
+                Some(TestBranch::Success)
+            }
+            (TestKind::CharClass(class), &TestCase::Constant { value }) => {
+                fully_matched = false;
+                let c = value.try_eval_bits(self.tcx, self.param_env)?;
+                if !class.contains(c as u32) {
+                    // `value` is not in the class, so it can be matched only if this test fails.
+                    Some(TestBranch::Failure)
+                } else {
+                    None
+                }
+            }
+
             (TestKind::Eq { value: test_val, .. }, TestCase::Constant { value: case_val }) => {
                 if test_val == case_val {
                     fully_matched = true;
                     Some(TestBranch::Success)
+                } else if matches!(test_val, Const::Unevaluated(..))
+                    || matches!(case_val, Const::Unevaluated(..))
+                {
+                    // The values depend on generic parameters, so they may turn out to be equal.
+                    fully_matched = false;
+                    None
                 } else {
                     fully_matched = false;
                     Some(TestBranch::Failure)
                 }
             }
 
+            (TestKind::SwitchLen, &TestCase::Slice { len, variable_length: false }) => {
+                fully_matched = true;
+                Some(TestBranch::Constant(Const::from_usize(self.tcx, len as u64), len as u128))
+            }
+
+            (TestKind::StrLen, &TestCase::Constant { value }) => {
+                match str_constant_bytes(self.tcx, value) {
+                    Some(bytes) => {
+                        // The empty string is fully matched by its length; other strings go on to
+                        // have their bytes tested.
+                        fully_matched = bytes.is_empty();
+                        if !fully_matched {
+                            candidate.match_pairs[match_pair_index].test_case =
+                                TestCase::StrBytes { bytes, tested: 0 };
+                        }
+                        let len = Const::from_usize(self.tcx, bytes.len() as u64);
+                        Some(TestBranch::Constant(len, bytes.len() as u128))
+                    }
+                    None => {
+                        fully_matched = false;
+                        None
+                    }
+                }
+            }
+
+            (TestKind::StrHash(hash), &TestCase::Constant { value }) => {
+                // The constant is still compared with the string once the hash is known.
+                fully_matched = false;
+                str_constant_bytes(self.tcx, value)
+                    .filter(|bytes| bytes.len() >= hash.min_len())
+                    .map(|bytes| {
+                        let hash = hash.hash(bytes);
+                        TestBranch::Constant(Const::from_usize(self.tcx, hash), hash as u128)
+                    })
+            }
+
+            (&TestKind::StrByte { index }, &TestCase::StrBytes { bytes, tested })
+                if tested == index =>
+            {
+                fully_matched = index + 1 == bytes.len();
+                if !fully_matched {
+                    candidate.match_pairs[match_pair_index].test_case =
+                        TestCase::StrBytes { bytes, tested: index + 1 };
+                }
+                let u8_ty = self.tcx.types.u8;
+                let byte = bytes[index] as u128;
+                let value = Const::from_bits(self.tcx, byte, ty::ParamEnv::empty().and(u8_ty));
+                Some(TestBranch::Constant(value, byte))
+            }
+
             (TestKind::Deref { temp: test_temp, .. }, TestCase::Deref { temp, .. })
                 if test_temp == temp =>
             {
@@ -740,7 +2158,13 @@ pub(super) fn sort_candidate(
                 | TestKind::If
                 | TestKind::Len { .. }
                 | TestKind::Range { .. }
+                | TestKind::BitSet { .. }
+                | TestKind::CharClass(_)
                 | TestKind::Eq { .. }
+                | TestKind::SwitchLen
+                | TestKind::StrLen
+                | TestKind::StrHash(_)
+                | TestKind::StrByte { .. }
                 | TestKind::Deref { .. },
                 _,
             ) => {
@@ -759,12 +2183,211 @@ pub(super) fn sort_candidate(
 
         ret
     }
+
+    /// Whether `range` is tested with a [`TestKind::SwitchInt`], see [`is_range_switch_ty`]. A
+    /// range with an end that depends on generic parameters is tested with comparisons instead.
+    fn is_range_switch(&self, range: &PatRange<'tcx>) -> bool {
+        is_range_switch_ty(self.tcx, range.ty)
+            && [range.lo, range.hi]
+                .into_iter()
+                .all(|end| end.try_eval_bits(range.ty, self.tcx, self.param_env).is_some())
+    }
+
+    /// Returns the inclusive bounds of a range pattern tested with a [`TestKind::SwitchInt`].
+    fn range_switch_bounds(&self, range: &PatRange<'tcx>) -> (Const<'tcx>, Const<'tcx>) {
+        let (tcx, param_env) = (self.tcx, self.param_env);
+        let size = range.ty.primitive_size(tcx);
+        let lo = range.lo.eval_bits(range.ty, tcx, param_env);
+        let mut hi = range.hi.eval_bits(range.ty, tcx, param_env);
+        if range.end == RangeEnd::Excluded {
+            hi = size.truncate(hi.wrapping_sub(1));
+        }
+        let to_const = |bits| Const::from_bits(tcx, bits, param_env.and(range.ty));
+        (to_const(lo), to_const(hi))
+    }
+
+    /// Returns the test that should match the string constant `value`, tested on `place` by the
+    /// first of `candidates`, together with the string constants of the following candidates, up
+    /// to the first candidate that doesn't test `place` for a string constant. This is:
+    /// - a [`TestKind::StrHash`] if there are at least as many distinct constants as required by
+    ///   `-Zmatch-perfect-hash`, and a perfect hash of them is found;
+    /// - otherwise, a [`TestKind::StrLen`] if there are at least [`MIN_STR_TRIE_CONSTANTS`]
+    ///   distinct constants.
+    ///
+    /// Const contexts keep comparing strings with `PartialEq`, so that matching on them is
+    /// rejected there whatever the number of constants.
+    pub(super) fn str_column_test(
+        &self,
+        place: Place<'tcx>,
+        value: Const<'tcx>,
+        candidates: &[&mut Candidate<'_, 'tcx>],
+    ) -> Option<TestKind<'tcx>> {
+        if str_constant_bytes(self.tcx, value).is_none()
+            || self.tcx.hir().body_const_context(self.def_id).is_some()
+        {
+            return None;
+        }
+        let mut keys = FxIndexSet::default();
+        for candidate in candidates {
+            let Some(match_pair) = candidate.match_pairs.iter().find(|mp| mp.place == Some(place))
+            else {
+                break;
+            };
+            let TestCase::Constant { value } = match_pair.test_case else { break };
+            let Some(bytes) = str_constant_bytes(self.tcx, value) else { break };
+            keys.insert(bytes);
+        }
+        let keys = Vec::from_iter(keys);
+
+        // Once sorted by their hash, the constants are compared one by one, so a perfect hash of
+        // a single constant would never make progress.
+        if let Some(min_keys) = self.tcx.sess.opts.unstable_opts.match_perfect_hash
+            && keys.len() >= min_keys.max(2)
+            && let Some(hash) = PerfectHash::find(&keys)
+        {
+            Some(TestKind::StrHash(Box::new(hash)))
+        } else if keys.len() >= MIN_STR_TRIE_CONSTANTS {
+            Some(TestKind::StrLen)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the slice pattern of fixed length tested on `place` by the first of `candidates`
+    /// should be tested together with the ones of the following candidates by a
+    /// [`TestKind::SwitchLen`], because they don't all have the same length.
+    pub(super) fn is_len_switch_column(
+        &self,
+        place: Place<'tcx>,
+        candidates: &[&mut Candidate<'_, 'tcx>],
+    ) -> bool {
+        let mut lens = FxHashSet::default();
+        for candidate in candidates {
+            let Some(match_pair) = candidate.match_pairs.iter().find(|mp| mp.place == Some(place))
+            else {
+                break;
+            };
+            let TestCase::Slice { len, variable_length: false } = match_pair.test_case else {
+                break;
+            };
+            lens.insert(len);
+        }
+        lens.len() > 1
+    }
+}
+
+/// Returns the targets of a switch on a value of type `ty`, sorted by value, if there are at least
+/// `min_constants` of them and they are too sparse to be lowered to a jump table, in which case
+/// the switch is lowered to a binary search instead (see `Builder::binary_search_switch`).
+fn sparse_switch_targets<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    switch_targets: &SwitchTargets,
+    min_constants: usize,
+) -> Option<Vec<(u128, BasicBlock)>> {
+    let mut targets: Vec<_> = switch_targets.iter().collect();
+    if targets.len() < min_constants.max(MAX_BINARY_SEARCH_LEAF + 1) {
+        return None;
+    }
+    let sign_bit = sign_bit(tcx, ty);
+    targets.sort_unstable_by_key(|&(bits, _)| bits ^ sign_bit);
+    let span = (targets[targets.len() - 1].0 ^ sign_bit) - (targets[0].0 ^ sign_bit);
+    if (targets.len() as u128) * 100 >= span.saturating_mul(MIN_JUMP_TABLE_DENSITY) {
+        return None;
+    }
+    Some(targets)
+}
+
+/// The smallest number of variants of an enum whose discriminants are normalized before switching
+/// on them, see [`affine_discriminants`]. Switches on fewer values don't become jump tables.
+const MIN_NORMALIZED_VARIANTS: usize = 4;
+
+/// If the discriminants of the variants of `adt_def`, in order of value, are `base`,
+/// `base + stride`, `base + 2 * stride`, ... for a power of two `stride`, but aren't already
+/// `0, 1, 2, ...`, returns the bits of `base` and `log2(stride)`. Switching on
+/// `(discr - base) >> log2(stride)` instead of `discr` numbers the variants from zero, which lets
+/// backends lower the switch to a jump table or a WASM `br_table` without relying on them to find
+/// this themselves.
+fn affine_discriminants<'tcx>(tcx: TyCtxt<'tcx>, adt_def: ty::AdtDef<'tcx>) -> Option<(u128, u32)> {
+    if adt_def.variants().len() < MIN_NORMALIZED_VARIANTS {
+        return None;
+    }
+    let discr_ty = adt_def.repr().discr_type().to_ty(tcx);
+    let sign_bit = sign_bit(tcx, discr_ty);
+    let mut values: Vec<_> =
+        adt_def.discriminants(tcx).map(|(_, discr)| discr.val ^ sign_bit).collect();
+    values.sort_unstable();
+    let stride = values[1] - values[0];
+    if !stride.is_power_of_two() || values.windows(2).any(|pair| pair[1] - pair[0] != stride) {
+        return None;
+    }
+    let base = values[0] ^ sign_bit;
+    let shift = stride.trailing_zeros();
+    if base == 0 && shift == 0 {
+        return None;
+    }
+    Some((base, shift))
+}
+
+/// The unsigned integer type with the same size as the integer type `ty`.
+fn unsigned_ty<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Ty<'tcx> {
+    match *ty.kind() {
+        ty::Int(int_ty) => Ty::new_uint(tcx, int_ty.to_unsigned()),
+        _ => ty,
+    }
 }
 
 fn is_switch_ty(ty: Ty<'_>) -> bool {
     ty.is_integral() || ty.is_char()
 }
 
+/// Whether range patterns on `ty` are tested with a [`TestKind::SwitchInt`] that lists all the
+/// values in the range, instead of comparisons with its ends. Together with the constants on the
+/// same place, this lowers the place to a single switch, which LLVM can turn into a jump table. We
+/// only do this for types with at most 256 values.
+fn is_small_switch_ty<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> bool {
+    ty.is_integral() && ty.primitive_size(tcx).bits() <= 8
+}
+
+/// Whether range patterns on `ty` are tested with a [`TestKind::SwitchInt`], together with the
+/// constants and other ranges on the same place. This is the case for types with few values (see
+/// [`is_small_switch_ty`]), and for all integer and `char` types with `-Zmatch-range-switch`, whose
+/// ranges are then found with a binary search (see `Builder::range_search`).
+fn is_range_switch_ty<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> bool {
+    is_small_switch_ty(tcx, ty)
+        || (is_switch_ty(ty) && tcx.sess.opts.unstable_opts.match_range_switch)
+}
+
+/// The sign bit of the signed integer type `ty`, or zero for other types. Flipping it in the bits
+/// of values of `ty` orders these bits like the values.
+fn sign_bit<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> u128 {
+    if ty.is_signed() { 1 << (ty.primitive_size(tcx).bits() - 1) } else { 0 }
+}
+
+/// Returns the bytes of `value` if it is a `&str` constant.
+fn str_constant_bytes<'tcx>(tcx: TyCtxt<'tcx>, value: Const<'tcx>) -> Option<&'tcx [u8]> {
+    match value {
+        Const::Ty(ty, ct) if ty.peel_refs().is_str() => {
+            ct.try_to_valtree()?.try_to_raw_bytes(tcx, ty)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the `&str` or `&CStr` constant `value` as a `&[u8]` constant of the same bytes,
+/// including the NUL terminator of a `&CStr`. All are represented by the valtree of these bytes.
+fn constant_as_bytes<'tcx>(tcx: TyCtxt<'tcx>, value: Const<'tcx>) -> Const<'tcx> {
+    let valtree = match value {
+        Const::Ty(_, ct) => match ct.kind() {
+            ty::ConstKind::Value(_, valtree) => valtree,
+            _ => bug!("expected a string valtree, found {value:?}"),
+        },
+        _ => bug!("expected a string valtree, found {value:?}"),
+    };
+    let ty = Ty::new_imm_ref(tcx, tcx.lifetimes.re_static, Ty::new_slice(tcx, tcx.types.u8));
+    Const::Ty(ty, ty::Const::new_value(tcx, valtree, ty))
+}
+
 fn trait_method<'tcx>(
     tcx: TyCtxt<'tcx>,
     trait_def_id: DefId,
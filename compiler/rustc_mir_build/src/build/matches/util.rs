@@ -3,10 +3,22 @@
 use crate::build::expr::as_place::PlaceBase;
 use crate::build::matches::{Binding, Candidate, FlatPat, MatchPair, TestCase};
 use crate::build::Builder;
-use rustc_data_structures::fx::FxIndexMap;
+use crate::errors::{GuardFakeBorrowsInvalidKind, GuardFakeBorrowsUnsoundShallow};
+use rustc_ast::LitKind;
+use rustc_data_structures::fx::{FxIndexMap, FxIndexSet};
+use rustc_hir::def_id::LocalDefId;
+use rustc_hir::{BindingMode, ByRef, LangItem};
 use rustc_middle::mir::*;
-use rustc_middle::ty::Ty;
+use rustc_middle::thir::visit::{self, Visitor};
+use rustc_middle::thir::{
+    BlockSafety, ClosureExpr, Expr, ExprId, ExprKind, LintLevel, LocalVarId, LogicalOp, PatKind,
+    StmtKind, Thir,
+};
+use rustc_middle::ty::{self, AdtDef, GenericArgsRef, Ty, TyCtxt};
+use rustc_session::lint::Level;
+use rustc_span::symbol::sym;
 use rustc_span::Span;
+use rustc_target::abi::VariantIdx;
 use tracing::debug;
 
 impl<'a, 'tcx> Builder<'a, 'tcx> {
@@ -19,23 +31,128 @@ pub(crate) fn false_edges(
         real_target: BasicBlock,
         imaginary_target: Option<BasicBlock>,
         source_info: SourceInfo,
+        origin: FalseEdgeOrigin,
     ) {
         match imaginary_target {
             Some(target) if target != real_target => {
                 self.cfg.terminate(
                     from_block,
                     source_info,
-                    TerminatorKind::FalseEdge { real_target, imaginary_target: target },
+                    TerminatorKind::FalseEdge { real_target, imaginary_target: target, origin },
                 );
             }
             _ => self.cfg.goto(from_block, source_info, real_target),
         }
     }
+
+    /// With `-Zmir-emit-fake-borrow-markers`, takes a raw pointer to each fake-borrowed place that
+    /// can be soundly pointed to before a match guard runs, and returns those pointers.
+    ///
+    /// Fake borrows only exist for borrowck and are removed by `CleanupPostBorrowck`. These
+    /// markers stay in the MIR, so that once [`Builder::check_fake_borrow_markers`] reborrows
+    /// them after the guard, an aliasing model like Stacked Borrows in Miri reports any code that
+    /// mutated the places while the guard ran.
+    pub(super) fn push_fake_borrow_markers(
+        &mut self,
+        block: BasicBlock,
+        source_info: SourceInfo,
+        fake_borrows: &[(Place<'tcx>, Local, FakeBorrowKind)],
+    ) -> Vec<Place<'tcx>> {
+        if !self.tcx.sess.opts.unstable_opts.mir_emit_fake_borrow_markers {
+            return Vec::new();
+        }
+        let mut markers = Vec::new();
+        for &(place, _, kind) in fake_borrows {
+            if !self.can_mark_fake_borrow(place, kind) {
+                continue;
+            }
+            let ty = place.ty(&self.local_decls, self.tcx).ty;
+            let marker = self.temp(Ty::new_imm_ptr(self.tcx, ty), source_info.span);
+            self.cfg.push_assign(
+                block,
+                source_info,
+                marker,
+                Rvalue::AddressOf(Mutability::Not, place),
+            );
+            markers.push(marker);
+        }
+        markers
+    }
+
+    /// Reborrows the pointers created by [`Builder::push_fake_borrow_markers`]. This is a read
+    /// access through each marker, which is invalid if the place was written to in the meantime.
+    pub(super) fn check_fake_borrow_markers(
+        &mut self,
+        block: BasicBlock,
+        source_info: SourceInfo,
+        markers: &[Place<'tcx>],
+    ) {
+        let tcx = self.tcx;
+        let re_erased = tcx.lifetimes.re_erased;
+        for &marker in markers {
+            let place = tcx.mk_place_deref(marker);
+            let ty = place.ty(&self.local_decls, tcx).ty;
+            let reborrow = self.temp(Ty::new_imm_ref(tcx, re_erased, ty), source_info.span);
+            self.cfg.push_assign(
+                block,
+                source_info,
+                reborrow,
+                Rvalue::Ref(re_erased, BorrowKind::Shared, place),
+            );
+        }
+    }
+
+    /// Whether a marker can be created for a fake borrow of `place`, see
+    /// [`Builder::push_fake_borrow_markers`].
+    ///
+    /// A fake-borrowed place isn't necessarily valid when the guard runs (see point 4 of
+    /// [`collect_fake_borrows`]). Pointing into an inactive variant is fine, but indexing may be
+    /// out of bounds and dereferencing inside an inactive variant reads an invalid pointer.
+    /// Moreover the marker covers the whole place, so for shallow borrows we only mark places
+    /// whose contents are the tested value itself, otherwise we would report guards that
+    /// legitimately mutate the fields of an enum that was switched on.
+    fn can_mark_fake_borrow(&self, place: Place<'tcx>, kind: FakeBorrowKind) -> bool {
+        let mut in_downcast = false;
+        for elem in place.projection {
+            match elem {
+                ProjectionElem::Downcast(..) => in_downcast = true,
+                ProjectionElem::Deref if in_downcast => return false,
+                ProjectionElem::Index(_)
+                | ProjectionElem::ConstantIndex { .. }
+                | ProjectionElem::Subslice { .. } => return false,
+                _ => {}
+            }
+        }
+        match kind {
+            FakeBorrowKind::Deep => true,
+            FakeBorrowKind::Shallow => {
+                let ty = place.ty(&self.local_decls, self.tcx).ty;
+                ty.is_scalar() || ty.is_any_ptr() || ty.is_box()
+            }
+        }
+    }
+
+    /// Whether match lowering may assume that no value ever has the given variant of `adt_def`.
+    /// This agrees with exhaustiveness checking, which lets matches omit such variants.
+    pub(super) fn is_variant_uninhabited(
+        &self,
+        adt_def: AdtDef<'tcx>,
+        args: GenericArgsRef<'tcx>,
+        variant_index: VariantIdx,
+    ) -> bool {
+        (self.tcx.features().exhaustive_patterns || self.tcx.features().min_exhaustive_patterns)
+            && !adt_def
+                .variant(variant_index)
+                .inhabited_predicate(self.tcx, adt_def)
+                .instantiate(self.tcx, args)
+                .apply_ignore_module(self.tcx, self.param_env)
+    }
 }
 
-/// Determine the set of places that have to be stable across match guards.
+/// Determine, for each candidate, the set of places that have to be stable across its match guard.
 ///
-/// Returns a list of places that need a fake borrow along with a local to store it.
+/// Returns a list of all the places that need a fake borrow along with a local to store it, and for
+/// each candidate the subset of that list that its guard borrows (empty if it has no guard).
 ///
 /// Match exhaustiveness checking is not able to handle the case where the place being matched on is
 /// mutated in the guards. We add "fake borrows" to the guards that prevent any mutation of the
@@ -68,21 +185,61 @@ pub(crate) fn false_edges(
 ///    ```
 ///    So it would be UB to generate code for the fake borrows. They therefore have to be removed by
 ///    a MIR pass run after borrow checking.
+/// 5. A guard only needs to keep stable the places that can be tested or bound once it has run,
+///    i.e. those of its own candidate and of the candidates after it. The places that only earlier
+///    candidates test are not looked at again, so a guard may mutate them:
+///    ```rust
+///    let mut x = (true, 0);
+///    match x {
+///        (false, _) => {}
+///        (_, 0) if { x.0 = false; true } => {}
+///        _ => {}
+///    }
+///    ```
+///    We keep the places of the candidate itself even though they are only tested again through
+///    its or-patterns, so that a guard can never change the value that selected its arm.
+///
+/// `#[rustc_match_guard_fake_borrows]` overrides the kind of all these borrows, see
+/// [`forced_guard_fake_borrow_kind`].
 pub(super) fn collect_fake_borrows<'tcx>(
     cx: &mut Builder<'_, 'tcx>,
     candidates: &[&mut Candidate<'_, 'tcx>],
     temp_span: Span,
     scrutinee_base: PlaceBase,
-) -> Vec<(Place<'tcx>, Local, FakeBorrowKind)> {
-    let mut collector =
-        FakeBorrowCollector { cx, scrutinee_base, fake_borrows: FxIndexMap::default() };
-    for candidate in candidates.iter() {
+) -> (Vec<(Place<'tcx>, Local, FakeBorrowKind)>, Vec<Vec<(Place<'tcx>, Local, FakeBorrowKind)>>) {
+    let Some(first_guard) = candidates.iter().position(|candidate| candidate.has_guard) else {
+        return (Vec::new(), vec![Vec::new(); candidates.len()]);
+    };
+
+    // Visit the candidates from last to first, so that the places collected when we reach a
+    // candidate are those of the candidates from it onwards.
+    let mut collector = FakeBorrowCollector {
+        forced_kind: cx.forced_guard_fake_borrow_kind,
+        cx,
+        scrutinee_base,
+        fake_borrows: FxIndexMap::default(),
+        weakened_deref_patterns: FxIndexSet::default(),
+    };
+    let mut guard_borrows = vec![None; candidates.len()];
+    for (i, candidate) in candidates.iter().enumerate().skip(first_guard).rev() {
+        collector.visit_candidate(candidate);
+        if candidate.has_guard {
+            guard_borrows[i] = Some(collector.fake_borrows.clone());
+        }
+    }
+    // The places of the first guard include those of all the others. We allocate their temporaries
+    // in the order the candidates test them.
+    collector.fake_borrows.clear();
+    for candidate in &candidates[first_guard..] {
         collector.visit_candidate(candidate);
     }
     let fake_borrows = collector.fake_borrows;
     debug!("add_fake_borrows fake_borrows = {:?}", fake_borrows);
     let tcx = cx.tcx;
-    fake_borrows
+    for span in collector.weakened_deref_patterns {
+        tcx.dcx().emit_warn(GuardFakeBorrowsUnsoundShallow { span });
+    }
+    let fake_borrow_temps: Vec<_> = fake_borrows
         .iter()
         .map(|(matched_place, borrow_kind)| {
             let fake_borrow_deref_ty = matched_place.ty(&cx.local_decls, tcx).ty;
@@ -93,7 +250,425 @@ pub(super) fn collect_fake_borrows<'tcx>(
             let fake_borrow_temp = cx.local_decls.push(fake_borrow_temp);
             (*matched_place, fake_borrow_temp, *borrow_kind)
         })
-        .collect()
+        .collect();
+    let guard_borrows = guard_borrows
+        .into_iter()
+        .map(|borrows| {
+            let Some(borrows) = borrows else { return Vec::new() };
+            fake_borrow_temps
+                .iter()
+                .filter_map(|&(place, temp, _)| Some((place, temp, *borrows.get(&place)?)))
+                .collect()
+        })
+        .collect();
+    (fake_borrow_temps, guard_borrows)
+}
+
+/// Returns the kind of fake borrow that `#[rustc_match_guard_fake_borrows(shallow)]` or
+/// `#[rustc_match_guard_fake_borrows(deep)]` on the body `def_id` makes match guards take for all
+/// the places they must not change, instead of the kind [`collect_fake_borrows`] picks for each.
+///
+/// This is meant for code generators that hit borrowck errors in guards. `deep` is always sound
+/// but rejects more guards, e.g. those mutating the fields of an enum whose variant was tested.
+/// `shallow` only differs from the default for deref patterns, which need a deep borrow because a
+/// guard could otherwise change the value behind the pointer and make the match unsound; we warn
+/// about those.
+pub(crate) fn forced_guard_fake_borrow_kind(
+    tcx: TyCtxt<'_>,
+    def_id: LocalDefId,
+) -> Option<FakeBorrowKind> {
+    let attr = tcx.get_attr(def_id, sym::rustc_match_guard_fake_borrows)?;
+    match attr.meta_item_list().as_deref() {
+        Some([item]) if item.has_name(sym::shallow) => Some(FakeBorrowKind::Shallow),
+        Some([item]) if item.has_name(sym::deep) => Some(FakeBorrowKind::Deep),
+        _ => {
+            tcx.dcx().emit_err(GuardFakeBorrowsInvalidKind { span: attr.span });
+            None
+        }
+    }
+}
+
+/// Whether evaluating the guard `guard` may leave it without producing a value, e.g. through `?`,
+/// `return`, `break` or `continue`. This is conservative: a `break` out of a loop nested in the
+/// guard counts too. Closures have their own bodies, so exits within them are not considered.
+pub(super) fn guard_may_exit_early<'tcx>(thir: &Thir<'tcx>, guard: ExprId) -> bool {
+    let mut visitor = EarlyExitVisitor { thir, found: false };
+    visitor.visit_expr(&thir[guard]);
+    visitor.found
+}
+
+struct EarlyExitVisitor<'a, 'tcx> {
+    thir: &'a Thir<'tcx>,
+    found: bool,
+}
+
+impl<'a, 'tcx> Visitor<'a, 'tcx> for EarlyExitVisitor<'a, 'tcx> {
+    fn thir(&self) -> &'a Thir<'tcx> {
+        self.thir
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr<'tcx>) {
+        match expr.kind {
+            ExprKind::Return { .. }
+            | ExprKind::Become { .. }
+            | ExprKind::Break { .. }
+            | ExprKind::Continue { .. }
+            | ExprKind::ConstContinue { .. } => self.found = true,
+            _ => visit::walk_expr(self, expr),
+        }
+    }
+}
+
+/// Whether evaluating the guard `guard` may have side effects, i.e. whether it calls functions,
+/// including overloaded operators, assigns to places or runs inline assembly. Closures defined in
+/// the guard only have side effects when called, which counts as a call.
+pub(super) fn guard_has_side_effects<'tcx>(thir: &Thir<'tcx>, guard: ExprId) -> bool {
+    let mut visitor = SideEffectVisitor { thir, found: false };
+    visitor.visit_expr(&thir[guard]);
+    visitor.found
+}
+
+struct SideEffectVisitor<'a, 'tcx> {
+    thir: &'a Thir<'tcx>,
+    found: bool,
+}
+
+impl<'a, 'tcx> Visitor<'a, 'tcx> for SideEffectVisitor<'a, 'tcx> {
+    fn thir(&self) -> &'a Thir<'tcx> {
+        self.thir
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr<'tcx>) {
+        match expr.kind {
+            ExprKind::Call { .. }
+            | ExprKind::Assign { .. }
+            | ExprKind::AssignOp { .. }
+            | ExprKind::InlineAsm(_) => self.found = true,
+            _ => visit::walk_expr(self, expr),
+        }
+    }
+}
+
+/// Whether the guard `guard` refers to any of the variables `vars`, including through the captures
+/// of a closure.
+pub(super) fn guard_uses_variables<'tcx>(
+    thir: &Thir<'tcx>,
+    guard: ExprId,
+    vars: &[LocalVarId],
+) -> bool {
+    let mut visitor = VariableUseVisitor { thir, vars, found: false };
+    visitor.visit_expr(&thir[guard]);
+    visitor.found
+}
+
+/// Whether `guard` is an `if let` guard, i.e. whether one of the conditions of its `&&` chain is a
+/// `let` expression whose bindings [`Builder::declare_guard_bindings`] declares with the arm.
+pub(super) fn is_if_let_guard<'tcx>(thir: &Thir<'tcx>, guard: ExprId) -> bool {
+    match thir[guard].kind {
+        ExprKind::Let { .. } => true,
+        ExprKind::Scope { value: inner, .. } | ExprKind::Use { source: inner } => {
+            is_if_let_guard(thir, inner)
+        }
+        ExprKind::LogicalOp { op: LogicalOp::And, lhs, rhs } => {
+            is_if_let_guard(thir, lhs) || is_if_let_guard(thir, rhs)
+        }
+        _ => false,
+    }
+}
+
+/// Whether `a` and `b` are the same expression, and evaluating it can neither panic nor have side
+/// effects, so that it has the same value wherever it is evaluated in a `match` that doesn't run
+/// any other code in between. This only accepts a small set of expressions, like `DEBUG`,
+/// `self.verbose && !QUIET` or `n >= LIMIT` where `n` is not bound by the pattern.
+pub(super) fn same_pure_expr<'tcx>(thir: &Thir<'tcx>, a: ExprId, b: ExprId) -> bool {
+    let (a, b) = (&thir[a], &thir[b]);
+    if a.ty != b.ty {
+        return false;
+    }
+    match (&a.kind, &b.kind) {
+        (&ExprKind::Scope { value: a, .. }, &ExprKind::Scope { value: b, .. })
+        | (&ExprKind::Use { source: a }, &ExprKind::Use { source: b }) => {
+            same_pure_expr(thir, a, b)
+        }
+        (&ExprKind::Literal { lit: a, neg: a_neg }, &ExprKind::Literal { lit: b, neg: b_neg }) => {
+            a.node == b.node && a_neg == b_neg
+        }
+        (&ExprKind::NonHirLiteral { lit: a, .. }, &ExprKind::NonHirLiteral { lit: b, .. }) => {
+            a == b
+        }
+        (
+            &ExprKind::NamedConst { def_id: a, args: a_args, .. },
+            &ExprKind::NamedConst { def_id: b, args: b_args, .. },
+        ) => a == b && a_args == b_args,
+        (&ExprKind::ConstParam { def_id: a, .. }, &ExprKind::ConstParam { def_id: b, .. }) => {
+            a == b
+        }
+        (&ExprKind::VarRef { id: a }, &ExprKind::VarRef { id: b }) => a == b,
+        (&ExprKind::UpvarRef { var_hir_id: a, .. }, &ExprKind::UpvarRef { var_hir_id: b, .. }) => {
+            a == b
+        }
+        // Reading a union field may be undefined behavior, so it is only done where it was written.
+        (
+            &ExprKind::Field { lhs: a, variant_index: a_variant, name: a_name },
+            &ExprKind::Field { lhs: b, variant_index: b_variant, name: b_name },
+        ) => {
+            !thir[a].ty.is_union()
+                && a_variant == b_variant
+                && a_name == b_name
+                && same_pure_expr(thir, a, b)
+        }
+        // Dereferencing a raw pointer may be undefined behavior, so only references are allowed.
+        (&ExprKind::Deref { arg: a }, &ExprKind::Deref { arg: b }) => {
+            thir[a].ty.is_ref() && same_pure_expr(thir, a, b)
+        }
+        // Negation and arithmetic may overflow.
+        (
+            &ExprKind::Unary { op: UnOp::Not, arg: a },
+            &ExprKind::Unary { op: UnOp::Not, arg: b },
+        ) => same_pure_expr(thir, a, b),
+        (
+            &ExprKind::Binary { op: a_op, lhs: a_lhs, rhs: a_rhs },
+            &ExprKind::Binary { op: b_op, lhs: b_lhs, rhs: b_rhs },
+        ) => {
+            a_op == b_op
+                && matches!(
+                    a_op,
+                    BinOp::Eq
+                        | BinOp::Ne
+                        | BinOp::Lt
+                        | BinOp::Le
+                        | BinOp::Gt
+                        | BinOp::Ge
+                        | BinOp::BitAnd
+                        | BinOp::BitOr
+                        | BinOp::BitXor
+                )
+                && same_pure_expr(thir, a_lhs, b_lhs)
+                && same_pure_expr(thir, a_rhs, b_rhs)
+        }
+        (
+            &ExprKind::LogicalOp { op: a_op, lhs: a_lhs, rhs: a_rhs },
+            &ExprKind::LogicalOp { op: b_op, lhs: b_lhs, rhs: b_rhs },
+        ) => {
+            a_op == b_op && same_pure_expr(thir, a_lhs, b_lhs) && same_pure_expr(thir, a_rhs, b_rhs)
+        }
+        _ => false,
+    }
+}
+
+/// Whether `lint_level`, the lint level of a `match` arm, comes from lint level attributes on the
+/// arm itself, like `#[allow(..)]`, rather than from its parents.
+pub(super) fn has_lint_level_attrs(tcx: TyCtxt<'_>, lint_level: LintLevel) -> bool {
+    match lint_level {
+        LintLevel::Inherited => false,
+        LintLevel::Explicit(hir_id) => {
+            tcx.hir().attrs(hir_id).iter().any(|attr| Level::from_attr(attr).is_some())
+        }
+    }
+}
+
+/// Whether the arm bodies `a` and `b` are the same expression, so that lowering either of them
+/// gives the same MIR, up to spans. Unlike [`same_pure_expr`], the expression may have side
+/// effects, since it is still evaluated once on each path through the `match`. Calls must have
+/// the same span though, as `#[track_caller]` functions observe it, which is the case for calls
+/// expanded from the same macro, like the `unreachable!()` of generated arms.
+pub(super) fn same_arm_body<'tcx>(thir: &Thir<'tcx>, a: ExprId, b: ExprId) -> bool {
+    if thir[a].ty != thir[b].ty {
+        return false;
+    }
+    match (&thir[a].kind, &thir[b].kind) {
+        // Lint levels and the safety of blocks are tracked by `HirId`, so they only allow blocks
+        // and scopes that don't set them.
+        (
+            &ExprKind::Scope { value: a, lint_level: a_lint_level, .. },
+            &ExprKind::Scope { value: b, lint_level: b_lint_level, .. },
+        ) => {
+            matches!((a_lint_level, b_lint_level), (LintLevel::Inherited, LintLevel::Inherited))
+                && same_arm_body(thir, a, b)
+        }
+        (&ExprKind::Block { block: a }, &ExprKind::Block { block: b }) => {
+            let (a, b) = (&thir[a], &thir[b]);
+            matches!((a.safety_mode, b.safety_mode), (BlockSafety::Safe, BlockSafety::Safe))
+                && !a.targeted_by_break
+                && !b.targeted_by_break
+                && a.stmts.is_empty()
+                && b.stmts.is_empty()
+                && match (a.expr, b.expr) {
+                    (Some(a), Some(b)) => same_arm_body(thir, a, b),
+                    (a, b) => a.is_none() && b.is_none(),
+                }
+        }
+        (&ExprKind::Use { source: a }, &ExprKind::Use { source: b })
+        | (&ExprKind::NeverToAny { source: a }, &ExprKind::NeverToAny { source: b }) => {
+            same_arm_body(thir, a, b)
+        }
+        (
+            &ExprKind::PointerCoercion { cast: a_cast, source: a },
+            &ExprKind::PointerCoercion { cast: b_cast, source: b },
+        ) => a_cast == b_cast && same_arm_body(thir, a, b),
+        (
+            &ExprKind::Borrow { borrow_kind: a_kind, arg: a },
+            &ExprKind::Borrow { borrow_kind: b_kind, arg: b },
+        ) => a_kind == b_kind && same_arm_body(thir, a, b),
+        (&ExprKind::Return { value: a }, &ExprKind::Return { value: b }) => match (a, b) {
+            (Some(a), Some(b)) => same_arm_body(thir, a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        },
+        (&ExprKind::ZstLiteral { user_ty: ref a }, &ExprKind::ZstLiteral { user_ty: ref b }) => {
+            a == b
+        }
+        (&ExprKind::Tuple { fields: ref a }, &ExprKind::Tuple { fields: ref b }) => {
+            a.len() == b.len() && std::iter::zip(a, b).all(|(&a, &b)| same_arm_body(thir, a, b))
+        }
+        (&ExprKind::Adt(ref a), &ExprKind::Adt(ref b)) => {
+            a.adt_def == b.adt_def
+                && a.variant_index == b.variant_index
+                && a.args == b.args
+                && a.user_ty == b.user_ty
+                && a.base.is_none()
+                && b.base.is_none()
+                && a.fields.len() == b.fields.len()
+                && std::iter::zip(&a.fields, &b.fields)
+                    .all(|(a, b)| a.name == b.name && same_arm_body(thir, a.expr, b.expr))
+        }
+        (
+            &ExprKind::Call {
+                ty: a_ty,
+                fun: a_fun,
+                args: ref a_args,
+                from_hir_call: a_from_hir_call,
+                fn_span: a_fn_span,
+            },
+            &ExprKind::Call {
+                ty: b_ty,
+                fun: b_fun,
+                args: ref b_args,
+                from_hir_call: b_from_hir_call,
+                fn_span: b_fn_span,
+            },
+        ) => {
+            a_ty == b_ty
+                && a_from_hir_call == b_from_hir_call
+                && a_fn_span == b_fn_span
+                && a_args.len() == b_args.len()
+                && same_arm_body(thir, a_fun, b_fun)
+                && std::iter::zip(a_args, b_args).all(|(&a, &b)| same_arm_body(thir, a, b))
+        }
+        // Expressions that can't panic, like constants, variables outside of the `match` and
+        // comparisons, whose spans don't matter either.
+        _ => same_pure_expr(thir, a, b),
+    }
+}
+
+/// Whether the arm body `body` only calls the `panic` lang item with a string literal, like
+/// `unreachable!()`, `todo!()` or `panic!("...")` in the 2015 edition expand to. Returns the call
+/// and the literal.
+pub(super) fn const_panic_call<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    thir: &Thir<'tcx>,
+    body: ExprId,
+) -> Option<(ExprId, ExprId)> {
+    match thir[body].kind {
+        ExprKind::Scope { value, lint_level: LintLevel::Inherited, .. }
+        | ExprKind::Use { source: value }
+        | ExprKind::NeverToAny { source: value } => const_panic_call(tcx, thir, value),
+        ExprKind::Block { block } => {
+            let block = &thir[block];
+            match block.expr {
+                Some(expr)
+                    if matches!(block.safety_mode, BlockSafety::Safe)
+                        && !block.targeted_by_break
+                        && block.stmts.is_empty() =>
+                {
+                    const_panic_call(tcx, thir, expr)
+                }
+                _ => None,
+            }
+        }
+        ExprKind::Call { fun, ref args, from_hir_call: true, .. } => {
+            let &ty::FnDef(def_id, _) = thir[fun].ty.kind() else { return None };
+            let &[mut message] = &**args else { return None };
+            while let ExprKind::Scope { value, .. } = thir[message].kind {
+                message = value;
+            }
+            let is_str_literal = matches!(
+                thir[message].kind,
+                ExprKind::Literal { lit, neg: false } if matches!(lit.node, LitKind::Str(..))
+            );
+            (tcx.is_lang_item(def_id, LangItem::Panic) && is_str_literal).then_some((body, message))
+        }
+        _ => None,
+    }
+}
+
+/// The variables that the first statement of the arm body `body` moves out of: the initializer
+/// of a `let` binding it by value, or the expression of the first statement (or of a body without
+/// statements), when it is one of these variables or a call or tuple taking them as operands. For
+/// example, `x` in `Some(x) => { let y = x; ... }` or in `Some(x) => consume(x)`.
+pub(super) fn vars_moved_first<'tcx>(thir: &Thir<'tcx>, body: ExprId) -> Vec<LocalVarId> {
+    let first = match thir[body].kind {
+        ExprKind::Scope { value, .. } => return vars_moved_first(thir, value),
+        ExprKind::Block { block } => {
+            let block = &thir[block];
+            match block.stmts.first().map(|&stmt| &thir[stmt].kind) {
+                Some(&StmtKind::Expr { expr, .. }) => expr,
+                Some(&StmtKind::Let { initializer: Some(init), ref pattern, .. })
+                    if matches!(
+                        pattern.kind,
+                        PatKind::Binding { mode: BindingMode(ByRef::No, _), .. }
+                    ) =>
+                {
+                    init
+                }
+                Some(&StmtKind::Let { .. }) => return Vec::new(),
+                None => match block.expr {
+                    Some(expr) => expr,
+                    None => return Vec::new(),
+                },
+            }
+        }
+        _ => body,
+    };
+    let operands = match thir[first].kind {
+        ExprKind::Call { ref args, .. } => args,
+        ExprKind::Tuple { ref fields } => fields,
+        _ => std::slice::from_ref(&first),
+    };
+    operands.iter().filter_map(|&operand| moved_var(thir, operand)).collect()
+}
+
+fn moved_var<'tcx>(thir: &Thir<'tcx>, expr: ExprId) -> Option<LocalVarId> {
+    match thir[expr].kind {
+        ExprKind::Scope { value, .. } | ExprKind::Use { source: value } => moved_var(thir, value),
+        ExprKind::VarRef { id } => Some(id),
+        _ => None,
+    }
+}
+
+struct VariableUseVisitor<'a, 'tcx> {
+    thir: &'a Thir<'tcx>,
+    vars: &'a [LocalVarId],
+    found: bool,
+}
+
+impl<'a, 'tcx> Visitor<'a, 'tcx> for VariableUseVisitor<'a, 'tcx> {
+    fn thir(&self) -> &'a Thir<'tcx> {
+        self.thir
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr<'tcx>) {
+        match expr.kind {
+            ExprKind::VarRef { id } => self.found |= self.vars.contains(&id),
+            // The body of a closure is separate, but the places it captures are expressions of
+            // this body.
+            ExprKind::Closure(box ClosureExpr { ref upvars, .. }) => {
+                for &upvar in upvars.iter() {
+                    self.visit_expr(&self.thir[upvar]);
+                }
+            }
+            _ => visit::walk_expr(self, expr),
+        }
+    }
 }
 
 pub(super) struct FakeBorrowCollector<'a, 'b, 'tcx> {
@@ -106,11 +681,17 @@ pub(super) struct FakeBorrowCollector<'a, 'b, 'tcx> {
     /// Invariant: for any place in `fake_borrows`, all the prefixes of this place that are
     /// dereferences are also borrowed with the same of stronger borrow kind.
     fake_borrows: FxIndexMap<Place<'tcx>, FakeBorrowKind>,
+    /// The kind of all the borrows, if set by `#[rustc_match_guard_fake_borrows]`.
+    forced_kind: Option<FakeBorrowKind>,
+    /// Spans of the deref patterns that needed a deep borrow but only got a shallow one because of
+    /// `forced_kind`.
+    weakened_deref_patterns: FxIndexSet<Span>,
 }
 
 impl<'a, 'b, 'tcx> FakeBorrowCollector<'a, 'b, 'tcx> {
     // Fake borrow this place and its dereference prefixes.
     fn fake_borrow(&mut self, place: Place<'tcx>, kind: FakeBorrowKind) {
+        let kind = self.forced_kind.unwrap_or(kind);
         if self.fake_borrows.get(&place).is_some_and(|k| *k >= kind) {
             return;
         }
@@ -121,6 +702,7 @@ fn fake_borrow(&mut self, place: Place<'tcx>, kind: FakeBorrowKind) {
 
     // Fake borrow the prefixes of this place that are dereferences.
     fn fake_borrow_deref_prefixes(&mut self, place: Place<'tcx>, kind: FakeBorrowKind) {
+        let kind = self.forced_kind.unwrap_or(kind);
         for (place_ref, elem) in place.as_ref().iter_projections().rev() {
             if let ProjectionElem::Deref = elem {
                 // Insert a shallow borrow after a deref. For other projections the borrow of
@@ -172,6 +754,9 @@ fn visit_match_pair(&mut self, match_pair: &MatchPair<'_, 'tcx>) {
             // ```
             // Hence we fake borrow using a deep borrow.
             if let Some(place) = match_pair.place {
+                if self.forced_kind == Some(FakeBorrowKind::Shallow) {
+                    self.weakened_deref_patterns.insert(match_pair.pattern.span);
+                }
                 self.fake_borrow(place, FakeBorrowKind::Deep);
             }
         } else {
@@ -1,10 +1,11 @@
 use crate::build::expr::as_place::PlaceBuilder;
 use crate::build::scope::DropKind;
+use crate::check_tail_calls::check_tail_calls;
 use itertools::Itertools;
 use rustc_apfloat::ieee::{Double, Half, Quad, Single};
 use rustc_apfloat::Float;
 use rustc_ast::attr;
-use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
 use rustc_data_structures::sorted_map::SortedIndexMultiMap;
 use rustc_errors::ErrorGuaranteed;
 use rustc_hir::def::DefKind;
@@ -66,6 +67,11 @@ pub(crate) fn mir_build<'tcx>(tcx: TyCtxtAt<'tcx>, def: LocalDefId) -> Body<'tcx
             // maybe move the check to a MIR pass?
             tcx.ensure().check_liveness(def);
 
+            // `become` is only lowered for calls that can reuse the frame of the caller.
+            if let Err(e) = check_tail_calls(tcx, def, &thir.borrow(), expr) {
+                return construct_error(tcx, def, e);
+            }
+
             // Don't steal here, instead steal in unsafeck. This is so that
             // pattern inline constants can be evaluated as part of building the
             // THIR of the parent function without a cycle.
@@ -220,6 +226,73 @@ struct Builder<'a, 'tcx> {
     /// Collects additional coverage information during MIR building.
     /// Only present if coverage is enabled and this function is eligible.
     coverage_info: Option<coverageinfo::CoverageInfoBuilder>,
+
+    /// Records the decision trees built by match lowering.
+    /// Only present if the body has `#[rustc_dump_decision_tree]`.
+    decision_tree_dump: Option<matches::DecisionTreeDump>,
+
+    /// Whether the body is marked `#[constant_time_match]`, so that its `match`es are lowered
+    /// without branching on their scrutinee.
+    constant_time_matches: bool,
+
+    /// Whether the body is marked `#[branchless_lazy_bool]`, so that its `&&`s and `||`s with
+    /// cheap operands without side effects evaluate both operands instead of branching.
+    branchless_lazy_bools: bool,
+
+    /// The kind of all the fake borrows taken by match guards.
+    /// Only present if the body has `#[rustc_match_guard_fake_borrows]`.
+    forced_guard_fake_borrow_kind: Option<FakeBorrowKind>,
+
+    /// How many more candidate copies match lowering may make for the current match.
+    /// Only present with `-Zmatch-lowering=decision-tree`.
+    match_duplication_budget: Option<usize>,
+
+    /// Blocks ending in a switch on the variants of an enum that has a target for each variant,
+    /// recorded while building the decision tree of the current match.
+    exhaustive_switch_blocks: Vec<BasicBlock>,
+
+    /// Blocks of a decision tree that only lead to match arms marked `#[cold]`, so that the
+    /// tests branching to them can be hinted as unlikely to do so.
+    cold_match_blocks: FxHashSet<BasicBlock>,
+
+    /// The outcomes of the tests of the enclosing branches, while building a branch of a
+    /// decision tree.
+    match_facts: Vec<matches::MatchFact<'tcx>>,
+
+    /// The tests and the patterns that multiply them in the current decision tree, for the
+    /// `large_match_trees` lint.
+    match_tree_stats: matches::MatchTreeStats,
+
+    /// The blocks ending the branches of the tests of the current decision tree, which fall
+    /// through to the candidates after these tests, keyed by the block these candidates start at
+    /// and with the outcome of the test in that branch.
+    match_fallthroughs: FxIndexMap<BasicBlock, Vec<(BasicBlock, matches::MatchFact<'tcx>)>>,
+
+    /// The temporaries holding the results of the `deref()` calls of the deref patterns of the
+    /// current decision tree, keyed by the place they deref and whether they deref it mutably.
+    deref_temps: FxIndexMap<(Place<'tcx>, Mutability), Place<'tcx>>,
+
+    /// Temporaries holding the values of the guards shared by consecutive arms of a `match`, see
+    /// [`Builder::hoist_shared_guards`].
+    hoisted_guards: FxHashMap<ExprId, Place<'tcx>>,
+
+    /// The bindings of `match` arms for which no `StorageLive` and `StorageDead` are emitted, see
+    /// [`Builder::elide_moved_binding_storage`].
+    storage_elided_bindings: FxHashSet<LocalVarId>,
+
+    /// Temporaries holding the discriminants of the places a `Switch` test has already read, on
+    /// every path to the candidates being matched, with the block of that test. Later `Switch`
+    /// tests on the same places reuse them instead of reading the discriminant again.
+    cached_discriminants: Vec<(Place<'tcx>, Place<'tcx>, BasicBlock)>,
+
+    /// A block terminated by `Unreachable`, created the first time match lowering needs a target
+    /// that can't be taken and reused for all the others, see [`Builder::unreachable_block`].
+    unreachable_block: Option<BasicBlock>,
+
+    /// The block that the edges of the match tree which only invalid values can take point to
+    /// with `-Zmatch-otherwise=trap` or `-Zmatch-otherwise=abort`, created the first time it is
+    /// needed, see [`Builder::invalid_value_block`].
+    invalid_value_block: Option<BasicBlock>,
 }
 
 type CaptureMap<'tcx> = SortedIndexMultiMap<usize, HirId, Capture<'tcx>>;
@@ -774,6 +847,22 @@ fn new(
             var_debug_info: vec![],
             lint_level_roots_cache: GrowableBitSet::new_empty(),
             coverage_info: coverageinfo::CoverageInfoBuilder::new_if_enabled(tcx, def),
+            decision_tree_dump: matches::DecisionTreeDump::new_if_requested(tcx, def),
+            constant_time_matches: tcx.has_attr(def, sym::constant_time_match),
+            branchless_lazy_bools: tcx.has_attr(def, sym::branchless_lazy_bool),
+            forced_guard_fake_borrow_kind: matches::forced_guard_fake_borrow_kind(tcx, def),
+            match_duplication_budget: None,
+            exhaustive_switch_blocks: Vec::new(),
+            cold_match_blocks: FxHashSet::default(),
+            match_facts: Vec::new(),
+            match_tree_stats: Default::default(),
+            match_fallthroughs: Default::default(),
+            deref_temps: Default::default(),
+            hoisted_guards: Default::default(),
+            storage_elided_bindings: Default::default(),
+            cached_discriminants: Vec::new(),
+            unreachable_block: None,
+            invalid_value_block: None,
         };
 
         assert_eq!(builder.cfg.start_new_block(), START_BLOCK);
@@ -89,7 +89,7 @@
 use rustc_index::{IndexSlice, IndexVec};
 use rustc_middle::middle::region;
 use rustc_middle::mir::*;
-use rustc_middle::thir::{ExprId, LintLevel};
+use rustc_middle::thir::{ArmId, ExprId, LintLevel};
 use rustc_middle::{bug, span_bug};
 use rustc_session::lint::Level;
 use rustc_span::source_map::Spanned;
@@ -106,6 +106,10 @@ pub(crate) struct Scopes<'tcx> {
     /// The scope of the innermost if-then currently being lowered.
     if_then_scope: Option<IfThenScope>,
 
+    /// The `#[loop_match]` loops currently being lowered, whose arms a `#[const_continue]` may
+    /// jump to directly.
+    const_continuable_scopes: Vec<ConstContinuableScope<'tcx>>,
+
     /// Drops that need to be done on unwind paths. See the comment on
     /// [DropTree] for more details.
     unwind_drops: DropTree,
@@ -171,6 +175,19 @@ struct BreakableScope<'tcx> {
     continue_drops: Option<DropTree>,
 }
 
+#[derive(Debug)]
+struct ConstContinuableScope<'tcx> {
+    /// Region scope of the labeled block of the `#[loop_match]`
+    region_scope: region::Scope,
+    /// The `state` place, which a `#[const_continue]` assigns before jumping
+    state_place: Place<'tcx>,
+    /// The arms of the `match` on `state`
+    arms: Box<[ArmId]>,
+    /// For each arm, the block where it is entered once its pattern matched, if a
+    /// `#[const_continue]` can jump there, and the drops on the paths to it.
+    arm_entries: Vec<Option<(BasicBlock, DropTree)>>,
+}
+
 #[derive(Debug)]
 struct IfThenScope {
     /// The if-then scope or arm scope
@@ -434,6 +451,7 @@ pub(crate) fn new() -> Self {
             scopes: Vec::new(),
             breakable_scopes: Vec::new(),
             if_then_scope: None,
+            const_continuable_scopes: Vec::new(),
             unwind_drops: DropTree::new(),
             coroutine_drops: DropTree::new(),
         }
@@ -524,6 +542,40 @@ pub(crate) fn in_breakable_scope<F>(
         }
     }
 
+    /// Lowers the arms of the `match` of a `#[loop_match]` with `f`, in which a
+    /// `#[const_continue]` assigns `state_place` and jumps to the block in `arm_blocks` of the arm
+    /// that its value selects.
+    pub(crate) fn in_const_continuable_scope<F>(
+        &mut self,
+        state_place: Place<'tcx>,
+        arms: &[ArmId],
+        arm_blocks: Vec<Option<BasicBlock>>,
+        span: Span,
+        f: F,
+    ) -> BlockAnd<()>
+    where
+        F: FnOnce(&mut Builder<'a, 'tcx>) -> BlockAnd<()>,
+    {
+        let region_scope = self.scopes.topmost();
+        let scope = ConstContinuableScope {
+            region_scope,
+            state_place,
+            arms: arms.into(),
+            arm_entries: arm_blocks
+                .into_iter()
+                .map(|block| block.map(|block| (block, DropTree::new())))
+                .collect(),
+        };
+        self.scopes.const_continuable_scopes.push(scope);
+        let normal_exit_block = f(self);
+        let const_continuable_scope = self.scopes.const_continuable_scopes.pop().unwrap();
+        assert!(const_continuable_scope.region_scope == region_scope);
+        for (arm_block, drops) in const_continuable_scope.arm_entries.into_iter().flatten() {
+            self.build_exit_tree(drops, region_scope, span, Some(arm_block));
+        }
+        normal_exit_block
+    }
+
     /// Start an if-then scope which tracks drop for `if` expressions and `if`
     /// guards.
     ///
@@ -711,6 +763,53 @@ pub(crate) fn break_scope(
         self.cfg.start_new_block().unit()
     }
 
+    /// Sets up the drops for a `#[const_continue]` from `block` to the arm of the `#[loop_match]`
+    /// with the labeled block `target` that `value` selects. If the arm can't be known before
+    /// runtime, this is a `break` to the labeled block, after which the `match` runs again.
+    pub(crate) fn break_const_continuable_scope(
+        &mut self,
+        mut block: BasicBlock,
+        value: ExprId,
+        target: region::Scope,
+        source_info: SourceInfo,
+    ) -> BlockAnd<()> {
+        let span = source_info.span;
+        let index = self
+            .scopes
+            .const_continuable_scopes
+            .iter()
+            .rposition(|scope| scope.region_scope == target)
+            .unwrap_or_else(|| span_bug!(span, "no enclosing `#[loop_match]` found"));
+        let scope = &self.scopes.const_continuable_scopes[index];
+        let state_place = scope.state_place;
+        let Some(arm) = self
+            .const_continue_target(&scope.arms, value)
+            .filter(|&arm| scope.arm_entries[arm].is_some())
+        else {
+            let target = BreakableTarget::Break(target);
+            return self.break_scope(block, Some(value), target, source_info);
+        };
+
+        self.block_context.push(BlockFrame::SubExpr);
+        unpack!(block = self.expr_into_dest(state_place, block, value));
+        self.block_context.pop();
+
+        let scope_index = self.scopes.scope_index(target, span);
+        let (_, drops) =
+            self.scopes.const_continuable_scopes[index].arm_entries[arm].as_mut().unwrap();
+        let drop_idx = self.scopes.scopes[scope_index + 1..]
+            .iter()
+            .flat_map(|scope| &scope.drops)
+            .fold(ROOT_NODE, |drop_idx, &drop| drops.add_drop(drop, drop_idx));
+
+        drops.add_entry_point(block, drop_idx);
+
+        // As in `break_scope`, this terminator is overwritten when the drop tree is built.
+        self.cfg.terminate(block, source_info, TerminatorKind::UnwindResume);
+
+        self.cfg.start_new_block().unit()
+    }
+
     /// Sets up the drops for breaking from `block` due to an `if` condition
     /// that turned out to be false.
     ///
@@ -0,0 +1,103 @@
+use crate::errors::*;
+
+use rustc_hir::def_id::LocalDefId;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::thir::visit::{self, Visitor};
+use rustc_middle::thir::*;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_span::ErrorGuaranteed;
+use rustc_target::spec::abi::Abi;
+
+/// Checks that every `become` in the body is a tail call that MIR building can lower to a
+/// `TerminatorKind::TailCall`: the locals of the caller are dropped before the jump, and the
+/// callee reuses the caller's frame, so it must be a plain function call with the same signature
+/// as the caller.
+pub(crate) fn check_tail_calls<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def: LocalDefId,
+    thir: &Thir<'tcx>,
+    expr: ExprId,
+) -> Result<(), ErrorGuaranteed> {
+    // `become` outside of a function body has already been reported by typeck.
+    let BodyTy::Fn(caller_sig) = thir.body_type else { return Ok(()) };
+    let mut visitor = TailCallCkVisitor {
+        tcx,
+        thir,
+        param_env: tcx.param_env(def),
+        caller_sig,
+        found_errors: Ok(()),
+    };
+    visitor.visit_expr(&thir[expr]);
+    visitor.found_errors
+}
+
+struct TailCallCkVisitor<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    thir: &'a Thir<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    caller_sig: ty::FnSig<'tcx>,
+    found_errors: Result<(), ErrorGuaranteed>,
+}
+
+impl<'a, 'tcx> TailCallCkVisitor<'a, 'tcx> {
+    fn check_tail_call(&mut self, value: ExprId) {
+        let tcx = self.tcx;
+        // `become` is lowered like `return`, so its operand is wrapped in a scope.
+        let mut expr = &self.thir[value];
+        if let ExprKind::Scope { value, .. } = expr.kind {
+            expr = &self.thir[value];
+        }
+        let ExprKind::Call { ty: fn_ty, from_hir_call: true, fn_span, .. } = expr.kind else {
+            // Overloaded operators are calls too, but they aren't written as calls.
+            let guar = tcx.dcx().emit_err(TailCallNotCall { span: expr.span });
+            self.found_errors = Err(guar);
+            return;
+        };
+
+        let callee_sig =
+            tcx.normalize_erasing_late_bound_regions(self.param_env, fn_ty.fn_sig(tcx));
+        if callee_sig.abi == Abi::RustCall {
+            // A call of a closure or of another value implementing the `Fn*` traits, which is
+            // rewritten to a call of a method of these traits taking its arguments as a tuple.
+            let guar = tcx.dcx().emit_err(TailCallRustCall { span: fn_span });
+            self.found_errors = Err(guar);
+            return;
+        }
+
+        if let ty::FnDef(callee, _) = *fn_ty.kind()
+            && tcx.codegen_fn_attrs(callee).flags.contains(CodegenFnAttrFlags::TRACK_CALLER)
+        {
+            // The location of the caller is an implicit argument that the caller's frame lacks.
+            let guar = tcx.dcx().emit_err(TailCallTrackCaller { span: fn_span });
+            self.found_errors = Err(guar);
+            return;
+        }
+
+        let caller_sig = tcx.normalize_erasing_regions(self.param_env, self.caller_sig);
+        if caller_sig.inputs_and_output != callee_sig.inputs_and_output
+            || caller_sig.c_variadic != callee_sig.c_variadic
+            || caller_sig.abi != callee_sig.abi
+        {
+            let fn_ptr = |sig| Ty::new_fn_ptr(tcx, ty::Binder::dummy(sig));
+            let guar = tcx.dcx().emit_err(TailCallMismatchedSignature {
+                span: fn_span,
+                caller: fn_ptr(caller_sig),
+                callee: fn_ptr(callee_sig),
+            });
+            self.found_errors = Err(guar);
+        }
+    }
+}
+
+impl<'a, 'tcx> Visitor<'a, 'tcx> for TailCallCkVisitor<'a, 'tcx> {
+    fn thir(&self) -> &'a Thir<'tcx> {
+        self.thir
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr<'tcx>) {
+        if let ExprKind::Become { value } = expr.kind {
+            self.check_tail_call(value);
+        }
+        visit::walk_expr(self, expr);
+    }
+}
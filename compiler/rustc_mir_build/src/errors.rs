@@ -785,6 +785,74 @@ pub(crate) struct TypeNotStructural<'tcx> {
     pub(crate) non_sm_ty: Ty<'tcx>,
 }
 
+#[derive(Diagnostic)]
+#[diag(mir_build_loop_match_invalid_shape)]
+#[note]
+pub(crate) struct LoopMatchInvalidShape {
+    #[primary_span]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(mir_build_const_continue_bad_label)]
+pub(crate) struct ConstContinueBadLabel {
+    #[primary_span]
+    #[label]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(mir_build_const_continue_not_const)]
+pub(crate) struct ConstContinueNotConst {
+    #[primary_span]
+    #[label]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(mir_build_constant_time_match_unsupported)]
+#[note]
+pub(crate) struct ConstantTimeMatchUnsupported {
+    #[primary_span]
+    #[label]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(mir_build_tail_call_not_call)]
+pub(crate) struct TailCallNotCall {
+    #[primary_span]
+    #[label]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(mir_build_tail_call_rust_call)]
+pub(crate) struct TailCallRustCall {
+    #[primary_span]
+    #[label]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(mir_build_tail_call_track_caller)]
+pub(crate) struct TailCallTrackCaller {
+    #[primary_span]
+    #[label]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(mir_build_tail_call_mismatched_signature)]
+#[note]
+pub(crate) struct TailCallMismatchedSignature<'tcx> {
+    #[primary_span]
+    #[label]
+    pub(crate) span: Span,
+    pub(crate) caller: Ty<'tcx>,
+    pub(crate) callee: Ty<'tcx>,
+}
+
 #[derive(Diagnostic)]
 #[diag(mir_build_non_partial_eq_match)]
 pub(crate) struct TypeNotPartialEq<'tcx> {
@@ -844,6 +912,46 @@ pub(crate) struct MCDCExceedsConditionLimit {
     pub(crate) max_conditions: usize,
 }
 
+#[derive(Diagnostic)]
+#[diag(mir_build_guard_fake_borrows_invalid_kind)]
+#[help]
+pub(crate) struct GuardFakeBorrowsInvalidKind {
+    #[primary_span]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(mir_build_guard_fake_borrows_unsound_shallow)]
+#[note]
+pub(crate) struct GuardFakeBorrowsUnsoundShallow {
+    #[primary_span]
+    #[label]
+    pub(crate) span: Span,
+}
+
+#[derive(LintDiagnostic)]
+#[diag(mir_build_duplicated_guard_side_effects)]
+pub(crate) struct DuplicatedGuardSideEffects {
+    #[label]
+    pub(crate) guard: Span,
+    #[label(mir_build_pattern_label)]
+    pub(crate) pattern: Span,
+    pub(crate) alternatives: usize,
+}
+
+#[derive(LintDiagnostic)]
+#[diag(mir_build_large_match_tree)]
+#[note]
+pub(crate) struct LargeMatchTree {
+    #[label]
+    pub(crate) span: Span,
+    #[label(mir_build_pattern_label)]
+    pub(crate) patterns: Vec<Span>,
+    pub(crate) blocks: usize,
+    pub(crate) tests: usize,
+    pub(crate) limit: usize,
+}
+
 #[derive(Diagnostic)]
 #[diag(mir_build_pattern_not_covered, code = E0005)]
 pub(crate) struct PatternNotCovered<'s, 'tcx> {
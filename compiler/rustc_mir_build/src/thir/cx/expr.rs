@@ -23,7 +23,7 @@
 };
 use rustc_middle::{bug, span_bug};
 use rustc_span::source_map::Spanned;
-use rustc_span::{sym, Span, DUMMY_SP};
+use rustc_span::{sym, Span, Symbol, DUMMY_SP};
 use rustc_target::abi::{FieldIdx, FIRST_VARIANT};
 use tracing::{debug, info, instrument, trace};
 
@@ -692,6 +692,9 @@ fn make_mirror_unadjusted(&mut self, expr: &'tcx hir::Expr<'tcx>) -> Expr<'tcx>
             }
             hir::ExprKind::Ret(v) => ExprKind::Return { value: v.map(|v| self.mirror_expr(v)) },
             hir::ExprKind::Become(call) => ExprKind::Become { value: self.mirror_expr(call) },
+            hir::ExprKind::Break(dest, value) if self.has_attr(expr, sym::const_continue) => {
+                self.mirror_const_continue(expr, dest, value)
+            }
             hir::ExprKind::Break(dest, ref value) => match dest.target_id {
                 Ok(target_id) => ExprKind::Break {
                     label: region::Scope { id: target_id.local_id, data: region::ScopeData::Node },
@@ -724,6 +727,12 @@ fn make_mirror_unadjusted(&mut self, expr: &'tcx hir::Expr<'tcx>) -> Expr<'tcx>
                 arms: arms.iter().map(|a| self.convert_arm(a)).collect(),
                 match_source,
             },
+            hir::ExprKind::Loop(body, ..)
+                if self.has_attr(expr, sym::loop_match)
+                    && let Some(kind) = self.mirror_loop_match(expr, body) =>
+            {
+                kind
+            }
             hir::ExprKind::Loop(body, ..) => {
                 let block_ty = self.typeck_results().node_type(body.hir_id);
                 let temp_lifetime = self
@@ -883,6 +892,88 @@ fn convert_arm(&mut self, arm: &'tcx hir::Arm<'tcx>) -> ArmId {
         self.thir.arms.push(arm)
     }
 
+    fn has_attr(&self, expr: &hir::Expr<'_>, name: Symbol) -> bool {
+        self.tcx.hir().attrs(expr.hir_id).iter().any(|attr| attr.has_name(name))
+    }
+
+    /// Lowers a `#[loop_match]` loop, `loop { state = 'blk: { match state { arms } } }`, to an
+    /// [`ExprKind::LoopMatch`]. Reports an error and returns `None` if the loop doesn't have this
+    /// form, so that it's lowered as an ordinary loop.
+    fn mirror_loop_match(
+        &mut self,
+        expr: &'tcx hir::Expr<'tcx>,
+        body: &'tcx hir::Block<'tcx>,
+    ) -> Option<ExprKind<'tcx>> {
+        let Some((block, scrutinee, arms, match_span)) = loop_match_parts(body) else {
+            self.tcx.dcx().emit_err(errors::LoopMatchInvalidShape { span: expr.span });
+            return None;
+        };
+
+        self.loop_match_blocks.push(block.hir_id);
+        let arms = arms.iter().map(|arm| self.convert_arm(arm)).collect();
+        self.loop_match_blocks.pop();
+
+        let region_scope =
+            region::Scope { id: block.hir_id.local_id, data: region::ScopeData::Node };
+        Some(ExprKind::LoopMatch {
+            state: self.mirror_expr(scrutinee),
+            region_scope,
+            arms,
+            match_span,
+        })
+    }
+
+    /// Lowers `#[const_continue] break 'blk value`, which must break to the labeled block of an
+    /// enclosing `#[loop_match]` with a constant. Otherwise, an error is reported and it's lowered
+    /// as an ordinary `break`.
+    fn mirror_const_continue(
+        &mut self,
+        expr: &'tcx hir::Expr<'tcx>,
+        dest: hir::Destination,
+        value: Option<&'tcx hir::Expr<'tcx>>,
+    ) -> ExprKind<'tcx> {
+        let target_id = match dest.target_id {
+            Ok(target_id) => target_id,
+            Err(err) => bug!("invalid loop id for break: {}", err),
+        };
+        let label = region::Scope { id: target_id.local_id, data: region::ScopeData::Node };
+
+        if !self.loop_match_blocks.contains(&target_id) {
+            self.tcx.dcx().emit_err(errors::ConstContinueBadLabel { span: expr.span });
+        } else if let Some(value) = value
+            && self.is_const_continue_value(value)
+        {
+            return ExprKind::ConstContinue { label, value: self.mirror_expr(value) };
+        } else {
+            let span = value.map_or(expr.span, |value| value.span);
+            self.tcx.dcx().emit_err(errors::ConstContinueNotConst { span });
+        }
+        ExprKind::Break { label, value: value.map(|value| self.mirror_expr(value)) }
+    }
+
+    /// Whether `value` is a literal, a constant or a unit variant, whose value is known while
+    /// building MIR, so that a `#[const_continue]` of it can jump directly to its arm.
+    fn is_const_continue_value(&self, value: &'tcx hir::Expr<'tcx>) -> bool {
+        match value.kind {
+            hir::ExprKind::Lit(_) | hir::ExprKind::ConstBlock(_) => true,
+            hir::ExprKind::Unary(hir::UnOp::Neg, arg) => {
+                !self.typeck_results().is_method_call(value)
+                    && matches!(arg.kind, hir::ExprKind::Lit(_))
+            }
+            hir::ExprKind::Path(ref qpath) => matches!(
+                self.typeck_results().qpath_res(qpath, value.hir_id),
+                Res::Def(
+                    DefKind::Const
+                        | DefKind::AssocConst
+                        | DefKind::ConstParam
+                        | DefKind::Ctor(_, CtorKind::Const),
+                    _,
+                )
+            ),
+            _ => false,
+        }
+    }
+
     fn convert_path_expr(&mut self, expr: &'tcx hir::Expr<'tcx>, res: Res) -> ExprKind<'tcx> {
         let args = self.typeck_results().node_args(expr.hir_id);
         match res {
@@ -1192,3 +1283,33 @@ fn bin_op(op: hir::BinOpKind) -> BinOp {
         _ => bug!("no equivalent for ast binop {:?}", op),
     }
 }
+
+/// Splits the body of a `#[loop_match]` loop, `state = 'blk: { match state { arms } }`, into the
+/// labeled block, the scrutinee `state`, the arms, and the span of the `match`.
+fn loop_match_parts<'tcx>(
+    body: &'tcx hir::Block<'tcx>,
+) -> Option<(&'tcx hir::Block<'tcx>, &'tcx hir::Expr<'tcx>, &'tcx [hir::Arm<'tcx>], Span)> {
+    let assign = match (body.stmts, body.expr) {
+        ([], Some(expr)) => expr,
+        ([hir::Stmt { kind: hir::StmtKind::Semi(expr) | hir::StmtKind::Expr(expr), .. }], None) => {
+            expr
+        }
+        _ => return None,
+    };
+    let hir::ExprKind::Assign(state, rhs, _) = assign.kind else { return None };
+    let hir::ExprKind::Block(block, Some(_)) = rhs.kind else { return None };
+    let hir::Block { stmts: [], expr: Some(match_expr), .. } = block else { return None };
+    let hir::ExprKind::Match(scrutinee, arms, hir::MatchSource::Normal) = match_expr.kind else {
+        return None;
+    };
+    let local = |expr: &hir::Expr<'_>| match expr.kind {
+        hir::ExprKind::Path(hir::QPath::Resolved(None, hir::Path { res: Res::Local(id), .. })) => {
+            Some(*id)
+        }
+        _ => None,
+    };
+    if local(state)? != local(scrutinee)? {
+        return None;
+    }
+    Some((block, scrutinee, arms, match_expr.span))
+}
@@ -68,6 +68,10 @@ struct Cx<'tcx> {
 
     /// The `DefId` of the owner of this body.
     body_owner: DefId,
+
+    /// The labeled blocks of the `#[loop_match]` loops whose arms are being lowered, which a
+    /// `#[const_continue]` may break to.
+    loop_match_blocks: Vec<HirId>,
 }
 
 impl<'tcx> Cx<'tcx> {
@@ -107,6 +111,7 @@ fn new(tcx: TyCtxt<'tcx>, def: LocalDefId) -> Cx<'tcx> {
                 .attrs(hir_id)
                 .iter()
                 .all(|attr| attr.name_or_empty() != rustc_span::sym::custom_mir),
+            loop_match_blocks: Vec::new(),
         }
     }
 
@@ -38,6 +38,21 @@ pub(super) fn const_to_pat(
         let mut convert = ConstToPat::new(self, id, span, infcx);
         convert.to_pat(cv)
     }
+
+    /// Converts a constant marked `#[match_with_partial_eq]` to a pattern. Unlike with
+    /// [`Self::const_to_pat`], a constant whose type is not structural is not rejected: the
+    /// pattern compares the scrutinee with it using `PartialEq::eq` instead.
+    pub(super) fn const_to_pat_with_partial_eq(
+        &self,
+        cv: mir::Const<'tcx>,
+        id: hir::HirId,
+        span: Span,
+    ) -> Box<Pat<'tcx>> {
+        let infcx = self.tcx.infer_ctxt().build();
+        let mut convert = ConstToPat::new(self, id, span, infcx);
+        convert.partial_eq_fallback = true;
+        convert.to_pat(cv)
+    }
 }
 
 struct ConstToPat<'tcx> {
@@ -54,6 +69,14 @@ struct ConstToPat<'tcx> {
     infcx: InferCtxt<'tcx>,
 
     treat_byte_string_as_slice: bool,
+
+    // Whether the constant is a byte string literal matched against a `[u8; N]`, so that its
+    // reference is peeled off and the bytes are matched directly.
+    treat_byte_string_as_array: bool,
+
+    // Whether the constant is marked `#[match_with_partial_eq]`, so that it is compared with
+    // `PartialEq::eq` if its type is not structural.
+    partial_eq_fallback: bool,
 }
 
 /// This error type signals that we encountered a non-struct-eq situation.
@@ -80,6 +103,11 @@ fn new(
                 .typeck_results
                 .treat_byte_string_as_slice
                 .contains(&id.local_id),
+            treat_byte_string_as_array: pat_ctxt
+                .typeck_results
+                .treat_byte_string_as_array
+                .contains(&id.local_id),
+            partial_eq_fallback: false,
         }
     }
 
@@ -100,6 +128,12 @@ fn to_pat(&mut self, cv: mir::Const<'tcx>) -> Box<Pat<'tcx>> {
         // once indirect_structural_match is a full fledged error, this
         // level of indirection can be eliminated
 
+        if self.partial_eq_fallback
+            && traits::search_for_structural_match_violation(self.tcx(), cv.ty()).is_some()
+        {
+            return self.partial_eq_pat(cv);
+        }
+
         let have_valtree =
             matches!(cv, mir::Const::Ty(_, c) if matches!(c.kind(), ty::ConstKind::Value(_, _)));
         let inlined_const_as_pat = match cv {
@@ -187,6 +221,11 @@ fn to_pat(&mut self, cv: mir::Const<'tcx>) -> Box<Pat<'tcx>> {
                     // complained about structural match violations there, so we don't
                     // have to check anything any more.
                 }
+            } else if !have_valtree && self.is_address_compared_pointer(cv.ty()) {
+                // A pointer that is not derived from an integer, which `pointer_const_patterns`
+                // allows to compare by address with `BinOp::Eq`. The same function or static
+                // may have different addresses in different codegen units, so such a pattern
+                // may fail to match a pointer to it.
             } else if !have_valtree {
                 // The only way valtree construction can fail without the structural match
                 // checker finding a violation is if there is a pointer somewhere.
@@ -207,6 +246,26 @@ fn to_pat(&mut self, cv: mir::Const<'tcx>) -> Box<Pat<'tcx>> {
         inlined_const_as_pat
     }
 
+    /// Whether a constant of type `ty` that has no valtree is a raw pointer or function pointer
+    /// that can be matched by comparing its address, with `#![feature(pointer_const_patterns)]`.
+    /// Pointers nested in other types are still rejected.
+    fn is_address_compared_pointer(&self, ty: Ty<'tcx>) -> bool {
+        self.tcx().features().pointer_const_patterns && (ty.is_unsafe_ptr() || ty.is_fn_ptr())
+    }
+
+    /// Returns an opaque pattern that compares the scrutinee with `cv` using `PartialEq::eq`, for
+    /// a constant marked `#[match_with_partial_eq]` whose type is not structural. Exhaustiveness
+    /// checking considers it to match nothing, like other opaque constants.
+    fn partial_eq_pat(&self, cv: mir::Const<'tcx>) -> Box<Pat<'tcx>> {
+        let kind = if self.type_has_partial_eq_impl(cv.ty()) {
+            PatKind::Constant { value: cv }
+        } else {
+            let err = TypeNotPartialEq { span: self.span, non_peq_ty: cv.ty() };
+            PatKind::Error(self.tcx().dcx().emit_err(err))
+        };
+        Box::new(Pat { span: self.span, ty: cv.ty(), kind })
+    }
+
     #[instrument(level = "trace", skip(self), ret)]
     fn type_has_partial_eq_impl(&self, ty: Ty<'tcx>) -> bool {
         let tcx = self.tcx();
@@ -338,6 +397,13 @@ fn recur(
                 ty::Str => PatKind::Constant {
                     value: mir::Const::Ty(ty, ty::Const::new_value(tcx, cv, ty)),
                 },
+                // `&CStr` is unsized but not a slice, so it can't be matched through a deref
+                // pattern. Its bytes, including the NUL terminator, are compared all at once.
+                ty::Adt(def, _) if tcx.is_lang_item(def.did(), hir::LangItem::CStr) => {
+                    PatKind::Constant {
+                        value: mir::Const::Ty(ty, ty::Const::new_value(tcx, cv, ty)),
+                    }
+                }
                 // All other references are converted into deref patterns and then recursively
                 // convert the dereferenced constant to a pattern that is the sub-pattern of the
                 // deref pattern.
@@ -362,6 +428,9 @@ fn recur(
                         };
                         // References have the same valtree representation as their pointee.
                         let subpattern = self.recur(cv, pointee_ty)?;
+                        if self.treat_byte_string_as_array {
+                            return Ok(subpattern);
+                        }
                         PatKind::Deref { subpattern }
                     }
                 }
@@ -23,7 +23,7 @@
 use rustc_middle::ty::{self, CanonicalUserTypeAnnotation, Ty, TyCtxt, TypeVisitableExt};
 use rustc_middle::{bug, span_bug};
 use rustc_span::def_id::LocalDefId;
-use rustc_span::{ErrorGuaranteed, Span};
+use rustc_span::{sym, ErrorGuaranteed, Span};
 use rustc_target::abi::{FieldIdx, Integer};
 use tracing::{debug, instrument};
 
@@ -214,6 +214,17 @@ fn error_on_literal_overflow(
         Ok(())
     }
 
+    /// Whether one of the endpoints of a range pattern can't be evaluated before
+    /// monomorphization, like an inline constant that uses the generic parameters of the function.
+    fn has_generic_endpoint(
+        &self,
+        lo: PatRangeBoundary<'tcx>,
+        hi: PatRangeBoundary<'tcx>,
+        ty: Ty<'tcx>,
+    ) -> bool {
+        [lo, hi].into_iter().any(|bdy| bdy.try_eval_bits(ty, self.tcx, self.param_env).is_none())
+    }
+
     fn lower_pattern_range(
         &mut self,
         lo_expr: Option<&'tcx hir::Expr<'tcx>>,
@@ -249,6 +260,9 @@ fn lower_pattern_range(
             // `x..` where `x == ty::MAX` (yes, `x..` gives `RangeEnd::Included` since it is meant
             // to include `ty::MAX`).
             (RangeEnd::Included, Some(Ordering::Equal)) if !hi.is_finite() => {}
+            // An endpoint depends on generic parameters, so the range can't be checked before
+            // monomorphization. If it turns out to be empty, it just never matches.
+            (_, None) if self.has_generic_endpoint(lo, hi, ty) => {}
             // `x..y` where `x >= y`, or `x..=y` where `x > y`. The range is empty => error.
             _ => {
                 // Emit a more appropriate message if there was overflow.
@@ -306,6 +320,11 @@ fn lower_pattern_unadjusted(&mut self, pat: &'tcx hir::Pat<'tcx>) -> Box<Pat<'tc
                 return self.lower_path(qpath, pat.hir_id, pat.span);
             }
 
+            // A deref pattern on a `Box` derefs the box itself, like a `box` pattern, so that its
+            // bindings can move out of the box and the box's drop only frees it if they do.
+            hir::PatKind::Deref(subpattern) if ty.is_box() => {
+                PatKind::Deref { subpattern: self.lower_pattern(subpattern) }
+            }
             hir::PatKind::Deref(subpattern) => {
                 let mutable = self.typeck_results.pat_has_ref_mut_binding(subpattern);
                 let mutability = if mutable { hir::Mutability::Mut } else { hir::Mutability::Not };
@@ -592,7 +611,11 @@ fn lower_path(&mut self, qpath: &hir::QPath<'_>, id: hir::HirId, span: Span) ->
 
         match const_value {
             Ok(const_) => {
-                let pattern = self.const_to_pat(const_, id, span);
+                let pattern = if self.tcx.has_attr(def_id, sym::match_with_partial_eq) {
+                    self.const_to_pat_with_partial_eq(const_, id, span)
+                } else {
+                    self.const_to_pat(const_, id, span)
+                };
 
                 if !is_associated_const {
                     return pattern;
@@ -694,6 +717,13 @@ fn lower_inline_const(
             // If that fails, convert it to an opaque constant pattern.
             match tcx.const_eval_resolve(self.param_env, uneval, span) {
                 Ok(val) => self.const_to_pat(mir::Const::Val(val, ty), id, span).kind,
+                Err(ErrorHandled::TooGeneric(_)) if ty.is_primitive() => {
+                    // A primitive is compared with `==` when matching, so the constant can stay
+                    // unevaluated until monomorphization.
+                    let value = mir::Const::Unevaluated(uneval, ty);
+                    let subpattern = Box::new(Pat { span, ty, kind: PatKind::Constant { value } });
+                    PatKind::InlineConstant { subpattern, def: def_id }
+                }
                 Err(ErrorHandled::TooGeneric(_)) => {
                     // If we land here it means the const can't be evaluated because it's `TooGeneric`.
                     let e = self.tcx.dcx().emit_err(ConstPatternDependsOnGenericParameter { span });
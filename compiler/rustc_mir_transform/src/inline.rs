@@ -1080,7 +1080,7 @@ fn visit_terminator(&mut self, terminator: &mut Terminator<'tcx>, loc: Location)
             }
             TerminatorKind::UnwindTerminate(_) => {}
             TerminatorKind::Unreachable => {}
-            TerminatorKind::FalseEdge { ref mut real_target, ref mut imaginary_target } => {
+            TerminatorKind::FalseEdge { ref mut real_target, ref mut imaginary_target, .. } => {
                 *real_target = self.map_block(*real_target);
                 *imaginary_target = self.map_block(*imaginary_target);
             }
@@ -469,7 +469,7 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                     self.check_edge(location, *drop, EdgeKind::Normal);
                 }
             }
-            TerminatorKind::FalseEdge { real_target, imaginary_target } => {
+            TerminatorKind::FalseEdge { real_target, imaginary_target, .. } => {
                 if self.mir_phase >= MirPhase::Runtime(RuntimePhase::Initial) {
                     self.fail(
                         location,
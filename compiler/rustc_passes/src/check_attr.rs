@@ -1361,6 +1361,7 @@ fn check_must_not_suspend(&self, attr: &Attribute, span: Span, target: Target) -
     fn check_cold(&self, hir_id: HirId, attr: &Attribute, span: Span, target: Target) {
         match target {
             Target::Fn | Target::Method(..) | Target::ForeignFn | Target::Closure => {}
+            Target::Arm if self.tcx.features().cold_match_arms => {}
             // FIXME(#80564): We permit struct fields, match arms and macro defs to have an
             // `#[cold]` attribute with just a lint, because we previously
             // erroneously allowed it and some crates used it accidentally, to be compatible
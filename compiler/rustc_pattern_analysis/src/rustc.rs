@@ -626,7 +626,13 @@ pub fn lower_pat(&self, pat: &'p Pat<'tcx>) -> DeconstructedPat<'p, 'tcx> {
                     rustc_hir::RangeEnd::Included => RangeEnd::Included,
                     rustc_hir::RangeEnd::Excluded => RangeEnd::Excluded,
                 };
+                let is_generic = |bdy: PatRangeBoundary<'tcx>| {
+                    bdy.try_eval_bits(ty.inner(), cx.tcx, cx.param_env).is_none()
+                };
                 ctor = match ty.kind() {
+                    // An endpoint that depends on generic parameters, e.g. an inline constant, is
+                    // only known after monomorphization.
+                    _ if is_generic(*lo) || is_generic(*hi) => Opaque(OpaqueId::new()),
                     ty::Char | ty::Int(_) | ty::Uint(_) => {
                         let lo = cx.lower_pat_range_bdy(*lo, ty);
                         let hi = cx.lower_pat_range_bdy(*hi, ty);
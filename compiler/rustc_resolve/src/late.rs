@@ -3490,7 +3490,13 @@ fn compute_and_check_or_pat_binding_map(
                         binding_error.target.insert(pat_outer.span);
                     }
                     Some(binding_outer) => {
-                        if binding_outer.annotation != binding_inner.annotation {
+                        // Since Rust 2024, the alternatives may bind by value or by reference as
+                        // long as the types of their bindings agree, which typeck checks.
+                        let differ_by_ref_only = binding_inner.span.at_least_rust_2024()
+                            && binding_outer.annotation.1 == binding_inner.annotation.1;
+                        if binding_outer.annotation != binding_inner.annotation
+                            && !differ_by_ref_only
+                        {
                             // The binding modes in the outer and inner bindings differ.
                             inconsistent_vars
                                 .entry(name)
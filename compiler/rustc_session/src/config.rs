@@ -2964,10 +2964,10 @@ pub(crate) mod dep_tracking {
         BranchProtection, CFGuard, CFProtection, CollapseMacroDebuginfo, CoverageOptions,
         CrateType, DebugInfo, DebugInfoCompression, ErrorOutputType, FunctionReturn,
         InliningThreshold, InstrumentCoverage, InstrumentXRay, LinkerPluginLto, LocationDetail,
-        LtoCli, NextSolverConfig, OomStrategy, OptLevel, OutFileName, OutputType, OutputTypes,
-        PatchableFunctionEntry, Polonius, RemapPathScopeComponents, ResolveDocLinks,
-        SourceFileHashAlgorithm, SplitDwarfKind, SwitchWithOptPath, SymbolManglingVersion,
-        WasiExecModel,
+        LtoCli, MatchLowering, MatchOtherwise, NextSolverConfig, OomStrategy, OptLevel,
+        OutFileName, OutputType, OutputTypes, PatchableFunctionEntry, Polonius,
+        RemapPathScopeComponents, ResolveDocLinks, SourceFileHashAlgorithm, SplitDwarfKind,
+        SwitchWithOptPath, SymbolManglingVersion, WasiExecModel,
     };
     use crate::lint;
     use crate::utils::NativeLib;
@@ -3078,6 +3078,8 @@ fn hash(
         Polonius,
         InliningThreshold,
         FunctionReturn,
+        MatchLowering,
+        MatchOtherwise,
         WasmCAbi,
     );
 
@@ -3331,3 +3333,32 @@ pub enum FunctionReturn {
     /// Replace returns with jumps to thunk, without emitting the thunk.
     ThunkExtern,
 }
+
+/// The different settings that the `-Zmatch-lowering` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug, Default)]
+pub enum MatchLowering {
+    /// Build a backtracking automaton: candidates that a test says nothing about are tried again
+    /// after the test, which may cause a place to be tested more than once.
+    #[default]
+    Backtracking,
+
+    /// Build a decision tree, copying candidates into every branch of a test that says nothing
+    /// about them, within the budget given by `-Zmatch-lowering-budget`.
+    DecisionTree,
+}
+
+/// The different settings that the `-Zmatch-otherwise` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug, Default)]
+pub enum MatchOtherwise {
+    /// Terminate the block with `Unreachable`, which lets codegen omit the default case of the
+    /// switches leading to it.
+    #[default]
+    Unreachable,
+
+    /// Call the `match_otherwise_trap` lang item, `core::panicking::match_otherwise_trap`, which
+    /// calls `core::intrinsics::abort`, a trap instruction on most targets.
+    Trap,
+
+    /// Call `core::panicking::panic_nounwind` with a message, which aborts after printing it.
+    Abort,
+}
@@ -443,6 +443,8 @@ mod desc {
         "either a boolean (`yes`, `no`, `on`, `off`, etc), or a non-negative number";
     pub const parse_llvm_module_flag: &str = "<key>:<type>:<value>:<behavior>. Type must currently be `u32`. Behavior should be one of (`error`, `warning`, `require`, `override`, `append`, `appendunique`, `max`, `min`)";
     pub const parse_function_return: &str = "`keep` or `thunk-extern`";
+    pub const parse_match_lowering: &str = "`backtracking` or `decision-tree`";
+    pub const parse_match_otherwise: &str = "`unreachable`, `trap` or `abort`";
     pub const parse_wasm_c_abi: &str = "`legacy` or `spec`";
 }
 
@@ -1475,6 +1477,25 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         true
     }
 
+    pub(crate) fn parse_match_lowering(slot: &mut MatchLowering, v: Option<&str>) -> bool {
+        match v {
+            Some("backtracking") => *slot = MatchLowering::Backtracking,
+            Some("decision-tree") => *slot = MatchLowering::DecisionTree,
+            _ => return false,
+        }
+        true
+    }
+
+    pub(crate) fn parse_match_otherwise(slot: &mut MatchOtherwise, v: Option<&str>) -> bool {
+        match v {
+            Some("unreachable") => *slot = MatchOtherwise::Unreachable,
+            Some("trap") => *slot = MatchOtherwise::Trap,
+            Some("abort") => *slot = MatchOtherwise::Abort,
+            _ => return false,
+        }
+        true
+    }
+
     pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         match v {
             Some("spec") => *slot = WasmCAbi::Spec,
@@ -1818,6 +1839,43 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         (space separated)"),
     macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
         "show macro backtraces (default: no)"),
+    match_binary_search: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "lower switches on at least this many sparse integer or `char` constants to a binary \
+        search over them instead of a single `SwitchInt` (default: never)"),
+    match_elide_moved_binding_storage: bool = (false, parse_bool, [TRACKED],
+        "emit no `StorageLive` and `StorageDead` for the immutable by-value bindings of `match` \
+        arms that the first statement of the arm moves out of (default: no)"),
+    match_lowering: MatchLowering = (MatchLowering::default(), parse_match_lowering, [TRACKED],
+        "strategy used to lower `match` expressions and other patterns: `backtracking` or \
+        `decision-tree` (default: `backtracking`)"),
+    match_lowering_budget: usize = (64, parse_number, [TRACKED],
+        "maximum number of candidate copies `-Zmatch-lowering=decision-tree` may make for a \
+        single `match` before falling back to backtracking (default: 64; none are made with \
+        `-Copt-level=z`)"),
+    match_niche_compare: bool = (false, parse_bool, [TRACKED],
+        "test the variant of two-variant enums whose tag is a niche in their only scalar, like \
+        `Option<&T>`, by comparing that scalar with the niche instead of switching on the \
+        discriminant (default: no)"),
+    match_otherwise: MatchOtherwise = (MatchOtherwise::default(), parse_match_otherwise, [TRACKED],
+        "what the otherwise blocks of exhaustive `match`es, which only values that are invalid \
+        for their type reach, do: `unreachable`, `trap` or `abort` (default: `unreachable`)"),
+    match_pack_bools: bool = (false, parse_bool, [TRACKED],
+        "lower `match`es on tuples of `bool`s, whose arms test them with `true`, `false` and `_`, \
+        by packing the `bool`s into an integer and dispatching on it with a single `SwitchInt`, \
+        or with bit masks for more than 8 `bool`s (default: no)"),
+    match_perfect_hash: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "lower patterns with at least this many distinct string constants to a perfect hash of \
+        the string followed by a single comparison, when such a hash is found (default: never)"),
+    match_range_switch: bool = (false, parse_bool, [TRACKED],
+        "test integer and `char` range patterns on the same place with a single multi-way test, \
+        lowered to a binary search over the ranges instead of one comparison per range \
+        (default: no)"),
+    match_repr_switch: bool = (false, parse_bool, [TRACKED],
+        "test the variant of enums without fields and with an explicit integer `repr` by \
+        switching on their value as an integer instead of on their discriminant (default: no)"),
+    match_size_limit: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "the number of basic blocks of the decision tree of a `match` above which the \
+        `large_match_trees` lint is emitted"),
     maximal_hir_to_mir_coverage: bool = (false, parse_bool, [TRACKED],
         "save as much information as possible about the correspondence between MIR and HIR \
         as source scopes (default: no)"),
@@ -1826,6 +1884,9 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         the same values as the target option of the same name"),
     meta_stats: bool = (false, parse_bool, [UNTRACKED],
         "gather metadata statistics (default: no)"),
+    mir_emit_fake_borrow_markers: bool = (false, parse_bool, [TRACKED],
+        "emit marker statements around match guards that let miri check that the guard does not \
+        mutate fake-borrowed places (default: no)"),
     mir_emit_retag: bool = (false, parse_bool, [TRACKED],
         "emit Retagging MIR statements, interpreted e.g., by miri; implies -Zmir-opt-level=0 \
         (default: no)"),
@@ -2022,6 +2083,10 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         "prefer dynamic linking to static linking for staticlibs (default: no)"),
     strict_init_checks: bool = (false, parse_bool, [TRACKED],
         "control if mem::uninitialized and mem::zeroed panic on more UB"),
+    switchint_threshold: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "lower the switches of `match` tests on fewer than this many values to a chain of \
+        comparisons instead of a `SwitchInt`, for targets where indirect jumps are slow \
+        (default: 6 with `-Copt-level=z`, never otherwise)"),
     #[rustc_lint_opt_deny_field_access("use `Session::teach` instead of this field")]
     teach: bool = (false, parse_bool, [TRACKED],
         "show extended diagnostic help (default: no)"),
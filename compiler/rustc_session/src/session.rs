@@ -128,6 +128,9 @@ pub struct Limits {
     pub move_size_limit: Limit,
     /// The maximum length of types during monomorphization.
     pub type_length_limit: Limit,
+    /// The number of basic blocks of the decision tree of a `match` at which the
+    /// `large_match_trees` lint starts being emitted.
+    pub match_size_limit: Limit,
 }
 
 pub struct CompilerIO {
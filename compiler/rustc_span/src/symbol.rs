@@ -502,10 +502,12 @@
         bpf_target_feature,
         braced_empty_structs,
         branch,
+        branchless_lazy_bool,
         breakpoint,
         bridge,
         bswap,
         builtin_syntax,
+        byte_string_array_patterns,
         c,
         c_str,
         c_str_literals,
@@ -576,6 +578,7 @@
         cmse_nonsecure_entry,
         coerce_unsized,
         cold,
+        cold_match_arms,
         collapse_debuginfo,
         column,
         compare_bytes,
@@ -595,6 +598,7 @@
         const_closures,
         const_compare_raw_pointers,
         const_constructor,
+        const_continue,
         const_deallocate,
         const_eval_limit,
         const_eval_select,
@@ -632,6 +636,7 @@
         const_trait_impl,
         const_try,
         constant,
+        constant_time_match,
         constructor,
         convert_identity,
         copy,
@@ -699,6 +704,7 @@
         decl_macro,
         declare_lint_pass,
         decode,
+        deep,
         default_alloc_error_handler,
         default_fn,
         default_lib_allocator,
@@ -1080,6 +1086,7 @@
         lang,
         lang_items,
         large_assignments,
+        large_match_trees,
         lateout,
         lazy_normalization_consts,
         lazy_type_alias,
@@ -1126,6 +1133,7 @@
         logf64,
         loongarch_target_feature,
         loop_break_value,
+        loop_match,
         lt,
         macro_at_most_once_rep,
         macro_attributes_in_derive_output,
@@ -1148,7 +1156,11 @@
         marker_trait_attr,
         masked,
         match_beginning_vert,
+        match_bytes_eq,
         match_default_bindings,
+        match_otherwise_trap,
+        match_size_limit,
+        match_with_partial_eq,
         matches_macro,
         maxnumf128,
         maxnumf16,
@@ -1409,6 +1421,7 @@
         pointee,
         pointee_trait,
         pointer,
+        pointer_const_patterns,
         pointer_like,
         poll,
         poll_next,
@@ -1615,6 +1628,7 @@
         rustc_do_not_const_check,
         rustc_doc_primitive,
         rustc_dummy,
+        rustc_dump_decision_tree,
         rustc_dump_def_parents,
         rustc_dump_item_bounds,
         rustc_dump_predicates,
@@ -1641,6 +1655,7 @@
         rustc_lint_query_instability,
         rustc_macro_transparency,
         rustc_main,
+        rustc_match_guard_fake_borrows,
         rustc_mir,
         rustc_must_implement_one_of,
         rustc_never_returns_null_ptr,
@@ -1701,6 +1716,7 @@
         self_struct_ctor,
         semitransparent,
         shadow_call_stack,
+        shallow,
         shl,
         shl_assign,
         shorter_tail_lifetimes,
@@ -987,6 +987,7 @@ pub unsafe fn drop_in_place<T: ?Sized>(to_drop: *mut T) {
 #[rustc_intrinsic]
 #[rustc_nounwind]
 #[miri::intrinsic_fallback_is_spec]
+#[cfg_attr(not(bootstrap), lang = "likely")]
 pub const fn likely(b: bool) -> bool {
     b
 }
@@ -1007,6 +1008,7 @@ pub const fn likely(b: bool) -> bool {
 #[rustc_intrinsic]
 #[rustc_nounwind]
 #[miri::intrinsic_fallback_is_spec]
+#[cfg_attr(not(bootstrap), lang = "unlikely")]
 pub const fn unlikely(b: bool) -> bool {
     b
 }
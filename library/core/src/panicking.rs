@@ -221,6 +221,15 @@ pub const fn panic_nounwind(expr: &'static str) -> ! {
     panic_nounwind_fmt(fmt::Arguments::new_const(&[expr]), /* force_no_backtrace */ false);
 }
 
+/// Traps without unwinding. Called, with `-Zmatch-otherwise=trap`, by the otherwise blocks of
+/// exhaustive `match`es, which only values that are invalid for their type reach.
+#[inline]
+#[cfg_attr(not(bootstrap), lang = "match_otherwise_trap")] // needed by MIR building
+#[rustc_nounwind]
+pub fn match_otherwise_trap() -> ! {
+    crate::intrinsics::abort()
+}
+
 /// Like `panic_nounwind`, but also inhibits showing a backtrace.
 #[cfg_attr(not(feature = "panic_immediate_abort"), inline(never), cold)]
 #[cfg_attr(feature = "panic_immediate_abort", inline)]
@@ -94,6 +94,20 @@ fn equal(&self, other: &[B]) -> bool {
     }
 }
 
+/// Compares the bytes of two byte slices, like `memcmp`. Called by the equality tests of `match`es
+/// with string constants longer than a few bytes, after comparing the lengths, so that these
+/// tests don't call `<str as PartialEq>::eq`.
+#[cfg(not(bootstrap))]
+#[inline]
+#[lang = "match_bytes_eq"] // needed by MIR building
+#[rustc_nounwind]
+fn match_bytes_eq(left: &[u8], right: &[u8]) -> bool {
+    // SAFETY: `left` and `right` are references and are thus guaranteed to be valid.
+    // The two slices have been checked to have the same length first.
+    left.len() == right.len()
+        && unsafe { compare_bytes(left.as_ptr(), right.as_ptr(), left.len()) == 0 }
+}
+
 #[doc(hidden)]
 // intermediate trait for specialization of slice's PartialOrd
 trait SlicePartialOrd: Sized {
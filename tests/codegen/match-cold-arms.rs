@@ -0,0 +1,18 @@
+// Check that the tests leading to match arms marked `#[cold]` are hinted as unlikely.
+//@ compile-flags: -O
+
+#![crate_type = "lib"]
+#![feature(cold_match_arms)]
+
+// CHECK-LABEL: @checked_div
+#[no_mangle]
+pub fn checked_div(x: u32, y: u32) -> Option<u32> {
+    // CHECK: br i1 %{{.+}}, label %{{.+}}, label %{{.+}}, !prof ![[PROF:[0-9]+]]
+    match y {
+        #[cold]
+        0 => None,
+        y => Some(x / y),
+    }
+}
+
+// CHECK: ![[PROF]] = !{!"branch_weights", {{.*}}i32 {{[0-9]+}}, i32 {{[0-9]+}}}
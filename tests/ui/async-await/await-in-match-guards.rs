@@ -0,0 +1,113 @@
+// Check that match guards can await futures that suspend, for arms with a single pattern and with
+// or-patterns whose guard is lowered per alternative or once for all of them, and that the arms
+// after a guard that fails after resuming are still tried.
+
+//@ run-pass
+//@ edition: 2021
+
+#![feature(if_let_guard, noop_waker)]
+
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::task::{Context, Poll, Waker};
+
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+async fn validate(n: u8) -> bool {
+    YieldOnce(false).await;
+    n > 1
+}
+
+async fn checked(n: u8) -> Option<u8> {
+    YieldOnce(false).await;
+    n.checked_sub(2)
+}
+
+enum E {
+    A(u8),
+    B(u8),
+    C(u8),
+    D,
+}
+
+async fn simple(x: Option<u8>) -> u8 {
+    match x {
+        Some(n) if validate(n).await => n,
+        Some(_) => 1,
+        None => 0,
+    }
+}
+
+async fn two_alternatives(x: Result<u8, u8>) -> u8 {
+    match x {
+        Ok(n) | Err(n) if validate(n).await => n,
+        Ok(_) => 10,
+        Err(_) => 20,
+    }
+}
+
+async fn three_alternatives(e: E) -> u8 {
+    match e {
+        E::A(n) | E::B(n) | E::C(n) if validate(n).await => n,
+        E::B(_) => 30,
+        _ => 0,
+    }
+}
+
+async fn if_let(x: (u8, u8)) -> u8 {
+    match x {
+        (n, 0) | (0, n) if let Some(m) = checked(n).await => m,
+        (n, _) => n + 100,
+    }
+}
+
+fn is_send<T: Send>(t: T) -> T {
+    t
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+fn main() {
+    assert_eq!(block_on(is_send(simple(Some(3)))), 3);
+    assert_eq!(block_on(simple(Some(1))), 1);
+    assert_eq!(block_on(simple(None)), 0);
+
+    assert_eq!(block_on(is_send(two_alternatives(Ok(5)))), 5);
+    assert_eq!(block_on(two_alternatives(Ok(1))), 10);
+    assert_eq!(block_on(two_alternatives(Err(0))), 20);
+    assert_eq!(block_on(two_alternatives(Err(7))), 7);
+
+    assert_eq!(block_on(is_send(three_alternatives(E::B(4)))), 4);
+    assert_eq!(block_on(three_alternatives(E::B(1))), 30);
+    assert_eq!(block_on(three_alternatives(E::C(0))), 0);
+    assert_eq!(block_on(three_alternatives(E::D)), 0);
+    assert_eq!(block_on(three_alternatives(E::A(9))), 9);
+
+    assert_eq!(block_on(is_send(if_let((5, 0)))), 3);
+    assert_eq!(block_on(if_let((0, 6))), 4);
+    assert_eq!(block_on(if_let((1, 0))), 101);
+    assert_eq!(block_on(if_let((0, 0))), 100);
+    assert_eq!(block_on(if_let((3, 3))), 103);
+}
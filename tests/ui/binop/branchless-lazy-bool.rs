@@ -0,0 +1,64 @@
+// Check that the `&&`s and `||`s of a `#[branchless_lazy_bool]` function, which evaluate both
+// operands when these are cheap and have no side effects, give the same results, and still only
+// evaluate the right operand when needed if it has side effects.
+//@ run-pass
+
+#![feature(branchless_lazy_bool)]
+
+use std::cell::Cell;
+
+struct Range {
+    lo: u32,
+    hi: u32,
+}
+
+#[branchless_lazy_bool]
+fn in_range(x: u32, range: &Range) -> bool {
+    range.lo <= x && x < range.hi
+}
+
+#[branchless_lazy_bool]
+fn outside(x: u32, range: &Range) -> bool {
+    x < range.lo || !(x < range.hi)
+}
+
+#[branchless_lazy_bool]
+fn all_three(a: bool, b: &bool, c: u8) -> bool {
+    if a && *b && c & 1 == 1 { true } else { false }
+}
+
+#[branchless_lazy_bool]
+fn count_calls(x: Option<u32>, calls: &Cell<u32>) -> bool {
+    x.is_some()
+        && {
+            calls.set(calls.get() + 1);
+            true
+        }
+}
+
+#[branchless_lazy_bool]
+fn checked(divisor: u32, x: u32) -> bool {
+    divisor != 0 && x % divisor == 0
+}
+
+fn main() {
+    let range = Range { lo: 3, hi: 7 };
+    for x in 0..10 {
+        assert_eq!(in_range(x, &range), (3..7).contains(&x));
+        assert_eq!(outside(x, &range), !(3..7).contains(&x));
+    }
+
+    assert!(all_three(true, &true, 3));
+    assert!(!all_three(true, &true, 2));
+    assert!(!all_three(true, &false, 3));
+    assert!(!all_three(false, &true, 3));
+
+    let calls = Cell::new(0);
+    assert!(!count_calls(None, &calls));
+    assert!(count_calls(Some(1), &calls));
+    assert_eq!(calls.get(), 1);
+
+    assert!(!checked(0, 4));
+    assert!(checked(2, 4));
+    assert!(!checked(3, 4));
+}
@@ -0,0 +1,14 @@
+// Check that a borrow that a later arm of a `match` only uses through the false edge out of a
+// failed guard gets a note pointing at the arm whose guard failed.
+
+fn guard_failed(x: Option<u8>) {
+    let mut n = 0;
+    let r = &mut n;
+    match x {
+        Some(_) if { n += 1; false } => {} //~ ERROR cannot assign to `n` because it is borrowed
+        None => drop(r),
+        _ => {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,46 @@
+//@ run-pass
+// Check that constants marked `#[match_with_partial_eq]` can be used in patterns even though their
+// type does not derive `PartialEq`, and that they are compared with its `PartialEq` impl.
+
+#![feature(match_with_partial_eq)]
+
+struct Name(&'static str);
+
+impl PartialEq for Name {
+    fn eq(&self, other: &Name) -> bool {
+        self.0.eq_ignore_ascii_case(other.0)
+    }
+}
+
+#[match_with_partial_eq]
+const ALICE: Name = Name("alice");
+#[match_with_partial_eq]
+const BOB: Name = Name("bob");
+
+fn greet(name: &Name) -> u32 {
+    match *name {
+        ALICE => 1,
+        BOB => 2,
+        _ => 0,
+    }
+}
+
+fn nested(x: (Option<Name>, u8)) -> u32 {
+    match x {
+        (Some(ALICE), 0) => 1,
+        (Some(BOB), _) => 2,
+        (None, n) => n.into(),
+        _ => 100,
+    }
+}
+
+fn main() {
+    assert_eq!(greet(&Name("Alice")), 1);
+    assert_eq!(greet(&Name("BOB")), 2);
+    assert_eq!(greet(&Name("carol")), 0);
+
+    assert_eq!(nested((Some(Name("ALICE")), 0)), 1);
+    assert_eq!(nested((Some(Name("alice")), 1)), 100);
+    assert_eq!(nested((Some(Name("Bob")), 7)), 2);
+    assert_eq!(nested((None, 7)), 7);
+}
@@ -0,0 +1,51 @@
+//@ run-pass
+// Check that raw pointers and function pointers can be matched against constants that are not
+// derived from integers with `pointer_const_patterns`, by comparing their addresses.
+
+#![feature(pointer_const_patterns)]
+
+const A: *const u8 = &1;
+const B: *const u8 = &2;
+const NULL: *const u8 = std::ptr::null();
+
+fn ptr(x: *const u8) -> u32 {
+    match x {
+        A => 1,
+        B => 2,
+        NULL => 3,
+        _ => 4,
+    }
+}
+
+type Func = fn(u32) -> u32;
+
+fn double(x: u32) -> u32 {
+    x * 2
+}
+
+fn square(x: u32) -> u32 {
+    x * x
+}
+
+const DOUBLE: Func = double;
+const SQUARE: Func = square;
+
+fn name(f: Func) -> &'static str {
+    match f {
+        DOUBLE => "double",
+        SQUARE => "square",
+        _ => "other",
+    }
+}
+
+fn main() {
+    assert_eq!(ptr(A), 1);
+    assert_eq!(ptr(B), 2);
+    assert_eq!(ptr(std::ptr::null()), 3);
+    let x = 1u8;
+    assert_eq!(ptr(&x), 4);
+
+    assert_eq!(name(double), "double");
+    assert_eq!(name(square), "square");
+    assert_eq!(name(|x| x + 1), "other");
+}
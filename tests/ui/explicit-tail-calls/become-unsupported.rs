@@ -0,0 +1,37 @@
+// Check that `become` is rejected for expressions that can't be lowered to a tail call which
+// reuses the frame of the caller.
+
+#![allow(incomplete_features)]
+#![feature(explicit_tail_calls)]
+
+fn _add(a: u32, b: u32) -> u32 {
+    become a + b;
+    //~^ error: `become` requires a function call
+}
+
+fn _closure(x: u32) -> u32 {
+    let f = |y: u32| y + 1;
+    become f(x);
+    //~^ error: tail calls of closures and other `Fn*` trait implementations are not supported
+}
+
+#[track_caller]
+fn _located(x: u32) -> u32 {
+    x
+}
+
+fn _track(x: u32) -> u32 {
+    become _located(x);
+    //~^ error: a function marked with `#[track_caller]` cannot be tail-called
+}
+
+fn _two(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+fn _mismatched(a: u32) -> u32 {
+    become _two(a, a);
+    //~^ error: mismatched signatures
+}
+
+fn main() {}
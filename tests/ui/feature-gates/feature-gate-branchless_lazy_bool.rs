@@ -0,0 +1,8 @@
+// Check that `#[branchless_lazy_bool]` needs `#![feature(branchless_lazy_bool)]`.
+
+#[branchless_lazy_bool] //~ ERROR the `#[branchless_lazy_bool]` attribute is an experimental feature
+fn in_range(x: u8, lo: u8, hi: u8) -> bool {
+    lo <= x && x < hi
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+fn is_png(header: [u8; 4]) -> bool {
+    match header {
+        b"\x89PNG" => true, //~ ERROR mismatched types
+        _ => false,
+    }
+}
+
+fn main() {}
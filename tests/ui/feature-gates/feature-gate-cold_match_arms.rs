@@ -0,0 +1,14 @@
+// Check that `#[cold]` on match arms is still ignored, with a future-compatibility warning,
+// without `#![feature(cold_match_arms)]`.
+#![deny(unused_attributes)]
+
+fn classify(x: Option<u32>) -> u32 {
+    match x {
+        Some(x) => x,
+        #[cold] //~ ERROR `#[cold]` is ignored on struct fields, match arms and macro defs
+        //~| WARN previously accepted
+        None => 0,
+    }
+}
+
+fn main() {}
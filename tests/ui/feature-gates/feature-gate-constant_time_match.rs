@@ -0,0 +1,12 @@
+// Check that `#[constant_time_match]` needs `#![feature(constant_time_match)]`.
+
+#[constant_time_match] //~ ERROR the `#[constant_time_match]` attribute is an experimental feature
+fn sign(x: i8) -> i8 {
+    match x {
+        0 => 0,
+        1.. => 1,
+        _ => -1,
+    }
+}
+
+fn main() {}
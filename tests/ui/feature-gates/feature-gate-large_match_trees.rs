@@ -0,0 +1,5 @@
+// check that `match_size_limit` is feature-gated
+
+#![match_size_limit = "42"] //~ ERROR the `#[match_size_limit]` attribute is an experimental feature
+
+fn main() {}
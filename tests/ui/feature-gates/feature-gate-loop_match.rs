@@ -0,0 +1,22 @@
+// Check that `#[loop_match]` and `#[const_continue]` need `#![feature(loop_match)]`.
+
+enum State {
+    A,
+    B,
+}
+
+fn main() {
+    let mut state = State::A;
+    #[loop_match] //~ ERROR the `#[loop_match]` attribute is an experimental feature
+    loop {
+        state = 'blk: {
+            match state {
+                State::A => {
+                    #[const_continue] //~ ERROR is an experimental feature
+                    break 'blk State::B;
+                }
+                State::B => break,
+            }
+        }
+    }
+}
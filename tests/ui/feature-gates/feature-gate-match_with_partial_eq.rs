@@ -0,0 +1,20 @@
+// check that `match_with_partial_eq` is feature-gated
+
+struct S;
+
+impl PartialEq for S {
+    fn eq(&self, _: &S) -> bool {
+        true
+    }
+}
+
+#[match_with_partial_eq]
+//~^ ERROR the `#[match_with_partial_eq]` attribute is an experimental feature
+const C: S = S;
+
+fn main() {
+    match S {
+        C => {}
+        _ => {}
+    }
+}
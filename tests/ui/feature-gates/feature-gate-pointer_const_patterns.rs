@@ -0,0 +1,19 @@
+// Check that raw pointers and function pointers can only be matched against constants that are
+// not derived from integers with `pointer_const_patterns`.
+
+const C: *const u8 = &0;
+
+fn f() {}
+
+const F: fn() = f;
+
+fn main() {
+    match &0u8 as *const u8 {
+        C => {} //~ ERROR behave unpredictably
+        _ => {}
+    }
+    match f as fn() {
+        F => {} //~ ERROR behave unpredictably
+        _ => {}
+    }
+}
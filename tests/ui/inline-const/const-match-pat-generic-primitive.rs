@@ -0,0 +1,82 @@
+//@ run-pass
+
+#![feature(inline_const_pat)]
+
+// Check that inline-const patterns of primitive types may depend on generic parameters, as values
+// and as the ends of ranges, and are compared with the scrutinee after monomorphization.
+
+trait Limit {
+    const MAX: u8;
+}
+
+struct Small;
+struct Big;
+
+impl Limit for Small {
+    const MAX: u8 = 10;
+}
+
+impl Limit for Big {
+    const MAX: u8 = 200;
+}
+
+fn value<const N: usize>(x: usize, guard: bool) -> u32 {
+    match x {
+        const { N } if guard => 0,
+        const { N + 1 } => 1,
+        0 => 2,
+        _ => 3,
+    }
+}
+
+fn after_switch<const N: u32>(x: u32) -> u32 {
+    match x {
+        0 => 0,
+        1 => 1,
+        const { N * 2 } => 2,
+        _ => 3,
+    }
+}
+
+fn flag<const B: bool>(x: bool) -> u32 {
+    match x {
+        const { B } => 1,
+        _ => 0,
+    }
+}
+
+fn range<L: Limit>(x: u8) -> u32 {
+    match x {
+        0 => 0,
+        1..const { L::MAX } => 1,
+        const { L::MAX }..=const { L::MAX } => 2,
+        const { L::MAX / 2 }..const { L::MAX / 2 } => unreachable!(),
+        _ => 3,
+    }
+}
+
+fn main() {
+    assert_eq!(value::<0>(0, true), 0);
+    assert_eq!(value::<0>(0, false), 2);
+    assert_eq!(value::<0>(1, false), 1);
+    assert_eq!(value::<4>(4, true), 0);
+    assert_eq!(value::<4>(5, true), 1);
+    assert_eq!(value::<4>(0, true), 2);
+    assert_eq!(value::<4>(6, true), 3);
+
+    assert_eq!(after_switch::<0>(0), 0);
+    assert_eq!(after_switch::<3>(6), 2);
+    assert_eq!(after_switch::<3>(3), 3);
+
+    assert_eq!(flag::<true>(true), 1);
+    assert_eq!(flag::<true>(false), 0);
+    assert_eq!(flag::<false>(false), 1);
+
+    assert_eq!(range::<Small>(0), 0);
+    assert_eq!(range::<Small>(9), 1);
+    assert_eq!(range::<Small>(10), 2);
+    assert_eq!(range::<Small>(11), 3);
+    assert_eq!(range::<Big>(150), 1);
+    assert_eq!(range::<Big>(200), 2);
+    assert_eq!(range::<Big>(255), 3);
+}
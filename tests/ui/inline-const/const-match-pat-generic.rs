@@ -3,19 +3,19 @@
 // rust-lang/rust#82518: ICE with inline-const in match referencing const-generic parameter
 
 fn foo<const V: usize>() {
-    match 0 {
-        const { V } => {},
+    match (0, 0) {
+        const { (V, V) } => {},
         //~^ ERROR constant pattern depends on a generic parameter
         _ => {},
     }
 }
 
-const fn f(x: usize) -> usize {
-    x + 1
+const fn f(x: usize) -> [usize; 1] {
+    [x + 1]
 }
 
 fn bar<const V: usize>() {
-    match 0 {
+    match [0] {
         const { f(V) } => {},
         //~^ ERROR constant pattern depends on a generic parameter
         _ => {},
@@ -0,0 +1,23 @@
+// Check that `-Zmatch-otherwise=trap` reports a missing `match_otherwise_trap` lang item rather
+// than leaving the otherwise blocks of exhaustive `match`es unreachable.
+//@ compile-flags: -Zmatch-otherwise=trap
+
+#![feature(lang_items, no_core)]
+#![no_core]
+#![crate_type = "lib"]
+
+#[lang = "sized"]
+pub trait Sized {}
+
+pub enum E {
+    A,
+    B,
+}
+
+pub fn f(e: E) -> u8 {
+    match e {
+        //~^ ERROR requires `match_otherwise_trap` lang_item
+        E::A => 5,
+        E::B => 7,
+    }
+}
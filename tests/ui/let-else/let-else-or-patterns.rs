@@ -0,0 +1,51 @@
+// Check that let-else statements and `if let` expressions with or-patterns bind the variables of
+// the alternative that matched, and take the else branch when none of them does.
+//@ run-pass
+
+enum E {
+    A(String),
+    B(String),
+    C(u8),
+}
+
+fn by_value(e: E) -> String {
+    let (E::A(s) | E::B(s)) = e else { return "else".to_string() };
+    s
+}
+
+fn by_ref(e: &mut (E, Option<u8>)) -> u8 {
+    let (E::A(_) | E::B(_), Some(n)) | (E::C(n), _) = e else { return 0 };
+    *n += 1;
+    *n
+}
+
+fn nested(x: (Option<u8>, u8, Result<u8, u8>)) -> u32 {
+    let (Some(a), b, Ok(1) | Err(2)) | (None, a, Ok(b) | Err(b)) = x else { return 0 };
+    a as u32 * 100 + b as u32
+}
+
+fn if_let(x: Result<(u8, u8), (u8, u8)>) -> u8 {
+    if let Ok((a, 0) | (0, a)) | Err((a, _)) = x { a } else { 0 }
+}
+
+fn main() {
+    assert_eq!(by_value(E::A("a".to_string())), "a");
+    assert_eq!(by_value(E::B("b".to_string())), "b");
+    assert_eq!(by_value(E::C(3)), "else");
+
+    let mut x = (E::B("b".to_string()), Some(1));
+    assert_eq!(by_ref(&mut x), 2);
+    assert_eq!(x.1, Some(2));
+    assert_eq!(by_ref(&mut (E::C(5), None)), 6);
+    assert_eq!(by_ref(&mut (E::A("a".to_string()), None)), 0);
+
+    assert_eq!(nested((Some(3), 4, Ok(1))), 304);
+    assert_eq!(nested((Some(3), 4, Err(2))), 304);
+    assert_eq!(nested((Some(3), 4, Ok(2))), 0);
+    assert_eq!(nested((None, 5, Err(6))), 506);
+
+    assert_eq!(if_let(Ok((4, 0))), 4);
+    assert_eq!(if_let(Ok((0, 5))), 5);
+    assert_eq!(if_let(Ok((6, 7))), 0);
+    assert_eq!(if_let(Err((8, 9))), 8);
+}
@@ -0,0 +1,41 @@
+// Check that the guards of or-pattern arms that are evaluated separately for each alternative are
+// linted when they have side effects.
+
+#![deny(duplicated_guard_side_effects)]
+
+fn check(x: u8) -> bool {
+    x > 1
+}
+
+fn guards(pair: (u8, u8), triple: (u8, u8, u8), seen: &mut u8) {
+    match pair {
+        (x, _) | (_, x) if check(x) => {}
+        //~^ ERROR guard with side effects is evaluated separately for each alternative
+        _ => {}
+    }
+    match pair {
+        (x, _) | (_, x) if { *seen = x; x > 1 } => {}
+        //~^ ERROR guard with side effects is evaluated separately for each alternative
+        _ => {}
+    }
+
+    // A guard without side effects.
+    match pair {
+        (x, _) | (_, x) if x > 1 => {}
+        _ => {}
+    }
+    // A single alternative.
+    match pair {
+        (x, _) if check(x) => {}
+        _ => {}
+    }
+    // Alternatives that share a single evaluation of the guard.
+    match triple {
+        (x, _, _) | (_, x, _) | (_, _, x) if check(x) => {}
+        _ => {}
+    }
+}
+
+fn main() {
+    guards((1, 2), (1, 2, 3), &mut 0);
+}
@@ -0,0 +1,42 @@
+// Check that the decision trees of `match` expressions with more basic blocks than the limit set
+// with `#![match_size_limit]` or `-Zmatch-size-limit` are linted, pointing at the slice patterns
+// and or-patterns that multiply their tests.
+
+//@ revisions: attribute option
+//@ [option]compile-flags: -Zmatch-size-limit=10
+// The size of the decision trees depends on the details of match lowering.
+//@ dont-check-compiler-stderr
+
+#![deny(large_match_trees)]
+#![cfg_attr(attribute, feature(large_match_trees))]
+#![cfg_attr(attribute, match_size_limit = "10")]
+
+fn small(x: bool) -> u8 {
+    match x {
+        true => 1,
+        false => 0,
+    }
+}
+
+fn slices(x: &[u8]) -> u8 {
+    match x {
+        //~^ ERROR the decision tree of this pattern is larger than the limit
+        [] => 0,
+        [1, ..] => 1,
+        [_, 2] => 2,
+        [_, _, 3] => 3,
+        [.., 4] => 4,
+        _ => 5,
+    }
+}
+
+fn or_patterns(x: (u8, u8, u8)) -> u8 {
+    match x {
+        //~^ ERROR the decision tree of this pattern is larger than the limit
+        (a @ (1 | 2 | 3), b @ (1 | 2 | 3), 1 | 2 | 3) => a + b,
+        (a, b @ (4 | 5), _) | (b @ (4 | 5), _, a) => a - b,
+        _ => 0,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,41 @@
+// Check the errors for `#[loop_match]` loops that don't have the expected form, and for
+// `#[const_continue]`s that can't jump to an arm.
+
+#![allow(incomplete_features)]
+#![feature(loop_match)]
+
+fn other_scrutinee(mut state: u8, other: u8) -> u8 {
+    #[loop_match]
+    loop { //~ ERROR invalid `#[loop_match]` loop
+        state = 'blk: { match other { 0 => break 'blk 1, _ => break state } }
+    }
+}
+
+fn not_a_loop_match_label(mut state: u8) -> u8 {
+    'outer: loop {
+        state = match state {
+            0 => {
+                #[const_continue]
+                break 'outer 1; //~ ERROR must break to the labeled block of a `#[loop_match]`
+            }
+            _ => 2,
+        };
+    }
+}
+
+fn not_const(mut state: u8, next: u8) -> u8 {
+    #[loop_match]
+    loop {
+        state = 'blk: {
+            match state {
+                0 => {
+                    #[const_continue]
+                    break 'blk next; //~ ERROR must be a constant
+                }
+                _ => break state,
+            }
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,143 @@
+// Check that a `#[const_continue]` in a `#[loop_match]` enters the arm its value selects, both
+// when that arm is entered directly and when the `match` has to run again, and that the values
+// going out of scope on the way are dropped.
+//@ run-pass
+
+#![allow(incomplete_features)]
+#![feature(loop_match)]
+
+use std::cell::Cell;
+
+enum State {
+    Start,
+    Digits,
+    Done,
+    Error,
+}
+
+fn count_digits(input: &[u8]) -> Result<usize, usize> {
+    let mut pos = 0;
+    let mut state = State::Start;
+    #[loop_match]
+    loop {
+        state = 'blk: {
+            match state {
+                State::Start if input.is_empty() => State::Error,
+                State::Start => State::Digits,
+                State::Digits => match input.get(pos) {
+                    Some(b'0'..=b'9') => {
+                        pos += 1;
+                        #[const_continue]
+                        break 'blk State::Digits;
+                    }
+                    Some(_) => {
+                        #[const_continue]
+                        break 'blk State::Error;
+                    }
+                    None => {
+                        #[const_continue]
+                        break 'blk State::Done;
+                    }
+                },
+                State::Done => break Ok(pos),
+                State::Error => break Err(pos),
+            }
+        }
+    }
+}
+
+fn collatz(mut n: u64) -> u32 {
+    let mut steps = 0;
+    let mut state: u8 = 0;
+    #[loop_match]
+    loop {
+        state = 'blk: {
+            match state {
+                0 if n == 1 => break steps,
+                0 | 1 => {
+                    steps += 1;
+                    if n % 2 == 0 {
+                        #[const_continue]
+                        break 'blk 2;
+                    }
+                    #[const_continue]
+                    break 'blk 3;
+                }
+                2 => {
+                    n /= 2;
+                    // The guard of the first arm may fail, so this runs the `match` again.
+                    #[const_continue]
+                    break 'blk 0;
+                }
+                3..=u8::MAX => {
+                    n = 3 * n + 1;
+                    #[const_continue]
+                    break 'blk 0;
+                }
+            }
+        }
+    }
+}
+
+fn generic<const N: u8>() -> u8 {
+    let mut state = 0u8;
+    #[loop_match]
+    loop {
+        state = 'blk: {
+            match state {
+                0 => {
+                    #[const_continue]
+                    break 'blk N;
+                }
+                1 => break 1,
+                _ => break 2,
+            }
+        }
+    }
+}
+
+struct Noisy<'a>(&'a Cell<u32>);
+
+impl Drop for Noisy<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+fn drops(dropped: &Cell<u32>) -> u32 {
+    let mut state = 0u8;
+    #[loop_match]
+    loop {
+        state = 'blk: {
+            match state {
+                0 => {
+                    let _a = Noisy(dropped);
+                    #[const_continue]
+                    break 'blk 1;
+                }
+                1 => {
+                    let _b = Noisy(dropped);
+                    assert_eq!(dropped.get(), 1);
+                    #[const_continue]
+                    break 'blk 2;
+                }
+                _ => break dropped.get(),
+            }
+        }
+    }
+}
+
+fn main() {
+    assert_eq!(count_digits(b""), Err(0));
+    assert_eq!(count_digits(b"123"), Ok(3));
+    assert_eq!(count_digits(b"12a"), Err(2));
+
+    assert_eq!(collatz(1), 0);
+    assert_eq!(collatz(6), 8);
+    assert_eq!(collatz(27), 111);
+
+    assert_eq!(generic::<1>(), 1);
+    assert_eq!(generic::<5>(), 2);
+
+    assert_eq!(drops(&Cell::new(0)), 2);
+}
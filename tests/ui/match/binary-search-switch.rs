@@ -0,0 +1,96 @@
+// Check that switches on many sparse constants, which are lowered to a binary search with
+// `-Zmatch-binary-search`, branch like a single `SwitchInt` would.
+//@ run-pass
+//@ compile-flags: -Zmatch-binary-search=8
+
+fn syscall(n: u64) -> u32 {
+    match n {
+        0 => 1,
+        1 => 2,
+        2 => 3,
+        39 => 4,
+        57 => 5,
+        59 => 6,
+        60 => 7,
+        231 => 8,
+        257 => 9,
+        435 => 10,
+        1000 | 2000 => 11,
+        _ => 0,
+    }
+}
+
+fn signed(x: i32) -> u32 {
+    match x {
+        i32::MIN => 1,
+        -100_000 => 2,
+        -1000 => 3,
+        -7 => 4,
+        0 => 5,
+        13 => 6,
+        500 => 7,
+        70_000 => 8,
+        i32::MAX => 9,
+        _ => 0,
+    }
+}
+
+fn chars(c: char) -> u32 {
+    match c {
+        '\0' => 1,
+        '!' => 2,
+        '@' => 3,
+        'Z' => 4,
+        'z' => 5,
+        '~' => 6,
+        'é' => 7,
+        'λ' => 8,
+        '😀' => 9,
+        _ => 0,
+    }
+}
+
+fn with_guard(x: (u16, bool)) -> u32 {
+    match x {
+        (10 | 300 | 5000, b) if b => 1,
+        (20, _) => 2,
+        (300, _) => 3,
+        (4000 | 40 | 400 | 60000, _) => 4,
+        (7, false) => 5,
+        _ => 0,
+    }
+}
+
+fn main() {
+    let expected = [(0, 1), (2, 3), (3, 0), (39, 4), (58, 0), (60, 7), (231, 8), (256, 0)];
+    for (n, result) in expected {
+        assert_eq!(syscall(n), result);
+    }
+    assert_eq!(syscall(435), 10);
+    assert_eq!(syscall(2000), 11);
+    assert_eq!(syscall(u64::MAX), 0);
+
+    assert_eq!(signed(i32::MIN), 1);
+    assert_eq!(signed(-100_000), 2);
+    assert_eq!(signed(-999), 0);
+    assert_eq!(signed(-7), 4);
+    assert_eq!(signed(0), 5);
+    assert_eq!(signed(1), 0);
+    assert_eq!(signed(70_000), 8);
+    assert_eq!(signed(i32::MAX), 9);
+
+    assert_eq!(chars('\0'), 1);
+    assert_eq!(chars('Z'), 4);
+    assert_eq!(chars('a'), 0);
+    assert_eq!(chars('~'), 6);
+    assert_eq!(chars('λ'), 8);
+    assert_eq!(chars('😀'), 9);
+    assert_eq!(chars(char::MAX), 0);
+
+    assert_eq!(with_guard((300, true)), 1);
+    assert_eq!(with_guard((300, false)), 3);
+    assert_eq!(with_guard((20, true)), 2);
+    assert_eq!(with_guard((400, false)), 4);
+    assert_eq!(with_guard((7, false)), 5);
+    assert_eq!(with_guard((7, true)), 0);
+}
@@ -0,0 +1,71 @@
+// Check that or-patterns of integer constants close to each other, which are tested by looking up
+// a bit of a mask, match the same values as before, including for signed integers and values on
+// either side of the mask.
+//@ run-pass
+
+fn odd_digits(x: u32) -> u32 {
+    match x {
+        1 | 3 | 5 | 7 | 9 => 1,
+        2 => 2,
+        _ => 0,
+    }
+}
+
+fn signed(x: i8) -> u32 {
+    match x {
+        -100 | -50 | 0 | 27 => 1,
+        i8::MIN | -1 | 1 | 10 => 2,
+        _ => 0,
+    }
+}
+
+fn wide(x: u64) -> u32 {
+    match x {
+        1000 | 1010 | 1100 | 1127 => 1,
+        0 | 64 | 65 | 127 => 2,
+        _ => 0,
+    }
+}
+
+fn with_guard(x: (i32, bool)) -> u32 {
+    match x {
+        (2 | 3 | 5 | 7 | 11 | 13, b) if b => 1,
+        (5, _) => 2,
+        (1 | 4 | 9 | 16, _) => 3,
+        _ => 0,
+    }
+}
+
+fn main() {
+    assert_eq!(odd_digits(0), 0);
+    assert_eq!(odd_digits(1), 1);
+    assert_eq!(odd_digits(2), 2);
+    assert_eq!(odd_digits(9), 1);
+    assert_eq!(odd_digits(10), 0);
+    assert_eq!(odd_digits(u32::MAX), 0);
+
+    assert_eq!(signed(-100), 1);
+    assert_eq!(signed(-101), 0);
+    assert_eq!(signed(0), 1);
+    assert_eq!(signed(27), 1);
+    assert_eq!(signed(28), 0);
+    assert_eq!(signed(i8::MAX), 0);
+    assert_eq!(signed(i8::MIN), 2);
+    assert_eq!(signed(-1), 2);
+    assert_eq!(signed(10), 2);
+
+    assert_eq!(wide(999), 0);
+    assert_eq!(wide(1000), 1);
+    assert_eq!(wide(1127), 1);
+    assert_eq!(wide(1128), 0);
+    assert_eq!(wide(0), 2);
+    assert_eq!(wide(127), 2);
+    assert_eq!(wide(128), 0);
+
+    assert_eq!(with_guard((5, true)), 1);
+    assert_eq!(with_guard((5, false)), 2);
+    assert_eq!(with_guard((13, true)), 1);
+    assert_eq!(with_guard((13, false)), 0);
+    assert_eq!(with_guard((9, true)), 3);
+    assert_eq!(with_guard((-1, true)), 0);
+}
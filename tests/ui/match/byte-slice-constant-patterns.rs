@@ -0,0 +1,74 @@
+// Check that slice patterns of constant bytes, which are matched by switching on the length of the
+// slice and then comparing it with each constant at once, behave like testing each byte in turn.
+//@ run-pass
+
+fn method(s: &[u8]) -> u32 {
+    match s {
+        b"GET" => 1,
+        b"PUT" => 2,
+        b"POST" => 3,
+        b"" => 4,
+        b"D" => 5,
+        [b'H', b'E', b'A', b'D'] => 6,
+        _ => 0,
+    }
+}
+
+fn mixed(s: &[u8]) -> u32 {
+    match s {
+        b"ab" => 1,
+        [b'a', x] if *x == b'c' => 2,
+        [b'a', ..] => 3,
+        b"xyz" => 4,
+        [_, _, _] => 5,
+        _ => 0,
+    }
+}
+
+fn with_tuple(x: (&[u8], bool)) -> u32 {
+    match x {
+        (b"on", true) => 1,
+        (b"off", _) => 2,
+        (b"on", false) => 3,
+        _ => 0,
+    }
+}
+
+const fn in_const(s: &[u8]) -> u32 {
+    match s {
+        b"one" => 1,
+        b"two" => 2,
+        b"three" => 3,
+        _ => 0,
+    }
+}
+
+const THREE: u32 = in_const(b"three");
+
+fn main() {
+    assert_eq!(method(b"GET"), 1);
+    assert_eq!(method(b"PUT"), 2);
+    assert_eq!(method(b"POST"), 3);
+    assert_eq!(method(b""), 4);
+    assert_eq!(method(b"D"), 5);
+    assert_eq!(method(b"HEAD"), 6);
+    assert_eq!(method(b"GOT"), 0);
+    assert_eq!(method(b"GETS"), 0);
+
+    assert_eq!(mixed(b"ab"), 1);
+    assert_eq!(mixed(b"ac"), 2);
+    assert_eq!(mixed(b"ad"), 3);
+    assert_eq!(mixed(b"abc"), 3);
+    assert_eq!(mixed(b"xyz"), 4);
+    assert_eq!(mixed(b"xyw"), 5);
+    assert_eq!(mixed(b"x"), 0);
+
+    assert_eq!(with_tuple((b"on", true)), 1);
+    assert_eq!(with_tuple((b"off", true)), 2);
+    assert_eq!(with_tuple((b"on", false)), 3);
+    assert_eq!(with_tuple((b"of", false)), 0);
+
+    assert_eq!(THREE, 3);
+    assert_eq!(in_const(b"two"), 2);
+    assert_eq!(in_const(b"four"), 0);
+}
@@ -0,0 +1,51 @@
+//@ run-pass
+// Check that byte string literals can be matched against `[u8; N]` scrutinees with
+// `#![feature(byte_string_array_patterns)]`, byte by byte, and still against references.
+#![feature(byte_string_array_patterns)]
+
+fn magic(header: [u8; 4]) -> &'static str {
+    match header {
+        b"\x89PNG" => "png",
+        b"GIF8" => "gif",
+        [0xff, 0xd8, ..] => "jpeg",
+        _ => "unknown",
+    }
+}
+
+fn nested(pair: (u8, [u8; 2])) -> u32 {
+    match pair {
+        (0, b"ok") => 1,
+        (_, b"ok") => 2,
+        (_, [b'o', _]) => 3,
+        _ => 4,
+    }
+}
+
+fn by_ref(header: &[u8; 3]) -> bool {
+    matches!(header, b"abc")
+}
+
+fn by_binding(mut header: [u8; 2]) -> [u8; 2] {
+    if let b"hi" = header {
+        header[0] = b'H';
+    }
+    header
+}
+
+fn main() {
+    assert_eq!(magic(*b"\x89PNG"), "png");
+    assert_eq!(magic(*b"GIF8"), "gif");
+    assert_eq!(magic([0xff, 0xd8, 0, 0]), "jpeg");
+    assert_eq!(magic(*b"RIFF"), "unknown");
+
+    assert_eq!(nested((0, *b"ok")), 1);
+    assert_eq!(nested((1, *b"ok")), 2);
+    assert_eq!(nested((1, *b"on")), 3);
+    assert_eq!(nested((1, *b"no")), 4);
+
+    assert!(by_ref(b"abc"));
+    assert!(!by_ref(b"abd"));
+
+    assert_eq!(by_binding(*b"hi"), *b"Hi");
+    assert_eq!(by_binding(*b"ho"), *b"ho");
+}
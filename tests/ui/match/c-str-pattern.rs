@@ -0,0 +1,40 @@
+//@ run-pass
+//@ edition: 2021
+// Check that C string literals and constants can be used as patterns on `&CStr`, and that their
+// bytes are compared up to and including the NUL terminator.
+
+use std::ffi::CStr;
+
+const VERSION: &CStr = c"version";
+
+fn command(name: &CStr) -> u32 {
+    match name {
+        c"" => 0,
+        c"help" => 1,
+        VERSION => 2,
+        c"\xff\x01" => 3,
+        _ => 4,
+    }
+}
+
+fn nested(arg: Option<&CStr>) -> bool {
+    matches!(arg, Some(c"-v" | c"--verbose"))
+}
+
+fn main() {
+    assert_eq!(command(c""), 0);
+    assert_eq!(command(c"help"), 1);
+    assert_eq!(command(c"version"), 2);
+    assert_eq!(command(c"\xff\x01"), 3);
+    assert_eq!(command(c"hel"), 4);
+    assert_eq!(command(c"helpme"), 4);
+
+    // Only the bytes up to the first NUL are part of the C string.
+    let buf = b"help\0me\0";
+    assert_eq!(command(CStr::from_bytes_until_nul(buf).unwrap()), 1);
+
+    assert!(nested(Some(c"-v")));
+    assert!(nested(Some(c"--verbose")));
+    assert!(!nested(Some(c"-q")));
+    assert!(!nested(None));
+}
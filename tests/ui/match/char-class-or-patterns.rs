@@ -0,0 +1,68 @@
+// Check that or-patterns of many `char` constants and ranges, which are tested by looking up the
+// `char` in a two-level table, match the same `char`s as before.
+//@ run-pass
+
+fn is_ident_start(c: char) -> bool {
+    match c {
+        'a'..='z' | 'A'..='Z' | '_' | '$'
+        | 'À'..='Ö' | 'Ø'..='ö' | 'ø'..='ÿ'
+        | 'Α'..='Ω' | 'α'..='ω'
+        | 'А'..='я'
+        | 'ぁ'..'ゟ' | 'ァ'..='ヺ'
+        | '一'..='鿿'
+        | '𝐀'..='𝑍' | '😀'
+        | 'ℵ' | '∞' | 'ﬀ' => true,
+        _ => false,
+    }
+}
+
+fn classify(c: char) -> u32 {
+    match c {
+        '!' | '#' | '%' | '&' | '*' | '+' | '-' | '/' | ':' | '<' | '=' | '>' | '?' | '@' | '^'
+        | '|' | '~' | '¬' | '±' | '×' | '÷' | '∧' | '∨' => 1,
+        ' ' | '\t' | '\n' | '\r' | '\u{85}' | '\u{a0}' | '\u{1680}' | '\u{2000}'..='\u{200a}'
+        | '\u{2028}' | '\u{2029}' | '\u{202f}' | '\u{205f}' | '\u{3000}' | '\u{feff}' | '\u{b}'
+        | '\u{c}' => 2,
+        '0'..='9' => 3,
+        _ => 0,
+    }
+}
+
+fn with_guard(x: (char, bool)) -> u32 {
+    match x {
+        ('a' | 'e' | 'i' | 'o' | 'u' | 'y' | 'à' | 'é' | 'è' | 'ê' | 'î' | 'ô' | 'û' | 'ü' | 'ÿ'
+        | 'æ' | 'œ', b) if b => 1,
+        ('é', _) => 2,
+        ('b', _) => 3,
+        _ => 0,
+    }
+}
+
+fn main() {
+    for c in ['a', 'z', 'A', 'Z', '_', '$', 'À', 'ÿ', 'Ω', 'λ', 'Ж', 'ぁ', 'ヺ', '一', '鿿', '𝐀'] {
+        assert!(is_ident_start(c), "{c:?}");
+    }
+    for c in ['😀', 'ℵ', '∞', 'ﬀ'] {
+        assert!(is_ident_start(c), "{c:?}");
+    }
+    for c in ['\0', '0', ' ', '×', '÷', 'ゟ', 'ー', '〇', '𝑎', '😁', char::MAX] {
+        assert!(!is_ident_start(c), "{c:?}");
+    }
+
+    assert_eq!(classify('!'), 1);
+    assert_eq!(classify('~'), 1);
+    assert_eq!(classify('∨'), 1);
+    assert_eq!(classify('"'), 0);
+    assert_eq!(classify(' '), 2);
+    assert_eq!(classify('\u{2005}'), 2);
+    assert_eq!(classify('\u{200b}'), 0);
+    assert_eq!(classify('\u{feff}'), 2);
+    assert_eq!(classify('7'), 3);
+    assert_eq!(classify('a'), 0);
+
+    assert_eq!(with_guard(('é', true)), 1);
+    assert_eq!(with_guard(('é', false)), 2);
+    assert_eq!(with_guard(('œ', true)), 1);
+    assert_eq!(with_guard(('b', true)), 3);
+    assert_eq!(with_guard(('c', true)), 0);
+}
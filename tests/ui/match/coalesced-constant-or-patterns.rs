@@ -0,0 +1,50 @@
+// Check that or-patterns made of consecutive constants, which are tested as ranges, match the same
+// values as before, including for signed integers, `char`s and unordered constants.
+//@ run-pass
+
+fn signed(x: i8) -> u32 {
+    match x {
+        -2 | -1 | 0 | 1 => 0,
+        2 | 4 | 3 | 5 => 1,
+        i8::MIN | -127 | -126 | -125 => 2,
+        _ => 3,
+    }
+}
+
+fn chars(c: char) -> u32 {
+    match c {
+        'a' | 'b' | 'c' | 'd' => 0,
+        'x' | 'y' | 'z' => 1,
+        _ => 2,
+    }
+}
+
+fn with_binding(x: (u16, bool)) -> u16 {
+    match x {
+        (n @ (10 | 11 | 12 | 13), true) => n,
+        (10 | 11 | 12 | 13, false) => 1,
+        _ => 0,
+    }
+}
+
+fn main() {
+    assert_eq!(signed(-3), 3);
+    assert_eq!(signed(-2), 0);
+    assert_eq!(signed(1), 0);
+    assert_eq!(signed(3), 1);
+    assert_eq!(signed(5), 1);
+    assert_eq!(signed(6), 3);
+    assert_eq!(signed(-128), 2);
+    assert_eq!(signed(-125), 2);
+    assert_eq!(signed(-124), 3);
+
+    assert_eq!(chars('a'), 0);
+    assert_eq!(chars('d'), 0);
+    assert_eq!(chars('e'), 2);
+    assert_eq!(chars('y'), 1);
+
+    assert_eq!(with_binding((12, true)), 12);
+    assert_eq!(with_binding((13, false)), 1);
+    assert_eq!(with_binding((14, true)), 0);
+    assert_eq!(with_binding((9, false)), 0);
+}
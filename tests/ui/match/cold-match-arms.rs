@@ -0,0 +1,58 @@
+// Check that match arms marked `#[cold]`, which are lowered after the other arms and whose tests
+// are hinted as unlikely, still match the same values.
+//@ run-pass
+#![feature(cold_match_arms)]
+#![deny(unused_attributes)]
+
+fn parse(s: &str) -> Result<u32, ()> {
+    match s {
+        #[cold]
+        "" => Err(()),
+        "one" => Ok(1),
+        "two" => Ok(2),
+        #[cold]
+        _ => Err(()),
+    }
+}
+
+fn classify(x: (u32, bool)) -> u32 {
+    match x {
+        (0, _) => 0,
+        #[cold]
+        (1..=9, false) | (100.., _) => 1,
+        (n, true) if n % 2 == 0 => 2,
+        #[cold]
+        (n, _) if n > 50 => 3,
+        _ => 4,
+    }
+}
+
+const fn in_const(x: Option<bool>) -> u32 {
+    match x {
+        Some(true) => 1,
+        #[cold]
+        Some(false) => 2,
+        None => 3,
+    }
+}
+
+const FALSE: u32 = in_const(Some(false));
+
+fn main() {
+    assert_eq!(parse(""), Err(()));
+    assert_eq!(parse("one"), Ok(1));
+    assert_eq!(parse("two"), Ok(2));
+    assert_eq!(parse("three"), Err(()));
+
+    assert_eq!(classify((0, true)), 0);
+    assert_eq!(classify((5, false)), 1);
+    assert_eq!(classify((5, true)), 4);
+    assert_eq!(classify((150, true)), 1);
+    assert_eq!(classify((10, true)), 2);
+    assert_eq!(classify((60, false)), 3);
+    assert_eq!(classify((11, false)), 4);
+
+    assert_eq!(FALSE, 2);
+    assert_eq!(in_const(Some(true)), 1);
+    assert_eq!(in_const(None), 3);
+}
@@ -0,0 +1,57 @@
+// Check that guards which are constants, and so are resolved while building the match instead of
+// being lowered, still select the right arm, including for or-patterns and arms with bindings.
+//@ run-pass
+
+const ENABLED: bool = true;
+const DISABLED: bool = false;
+
+fn literal(x: Option<u32>) -> u32 {
+    match x {
+        Some(n) if false => n + 100,
+        Some(n) if true => n,
+        Some(_) => unreachable!(),
+        None => 0,
+    }
+}
+
+fn named(x: u32) -> u32 {
+    match x {
+        0 | 1 if DISABLED => 10,
+        0 | 1 if ENABLED => 20,
+        n if const { 1 + 1 == 3 } => n,
+        n if const { 1 + 1 == 2 } => n + 1,
+        _ => unreachable!(),
+    }
+}
+
+fn config(x: (u8, u8)) -> u8 {
+    match x {
+        (a, b) if cfg!(debug_assertions) => a + b,
+        (a, b) => a * b,
+    }
+}
+
+fn borrowed(s: &mut String) -> usize {
+    match s {
+        s if false => {
+            s.push('!');
+            0
+        }
+        s => s.len(),
+    }
+}
+
+fn main() {
+    assert_eq!(literal(Some(3)), 3);
+    assert_eq!(literal(None), 0);
+
+    assert_eq!(named(1), 20);
+    assert_eq!(named(5), 6);
+
+    let expected = if cfg!(debug_assertions) { 5 } else { 6 };
+    assert_eq!(config((2, 3)), expected);
+
+    let mut s = "abc".to_string();
+    assert_eq!(borrowed(&mut s), 3);
+    assert_eq!(s, "abc");
+}
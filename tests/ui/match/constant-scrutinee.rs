@@ -0,0 +1,59 @@
+// Check that a `match` on a literal or a constant, whose tests are evaluated while building MIR,
+// still picks the right arm, including with ranges, guards and or-patterns.
+//@ run-pass
+
+#![allow(unreachable_patterns, overlapping_range_endpoints)]
+
+const LIMIT: u32 = 300;
+const LETTER: char = 'q';
+
+fn literal() -> &'static str {
+    match 7u8 {
+        0 => "zero",
+        1..=5 => "small",
+        6 | 7 => "six or seven",
+        _ => "large",
+    }
+}
+
+fn named(flag: bool) -> u32 {
+    match LIMIT {
+        0..=99 => 1,
+        100..=299 => 2,
+        300 if flag => 3,
+        300..=999 => 4,
+        _ => 5,
+    }
+}
+
+fn character() -> u32 {
+    match LETTER {
+        'a'..='m' => 1,
+        'n'..='z' => 2,
+        _ => 3,
+    }
+}
+
+fn boolean() -> u32 {
+    match cfg!(debug_assertions) {
+        false => 0,
+        true => 1,
+    }
+}
+
+fn negative() -> i32 {
+    match const { -4i32 * 3 } {
+        i32::MIN..=-13 => 1,
+        -12 => 2,
+        _ => 3,
+    }
+}
+
+fn main() {
+    assert_eq!(literal(), "six or seven");
+    assert_eq!(named(true), 3);
+    assert_eq!(named(false), 4);
+    assert_eq!(character(), 2);
+    assert_eq!(boolean(), cfg!(debug_assertions) as u32);
+    assert_eq!(negative(), 2);
+}
@@ -0,0 +1,27 @@
+// Check the errors for the `match`es of a `#[constant_time_match]` function that can't be lowered
+// without branching on their scrutinee.
+
+#![allow(incomplete_features)]
+#![feature(constant_time_match)]
+
+#[constant_time_match]
+fn guard(x: u8, y: u8) -> u8 {
+    match x { 0 if y == 0 => 1, _ => 2 } //~ ERROR can't be lowered without branching
+}
+
+#[constant_time_match]
+fn binding(x: u8) -> u8 {
+    match x { _n @ 0 => 1, _ => 2 } //~ ERROR can't be lowered without branching
+}
+
+#[constant_time_match]
+fn variant(x: Option<u8>) -> u8 {
+    match x { Some(0) => 1, _ => 2 } //~ ERROR can't be lowered without branching
+}
+
+#[constant_time_match]
+fn not_constant(x: u8, y: u8) -> u8 {
+    match x { 0 => y, _ => 2 } //~ ERROR can't be lowered without branching
+}
+
+fn main() {}
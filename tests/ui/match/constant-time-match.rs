@@ -0,0 +1,65 @@
+// Check that the `match`es of a `#[constant_time_match]` function, which are lowered by selecting
+// the value of the matching arm with masks instead of by branching, select the first arm that
+// matches, including with or-patterns, ranges and tuples.
+//@ run-pass
+
+#![allow(incomplete_features)]
+#![feature(constant_time_match)]
+
+const INVALID: u8 = 0xff;
+
+#[constant_time_match]
+fn hex_digit(c: u8) -> u8 {
+    match c {
+        b'0' => 0,
+        b'1'..=b'9' => 1,
+        b'a'..=b'f' | b'A'..=b'F' => 2,
+        _ => INVALID,
+    }
+}
+
+#[constant_time_match]
+fn overlapping(x: i32) -> i64 {
+    match x {
+        i32::MIN..0 => -1,
+        0 | 2 | 4 | 6 | 8 | 10 => 2,
+        0..=10 => 1,
+        _ => i64::MAX,
+    }
+}
+
+#[constant_time_match]
+fn is_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\r' | '\u{a0}' | '\u{2000}'..='\u{200a}' | '\u{3000}')
+}
+
+#[constant_time_match]
+fn both(pair: (bool, u16)) -> bool {
+    match pair {
+        (true, 0) => false,
+        (true, _) | (false, 0) => true,
+        (false, _) => false,
+    }
+}
+
+fn main() {
+    assert_eq!(hex_digit(b'0'), 0);
+    assert_eq!(hex_digit(b'7'), 1);
+    assert_eq!(hex_digit(b'c'), 2);
+    assert_eq!(hex_digit(b'F'), 2);
+    assert_eq!(hex_digit(b'g'), INVALID);
+
+    assert_eq!(overlapping(-5), -1);
+    assert_eq!(overlapping(4), 2);
+    assert_eq!(overlapping(5), 1);
+    assert_eq!(overlapping(11), i64::MAX);
+
+    assert!(is_whitespace('\t'));
+    assert!(is_whitespace('\u{2005}'));
+    assert!(!is_whitespace('a'));
+
+    assert!(!both((true, 0)));
+    assert!(both((true, 3)));
+    assert!(both((false, 0)));
+    assert!(!both((false, 3)));
+}
@@ -0,0 +1,82 @@
+// Check that `-Zmatch-lowering=decision-tree` preserves the semantics of matches whose candidates
+// get copied into several branches, including bindings, guards and or-patterns in the copies.
+//@ run-pass
+//@ compile-flags: -Zmatch-lowering=decision-tree
+
+fn bools(x: (bool, bool, bool)) -> u32 {
+    match x {
+        (true, _, true) => 0,
+        (false, false, _) => 1,
+        (_, true, _) => 2,
+        (true, _, false) => 3,
+    }
+}
+
+fn with_bindings(x: (Option<u8>, u8)) -> u32 {
+    match x {
+        (Some(0), _) => 0,
+        (_, n @ (1 | 2)) => n as u32,
+        (Some(m), n) if m == n => 10,
+        (None, _) => 20,
+        (Some(m), _) => 30 + m as u32,
+    }
+}
+
+fn with_guards(x: (bool, Result<u8, u8>)) -> u32 {
+    let mut guard_calls = 0;
+    let mut guard = |b| {
+        guard_calls += 1;
+        b
+    };
+    let arm = match x {
+        (true, Ok(_)) if guard(false) => 0,
+        (_, Ok(n) | Err(n)) if guard(n > 5) => 1,
+        (false, _) => 2,
+        _ => 3,
+    };
+    arm * 10 + guard_calls
+}
+
+enum E {
+    A,
+    B,
+    C,
+}
+
+fn with_or_patterns(x: (E, u8)) -> u32 {
+    match x {
+        (E::A, 0) => 0,
+        (E::A | E::B, 1 | 2) => 1,
+        (E::C | E::A, n) if n > 5 => 2,
+        (E::B, _) => 3,
+        _ => 4,
+    }
+}
+
+fn main() {
+    assert_eq!(bools((true, false, true)), 0);
+    assert_eq!(bools((false, false, false)), 1);
+    assert_eq!(bools((false, true, false)), 2);
+    assert_eq!(bools((true, true, false)), 2);
+    assert_eq!(bools((true, false, false)), 3);
+
+    assert_eq!(with_bindings((Some(0), 1)), 0);
+    assert_eq!(with_bindings((None, 2)), 2);
+    assert_eq!(with_bindings((Some(7), 7)), 10);
+    assert_eq!(with_bindings((None, 7)), 20);
+    assert_eq!(with_bindings((Some(7), 8)), 37);
+
+    assert_eq!(with_guards((true, Ok(1))), 32);
+    assert_eq!(with_guards((true, Ok(9))), 12);
+    assert_eq!(with_guards((false, Err(9))), 11);
+    assert_eq!(with_guards((false, Err(1))), 21);
+
+    assert_eq!(with_or_patterns((E::A, 0)), 0);
+    assert_eq!(with_or_patterns((E::A, 1)), 1);
+    assert_eq!(with_or_patterns((E::B, 2)), 1);
+    assert_eq!(with_or_patterns((E::C, 1)), 4);
+    assert_eq!(with_or_patterns((E::C, 9)), 2);
+    assert_eq!(with_or_patterns((E::A, 9)), 2);
+    assert_eq!(with_or_patterns((E::B, 9)), 3);
+    assert_eq!(with_or_patterns((E::A, 3)), 4);
+}
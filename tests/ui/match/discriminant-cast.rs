@@ -0,0 +1,80 @@
+// Check that matches mapping each variant of a fieldless enum to its discriminant, which are
+// lowered to a cast of the discriminant, give the same values as the arms, and that similar
+// matches that aren't such a mapping still run their arms.
+//@ run-pass
+
+#[derive(Clone, Copy, Debug)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+    Cyan,
+    Magenta,
+    Yellow,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[repr(i16)]
+enum Signed {
+    A = -300,
+    B = -2,
+    C = 0,
+    D = 7,
+    E = 1000,
+}
+
+const ALL_COLORS: [Color; 6] =
+    [Color::Red, Color::Green, Color::Blue, Color::Cyan, Color::Magenta, Color::Yellow];
+const ALL_SIGNED: [Signed; 5] = [Signed::A, Signed::B, Signed::C, Signed::D, Signed::E];
+
+fn color(c: Color) -> u8 {
+    match c {
+        Color::Red => 0,
+        Color::Green => 1,
+        Color::Blue => 2,
+        Color::Cyan => 3,
+        Color::Magenta => 4,
+        Color::Yellow => 5,
+    }
+}
+
+fn color_or_patterns(c: Color) -> u64 {
+    match c {
+        Color::Red => 0,
+        Color::Green => 1,
+        Color::Blue => 2,
+        Color::Cyan => 3,
+        Color::Magenta | Color::Yellow => 4,
+    }
+}
+
+fn signed_wide(s: Signed) -> i64 {
+    match s {
+        Signed::A => -300,
+        Signed::B => -2,
+        Signed::C => 0,
+        Signed::D => 7,
+        Signed::E => 1000,
+    }
+}
+
+fn signed_truncated(s: &Signed) -> u8 {
+    match *s {
+        Signed::A => 212,
+        Signed::B => 254,
+        Signed::C => 0,
+        Signed::D => 7,
+        Signed::E => 232,
+    }
+}
+
+fn main() {
+    for (i, c) in ALL_COLORS.into_iter().enumerate() {
+        assert_eq!(color(c), i as u8, "{c:?}");
+        assert_eq!(color_or_patterns(c), (i as u64).min(4), "{c:?}");
+    }
+    for s in ALL_SIGNED {
+        assert_eq!(signed_wide(s), s as i64, "{s:?}");
+        assert_eq!(signed_truncated(&s), s as u8, "{s:?}");
+    }
+}
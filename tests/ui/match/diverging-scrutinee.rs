@@ -0,0 +1,47 @@
+// Check that a `match` whose scrutinee diverges still evaluates it, even though its arms, which
+// can't be reached, are not lowered.
+//@ run-pass
+//@ needs-unwind
+
+#![allow(unreachable_code, unreachable_patterns)]
+
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+
+fn diverge(count: &Cell<u32>) -> ! {
+    count.set(count.get() + 1);
+    panic!("diverged")
+}
+
+fn call(count: &Cell<u32>) -> u32 {
+    match diverge(count) {
+        x => x,
+    }
+}
+
+fn block(count: &Cell<u32>) -> u32 {
+    let mut s = String::new();
+    match {
+        s.push('a');
+        diverge(count)
+    } {
+        _ if s.is_empty() => 1,
+        _ => {
+            s.push('b');
+            2
+        }
+    }
+}
+
+fn early_return(n: u32) -> u32 {
+    match return n + 1 {}
+}
+
+fn main() {
+    let count = Cell::new(0);
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| call(&count))).is_err());
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| block(&count))).is_err());
+    assert_eq!(count.get(), 2);
+
+    assert_eq!(early_return(1), 2);
+}
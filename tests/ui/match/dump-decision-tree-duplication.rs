@@ -0,0 +1,44 @@
+// Snapshot the decision trees built with `-Zmatch-lowering=decision-tree`, which copies candidates
+// into every branch of a test that says nothing about them instead of testing again afterwards.
+//@ compile-flags: -Zmatch-lowering=decision-tree -Zmatch-lowering-budget=2
+
+#![feature(rustc_attrs)]
+
+// `x.0` is only tested once, unlike with the default lowering.
+#[rustc_dump_decision_tree]
+fn bools(x: (bool, bool)) -> u32 {
+    match x { //~ ERROR rustc_dump_decision_tree
+        (true, true) => 1,
+        (_, false) => 2,
+        (false, true) => 3,
+    }
+}
+
+// Once the budget is spent, the remaining candidates are tested after the branches again.
+#[rustc_dump_decision_tree]
+fn over_budget(x: (bool, bool, bool)) -> u32 {
+    match x { //~ ERROR rustc_dump_decision_tree
+        (true, _, _) => 1,
+        (_, true, _) => 2,
+        (_, _, true) => 3,
+        _ => 4,
+    }
+}
+
+enum E {
+    A,
+    B,
+    C,
+}
+
+// The copies of the or-pattern know which variant `x.0` has, and don't test it again.
+#[rustc_dump_decision_tree]
+fn or_patterns(x: (E, bool)) -> u32 {
+    match x { //~ ERROR rustc_dump_decision_tree
+        (E::A, true) => 1,
+        (E::A | E::B, false) => 2,
+        _ => 3,
+    }
+}
+
+fn main() {}
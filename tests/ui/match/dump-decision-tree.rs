@@ -0,0 +1,127 @@
+// Snapshot the decision trees that match lowering builds, using `#[rustc_dump_decision_tree]`.
+
+#![feature(rustc_attrs)]
+
+#[rustc_dump_decision_tree]
+fn bools(x: (bool, bool)) -> u32 {
+    match x { //~ ERROR rustc_dump_decision_tree
+        (true, true) => 1,
+        (_, false) => 2,
+        (false, true) => 3,
+    }
+}
+
+enum E {
+    A,
+    B,
+    C,
+}
+
+#[rustc_dump_decision_tree]
+fn enums(x: Option<E>) -> u32 {
+    match x { //~ ERROR rustc_dump_decision_tree
+        Some(E::A | E::B) => 1,
+        Some(E::C) => 2,
+        None => 3,
+    }
+}
+
+#[rustc_dump_decision_tree]
+fn ints(x: u8) -> u32 {
+    match x { //~ ERROR rustc_dump_decision_tree
+        0 => 1,
+        1..=9 => 2,
+        10 => 3,
+        _ => 4,
+    }
+}
+
+#[rustc_dump_decision_tree]
+fn runs(x: u8) -> u32 {
+    match x { //~ ERROR rustc_dump_decision_tree
+        0 | 1 | 2 | 3 => 1,
+        5 | 7 => 2,
+        _ => 3,
+    }
+}
+
+#[rustc_dump_decision_tree]
+fn keywords(s: &str) -> u32 {
+    match s { //~ ERROR rustc_dump_decision_tree
+        "if" => 1,
+        "in" => 2,
+        "for" => 3,
+        "fn" => 4,
+        _ => 5,
+    }
+}
+
+#[rustc_dump_decision_tree]
+fn methods(s: &[u8]) -> u32 {
+    match s { //~ ERROR rustc_dump_decision_tree
+        b"GET" => 1,
+        b"PUT" => 2,
+        b"POST" => 3,
+        _ => 4,
+    }
+}
+
+#[rustc_dump_decision_tree]
+fn odd_digits(x: u32) -> u32 {
+    match x { //~ ERROR rustc_dump_decision_tree
+        1 | 3 | 5 | 7 | 9 => 1,
+        2 => 2,
+        _ => 3,
+    }
+}
+
+// The variant of `x.1` is tested before comparing strings.
+#[rustc_dump_decision_tree]
+fn str_and_option(x: (&str, Option<u8>)) -> u32 {
+    match x { //~ ERROR rustc_dump_decision_tree
+        ("a", None) => 1,
+        ("b", Some(_)) => 2,
+        _ => 3,
+    }
+}
+
+// Slice patterns of different fixed lengths share a single switch on the length.
+#[rustc_dump_decision_tree]
+fn slices(s: &[u32]) -> u32 {
+    match s { //~ ERROR rustc_dump_decision_tree
+        [a] => *a,
+        [a, b] => a + b,
+        [a, b, c] => a + b + c,
+        _ => 0,
+    }
+}
+
+enum Wrapper {
+    W(u8),
+}
+
+// The variant of an enum with a single variant is known without testing it.
+#[rustc_dump_decision_tree]
+fn newtype_enum(x: Wrapper) -> u32 {
+    match x { //~ ERROR rustc_dump_decision_tree
+        Wrapper::W(0) => 1,
+        Wrapper::W(_) => 2,
+    }
+}
+
+// A `matches!` only builds the tree of its pattern, not one for the `_` arm.
+#[rustc_dump_decision_tree]
+fn matches_macro(x: Option<u8>) -> bool {
+    matches!(x, Some(0)) //~ ERROR rustc_dump_decision_tree
+}
+
+// A `match` that only switches on the variant of an enum is lowered without a match tree.
+#[rustc_dump_decision_tree]
+fn single_switch(x: &Option<u8>) -> u8 {
+    match x { //~ ERROR rustc_dump_decision_tree
+        Some(y) => *y,
+        None => 0,
+    }
+}
+
+fn main() {}
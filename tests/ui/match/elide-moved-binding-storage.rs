@@ -0,0 +1,80 @@
+// Check that the bindings moved out of by the first statement of their arm, which get no storage
+// markers with `-Zmatch-elide-moved-binding-storage`, are still bound and dropped like before.
+//@ run-pass
+//@ needs-unwind
+//@ revisions: default elided
+//@[elided] compile-flags: -Zmatch-elide-moved-binding-storage
+
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+
+struct Noisy<'a>(&'a Cell<u32>, u32);
+
+impl Drop for Noisy<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+fn consume(n: Noisy<'_>) -> u32 {
+    n.1
+}
+
+fn first(x: Result<Noisy<'_>, (Noisy<'_>, u32)>) -> u32 {
+    match x {
+        Ok(n) => consume(n),
+        Err((n, k @ 0)) | Err((n, k)) => {
+            let m = n;
+            m.1 + k
+        }
+    }
+}
+
+fn guarded(x: Option<Noisy<'_>>) -> u32 {
+    match x {
+        Some(n) if n.1 > 10 => consume(n),
+        Some(n) => consume(n) + 100,
+        None => 0,
+    }
+}
+
+fn in_loop(dropped: &Cell<u32>) -> u32 {
+    let mut total = 0;
+    for i in 0..3 {
+        match Some(Noisy(dropped, i)) {
+            Some(n) => total += consume(n),
+            None => {}
+        }
+    }
+    total
+}
+
+fn panics(n: Noisy<'_>) -> u32 {
+    panic!("{}", n.1)
+}
+
+fn unwinding(x: Option<Noisy<'_>>) -> u32 {
+    match x {
+        Some(n) => panics(n),
+        None => 0,
+    }
+}
+
+fn main() {
+    let dropped = Cell::new(0);
+    assert_eq!(first(Ok(Noisy(&dropped, 1))), 1);
+    assert_eq!(first(Err((Noisy(&dropped, 2), 3))), 5);
+    assert_eq!(dropped.get(), 2);
+
+    assert_eq!(guarded(Some(Noisy(&dropped, 11))), 11);
+    assert_eq!(guarded(Some(Noisy(&dropped, 1))), 101);
+    assert_eq!(guarded(None), 0);
+    assert_eq!(dropped.get(), 4);
+
+    assert_eq!(in_loop(&dropped), 3);
+    assert_eq!(dropped.get(), 7);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| unwinding(Some(Noisy(&dropped, 4)))));
+    assert!(result.is_err());
+    assert_eq!(dropped.get(), 8);
+}
@@ -0,0 +1,107 @@
+// Check that `if`-`else if` chains comparing the same variable to distinct constants, which are
+// lowered to a single switch on the variable, take the same branches as the comparisons would,
+// including when the chain ends before the last `else`.
+//@ run-pass
+
+const TEN: i8 = 10;
+
+fn classify(x: i8) -> &'static str {
+    if x == 1 {
+        "one"
+    } else if x == -1 {
+        "minus one"
+    } else if TEN == x {
+        "ten"
+    } else if x == i8::MIN {
+        "min"
+    } else {
+        "other"
+    }
+}
+
+fn vowel(c: char) -> Option<u32> {
+    if c == 'a' {
+        Some(0)
+    } else if c == 'e' {
+        Some(1)
+    } else if c == 'i' {
+        Some(2)
+    } else if c == 'é' {
+        Some(3)
+    } else {
+        None
+    }
+}
+
+fn duplicate(x: u64) -> u32 {
+    if x == 1 {
+        1
+    } else if x == 2 {
+        2
+    } else if x == 1 {
+        3
+    } else {
+        4
+    }
+}
+
+fn mixed(x: u32, y: u32) -> u32 {
+    if x == 1 {
+        1
+    } else if x == 2 {
+        2
+    } else if y == 3 {
+        3
+    } else if x == 4 {
+        4
+    } else {
+        5
+    }
+}
+
+fn without_else(x: usize, hits: &mut [u32; 3]) {
+    if x == 0 {
+        hits[0] += 1;
+    } else if x == 1 {
+        hits[1] += 1;
+    } else if x == usize::MAX {
+        hits[2] += 1;
+    }
+}
+
+fn main() {
+    assert_eq!(classify(1), "one");
+    assert_eq!(classify(-1), "minus one");
+    assert_eq!(classify(10), "ten");
+    assert_eq!(classify(-128), "min");
+    assert_eq!(classify(0), "other");
+    assert_eq!(classify(127), "other");
+
+    assert_eq!(vowel('a'), Some(0));
+    assert_eq!(vowel('e'), Some(1));
+    assert_eq!(vowel('i'), Some(2));
+    assert_eq!(vowel('é'), Some(3));
+    assert_eq!(vowel('z'), None);
+
+    assert_eq!(duplicate(1), 1);
+    assert_eq!(duplicate(2), 2);
+    assert_eq!(duplicate(3), 4);
+
+    assert_eq!(mixed(1, 3), 1);
+    assert_eq!(mixed(2, 3), 2);
+    assert_eq!(mixed(4, 3), 3);
+    assert_eq!(mixed(4, 0), 4);
+    assert_eq!(mixed(5, 0), 5);
+
+    let mut hits = [0; 3];
+    for x in [0, 1, 1, 2, usize::MAX] {
+        without_else(x, &mut hits);
+    }
+    assert_eq!(hits, [1, 2, 1]);
+
+    let base = 7u16;
+    let describe = |x: u16| {
+        if base == 7 { "seven" } else if base == 8 { "eight" } else if x == 0 { "zero" } else { "" }
+    };
+    assert_eq!(describe(0), "seven");
+}
@@ -0,0 +1,44 @@
+// Check that the markers emitted around match guards with `-Zmir-emit-fake-borrow-markers` produce
+// well-formed MIR, including for places in inactive variants and behind references.
+//@ run-pass
+//@ compile-flags: -Zmir-emit-fake-borrow-markers
+
+fn guard_on_ints(x: (u8, Option<u32>)) -> u32 {
+    match x {
+        (0, Some(n)) if n > 10 => n,
+        (1, Some(_)) if x.0 == 1 => 1,
+        (_, None) if { let _ = &x; false } => 2,
+        _ => 3,
+    }
+}
+
+fn guard_behind_ref(x: &Option<&(bool, i32)>) -> i32 {
+    match *x {
+        Some(&(true, n)) if n > 0 => n,
+        Some(&(false, _)) if true => -1,
+        _ => 0,
+    }
+}
+
+fn guard_on_slice(s: &[u8]) -> usize {
+    match s {
+        [a, ..] if *a == 0 => 0,
+        [_, b] if *b == 1 => 1,
+        _ => s.len(),
+    }
+}
+
+fn main() {
+    assert_eq!(guard_on_ints((0, Some(11))), 11);
+    assert_eq!(guard_on_ints((0, Some(1))), 3);
+    assert_eq!(guard_on_ints((1, Some(5))), 1);
+    assert_eq!(guard_on_ints((2, None)), 3);
+
+    assert_eq!(guard_behind_ref(&Some(&(true, 4))), 4);
+    assert_eq!(guard_behind_ref(&Some(&(false, 4))), -1);
+    assert_eq!(guard_behind_ref(&None), 0);
+
+    assert_eq!(guard_on_slice(&[0, 1]), 0);
+    assert_eq!(guard_on_slice(&[2, 1]), 1);
+    assert_eq!(guard_on_slice(&[2, 2, 2]), 3);
+}
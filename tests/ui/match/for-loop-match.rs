@@ -0,0 +1,76 @@
+// Check that `for` loops whose pattern is a binding or `_`, and so whose `match` on the next item
+// is lowered to a switch on the `Option` directly, bind, drop and stop like other loops.
+//@ run-pass
+
+use std::cell::RefCell;
+
+struct Noisy<'a>(&'a RefCell<Vec<u32>>, u32);
+
+impl Drop for Noisy<'_> {
+    fn drop(&mut self) {
+        self.0.borrow_mut().push(self.1);
+    }
+}
+
+fn sum(v: &[u32]) -> u32 {
+    let mut total = 0;
+    for x in v {
+        total += x;
+    }
+    total
+}
+
+fn count(n: usize) -> usize {
+    let mut count = 0;
+    for _ in 0..n {
+        count += 1;
+    }
+    count
+}
+
+fn mutated(v: Vec<String>) -> Vec<String> {
+    let mut out = Vec::new();
+    for mut s in v {
+        s.push('!');
+        out.push(s);
+    }
+    out
+}
+
+fn first_even(v: &[u32]) -> Option<u32> {
+    for &x in v {
+        if x % 2 == 0 {
+            return Some(x);
+        }
+    }
+    None
+}
+
+fn labeled(rows: &[&[u32]]) -> u32 {
+    let mut found = 0;
+    'rows: for row in rows {
+        for x @ _ in row.iter() {
+            if *x == 0 {
+                continue 'rows;
+            }
+            found += x;
+        }
+    }
+    found
+}
+
+fn main() {
+    assert_eq!(sum(&[1, 2, 3]), 6);
+    assert_eq!(sum(&[]), 0);
+    assert_eq!(count(5), 5);
+    assert_eq!(mutated(vec!["a".to_string(), "b".to_string()]), ["a!", "b!"]);
+    assert_eq!(first_even(&[1, 3, 4, 6]), Some(4));
+    assert_eq!(first_even(&[1, 3]), None);
+    assert_eq!(labeled(&[&[1, 2], &[3, 0, 5], &[6]]), 12);
+
+    let log = RefCell::new(Vec::new());
+    for noisy in (1..=3).map(|n| Noisy(&log, n)) {
+        log.borrow_mut().push(noisy.1 * 10);
+    }
+    assert_eq!(*log.borrow(), [10, 1, 20, 2, 30, 3]);
+}
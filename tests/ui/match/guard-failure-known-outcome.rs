@@ -0,0 +1,63 @@
+// Check that the arms after a failed guard still select the right arm when the tests before the
+// guard already tell the outcome of some of their tests, which are then skipped.
+//@ run-pass
+
+enum E {
+    A(u8),
+    B(u8),
+    C,
+}
+
+fn variants(e: E, b: bool) -> u32 {
+    match e {
+        E::A(1) if b => 1,
+        E::B(_) | E::C if b => 2,
+        E::A(_) => 3,
+        E::B(n) if n > 1 => 4,
+        _ => 5,
+    }
+}
+
+fn strings(s: &str, n: u8) -> u32 {
+    match (s, n) {
+        ("a", _) if n > 5 => 1,
+        ("b", 0) => 2,
+        (_, 1) => 3,
+        ("a", _) => 4,
+        _ => 5,
+    }
+}
+
+fn ranges(x: i32, b: bool) -> u32 {
+    match x {
+        0..10 if b => 1,
+        5 if !b => 2,
+        0..10 => 3,
+        _ => 4,
+    }
+}
+
+fn main() {
+    assert_eq!(variants(E::A(1), true), 1);
+    assert_eq!(variants(E::A(1), false), 3);
+    assert_eq!(variants(E::A(2), true), 3);
+    assert_eq!(variants(E::B(0), true), 2);
+    assert_eq!(variants(E::B(2), false), 4);
+    assert_eq!(variants(E::B(0), false), 5);
+    assert_eq!(variants(E::C, true), 2);
+    assert_eq!(variants(E::C, false), 5);
+
+    assert_eq!(strings("a", 6), 1);
+    assert_eq!(strings("a", 1), 3);
+    assert_eq!(strings("a", 0), 4);
+    assert_eq!(strings("b", 0), 2);
+    assert_eq!(strings("b", 1), 3);
+    assert_eq!(strings("c", 0), 5);
+    assert_eq!(strings("c", 1), 3);
+
+    assert_eq!(ranges(3, true), 1);
+    assert_eq!(ranges(5, false), 2);
+    assert_eq!(ranges(5, true), 1);
+    assert_eq!(ranges(3, false), 3);
+    assert_eq!(ranges(20, true), 4);
+}
@@ -0,0 +1,40 @@
+// Check that `#[rustc_match_guard_fake_borrows(deep)]` keeps match guards from mutating any part of
+// the places that the arms after them test, and that the attribute rejects unknown kinds.
+
+#![feature(rustc_attrs)]
+
+fn default(mut x: (Option<u8>, bool)) {
+    match x {
+        (Some(_), false) => {}
+        _ if {
+            if let Some(ref mut y) = x.0 {
+                *y += 1;
+            }
+            true
+        } => {}
+        (Some(_), _) => {}
+        (None, _) => {}
+    }
+}
+
+#[rustc_match_guard_fake_borrows(deep)]
+fn deep(mut x: (Option<u8>, bool)) {
+    match x {
+        (Some(_), false) => {}
+        _ if {
+            if let Some(ref mut y) = x.0 {
+                //~^ ERROR cannot mutably borrow `x.0.0` in match guard
+                *y += 1;
+            }
+            true
+        } => {}
+        (Some(_), _) => {}
+        (None, _) => {}
+    }
+}
+
+#[rustc_match_guard_fake_borrows(weak)]
+//~^ ERROR invalid argument to `#[rustc_match_guard_fake_borrows]`
+fn invalid() {}
+
+fn main() {}
@@ -0,0 +1,21 @@
+// Check that `#[rustc_match_guard_fake_borrows(shallow)]` lets match guards mutate the value behind
+// a deref pattern, and warns that this makes the match unsound.
+//@ check-pass
+
+#![feature(deref_patterns, rustc_attrs)]
+#![allow(incomplete_features)]
+
+#[rustc_match_guard_fake_borrows(shallow)]
+fn main() {
+    let mut b = Box::new(false);
+    match b {
+        deref!(true) => {}
+        _ if {
+            *b = true;
+            false
+        } => {}
+        deref!(false) => {}
+        //~^ WARN match guards only shallowly borrow the place this deref pattern looks through
+        _ => {}
+    }
+}
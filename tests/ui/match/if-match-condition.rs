@@ -0,0 +1,106 @@
+// Check that an `if` or a `match` in condition position, which branches to the targets of the
+// condition through its branches and arms, takes the same branches, and drops the bindings and
+// temporaries of its arms on both paths.
+//@ run-pass
+
+use std::cell::RefCell;
+
+#[derive(Clone, Copy)]
+enum Kind {
+    A,
+    B,
+    C,
+}
+
+struct Noisy<'a>(&'static str, &'a RefCell<Vec<&'static str>>);
+
+impl Drop for Noisy<'_> {
+    fn drop(&mut self) {
+        self.1.borrow_mut().push(self.0);
+    }
+}
+
+fn match_literals(k: Kind, flag: bool) -> u32 {
+    if match k {
+        Kind::A => true,
+        Kind::B => false,
+        _ => flag,
+    } {
+        1
+    } else {
+        2
+    }
+}
+
+fn nested_if(x: u32, y: Option<u32>) -> u32 {
+    if if let Some(y) = y { y > x } else { x == 0 } { 1 } else { 2 }
+}
+
+fn and_chain(k: Kind, x: u32) -> bool {
+    x > 1 && match k {
+        Kind::A | Kind::B => x < 10,
+        Kind::C => { false }
+    }
+}
+
+fn guard(k: Kind, x: Option<u32>) -> u32 {
+    match x {
+        Some(x) if match k { Kind::A => x > 5, _ => true } => x,
+        Some(_) => 1,
+        None => 0,
+    }
+}
+
+fn drops(k: Kind, log: &RefCell<Vec<&'static str>>) -> u32 {
+    if match (k, Noisy("scrutinee", log)) {
+        (Kind::A, noisy) => noisy.0.len() > 100,
+        (Kind::B, _) => Noisy("temp", log).0.is_empty(),
+        (Kind::C, _) => true,
+    } {
+        log.borrow_mut().push("then");
+        1
+    } else {
+        log.borrow_mut().push("else");
+        2
+    }
+}
+
+fn main() {
+    assert_eq!(match_literals(Kind::A, false), 1);
+    assert_eq!(match_literals(Kind::B, true), 2);
+    assert_eq!(match_literals(Kind::C, true), 1);
+    assert_eq!(match_literals(Kind::C, false), 2);
+
+    assert_eq!(nested_if(3, Some(4)), 1);
+    assert_eq!(nested_if(3, Some(2)), 2);
+    assert_eq!(nested_if(0, None), 1);
+    assert_eq!(nested_if(3, None), 2);
+
+    assert!(and_chain(Kind::A, 5));
+    assert!(!and_chain(Kind::B, 10));
+    assert!(!and_chain(Kind::C, 5));
+    assert!(!and_chain(Kind::A, 1));
+
+    assert_eq!(guard(Kind::A, Some(6)), 6);
+    assert_eq!(guard(Kind::A, Some(3)), 1);
+    assert_eq!(guard(Kind::B, Some(3)), 3);
+    assert_eq!(guard(Kind::C, None), 0);
+
+    let mut counter = 0;
+    while match counter {
+        0..5 => true,
+        _ => false,
+    } {
+        counter += 1;
+    }
+    assert_eq!(counter, 5);
+
+    let log = RefCell::new(Vec::new());
+    assert_eq!(drops(Kind::A, &log), 2);
+    assert_eq!(drops(Kind::B, &log), 2);
+    assert_eq!(drops(Kind::C, &log), 1);
+    assert_eq!(
+        *log.borrow(),
+        ["scrutinee", "else", "temp", "scrutinee", "else", "scrutinee", "then"],
+    );
+}
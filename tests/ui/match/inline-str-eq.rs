@@ -0,0 +1,42 @@
+// Check that `match`es on `&str`s, whose equality tests with a few string constants compare the
+// length and then the bytes of the string, one by one for short constants and all at once for
+// longer ones, rather than calling `<str as PartialEq>::eq`, pick the same arms as `==` would,
+// including for strings that only differ in length or in a single byte.
+//@ run-pass
+
+fn short(s: &str) -> u32 {
+    match s {
+        "" => 0,
+        "a" => 1,
+        "ab" => 2,
+        "é" => 3,
+        _ => 4,
+    }
+}
+
+fn long(s: &str) -> u32 {
+    match s {
+        "a string longer than eight bytes" => 1,
+        "a string longer than eight bytez" => 2,
+        "exactly8" => 3,
+        _ => 4,
+    }
+}
+
+fn main() {
+    assert_eq!(short(""), 0);
+    assert_eq!(short("a"), 1);
+    assert_eq!(short("ab"), 2);
+    assert_eq!(short("é"), 3);
+    assert_eq!(short("b"), 4);
+    assert_eq!(short("abc"), 4);
+    assert_eq!(short("\u{e8}"), 4);
+
+    assert_eq!(long("a string longer than eight bytes"), 1);
+    assert_eq!(long("a string longer than eight bytez"), 2);
+    assert_eq!(long("exactly8"), 3);
+    assert_eq!(long("exactly9"), 4);
+    assert_eq!(long("a string longer than eight byte"), 4);
+    assert_eq!(long("a string longer than eight bytes!"), 4);
+    assert_eq!(long(&String::from("a string longer than eight bytes")), 1);
+}
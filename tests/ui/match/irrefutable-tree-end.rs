@@ -0,0 +1,50 @@
+// Check that the last arm of an exhaustive match, which gets no block to continue to when it has
+// no guard, is still reached from every test that leads to it.
+//@ run-pass
+//@ revisions: backtracking decision_tree
+//@[decision_tree] compile-flags: -Zmatch-lowering=decision-tree
+
+fn both(pair: (bool, bool)) -> u32 {
+    match pair {
+        (true, true) => 1,
+        (_, false) => 2,
+        (false, true) => 3,
+    }
+}
+
+fn guarded(x: Option<u32>) -> u32 {
+    match x {
+        Some(n) if n > 10 => 1,
+        Some(n) => n + 2,
+        None => 0,
+    }
+}
+
+fn or_last(x: Result<u32, u32>) -> u32 {
+    match x {
+        Ok(0) => 1,
+        Ok(n) | Err(n) => n * 2,
+    }
+}
+
+fn destructure(x: (u32, (u32, u32))) -> u32 {
+    let (a, (b, c)) = x;
+    a + b * c
+}
+
+fn main() {
+    assert_eq!(both((true, true)), 1);
+    assert_eq!(both((false, false)), 2);
+    assert_eq!(both((true, false)), 2);
+    assert_eq!(both((false, true)), 3);
+
+    assert_eq!(guarded(Some(11)), 1);
+    assert_eq!(guarded(Some(3)), 5);
+    assert_eq!(guarded(None), 0);
+
+    assert_eq!(or_last(Ok(0)), 1);
+    assert_eq!(or_last(Ok(4)), 8);
+    assert_eq!(or_last(Err(5)), 10);
+
+    assert_eq!(destructure((1, (2, 3))), 7);
+}
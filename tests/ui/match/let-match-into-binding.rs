@@ -0,0 +1,60 @@
+// Check that `let` statements with a plain binding, which build the result of their `match`
+// initializer directly in the binding, still bind the right value, with type ascriptions, `@ _`
+// subpatterns and large types.
+//@ run-pass
+
+#![allow(unused_mut)]
+
+#[derive(Debug, PartialEq)]
+struct Large([u64; 64]);
+
+fn large(n: u64) -> Large {
+    let x = match n {
+        0 => Large([0; 64]),
+        n => Large([n; 64]),
+    };
+    x
+}
+
+fn ascribed<'a>(s: &'a str, flag: bool) -> &'a str {
+    let x: &'a str = match flag {
+        true => s,
+        false => "",
+    };
+    x
+}
+
+fn wildcard_subpattern(x: Option<u32>) -> u32 {
+    let mut y @ _ = match x {
+        Some(n) => n,
+        None => 0,
+    };
+    y += 1;
+    y
+}
+
+fn nested(x: u8) -> String {
+    let s: String = match x {
+        0 => String::new(),
+        _ => {
+            let inner: String = match x % 2 {
+                0 => "even".to_string(),
+                _ => "odd".to_string(),
+            };
+            inner
+        }
+    };
+    s
+}
+
+fn main() {
+    assert_eq!(large(0), Large([0; 64]));
+    assert_eq!(large(7), Large([7; 64]));
+    assert_eq!(ascribed("abc", true), "abc");
+    assert_eq!(ascribed("abc", false), "");
+    assert_eq!(wildcard_subpattern(Some(4)), 5);
+    assert_eq!(wildcard_subpattern(None), 1);
+    assert_eq!(nested(0), "");
+    assert_eq!(nested(3), "odd");
+    assert_eq!(nested(4), "even");
+}
@@ -0,0 +1,49 @@
+// Check that `let` statements binding each field of a tuple literal, which write the fields
+// directly into the bindings, evaluate and drop them in the same order as through a tuple.
+//@ run-pass
+//@ needs-unwind
+
+use std::cell::RefCell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+struct Noisy<'a>(&'a RefCell<Vec<String>>, &'static str);
+
+impl Drop for Noisy<'_> {
+    fn drop(&mut self) {
+        self.0.borrow_mut().push(format!("drop {}", self.1));
+    }
+}
+
+fn make<'a>(log: &'a RefCell<Vec<String>>, name: &'static str) -> Noisy<'a> {
+    log.borrow_mut().push(format!("make {name}"));
+    Noisy(log, name)
+}
+
+fn fail<'a>(log: &'a RefCell<Vec<String>>) -> Noisy<'a> {
+    log.borrow_mut().push("fail".to_string());
+    panic!()
+}
+
+fn main() {
+    let log = RefCell::new(Vec::new());
+    {
+        let (a, b) = (make(&log, "a"), make(&log, "b"));
+        assert_eq!((a.1, b.1), ("a", "b"));
+    }
+    assert_eq!(*log.borrow(), ["make a", "make b", "drop b", "drop a"]);
+
+    log.borrow_mut().clear();
+    let (x, mut y): (u32, u32) = (1, 2);
+    y += x;
+    assert_eq!((x, y), (1, 3));
+
+    let (a, b) = (String::from("swap"), 7);
+    let (b, a) = (a, b);
+    assert_eq!((a, b.as_str()), (7, "swap"));
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let (_a, _b) = (make(&log, "a"), fail(&log));
+    }));
+    assert!(result.is_err());
+    assert_eq!(*log.borrow(), ["make a", "fail", "drop a"]);
+}
@@ -0,0 +1,63 @@
+//@ run-pass
+// Check that `f16` and `f128` constants are matched with `==` semantics, like `f32` and `f64`,
+// across several arms and next to range patterns.
+
+#![feature(f128)]
+#![feature(f16)]
+
+// FIXME(f16_f128): remove gates when ABI issues are resolved
+
+#[cfg(all(target_arch = "aarch64", target_os = "linux"))]
+fn classify_f16(x: f16) -> u32 {
+    match x {
+        0.0 => 0,
+        1.0 => 1,
+        0.5 => 2,
+        2.0..4.0 => 3,
+        -1.0 => 4,
+        _ => 5,
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_os = "linux"))]
+fn classify_f128(x: f128) -> u32 {
+    match x {
+        0.0 => 0,
+        1.0 => 1,
+        0.5 => 2,
+        2.0..4.0 => 3,
+        -1.0 => 4,
+        _ => 5,
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_os = "linux"))]
+fn check_f16() {
+    assert_eq!(classify_f16(0.0), 0);
+    assert_eq!(classify_f16(-0.0), 0);
+    assert_eq!(classify_f16(1.0), 1);
+    assert_eq!(classify_f16(0.5), 2);
+    assert_eq!(classify_f16(3.0), 3);
+    assert_eq!(classify_f16(4.0), 5);
+    assert_eq!(classify_f16(-1.0), 4);
+    assert_eq!(classify_f16(f16::NAN), 5);
+}
+
+#[cfg(all(target_arch = "aarch64", target_os = "linux"))]
+fn check_f128() {
+    assert_eq!(classify_f128(0.0), 0);
+    assert_eq!(classify_f128(-0.0), 0);
+    assert_eq!(classify_f128(1.0), 1);
+    assert_eq!(classify_f128(0.5), 2);
+    assert_eq!(classify_f128(3.0), 3);
+    assert_eq!(classify_f128(4.0), 5);
+    assert_eq!(classify_f128(-1.0), 4);
+    assert_eq!(classify_f128(f128::NAN), 5);
+}
+
+fn main() {
+    #[cfg(all(target_arch = "aarch64", target_os = "linux"))]
+    check_f16();
+    #[cfg(all(target_arch = "aarch64", target_os = "linux"))]
+    check_f128();
+}
@@ -0,0 +1,31 @@
+// Check that an invalid value reaching the otherwise block of an exhaustive match traps or aborts
+// with `-Zmatch-otherwise`, instead of being undefined behavior.
+//@ run-fail
+//@ revisions: trap abort
+//@[trap] compile-flags: -Zmatch-otherwise=trap
+//@[abort] compile-flags: -Zmatch-otherwise=abort
+//@[abort] error-pattern: invalid value reached the otherwise block of an exhaustive `match`
+//@ ignore-emscripten no processes
+
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+#[repr(u8)]
+enum Level {
+    Low = 0,
+    Medium = 1,
+    High = 2,
+}
+
+#[inline(never)]
+fn describe(level: Level) -> &'static str {
+    match level {
+        Level::Low => "low",
+        Level::Medium => "medium",
+        Level::High => "high",
+    }
+}
+
+fn main() {
+    let level = unsafe { std::mem::transmute::<u8, Level>(std::hint::black_box(7)) };
+    println!("{}", describe(level));
+}
@@ -0,0 +1,62 @@
+// Check that matches whose otherwise block only invalid values can reach, which trap or abort
+// there with `-Zmatch-otherwise`, pick the same arms as otherwise, including in `const fn`s, which
+// keep the block unreachable.
+//@ run-pass
+//@ revisions: unreachable trap abort
+//@[unreachable] compile-flags: -Zmatch-otherwise=unreachable
+//@[trap] compile-flags: -Zmatch-otherwise=trap
+//@[abort] compile-flags: -Zmatch-otherwise=abort
+
+#[derive(Clone, Copy)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+enum Shape {
+    Circle(u32),
+    Square(u32),
+}
+
+fn turn(d: Direction) -> Direction {
+    match d {
+        Direction::North => Direction::East,
+        Direction::East => Direction::South,
+        Direction::South => Direction::West,
+        Direction::West => Direction::North,
+    }
+}
+
+fn index(d: Direction) -> u32 {
+    match d {
+        Direction::North => 0,
+        Direction::East => 1,
+        Direction::South => 2,
+        Direction::West => 3,
+    }
+}
+
+fn size(s: &Shape) -> u32 {
+    let (Shape::Circle(n) | Shape::Square(n)) = *s;
+    n
+}
+
+const fn is_vertical(d: Direction) -> bool {
+    match d {
+        Direction::North | Direction::South => true,
+        Direction::East | Direction::West => false,
+    }
+}
+
+const VERTICAL: bool = is_vertical(Direction::South);
+
+fn main() {
+    assert_eq!(index(turn(Direction::North)), 1);
+    assert_eq!(index(turn(Direction::West)), 0);
+    assert_eq!(size(&Shape::Circle(3)), 3);
+    assert_eq!(size(&Shape::Square(4)), 4);
+    assert!(VERTICAL);
+    assert!(!is_vertical(Direction::East));
+}
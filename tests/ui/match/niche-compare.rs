@@ -0,0 +1,88 @@
+// Check that the variant of enums whose tag is a niche in their only scalar, which is tested by
+// comparing that scalar with the niche with `-Zmatch-niche-compare`, is found like before,
+// including for enums that aren't `Copy` and in const contexts, where this isn't done.
+//@ run-pass
+//@ revisions: switch compare
+//@[compare] compile-flags: -Zmatch-niche-compare
+
+use std::num::NonZeroU32;
+
+fn reference(x: Option<&u8>) -> u32 {
+    match x {
+        Some(&0) => 1,
+        Some(_) => 2,
+        None => 3,
+    }
+}
+
+fn boxed(x: Option<Box<u64>>) -> u64 {
+    match x {
+        Some(b) => *b,
+        None => 0,
+    }
+}
+
+fn non_zero(x: Option<NonZeroU32>) -> u32 {
+    match x {
+        Some(n) => n.get(),
+        None => 0,
+    }
+}
+
+fn boolean(x: Option<bool>) -> u32 {
+    match x {
+        Some(true) => 1,
+        Some(false) => 2,
+        None => 3,
+    }
+}
+
+fn character(x: Option<char>) -> u32 {
+    match x {
+        Some('a') => 1,
+        None => 2,
+        _ => 0,
+    }
+}
+
+fn result(x: Result<(), &str>) -> usize {
+    match x {
+        Ok(()) => 0,
+        Err(e) => e.len(),
+    }
+}
+
+const fn in_const(x: Option<&u8>) -> u8 {
+    match x {
+        Some(&b) => b,
+        None => 0,
+    }
+}
+
+const IN_CONST: u8 = in_const(Some(&7));
+
+fn main() {
+    assert_eq!(reference(Some(&0)), 1);
+    assert_eq!(reference(Some(&5)), 2);
+    assert_eq!(reference(None), 3);
+
+    assert_eq!(boxed(Some(Box::new(42))), 42);
+    assert_eq!(boxed(None), 0);
+
+    assert_eq!(non_zero(NonZeroU32::new(9)), 9);
+    assert_eq!(non_zero(None), 0);
+
+    assert_eq!(boolean(Some(true)), 1);
+    assert_eq!(boolean(Some(false)), 2);
+    assert_eq!(boolean(None), 3);
+
+    assert_eq!(character(Some('a')), 1);
+    assert_eq!(character(Some('b')), 0);
+    assert_eq!(character(None), 2);
+
+    assert_eq!(result(Ok(())), 0);
+    assert_eq!(result(Err("four")), 4);
+
+    assert_eq!(IN_CONST, 7);
+    assert_eq!(in_const(None), 0);
+}
@@ -0,0 +1,93 @@
+// Check that matches on enums whose discriminants are evenly spaced but don't start at zero, which
+// switch on the discriminant shifted down to `0, 1, 2, ...`, branch like before.
+//@ run-pass
+//@ revisions: switch compare
+//@[compare] compile-flags: -Zswitchint-threshold=100
+
+#[derive(Clone, Copy)]
+enum Offset {
+    A = 100,
+    B = 101,
+    C = 102,
+    D = 103,
+}
+
+#[derive(Clone, Copy)]
+#[repr(u16)]
+enum Flag {
+    Read = 4,
+    Write = 8,
+    Exec = 12,
+    Sync = 16,
+    Append = 20,
+}
+
+#[derive(Clone, Copy)]
+#[repr(i8)]
+enum Signed {
+    Min = -128,
+    Low = -64,
+    Zero = 0,
+    High = 64,
+}
+
+fn offset(x: Offset) -> u32 {
+    match x {
+        Offset::A => 1,
+        Offset::B | Offset::D => 2,
+        Offset::C => 3,
+    }
+}
+
+fn flag(x: Option<Flag>) -> u32 {
+    match x {
+        Some(Flag::Read) => 1,
+        Some(Flag::Write) => 2,
+        Some(Flag::Exec | Flag::Sync) => 3,
+        Some(Flag::Append) => 4,
+        None => 0,
+    }
+}
+
+fn signed(x: Signed) -> u32 {
+    match x {
+        Signed::Min => 1,
+        Signed::Low => 2,
+        Signed::Zero => 3,
+        Signed::High => 4,
+    }
+}
+
+fn partial(x: (Signed, bool)) -> u32 {
+    match x {
+        (Signed::Low, true) => 1,
+        (Signed::High, _) => 2,
+        (_, false) => 3,
+        _ => 0,
+    }
+}
+
+fn main() {
+    assert_eq!(offset(Offset::A), 1);
+    assert_eq!(offset(Offset::B), 2);
+    assert_eq!(offset(Offset::C), 3);
+    assert_eq!(offset(Offset::D), 2);
+
+    assert_eq!(flag(Some(Flag::Read)), 1);
+    assert_eq!(flag(Some(Flag::Write)), 2);
+    assert_eq!(flag(Some(Flag::Exec)), 3);
+    assert_eq!(flag(Some(Flag::Sync)), 3);
+    assert_eq!(flag(Some(Flag::Append)), 4);
+    assert_eq!(flag(None), 0);
+
+    assert_eq!(signed(Signed::Min), 1);
+    assert_eq!(signed(Signed::Low), 2);
+    assert_eq!(signed(Signed::Zero), 3);
+    assert_eq!(signed(Signed::High), 4);
+
+    assert_eq!(partial((Signed::Low, true)), 1);
+    assert_eq!(partial((Signed::Low, false)), 3);
+    assert_eq!(partial((Signed::High, false)), 2);
+    assert_eq!(partial((Signed::Min, false)), 3);
+    assert_eq!(partial((Signed::Zero, true)), 0);
+}
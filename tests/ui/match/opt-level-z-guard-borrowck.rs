@@ -0,0 +1,29 @@
+// Check that borrowck accepts and rejects the same guards of or-patterns with `-Copt-level=z`,
+// which lowers the guard of every or-pattern once for all its alternatives, as without it, where
+// the guard of an or-pattern with only two alternatives is lowered for each of them.
+//@ revisions: default size
+//@[size] compile-flags: -Copt-level=z
+
+fn moves_binding_in_arm(x: Result<String, String>) -> String {
+    match x {
+        Ok(s) | Err(s) if s.len() > 1 => s,
+        Ok(_) | Err(_) => String::new(),
+    }
+}
+
+fn mutates_other_place(x: Result<u8, u8>, mut calls: u32) -> u8 {
+    match x {
+        Ok(n) | Err(n) if { calls += 1; n > calls as u8 } => n,
+        _ => 0,
+    }
+}
+
+fn mutates_scrutinee(mut x: Result<u8, u8>) -> u8 {
+    match x {
+        Ok(n) | Err(n) if { x = Err(0); n > 1 } => n,
+        //~^ ERROR cannot assign `x` in match guard
+        _ => 0,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,62 @@
+// Check that matches lowered for size with `-Copt-level=z`, which test small switches with chains
+// of comparisons, lower the guard of each or-pattern once and make no copies of candidates, pick
+// the same arms as otherwise.
+//@ run-pass
+//@ revisions: default size size_decision_tree
+//@[size] compile-flags: -Copt-level=z
+//@[size_decision_tree] compile-flags: -Copt-level=z -Zmatch-lowering=decision-tree
+
+use std::cell::Cell;
+
+fn small_switch(x: u8) -> u32 {
+    match x {
+        0 => 10,
+        3 => 11,
+        7 | 9 => 12,
+        200 => 13,
+        _ => 14,
+    }
+}
+
+fn guarded(x: Result<u32, u32>, calls: &Cell<u32>) -> u32 {
+    match x {
+        Ok(n) | Err(n)
+            if {
+                calls.set(calls.get() + 1);
+                n > 10
+            } =>
+        {
+            n
+        }
+        Ok(_) => 1,
+        Err(_) => 2,
+    }
+}
+
+fn tuples(x: (bool, bool)) -> u32 {
+    match x {
+        (true, true) => 1,
+        (_, false) => 2,
+        (false, true) => 3,
+    }
+}
+
+fn main() {
+    assert_eq!(small_switch(0), 10);
+    assert_eq!(small_switch(3), 11);
+    assert_eq!(small_switch(9), 12);
+    assert_eq!(small_switch(200), 13);
+    assert_eq!(small_switch(1), 14);
+
+    let calls = Cell::new(0);
+    assert_eq!(guarded(Ok(20), &calls), 20);
+    assert_eq!(guarded(Err(30), &calls), 30);
+    assert_eq!(guarded(Ok(1), &calls), 1);
+    assert_eq!(guarded(Err(1), &calls), 2);
+    assert_eq!(calls.get(), 4);
+
+    assert_eq!(tuples((true, true)), 1);
+    assert_eq!(tuples((true, false)), 2);
+    assert_eq!(tuples((false, false)), 2);
+    assert_eq!(tuples((false, true)), 3);
+}
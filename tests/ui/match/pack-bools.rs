@@ -0,0 +1,89 @@
+// Check that `match`es on tuples of `bool`s lowered with `-Zmatch-pack-bools`, which dispatch on
+// the `bool`s packed into an integer with a table of all their values or with bit masks, pick the
+// first matching arm for every value of the `bool`s.
+//@ run-pass
+//@ revisions: default packed
+//@[packed] compile-flags: -Zmatch-pack-bools
+
+fn table(a: bool, b: bool, c: bool) -> u32 {
+    match (a, b, c) {
+        (true, false, _) => 1,
+        (_, true, true) | (false, false, _) => 2,
+        (true, ..) => 3,
+        _ => 4,
+    }
+}
+
+fn table_reference(a: bool, b: bool, c: bool) -> u32 {
+    if a && !b {
+        1
+    } else if (b && c) || (!a && !b) {
+        2
+    } else if a {
+        3
+    } else {
+        4
+    }
+}
+
+fn untested_fields(t: (bool, bool, bool, bool)) -> u32 {
+    match t {
+        (_, true, _, false) => 1,
+        (_, false, _, _) => 2,
+        (_, _, _, true) => 3,
+    }
+}
+
+type Ten = (bool, bool, bool, bool, bool, bool, bool, bool, bool, bool);
+
+fn masks(t: Ten) -> u32 {
+    match t {
+        (true, _, _, _, _, _, _, _, _, true) => 1,
+        (_, true, true, ..) | (.., true, false, false) => 2,
+        (false, false, false, false, false, false, false, false, false, false) => 3,
+        _ => 4,
+    }
+}
+
+fn masks_reference(t: Ten) -> u32 {
+    if t.0 && t.9 {
+        1
+    } else if (t.1 && t.2) || (t.7 && !t.8 && !t.9) {
+        2
+    } else if !(t.0 || t.1 || t.2 || t.3 || t.4 || t.5 || t.6 || t.7 || t.8 || t.9) {
+        3
+    } else {
+        4
+    }
+}
+
+fn bit(bits: u32, i: u32) -> bool {
+    bits & (1 << i) != 0
+}
+
+fn main() {
+    for bits in 0..8 {
+        let (a, b, c) = (bit(bits, 0), bit(bits, 1), bit(bits, 2));
+        assert_eq!(table(a, b, c), table_reference(a, b, c));
+    }
+
+    assert_eq!(untested_fields((false, true, false, false)), 1);
+    assert_eq!(untested_fields((true, false, true, true)), 2);
+    assert_eq!(untested_fields((false, true, true, true)), 3);
+
+    for bits in 0..1 << 10 {
+        let t = (
+            bit(bits, 0),
+            bit(bits, 1),
+            bit(bits, 2),
+            bit(bits, 3),
+            bit(bits, 4),
+            bit(bits, 5),
+            bit(bits, 6),
+            bit(bits, 7),
+            bit(bits, 8),
+            bit(bits, 9),
+        );
+        assert_eq!(masks(t), masks_reference(t));
+    }
+}
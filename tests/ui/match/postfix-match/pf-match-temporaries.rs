@@ -0,0 +1,55 @@
+// Check that the temporaries of the receiver of a `.match` live as long as the ones of the
+// scrutinee of the equivalent `match`: through the arms, until the end of the enclosing statement.
+//@ run-pass
+
+#![feature(postfix_match)]
+
+use std::cell::RefCell;
+
+struct Logged<'a>(&'a RefCell<Vec<&'static str>>, &'static str);
+
+impl Logged<'_> {
+    fn name(&self) -> &str {
+        self.1
+    }
+}
+
+impl Drop for Logged<'_> {
+    fn drop(&mut self) {
+        self.0.borrow_mut().push(self.1);
+    }
+}
+
+fn prefix(log: &RefCell<Vec<&'static str>>) {
+    match Logged(log, "scrutinee").name() {
+        "scrutinee" => log.borrow_mut().push("arm"),
+        _ => unreachable!(),
+    }
+    let len = match Logged(log, "let").name() {
+        name => name.len(),
+    };
+    log.borrow_mut().push(if len == 3 { "after" } else { "wrong" });
+}
+
+fn postfix(log: &RefCell<Vec<&'static str>>) {
+    Logged(log, "scrutinee").name().match {
+        "scrutinee" => log.borrow_mut().push("arm"),
+        _ => unreachable!(),
+    }
+    let len = Logged(log, "let").name().match {
+        name => name.len(),
+    };
+    log.borrow_mut().push(if len == 3 { "after" } else { "wrong" });
+}
+
+fn main() {
+    let expected = ["arm", "scrutinee", "let", "after"];
+
+    let log = RefCell::new(Vec::new());
+    prefix(&log);
+    assert_eq!(*log.borrow(), expected);
+
+    let log = RefCell::new(Vec::new());
+    postfix(&log);
+    assert_eq!(*log.borrow(), expected);
+}
@@ -0,0 +1,76 @@
+// Check that `?` and other early exits in match guards are lowered correctly, including when the
+// scrutinee is fake-borrowed and the guard has bindings that need to be dropped.
+//@ run-pass
+
+use std::cell::Cell;
+
+struct Noisy<'a>(&'a Cell<u32>);
+
+impl Drop for Noisy<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+fn parse(s: &str) -> Result<u32, ()> {
+    s.parse().map_err(|_| ())
+}
+
+fn guard_with_question_mark(x: &mut Option<u32>, limit: &str) -> Result<u32, ()> {
+    match x {
+        Some(n) if *n > parse(limit)? => Ok(*n),
+        Some(_) | None if parse(limit)? == 0 => Ok(0),
+        _ => Ok(1),
+    }
+}
+
+fn guard_with_return(x: (Option<String>, bool)) -> &'static str {
+    match x {
+        (Some(ref s), _) if s.is_empty() => "empty",
+        (Some(s), true) if { if s == "return" { return "early" } false } => "unreachable",
+        (Some(_), _) => "some",
+        (None, _) => "none",
+    }
+}
+
+fn guard_drops_on_exit(x: Option<Noisy<'_>>, exit: bool) -> Option<u32> {
+    match x {
+        Some(n) if n.0.get() == (if exit { None } else { Some(7) })? => Some(1),
+        Some(_) => Some(2),
+        None => Some(3),
+    }
+}
+
+fn guard_in_loop(xs: &[Option<u32>]) -> Option<u32> {
+    let mut sum = 0;
+    for x in xs {
+        match *x {
+            Some(n) if n > x.map(|_| 5)?.checked_sub(5)? => sum += n,
+            _ => continue,
+        }
+    }
+    Some(sum)
+}
+
+fn main() {
+    assert_eq!(guard_with_question_mark(&mut Some(5), "3"), Ok(5));
+    assert_eq!(guard_with_question_mark(&mut Some(5), "0"), Ok(5));
+    assert_eq!(guard_with_question_mark(&mut None, "0"), Ok(0));
+    assert_eq!(guard_with_question_mark(&mut None, "2"), Ok(1));
+    assert_eq!(guard_with_question_mark(&mut Some(5), "x"), Err(()));
+
+    assert_eq!(guard_with_return((Some(String::new()), true)), "empty");
+    assert_eq!(guard_with_return((Some("return".to_owned()), true)), "early");
+    assert_eq!(guard_with_return((Some("return".to_owned()), false)), "some");
+    assert_eq!(guard_with_return((None, true)), "none");
+
+    let drops = Cell::new(0);
+    assert_eq!(guard_drops_on_exit(Some(Noisy(&drops)), true), None);
+    assert_eq!(drops.get(), 1);
+    assert_eq!(guard_drops_on_exit(Some(Noisy(&drops)), false), Some(2));
+    assert_eq!(drops.get(), 2);
+    assert_eq!(guard_drops_on_exit(None, true), Some(3));
+    assert_eq!(drops.get(), 2);
+
+    assert_eq!(guard_in_loop(&[Some(1), None, Some(2)]), Some(3));
+}
@@ -0,0 +1,79 @@
+// Check that range patterns with two ends to compare, which are tested with the single comparison
+// `(x - lo) as uN <= hi - lo`, match the same values as the two comparisons `lo <= x && x <= hi`,
+// including for signed integers, `char`s, exclusive ends and ends close to the limits of the type.
+//@ run-pass
+
+fn signed(x: i32) -> u32 {
+    match x {
+        -100..=-10 => 1,
+        -9..0 => 2,
+        1..=1000 => 3,
+        i32::MIN..=-101 => 4,
+        _ => 5,
+    }
+}
+
+fn wide(x: i128) -> u32 {
+    match x {
+        -170141183460469231731687303715884105727..=-1 => 1,
+        1..=170141183460469231731687303715884105726 => 2,
+        _ => 3,
+    }
+}
+
+fn unsigned(x: u64) -> u32 {
+    match x {
+        10..=20 => 1,
+        1099511627776..u64::MAX => 2,
+        _ => 3,
+    }
+}
+
+fn class(c: char) -> u32 {
+    match c {
+        'a'..='z' => 1,
+        '0'..='9' => 2,
+        '\u{80}'..'\u{d800}' => 3,
+        _ => 4,
+    }
+}
+
+fn main() {
+    assert_eq!(signed(-101), 4);
+    assert_eq!(signed(-100), 1);
+    assert_eq!(signed(-10), 1);
+    assert_eq!(signed(-9), 2);
+    assert_eq!(signed(-1), 2);
+    assert_eq!(signed(0), 5);
+    assert_eq!(signed(1), 3);
+    assert_eq!(signed(1000), 3);
+    assert_eq!(signed(1001), 5);
+    assert_eq!(signed(i32::MIN), 4);
+    assert_eq!(signed(i32::MAX), 5);
+
+    assert_eq!(wide(i128::MIN), 3);
+    assert_eq!(wide(i128::MIN + 1), 1);
+    assert_eq!(wide(-1), 1);
+    assert_eq!(wide(0), 3);
+    assert_eq!(wide(1), 2);
+    assert_eq!(wide(i128::MAX - 1), 2);
+    assert_eq!(wide(i128::MAX), 3);
+
+    assert_eq!(unsigned(9), 3);
+    assert_eq!(unsigned(10), 1);
+    assert_eq!(unsigned(20), 1);
+    assert_eq!(unsigned(21), 3);
+    assert_eq!(unsigned(1 << 40), 2);
+    assert_eq!(unsigned(u64::MAX - 1), 2);
+    assert_eq!(unsigned(u64::MAX), 3);
+
+    assert_eq!(class('a'), 1);
+    assert_eq!(class('z'), 1);
+    assert_eq!(class('`'), 4);
+    assert_eq!(class('{'), 4);
+    assert_eq!(class('5'), 2);
+    assert_eq!(class('\u{80}'), 3);
+    assert_eq!(class('\u{d7ff}'), 3);
+    assert_eq!(class('\u{e000}'), 4);
+    assert_eq!(class(char::MAX), 4);
+}
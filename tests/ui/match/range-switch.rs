@@ -0,0 +1,105 @@
+// Check that range patterns on the same place, which are tested together with a binary search
+// over the ranges with `-Zmatch-range-switch`, match the same values as with one comparison per
+// range.
+//@ run-pass
+//@ revisions: ladder switch
+//@[switch] compile-flags: -Zmatch-range-switch
+
+fn buckets(x: u32) -> u32 {
+    match x {
+        0..10 => 1,
+        10..100 => 2,
+        100..1000 => 3,
+        1000 | 2000 => 4,
+        5000..=u32::MAX => 5,
+        _ => 0,
+    }
+}
+
+fn signed(x: i64) -> u32 {
+    match x {
+        i64::MIN..-1000 => 1,
+        -1000..=-1 => 2,
+        0 => 3,
+        1..=9 => 4,
+        100..=i64::MAX => 5,
+        _ => 0,
+    }
+}
+
+fn chars(c: char) -> u32 {
+    match c {
+        'a'..='z' => 1,
+        'A'..='Z' => 2,
+        '0'..='9' => 3,
+        '_' => 4,
+        'α'..='ω' => 5,
+        _ => 0,
+    }
+}
+
+fn overlapping(x: u16) -> u32 {
+    match x {
+        0..=99 => 1,
+        50..=150 => 2,
+        120 => 3,
+        200..=300 => 4,
+        _ => 0,
+    }
+}
+
+fn with_guard(x: (i32, bool)) -> u32 {
+    match x {
+        (0..=9, b) if b => 1,
+        (5..=20, _) => 2,
+        (-10..0, _) => 3,
+        (30 | 40, false) => 4,
+        _ => 0,
+    }
+}
+
+fn main() {
+    let expected = [(0, 1), (9, 1), (10, 2), (99, 2), (100, 3), (999, 3), (1000, 4), (1001, 0)];
+    for (x, result) in expected {
+        assert_eq!(buckets(x), result);
+    }
+    assert_eq!(buckets(2000), 4);
+    assert_eq!(buckets(4999), 0);
+    assert_eq!(buckets(5000), 5);
+    assert_eq!(buckets(u32::MAX), 5);
+
+    assert_eq!(signed(i64::MIN), 1);
+    assert_eq!(signed(-1001), 1);
+    assert_eq!(signed(-1000), 2);
+    assert_eq!(signed(-1), 2);
+    assert_eq!(signed(0), 3);
+    assert_eq!(signed(9), 4);
+    assert_eq!(signed(10), 0);
+    assert_eq!(signed(99), 0);
+    assert_eq!(signed(100), 5);
+    assert_eq!(signed(i64::MAX), 5);
+
+    assert_eq!(chars('a'), 1);
+    assert_eq!(chars('Z'), 2);
+    assert_eq!(chars('5'), 3);
+    assert_eq!(chars('_'), 4);
+    assert_eq!(chars('λ'), 5);
+    assert_eq!(chars('`'), 0);
+    assert_eq!(chars(char::MAX), 0);
+
+    assert_eq!(overlapping(0), 1);
+    assert_eq!(overlapping(75), 1);
+    assert_eq!(overlapping(100), 2);
+    assert_eq!(overlapping(120), 2);
+    assert_eq!(overlapping(151), 0);
+    assert_eq!(overlapping(250), 4);
+    assert_eq!(overlapping(u16::MAX), 0);
+
+    assert_eq!(with_guard((5, true)), 1);
+    assert_eq!(with_guard((5, false)), 2);
+    assert_eq!(with_guard((15, true)), 2);
+    assert_eq!(with_guard((-1, true)), 3);
+    assert_eq!(with_guard((-11, true)), 0);
+    assert_eq!(with_guard((40, false)), 4);
+    assert_eq!(with_guard((40, true)), 0);
+}
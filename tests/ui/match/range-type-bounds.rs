@@ -0,0 +1,62 @@
+// Check that ranges with an end at the smallest or largest value of their type, which is tested
+// with a single comparison, still match the right values.
+//@ run-pass
+
+fn unsigned(x: u32) -> u32 {
+    match x {
+        0..=9 => 0,
+        0x4000_0000..=u32::MAX => 1,
+        _ => 2,
+    }
+}
+
+fn signed(x: i64) -> u32 {
+    match x {
+        i64::MIN..-100 => 0,
+        100..=i64::MAX => 1,
+        _ => 2,
+    }
+}
+
+fn chars(c: char) -> u32 {
+    match c {
+        '\0'..='\x1f' => 0,
+        '\u{10000}'..=char::MAX => 1,
+        _ => 2,
+    }
+}
+
+fn excluded(x: u64) -> u32 {
+    match x {
+        10..u64::MAX => 0,
+        _ => 1,
+    }
+}
+
+fn main() {
+    assert_eq!(unsigned(0), 0);
+    assert_eq!(unsigned(9), 0);
+    assert_eq!(unsigned(10), 2);
+    assert_eq!(unsigned(0x3fff_ffff), 2);
+    assert_eq!(unsigned(0x4000_0000), 1);
+    assert_eq!(unsigned(u32::MAX), 1);
+
+    assert_eq!(signed(i64::MIN), 0);
+    assert_eq!(signed(-101), 0);
+    assert_eq!(signed(-100), 2);
+    assert_eq!(signed(99), 2);
+    assert_eq!(signed(100), 1);
+    assert_eq!(signed(i64::MAX), 1);
+
+    assert_eq!(chars('\0'), 0);
+    assert_eq!(chars('\x1f'), 0);
+    assert_eq!(chars(' '), 2);
+    assert_eq!(chars('\u{ffff}'), 2);
+    assert_eq!(chars('\u{10000}'), 1);
+    assert_eq!(chars(char::MAX), 1);
+
+    assert_eq!(excluded(9), 1);
+    assert_eq!(excluded(10), 0);
+    assert_eq!(excluded(u64::MAX - 1), 0);
+    assert_eq!(excluded(u64::MAX), 1);
+}
@@ -0,0 +1,79 @@
+// Check that the variant of enums without fields and with an explicit integer `repr`, which is
+// tested by switching on their integer value with `-Zmatch-repr-switch`, is found like before.
+//@ run-pass
+//@ revisions: discriminant repr
+//@[repr] compile-flags: -Zmatch-repr-switch
+
+#[derive(Clone, Copy)]
+#[repr(u8)]
+enum Opcode {
+    Nop = 0,
+    Load = 4,
+    Store = 8,
+    Jump = 12,
+    Halt = 255,
+}
+
+#[derive(Clone, Copy)]
+#[repr(i8)]
+enum Sign {
+    Negative = -1,
+    Zero = 0,
+    Positive = 1,
+}
+
+// Not `Copy`, so the discriminant is read as usual.
+#[repr(u16)]
+enum Owned {
+    A = 1000,
+    B = 2000,
+}
+
+fn opcode(op: Opcode) -> u32 {
+    match op {
+        Opcode::Nop => 0,
+        Opcode::Load | Opcode::Store => 1,
+        Opcode::Jump => 2,
+        Opcode::Halt => 3,
+    }
+}
+
+fn sign(pair: (Sign, Sign)) -> i32 {
+    match pair {
+        (Sign::Zero, _) | (_, Sign::Zero) => 0,
+        (Sign::Negative, Sign::Negative) | (Sign::Positive, Sign::Positive) => 1,
+        _ => -1,
+    }
+}
+
+fn owned(x: &Owned) -> u32 {
+    match x {
+        Owned::A => 1,
+        Owned::B => 2,
+    }
+}
+
+const fn in_const(op: Opcode) -> bool {
+    matches!(op, Opcode::Halt)
+}
+
+const HALTS: bool = in_const(Opcode::Halt);
+
+fn main() {
+    assert_eq!(opcode(Opcode::Nop), 0);
+    assert_eq!(opcode(Opcode::Load), 1);
+    assert_eq!(opcode(Opcode::Store), 1);
+    assert_eq!(opcode(Opcode::Jump), 2);
+    assert_eq!(opcode(Opcode::Halt), 3);
+
+    assert_eq!(sign((Sign::Zero, Sign::Negative)), 0);
+    assert_eq!(sign((Sign::Positive, Sign::Zero)), 0);
+    assert_eq!(sign((Sign::Negative, Sign::Negative)), 1);
+    assert_eq!(sign((Sign::Positive, Sign::Negative)), -1);
+
+    assert_eq!(owned(&Owned::A), 1);
+    assert_eq!(owned(&Owned::B), 2);
+
+    assert!(HALTS);
+    assert!(!in_const(Opcode::Nop));
+}
@@ -0,0 +1,74 @@
+// Check that switches on the scalar of a newtype with a restricted valid range, whose otherwise
+// edge is unreachable when there's a target for each valid value, still branch correctly.
+//@ run-pass
+#![feature(rustc_attrs)]
+#![allow(internal_features)]
+
+use std::num::NonZeroU8;
+
+#[rustc_layout_scalar_valid_range_start(1)]
+#[rustc_layout_scalar_valid_range_end(3)]
+#[derive(Clone, Copy)]
+struct Small(u8);
+
+// Valid values wrap around: 254, 255, 0 and 1.
+#[rustc_layout_scalar_valid_range_start(254)]
+#[rustc_layout_scalar_valid_range_end(1)]
+#[derive(Clone, Copy)]
+struct Wrapping(u8);
+
+const ONE: NonZeroU8 = unsafe { NonZeroU8::new_unchecked(1) };
+const TWO: NonZeroU8 = unsafe { NonZeroU8::new_unchecked(2) };
+
+fn small(x: Small) -> u32 {
+    match x {
+        Small(1) => 1,
+        Small(2) => 2,
+        Small(3) => 3,
+        _ => 0,
+    }
+}
+
+fn partial(x: Small) -> u32 {
+    match x {
+        Small(1 | 3) => 1,
+        _ => 0,
+    }
+}
+
+fn wrapping(x: Wrapping) -> u32 {
+    match x {
+        Wrapping(254) => 1,
+        Wrapping(255) => 2,
+        Wrapping(0) => 3,
+        Wrapping(1) => 4,
+        _ => 0,
+    }
+}
+
+fn non_zero(x: NonZeroU8) -> u32 {
+    match x {
+        ONE => 1,
+        TWO => 2,
+        _ => 0,
+    }
+}
+
+fn main() {
+    let small_values = unsafe { [Small(1), Small(2), Small(3)] };
+    for (i, x) in small_values.into_iter().enumerate() {
+        assert_eq!(small(x), i as u32 + 1);
+    }
+    assert_eq!(partial(small_values[0]), 1);
+    assert_eq!(partial(small_values[1]), 0);
+    assert_eq!(partial(small_values[2]), 1);
+
+    let wrapping_values = unsafe { [Wrapping(254), Wrapping(255), Wrapping(0), Wrapping(1)] };
+    for (i, x) in wrapping_values.into_iter().enumerate() {
+        assert_eq!(wrapping(x), i as u32 + 1);
+    }
+
+    assert_eq!(non_zero(ONE), 1);
+    assert_eq!(non_zero(TWO), 2);
+    assert_eq!(non_zero(NonZeroU8::MAX), 0);
+}
@@ -0,0 +1,90 @@
+//@ run-pass
+//@ needs-unwind
+// Check that arms with the same body, which is then only lowered once, still evaluate it on each
+// path, and that arms with bindings, or bodies that differ, keep their own.
+
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+
+#[derive(Debug, PartialEq)]
+enum Kind {
+    Digit,
+    Space,
+    Other,
+}
+
+macro_rules! classify {
+    ($c:expr; $($kind:ident: $($p:pat),*;)*) => {
+        match $c {
+            $($($p => Kind::$kind,)*)*
+            _ => Kind::Other,
+        }
+    };
+}
+
+fn kind(c: char) -> Kind {
+    classify!(c;
+        Digit: '0', '1', '2', '3';
+        Space: ' ', '\t';
+    )
+}
+
+fn count(calls: &Cell<u32>, x: Option<u8>) -> u32 {
+    let mut bump = || {
+        calls.set(calls.get() + 1);
+        calls.get()
+    };
+    match x {
+        Some(0) => bump(),
+        Some(1) => bump(),
+        Some(n) => u32::from(n),
+        None => bump(),
+    }
+}
+
+fn guarded(x: u32, flag: bool) -> Option<u32> {
+    match x {
+        0 if flag => None,
+        1 => None,
+        2 => return Some(2),
+        3 => return Some(2),
+        _ => Some(x),
+    }
+}
+
+macro_rules! unreachable_arms {
+    ($x:expr; $($p:pat),*) => {
+        match $x {
+            $($p => unreachable!(),)*
+            _ => 0,
+        }
+    };
+}
+
+fn unreachable(x: u8) -> u8 {
+    unreachable_arms!(x; 1, 2, 3)
+}
+
+fn main() {
+    assert_eq!(kind('2'), Kind::Digit);
+    assert_eq!(kind('0'), Kind::Digit);
+    assert_eq!(kind('\t'), Kind::Space);
+    assert_eq!(kind('x'), Kind::Other);
+
+    let calls = Cell::new(0);
+    assert_eq!(count(&calls, Some(0)), 1);
+    assert_eq!(count(&calls, Some(1)), 2);
+    assert_eq!(count(&calls, None), 3);
+    assert_eq!(count(&calls, Some(7)), 7);
+    assert_eq!(calls.get(), 3);
+
+    assert_eq!(guarded(0, true), None);
+    assert_eq!(guarded(0, false), Some(0));
+    assert_eq!(guarded(1, false), None);
+    assert_eq!(guarded(2, false), Some(2));
+    assert_eq!(guarded(3, false), Some(2));
+    assert_eq!(guarded(4, false), Some(4));
+
+    assert_eq!(unreachable(0), 0);
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| unreachable(2))).is_err());
+}
@@ -0,0 +1,69 @@
+// Check that `match`es with as few as two arms that only panic with a constant message, which
+// share a single call to `panic` lowered in the scope of the `match` when the location of panics
+// is hidden, build and panic with the message of the arm taken, including in closures, with
+// guards, in nested `match`es and next to arms with temporaries to drop.
+//@ run-pass
+//@ needs-unwind
+//@ revisions: location none
+//@[none] compile-flags: -Zlocation-detail=none
+
+use std::panic;
+
+fn two(x: u8) -> u8 {
+    match x {
+        0 => unreachable!(),
+        1 => todo!(),
+        _ => x,
+    }
+}
+
+fn guarded(x: u8, allow: bool) -> String {
+    match x {
+        0 if allow => String::from("zero"),
+        0 => unimplemented!(),
+        1 => String::from("one").to_uppercase(),
+        _ => unreachable!(),
+    }
+}
+
+fn nested(x: Option<u8>) -> u8 {
+    match x {
+        Some(y) => match y {
+            0 => unreachable!(),
+            1 => todo!(),
+            _ => y,
+        },
+        None => unimplemented!(),
+    }
+}
+
+fn message<T>(f: impl FnOnce() -> T + panic::UnwindSafe) -> String {
+    let payload = panic::catch_unwind(f).map(|_| ()).unwrap_err();
+    payload.downcast_ref::<&str>().unwrap().to_string()
+}
+
+fn main() {
+    panic::set_hook(Box::new(|_| {}));
+
+    assert_eq!(two(2), 2);
+    assert_eq!(message(|| two(0)), "internal error: entered unreachable code");
+    assert_eq!(message(|| two(1)), "not yet implemented");
+
+    assert_eq!(guarded(0, true), "zero");
+    assert_eq!(guarded(1, false), "ONE");
+    assert_eq!(message(|| guarded(0, false)), "not implemented");
+    assert_eq!(message(|| guarded(2, true)), "internal error: entered unreachable code");
+
+    assert_eq!(nested(Some(2)), 2);
+    assert_eq!(message(|| nested(Some(0))), "internal error: entered unreachable code");
+    assert_eq!(message(|| nested(Some(1))), "not yet implemented");
+    assert_eq!(message(|| nested(None)), "not implemented");
+
+    let closure = |x: u8| match x {
+        0 => unreachable!(),
+        1 => todo!(),
+        _ => x + 1,
+    };
+    assert_eq!(closure(2), 3);
+    assert_eq!(message(move || closure(1)), "not yet implemented");
+}
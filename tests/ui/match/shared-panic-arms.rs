@@ -0,0 +1,42 @@
+// Check that the arms of a `match` that only panic with a constant message, which share a single
+// call to `panic` when the location of panics is hidden, still panic with their own message.
+//@ run-pass
+//@ needs-unwind
+//@ revisions: location none
+//@[none] compile-flags: -Zlocation-detail=none
+
+use std::panic;
+
+#[derive(Clone, Copy)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Nop,
+}
+
+fn apply(op: Op, a: u32, b: u32) -> u32 {
+    match op {
+        Op::Add => a + b,
+        Op::Sub => a - b,
+        Op::Mul => todo!(),
+        Op::Div => unimplemented!(),
+        Op::Nop => unreachable!(),
+    }
+}
+
+fn message(op: Op) -> String {
+    let payload = panic::catch_unwind(|| apply(op, 1, 2)).unwrap_err();
+    payload.downcast_ref::<&str>().unwrap().to_string()
+}
+
+fn main() {
+    panic::set_hook(Box::new(|_| {}));
+
+    assert_eq!(apply(Op::Add, 1, 2), 3);
+    assert_eq!(apply(Op::Sub, 3, 1), 2);
+    assert_eq!(message(Op::Mul), "not yet implemented");
+    assert_eq!(message(Op::Div), "not implemented");
+    assert_eq!(message(Op::Nop), "internal error: entered unreachable code");
+}
@@ -0,0 +1,65 @@
+// Check that consecutive match arms without bindings that share the same side-effect free guard,
+// which is evaluated once for all of them, still select the same arms.
+//@ run-pass
+
+const DEBUG: bool = true;
+const QUIET: bool = false;
+
+struct Config {
+    verbose: bool,
+    level: u8,
+}
+
+macro_rules! debug_arms {
+    ($x:expr, $($pat:pat => $e:expr),*) => {
+        match $x {
+            $($pat if DEBUG && !QUIET => $e,)*
+            _ => 0,
+        }
+    };
+}
+
+fn macro_arms(x: u8) -> u32 {
+    debug_arms!(x, 0 => 10, 1 => 11, 2 => 12)
+}
+
+fn fields(x: Option<u8>, config: &Config) -> u32 {
+    match x {
+        None if config.verbose && config.level >= 2 => 1,
+        Some(0) if config.verbose && config.level >= 2 => 2,
+        Some(_) if config.verbose && config.level >= 2 => 3,
+        Some(1) => 4,
+        _ => 5,
+    }
+}
+
+fn different_guards(x: u8, a: bool, b: bool) -> u32 {
+    match x {
+        0 if a => 1,
+        0 if b => 2,
+        _ if a => 3,
+        _ if a == b => 4,
+        _ => 5,
+    }
+}
+
+fn main() {
+    assert_eq!(macro_arms(0), 10);
+    assert_eq!(macro_arms(2), 12);
+    assert_eq!(macro_arms(3), 0);
+
+    let loud = Config { verbose: true, level: 2 };
+    let quiet = Config { verbose: true, level: 1 };
+    assert_eq!(fields(None, &loud), 1);
+    assert_eq!(fields(Some(0), &loud), 2);
+    assert_eq!(fields(Some(1), &loud), 3);
+    assert_eq!(fields(None, &quiet), 5);
+    assert_eq!(fields(Some(0), &quiet), 5);
+    assert_eq!(fields(Some(1), &quiet), 4);
+
+    assert_eq!(different_guards(0, true, false), 1);
+    assert_eq!(different_guards(0, false, true), 2);
+    assert_eq!(different_guards(1, true, false), 3);
+    assert_eq!(different_guards(0, false, false), 4);
+    assert_eq!(different_guards(1, false, true), 5);
+}
@@ -0,0 +1,37 @@
+// Check that the matches of a body, which all point the edges of their match trees that can't be
+// taken to the same unreachable block, still pick the right arms.
+//@ run-pass
+
+#[derive(Clone, Copy)]
+enum Shape {
+    Circle(u32),
+    Square(u32),
+    Line,
+}
+
+fn area(shapes: &[Shape]) -> u32 {
+    let mut total = 0;
+    for &shape in shapes {
+        total += match shape {
+            Shape::Circle(r) if r > 10 => 300,
+            Shape::Circle(r) => 3 * r * r,
+            Shape::Square(s) => s * s,
+            Shape::Line => 0,
+        };
+        let bonus = match (shape, total) {
+            (Shape::Circle(_) | Shape::Square(0), 0) => 1,
+            (Shape::Square(_), _) | (Shape::Line, 0) => 2,
+            _ => 0,
+        };
+        total += bonus;
+    }
+    total
+}
+
+fn main() {
+    assert_eq!(area(&[]), 0);
+    assert_eq!(area(&[Shape::Circle(1)]), 3);
+    assert_eq!(area(&[Shape::Circle(11)]), 300);
+    assert_eq!(area(&[Shape::Square(2), Shape::Line]), 6);
+    assert_eq!(area(&[Shape::Line, Shape::Square(0)]), 4);
+}
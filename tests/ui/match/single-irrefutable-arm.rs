@@ -0,0 +1,76 @@
+// Check that matches with a single arm without a guard, whose pattern needs no test and which are
+// bound right where the match starts, bind, borrow and drop like other matches.
+//@ run-pass
+
+use std::cell::RefCell;
+
+struct Noisy<'a>(&'a RefCell<Vec<&'static str>>, &'static str);
+
+impl Drop for Noisy<'_> {
+    fn drop(&mut self) {
+        self.0.borrow_mut().push(self.1);
+    }
+}
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+macro_rules! sum_pair {
+    ($e:expr) => {
+        match $e {
+            (a, b) => a + b,
+        }
+    };
+}
+
+fn swap(p: Point) -> Point {
+    match p {
+        Point { x, y } => Point { x: y, y: x },
+    }
+}
+
+fn bump(pair: &mut (u32, (u32, u32))) {
+    match pair {
+        (a, (_, c)) => {
+            *a += 1;
+            *c += 10;
+        }
+    }
+}
+
+fn nested(v: (u8, (String, [u8; 2]))) -> (u8, usize, u8) {
+    match v {
+        (a, (ref s, [b, _])) => (a, s.len(), b),
+    }
+}
+
+fn whole(p: (u8, u8)) -> u8 {
+    match p {
+        all @ (a, _) => all.1 + a,
+    }
+}
+
+fn main() {
+    assert_eq!(sum_pair!((1, 2)), 3);
+
+    let p = swap(Point { x: 1, y: 2 });
+    assert_eq!((p.x, p.y), (2, 1));
+
+    let mut pair = (0, (5, 6));
+    bump(&mut pair);
+    assert_eq!(pair, (1, (5, 16)));
+
+    assert_eq!(nested((1, ("abc".to_string(), [7, 8]))), (1, 3, 7));
+    assert_eq!(whole((3, 4)), 7);
+
+    let log = RefCell::new(Vec::new());
+    match (Noisy(&log, "first"), Noisy(&log, "second")) {
+        (_, second) => {
+            log.borrow_mut().push("body");
+            drop(second);
+        }
+    }
+    assert_eq!(*log.borrow(), ["body", "second", "first"]);
+}
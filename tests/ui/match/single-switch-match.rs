@@ -0,0 +1,89 @@
+// Check that matches whose arms are only told apart by the discriminant of an enum, like the ones
+// of derives, which are lowered to a single switch, bind and pick the same arms as otherwise,
+// including with the options that make them go through a match tree instead.
+//@ run-pass
+//@ revisions: default tree
+//@[tree] compile-flags: -Zswitchint-threshold=3 -Zmatch-binary-search=2
+//@[tree] compile-flags: -Zmatch-elide-moved-binding-storage
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+enum Shape {
+    Circle(u32),
+    Rect { width: u32, height: u32 },
+    Named(String, Box<Shape>),
+    Empty,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(i8)]
+enum Level {
+    Low = -5,
+    Mid = 0,
+    High = 100,
+}
+
+fn area(shape: &Shape) -> u32 {
+    match shape {
+        Shape::Circle(r) => 3 * r * r,
+        Shape::Rect { width, height } => width * height,
+        Shape::Named(_, inner) => area(inner),
+        Shape::Empty => 0,
+    }
+}
+
+fn rename(shape: &mut Shape) {
+    match shape {
+        Shape::Named(name, _) => name.push('!'),
+        _ => {}
+    }
+}
+
+fn into_name(shape: Shape) -> Option<String> {
+    match shape {
+        Shape::Named(name, _) => Some(name),
+        Shape::Circle(_) | Shape::Rect { .. } => None,
+        Shape::Empty => None,
+    }
+}
+
+fn level(l: Level) -> &'static str {
+    match l {
+        Level::Low | Level::Mid => "low",
+        Level::High => "high",
+    }
+}
+
+fn hash(shape: &Shape) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    shape.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn main() {
+    let rect = Shape::Rect { width: 2, height: 3 };
+    let named = Shape::Named("n".to_string(), Box::new(Shape::Circle(1)));
+    assert_eq!(area(&rect), 6);
+    assert_eq!(area(&named), 3);
+    assert_eq!(area(&Shape::Empty), 0);
+
+    let mut renamed = named.clone();
+    rename(&mut renamed);
+    assert_eq!(into_name(renamed), Some("n!".to_string()));
+    assert_eq!(into_name(rect.clone()), None);
+
+    assert_eq!(format!("{rect:?}"), "Rect { width: 2, height: 3 }");
+    assert_eq!(format!("{:?}", Shape::Empty), "Empty");
+    assert_eq!(named.clone(), named);
+    assert_ne!(rect, Shape::Circle(2));
+    assert!(Shape::Circle(9) < rect);
+    assert_eq!(hash(&named), hash(&named.clone()));
+    assert_ne!(hash(&Shape::Circle(1)), hash(&Shape::Empty));
+
+    assert_eq!(level(Level::Low), "low");
+    assert_eq!(level(Level::Mid), "low");
+    assert_eq!(level(Level::High), "high");
+    assert_eq!(format!("{:?}", Level::High), "High");
+}
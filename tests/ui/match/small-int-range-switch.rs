@@ -0,0 +1,77 @@
+// Check that range patterns on 8-bit integers, which are tested with a `SwitchInt` over every
+// value in the range, behave like the comparisons they replace.
+//@ run-pass
+
+fn unsigned(x: u8) -> u32 {
+    match x {
+        0 => 0,
+        1..=9 => 1,
+        10..20 => 2,
+        20 => 3,
+        200.. => 4,
+        _ => 5,
+    }
+}
+
+fn signed(x: i8) -> u32 {
+    match x {
+        ..-100 => 0,
+        -3..=3 => 1,
+        4 => 2,
+        100..=i8::MAX => 3,
+        _ => 4,
+    }
+}
+
+fn overlapping(x: u8) -> u32 {
+    match x {
+        5 => 0,
+        0..=10 => 1,
+        5..=15 => 2,
+        16 | 17 => 3,
+        _ => 4,
+    }
+}
+
+fn with_guard(x: (u8, bool)) -> u32 {
+    match x {
+        (0..=9, b) if b => 0,
+        (5..=14, _) => 1,
+        (0..=4, false) => 2,
+        _ => 3,
+    }
+}
+
+fn main() {
+    assert_eq!(unsigned(0), 0);
+    assert_eq!(unsigned(9), 1);
+    assert_eq!(unsigned(10), 2);
+    assert_eq!(unsigned(19), 2);
+    assert_eq!(unsigned(20), 3);
+    assert_eq!(unsigned(21), 5);
+    assert_eq!(unsigned(200), 4);
+    assert_eq!(unsigned(255), 4);
+
+    assert_eq!(signed(i8::MIN), 0);
+    assert_eq!(signed(-100), 4);
+    assert_eq!(signed(-3), 1);
+    assert_eq!(signed(0), 1);
+    assert_eq!(signed(3), 1);
+    assert_eq!(signed(4), 2);
+    assert_eq!(signed(99), 4);
+    assert_eq!(signed(i8::MAX), 3);
+
+    assert_eq!(overlapping(5), 0);
+    assert_eq!(overlapping(0), 1);
+    assert_eq!(overlapping(10), 1);
+    assert_eq!(overlapping(11), 2);
+    assert_eq!(overlapping(15), 2);
+    assert_eq!(overlapping(17), 3);
+    assert_eq!(overlapping(18), 4);
+
+    assert_eq!(with_guard((3, true)), 0);
+    assert_eq!(with_guard((7, false)), 1);
+    assert_eq!(with_guard((12, true)), 1);
+    assert_eq!(with_guard((3, false)), 2);
+    assert_eq!(with_guard((20, true)), 3);
+}
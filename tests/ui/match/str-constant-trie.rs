@@ -0,0 +1,77 @@
+// Check that matching many string constants by switching on their length and then on their bytes
+// behaves like comparing them one after the other.
+//@ run-pass
+
+const ELSE: &str = "else";
+
+fn keyword(s: &str) -> u32 {
+    match s {
+        "if" => 1,
+        "in" => 2,
+        "for" => 3,
+        "fn" => 4,
+        ELSE => 5,
+        "" => 6,
+        "é" => 7,
+        _ => 0,
+    }
+}
+
+fn with_guard(x: (&str, bool)) -> u32 {
+    match x {
+        ("let", true) => 1,
+        ("let", false) => 2,
+        ("loop", _) if x.1 => 3,
+        ("lo", _) => 4,
+        ("l", true) => 5,
+        _ => 0,
+    }
+}
+
+fn with_wildcard(x: (&str, u8)) -> u32 {
+    match x {
+        ("a", _) => 1,
+        ("ab", _) => 2,
+        ("abc", _) => 3,
+        ("abd", _) => 4,
+        (_, 0) => 5,
+        ("b", _) => 6,
+        ("ba", _) => 7,
+        ("bb", _) => 8,
+        ("bc", _) => 9,
+        _ => 0,
+    }
+}
+
+fn main() {
+    assert_eq!(keyword("if"), 1);
+    assert_eq!(keyword("in"), 2);
+    assert_eq!(keyword("for"), 3);
+    assert_eq!(keyword("fn"), 4);
+    assert_eq!(keyword("else"), 5);
+    assert_eq!(keyword(""), 6);
+    assert_eq!(keyword("é"), 7);
+    assert_eq!(keyword("e"), 0);
+    assert_eq!(keyword("fo"), 0);
+    assert_eq!(keyword("fork"), 0);
+    assert_eq!(keyword("elsa"), 0);
+
+    assert_eq!(with_guard(("let", true)), 1);
+    assert_eq!(with_guard(("let", false)), 2);
+    assert_eq!(with_guard(("loop", true)), 3);
+    assert_eq!(with_guard(("loop", false)), 0);
+    assert_eq!(with_guard(("lo", false)), 4);
+    assert_eq!(with_guard(("l", true)), 5);
+    assert_eq!(with_guard(("l", false)), 0);
+
+    assert_eq!(with_wildcard(("a", 0)), 1);
+    assert_eq!(with_wildcard(("ab", 1)), 2);
+    assert_eq!(with_wildcard(("abc", 1)), 3);
+    assert_eq!(with_wildcard(("abd", 1)), 4);
+    assert_eq!(with_wildcard(("abe", 0)), 5);
+    assert_eq!(with_wildcard(("b", 0)), 5);
+    assert_eq!(with_wildcard(("b", 1)), 6);
+    assert_eq!(with_wildcard(("ba", 1)), 7);
+    assert_eq!(with_wildcard(("bc", 1)), 9);
+    assert_eq!(with_wildcard(("bd", 1)), 0);
+}
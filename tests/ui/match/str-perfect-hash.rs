@@ -0,0 +1,75 @@
+// Check that matching string constants with a perfect hash of the string, followed by a single
+// comparison, behaves like comparing them one after the other.
+//@ run-pass
+//@ compile-flags: -Zmatch-perfect-hash=4
+
+fn method(s: &str) -> u32 {
+    match s {
+        "GET" => 1,
+        "PUT" => 2,
+        "POST" => 3,
+        "HEAD" => 4,
+        "DELETE" => 5,
+        "PATCH" => 6,
+        "OPTIONS" => 7,
+        "CONNECT" => 8,
+        _ => 0,
+    }
+}
+
+// The empty string only leaves the length to hash, which doesn't tell these constants apart.
+fn lengths(s: &str) -> u32 {
+    match s {
+        "" => 1,
+        "a" => 2,
+        "b" => 3,
+        "cd" => 4,
+        "ef" => 5,
+        _ => 0,
+    }
+}
+
+fn with_guard(x: (&str, bool)) -> u32 {
+    match x {
+        ("let", true) => 1,
+        ("loop", _) if x.1 => 2,
+        ("let", false) => 3,
+        ("match", _) => 4,
+        ("l", true) => 5,
+        ("while", false) => 6,
+        _ => 0,
+    }
+}
+
+fn main() {
+    assert_eq!(method("GET"), 1);
+    assert_eq!(method("PUT"), 2);
+    assert_eq!(method("POST"), 3);
+    assert_eq!(method("HEAD"), 4);
+    assert_eq!(method("DELETE"), 5);
+    assert_eq!(method("PATCH"), 6);
+    assert_eq!(method("OPTIONS"), 7);
+    assert_eq!(method("CONNECT"), 8);
+    assert_eq!(method(""), 0);
+    assert_eq!(method("G"), 0);
+    assert_eq!(method("GOT"), 0);
+    assert_eq!(method("HEAT"), 0);
+    assert_eq!(method("get"), 0);
+
+    assert_eq!(lengths(""), 1);
+    assert_eq!(lengths("a"), 2);
+    assert_eq!(lengths("b"), 3);
+    assert_eq!(lengths("cd"), 4);
+    assert_eq!(lengths("ef"), 5);
+    assert_eq!(lengths("c"), 0);
+
+    assert_eq!(with_guard(("let", true)), 1);
+    assert_eq!(with_guard(("loop", true)), 2);
+    assert_eq!(with_guard(("loop", false)), 0);
+    assert_eq!(with_guard(("let", false)), 3);
+    assert_eq!(with_guard(("match", false)), 4);
+    assert_eq!(with_guard(("l", true)), 5);
+    assert_eq!(with_guard(("l", false)), 0);
+    assert_eq!(with_guard(("while", false)), 6);
+    assert_eq!(with_guard(("while", true)), 0);
+}
@@ -0,0 +1,92 @@
+// Check that the switches of match tests, which are lowered to chains of comparisons with
+// `-Zswitchint-threshold`, branch like a single `SwitchInt` would.
+//@ run-pass
+//@ compile-flags: -Zswitchint-threshold=100
+
+#[derive(Clone, Copy)]
+#[repr(i8)]
+enum Level {
+    Low = -10,
+    Mid = 0,
+    High = 10,
+    Max = i8::MAX,
+}
+
+fn level(x: Option<Level>) -> u32 {
+    match x {
+        Some(Level::Low) => 1,
+        Some(Level::Mid | Level::Max) => 2,
+        Some(Level::High) => 3,
+        None => 0,
+    }
+}
+
+fn ints(x: i64) -> u32 {
+    match x {
+        i64::MIN => 1,
+        -1 => 2,
+        0 => 3,
+        7 | 8 => 4,
+        i64::MAX => 5,
+        _ => 0,
+    }
+}
+
+fn bytes(x: u8) -> u32 {
+    match x {
+        0..=9 => 1,
+        b'a' | b'z' => 2,
+        255 => 3,
+        _ => 0,
+    }
+}
+
+fn keyword(s: &str) -> u32 {
+    match s {
+        "if" => 1,
+        "in" => 2,
+        "for" => 3,
+        "fn" => 4,
+        _ => 0,
+    }
+}
+
+fn slices(s: &[u8]) -> u32 {
+    match s {
+        [] => 1,
+        [_] => 2,
+        [_, _, _] => 3,
+        _ => 0,
+    }
+}
+
+fn main() {
+    assert_eq!(level(Some(Level::Low)), 1);
+    assert_eq!(level(Some(Level::Mid)), 2);
+    assert_eq!(level(Some(Level::High)), 3);
+    assert_eq!(level(Some(Level::Max)), 2);
+    assert_eq!(level(None), 0);
+
+    assert_eq!(ints(i64::MIN), 1);
+    assert_eq!(ints(-1), 2);
+    assert_eq!(ints(0), 3);
+    assert_eq!(ints(8), 4);
+    assert_eq!(ints(9), 0);
+    assert_eq!(ints(i64::MAX), 5);
+
+    assert_eq!(bytes(0), 1);
+    assert_eq!(bytes(9), 1);
+    assert_eq!(bytes(10), 0);
+    assert_eq!(bytes(b'z'), 2);
+    assert_eq!(bytes(255), 3);
+
+    assert_eq!(keyword("if"), 1);
+    assert_eq!(keyword("for"), 3);
+    assert_eq!(keyword("fn"), 4);
+    assert_eq!(keyword("fo"), 0);
+
+    assert_eq!(slices(&[]), 1);
+    assert_eq!(slices(&[1]), 2);
+    assert_eq!(slices(&[1, 2]), 0);
+    assert_eq!(slices(&[1, 2, 3]), 3);
+}
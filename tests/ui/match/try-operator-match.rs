@@ -0,0 +1,51 @@
+// Check that the `match` that `?` desugars to, which is lowered to a switch on the `ControlFlow`
+// directly, passes on the output and converts the residual like before.
+//@ run-pass
+
+use std::num::ParseIntError;
+
+#[derive(Debug, PartialEq)]
+enum Error {
+    Parse,
+    Negative(i64),
+}
+
+impl From<ParseIntError> for Error {
+    fn from(_: ParseIntError) -> Self {
+        Error::Parse
+    }
+}
+
+fn parse(s: &str) -> Result<i64, Error> {
+    let n: i64 = s.parse()?;
+    if n < 0 {
+        Err(Error::Negative(n))?;
+    }
+    Ok(n)
+}
+
+fn sum(a: &str, b: &str) -> Result<i64, Error> {
+    Ok(parse(a)? + parse(b)?)
+}
+
+fn first_char_upper(s: &str) -> Option<char> {
+    let c = s.chars().next()?;
+    c.to_uppercase().next()
+}
+
+fn owned(v: Option<Vec<String>>) -> Option<usize> {
+    let v = v?;
+    Some(v.into_iter().map(|s| s.len()).sum())
+}
+
+fn main() {
+    assert_eq!(parse("12"), Ok(12));
+    assert_eq!(parse("x"), Err(Error::Parse));
+    assert_eq!(parse("-3"), Err(Error::Negative(-3)));
+    assert_eq!(sum("1", "2"), Ok(3));
+    assert_eq!(sum("1", "-2"), Err(Error::Negative(-2)));
+    assert_eq!(first_char_upper("abc"), Some('A'));
+    assert_eq!(first_char_upper(""), None);
+    assert_eq!(owned(Some(vec!["ab".to_string(), "c".to_string()])), Some(3));
+    assert_eq!(owned(None), None);
+}
@@ -0,0 +1,92 @@
+// Check that `let` expressions whose pattern only matches a variant and binds its fields by value,
+// like the one of `while let Some(x) = iter.next()`, which are lowered to a switch on the
+// discriminant directly, bind, drop and fail like other `let`s.
+//@ run-pass
+
+#![feature(let_chains)]
+
+use std::cell::RefCell;
+
+enum Message {
+    Move(i32, i32),
+    Write(String),
+    Quit,
+}
+
+struct Noisy<'a>(&'a RefCell<Vec<u32>>, u32);
+
+impl Drop for Noisy<'_> {
+    fn drop(&mut self) {
+        self.0.borrow_mut().push(self.1);
+    }
+}
+
+fn drain(mut stack: Vec<u32>) -> u32 {
+    let mut total = 0;
+    while let Some(x) = stack.pop() {
+        total = total * 10 + x;
+    }
+    total
+}
+
+fn moves(messages: Vec<Message>) -> (i32, String) {
+    let mut iter = messages.into_iter();
+    let mut position = 0;
+    let mut text = String::new();
+    while let Some(message) = iter.next() {
+        if let Message::Move(x, _) = message {
+            position += x;
+        } else if let Message::Write(mut s) = message {
+            s.push(';');
+            text.push_str(&s);
+        } else if let Message::Quit = message {
+            break;
+        }
+    }
+    (position, text)
+}
+
+fn let_else(r: Result<u32, String>) -> u32 {
+    let Ok(n) = r else {
+        return 0;
+    };
+    n + 1
+}
+
+fn chained(a: Option<u32>, b: Result<u32, ()>) -> Option<u32> {
+    if let Some(x) = a
+        && let Ok(y) = b
+    {
+        Some(x + y)
+    } else {
+        None
+    }
+}
+
+fn main() {
+    assert_eq!(drain(vec![1, 2, 3]), 321);
+    assert_eq!(drain(vec![]), 0);
+
+    let messages = vec![
+        Message::Move(2, 0),
+        Message::Write("a".to_string()),
+        Message::Move(3, 1),
+        Message::Quit,
+        Message::Write("b".to_string()),
+    ];
+    assert_eq!(moves(messages), (5, "a;".to_string()));
+
+    assert_eq!(let_else(Ok(1)), 2);
+    assert_eq!(let_else(Err(String::new())), 0);
+
+    assert_eq!(chained(Some(1), Ok(2)), Some(3));
+    assert_eq!(chained(Some(1), Err(())), None);
+    assert_eq!(chained(None, Ok(2)), None);
+
+    let log = RefCell::new(Vec::new());
+    let mut items = vec![Noisy(&log, 1), Noisy(&log, 2)];
+    while let Some(item) = items.pop() {
+        log.borrow_mut().push(item.1 * 10);
+    }
+    assert_eq!(*log.borrow(), [20, 2, 10, 1]);
+}
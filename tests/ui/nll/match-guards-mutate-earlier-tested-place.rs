@@ -0,0 +1,40 @@
+// Check that a match guard may mutate the places that only the arms before it test, since they are
+// not tested again once the guard has run, and that the arms after it see the new values.
+//@ run-pass
+
+#![feature(if_let_guard)]
+
+fn field(mut x: (bool, u8)) -> (u8, (bool, u8)) {
+    let arm = match x {
+        (false, _) => 0,
+        (_, 0) if {
+            x.0 = false;
+            false
+        } => 1,
+        (_, 0) => 2,
+        _ => 3,
+    };
+    (arm, x)
+}
+
+fn variant(mut x: (Option<u8>, u8)) -> (u8, (Option<u8>, u8)) {
+    let arm = match x {
+        (Some(1), _) => 0,
+        (_, 2) if let Some(()) = {
+            x.0 = None;
+            None
+        } => 1,
+        (_, n) => n,
+    };
+    (arm, x)
+}
+
+fn main() {
+    assert_eq!(field((false, 0)), (0, (false, 0)));
+    assert_eq!(field((true, 0)), (2, (false, 0)));
+    assert_eq!(field((true, 1)), (3, (true, 1)));
+
+    assert_eq!(variant((Some(1), 2)), (0, (Some(1), 2)));
+    assert_eq!(variant((Some(5), 2)), (2, (None, 2)));
+    assert_eq!(variant((Some(5), 3)), (3, (Some(5), 3)));
+}
@@ -115,24 +115,24 @@ fn mutation_invalidates_pattern_in_if_let_guard(mut q: bool) {
     }
 }
 
-fn mutation_invalidates_previous_pattern_in_if_guard(mut r: bool) {
+fn ok_mutation_matching_previous_pattern_in_if_guard(mut r: bool) {
     match r {
-        // r matches a previous pattern by the end of the guard.
+        // OK, r matches a previous pattern by the end of the guard, but it isn't tested again.
         true => (),
         _ if {
-            r = true; //~ ERROR
+            r = true;
             true
         } => (),
         _ => (),
     }
 }
 
-fn mutation_invalidates_previous_pattern_in_if_let_guard(mut r: bool) {
+fn ok_mutation_matching_previous_pattern_in_if_let_guard(mut r: bool) {
     match r {
-        // r matches a previous pattern by the end of the guard.
+        // OK, r matches a previous pattern by the end of the guard, but it isn't tested again.
         true => (),
         _ if let Some(()) = {
-            r = true; //~ ERROR
+            r = true;
             Some(())
         } => (),
         _ => (),
@@ -0,0 +1,16 @@
+// Check that a type ascribed to an or-pattern constrains the bindings of each of its
+// alternatives, even though it is only ascribed once for all of them.
+
+type Pair<'a, T> = (&'a T, &'a T);
+
+fn uncoupled<'a>(x: Result<(&'static u32, &'a u32), (&'static u32, &'a u32)>) -> &'static u32 {
+    let (Ok((y, _z)) | Err((y, _z))): Result<(&u32, &u32), (&u32, &u32)> = x; // ok
+    y
+}
+
+fn coupled<'a>(x: Result<(&'static u32, &'a u32), (&'static u32, &'a u32)>) -> &'static u32 {
+    let (Ok((y, _z)) | Err((y, _z))): Result<Pair<u32>, Pair<u32>> = x;
+    y //~ ERROR lifetime may not live long enough
+}
+
+fn main() {}
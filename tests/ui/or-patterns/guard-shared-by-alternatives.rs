@@ -0,0 +1,97 @@
+// Check that or-patterns with a guard and enough alternatives, whose guard is lowered once for all
+// of them, run the guard for each matching alternative, bind the variables of the alternative that
+// matched, and continue with the right arm when the guard fails.
+//@ run-pass
+
+enum E {
+    A(String),
+    B(String),
+    C(String),
+    D(String),
+}
+
+fn by_value(e: E, len: usize, guard_count: &mut u32) -> String {
+    match e {
+        E::A(s) | E::B(s) | E::C(s)
+            if {
+                *guard_count += 1;
+                s.len() == len
+            } =>
+        {
+            s + "!"
+        }
+        E::B(s) => s + "?",
+        E::A(s) | E::C(s) | E::D(s) => s,
+    }
+}
+
+fn by_ref(e: &mut E) -> u32 {
+    match e {
+        E::A(s) | E::B(s) | E::C(s) if s.starts_with('x') => {
+            s.push('y');
+            1
+        }
+        E::C(_) => 2,
+        _ => 0,
+    }
+}
+
+fn nested(x: (Option<u8>, u8), guard_count: &mut u32) -> u32 {
+    match x {
+        (Some(a), 1 | 2 | 3) | (None, a)
+            if {
+                *guard_count += 1;
+                a > 10
+            } =>
+        {
+            a as u32
+        }
+        (Some(_), 2) => 1000,
+        (None, _) => 2000,
+        _ => 0,
+    }
+}
+
+fn overlapping(x: (u8, u8, u8), guard_count: &mut u32) -> u32 {
+    match x {
+        (1, _, _) | (_, 1, _) | (_, _, 1)
+            if {
+                *guard_count += 1;
+                false
+            } =>
+        {
+            1
+        }
+        _ => 0,
+    }
+}
+
+fn main() {
+    let mut guard_count = 0;
+    assert_eq!(by_value(E::A("a".to_string()), 1, &mut guard_count), "a!");
+    assert_eq!(by_value(E::B("bb".to_string()), 2, &mut guard_count), "bb!");
+    assert_eq!(by_value(E::B("bb".to_string()), 1, &mut guard_count), "bb?");
+    assert_eq!(by_value(E::C("c".to_string()), 0, &mut guard_count), "c");
+    assert_eq!(by_value(E::D("d".to_string()), 1, &mut guard_count), "d");
+    assert_eq!(guard_count, 4);
+
+    let mut e = E::C("x".to_string());
+    assert_eq!(by_ref(&mut e), 1);
+    assert!(matches!(&e, E::C(s) if s == "xy"));
+    assert_eq!(by_ref(&mut E::C("z".to_string())), 2);
+    assert_eq!(by_ref(&mut E::B("z".to_string())), 0);
+    assert_eq!(by_ref(&mut E::D("x".to_string())), 0);
+
+    let mut guard_count = 0;
+    assert_eq!(nested((Some(20), 3), &mut guard_count), 20);
+    assert_eq!(nested((Some(5), 2), &mut guard_count), 1000);
+    assert_eq!(nested((None, 30), &mut guard_count), 30);
+    assert_eq!(nested((None, 5), &mut guard_count), 2000);
+    assert_eq!(nested((Some(20), 4), &mut guard_count), 0);
+    assert_eq!(guard_count, 4);
+
+    // All alternatives match `(1, 1, 1)`, so the guard runs for each of them.
+    let mut guard_count = 0;
+    assert_eq!(overlapping((1, 1, 1), &mut guard_count), 0);
+    assert_eq!(guard_count, 3);
+}
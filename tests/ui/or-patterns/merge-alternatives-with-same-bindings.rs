@@ -0,0 +1,72 @@
+// Check that or-patterns followed by more patterns, whose alternatives bind the same variables
+// from the same places and are merged before testing the rest, bind the right values.
+//@ run-pass
+
+fn tuples(x: ((u8, u8), u8)) -> u32 {
+    match x {
+        ((a, 0) | (a, 1), 5) => a as u32,
+        ((a, 2 | 3), b) => a as u32 + b as u32 * 100,
+        _ => 0,
+    }
+}
+
+fn strings(x: (Result<String, String>, bool)) -> String {
+    match x {
+        (Ok(s) | Err(s), true) if s.len() > 1 => s + "!",
+        (Ok(s), false) => s,
+        _ => String::new(),
+    }
+}
+
+fn by_ref(x: &mut (Option<(u8, u32)>, u8)) -> u32 {
+    match x {
+        (Some((0, n) | (1, n)), 7) => {
+            *n += 1;
+            *n
+        }
+        _ => 0,
+    }
+}
+
+fn guarded(x: ((u8, u8), u8), guard_count: &mut u32) -> u32 {
+    match x {
+        ((a, 0) | (a, 1), 5)
+            if {
+                *guard_count += 1;
+                a > 10
+            } =>
+        {
+            a as u32
+        }
+        ((_, 1), _) => 1000,
+        _ => 0,
+    }
+}
+
+fn main() {
+    assert_eq!(tuples(((7, 0), 5)), 7);
+    assert_eq!(tuples(((8, 1), 5)), 8);
+    assert_eq!(tuples(((9, 1), 6)), 0);
+    assert_eq!(tuples(((9, 2), 6)), 609);
+    assert_eq!(tuples(((4, 3), 5)), 504);
+
+    assert_eq!(strings((Ok("ab".to_string()), true)), "ab!");
+    assert_eq!(strings((Err("cd".to_string()), true)), "cd!");
+    assert_eq!(strings((Ok("e".to_string()), true)), "");
+    assert_eq!(strings((Ok("f".to_string()), false)), "f");
+    assert_eq!(strings((Err("g".to_string()), false)), "");
+
+    let mut x = (Some((1, 41)), 7);
+    assert_eq!(by_ref(&mut x), 42);
+    assert_eq!(x, (Some((1, 42)), 7));
+    assert_eq!(by_ref(&mut (Some((2, 41)), 7)), 0);
+    assert_eq!(by_ref(&mut (Some((0, 41)), 6)), 0);
+
+    let mut guard_count = 0;
+    assert_eq!(guarded(((20, 0), 5), &mut guard_count), 20);
+    assert_eq!(guarded(((20, 1), 5), &mut guard_count), 20);
+    assert_eq!(guarded(((2, 1), 5), &mut guard_count), 1000);
+    assert_eq!(guarded(((2, 0), 5), &mut guard_count), 0);
+    assert_eq!(guarded(((20, 1), 6), &mut guard_count), 1000);
+    assert_eq!(guard_count, 4);
+}
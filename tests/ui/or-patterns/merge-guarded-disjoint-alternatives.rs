@@ -0,0 +1,95 @@
+// Check that or-patterns with a guard whose alternatives can't match the same value, whose
+// alternatives are merged before the guard, run the guard once and continue with the right arm
+// when it fails.
+//@ run-pass
+
+#[derive(Clone, Copy)]
+enum E {
+    A,
+    B,
+    C,
+}
+
+fn ints(x: u32, cond: bool, guard_count: &mut u32) -> u32 {
+    match x {
+        1 | 5 | 9 | 100..=200
+            if {
+                *guard_count += 1;
+                cond
+            } =>
+        {
+            1
+        }
+        5 => 2,
+        150 => 3,
+        _ => 0,
+    }
+}
+
+fn enums(x: Option<E>, cond: bool, guard_count: &mut u32) -> u32 {
+    match x {
+        Some(E::A | E::B)
+            if {
+                *guard_count += 1;
+                cond
+            } =>
+        {
+            1
+        }
+        Some(E::A) => 2,
+        Some(_) => 3,
+        None => 0,
+    }
+}
+
+fn nested(x: (Option<E>, u8), cond: bool) -> u32 {
+    match x {
+        (Some(E::A | E::C), 1 | 2) if cond => 1,
+        (Some(E::C), 2) => 2,
+        (Some(_), _) => 3,
+        (None, _) => 0,
+    }
+}
+
+fn overlapping(x: (u8, u8), guard_count: &mut u32) -> u32 {
+    match x {
+        (1, _) | (_, 1)
+            if {
+                *guard_count += 1;
+                false
+            } =>
+        {
+            1
+        }
+        _ => 0,
+    }
+}
+
+fn main() {
+    let mut guard_count = 0;
+    assert_eq!(ints(5, true, &mut guard_count), 1);
+    assert_eq!(ints(5, false, &mut guard_count), 2);
+    assert_eq!(ints(150, false, &mut guard_count), 3);
+    assert_eq!(ints(9, false, &mut guard_count), 0);
+    assert_eq!(ints(7, true, &mut guard_count), 0);
+    assert_eq!(guard_count, 4);
+
+    let mut guard_count = 0;
+    assert_eq!(enums(Some(E::A), true, &mut guard_count), 1);
+    assert_eq!(enums(Some(E::A), false, &mut guard_count), 2);
+    assert_eq!(enums(Some(E::B), false, &mut guard_count), 3);
+    assert_eq!(enums(Some(E::C), true, &mut guard_count), 3);
+    assert_eq!(enums(None, true, &mut guard_count), 0);
+    assert_eq!(guard_count, 3);
+
+    assert_eq!(nested((Some(E::C), 2), true), 1);
+    assert_eq!(nested((Some(E::C), 2), false), 2);
+    assert_eq!(nested((Some(E::A), 1), false), 3);
+    assert_eq!(nested((Some(E::B), 1), true), 3);
+    assert_eq!(nested((None, 1), true), 0);
+
+    // Both alternatives match `(1, 1)`, so the guard runs for each of them.
+    let mut guard_count = 0;
+    assert_eq!(overlapping((1, 1), &mut guard_count), 0);
+    assert_eq!(guard_count, 2);
+}
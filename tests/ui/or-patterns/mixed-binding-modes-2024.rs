@@ -0,0 +1,40 @@
+// Check that since Rust 2024, the alternatives of an or-pattern may bind a variable by value in
+// some and by reference in others, as long as its type is the same in all of them, and that each
+// alternative binds it in its own way, also in guards.
+
+//@ run-pass
+//@ edition: 2024
+//@ compile-flags: -Zunstable-options
+
+fn first(r: &Result<u8, &u8>) -> u8 {
+    match *r {
+        Ok(ref x) | Err(x) => *x,
+    }
+}
+
+fn guarded(r: Result<String, &String>, min: usize) -> usize {
+    match r {
+        Ok(ref s) | Err(s) if s.len() >= min => s.len(),
+        Ok(s) => s.len() + 100,
+        Err(_) => 0,
+    }
+}
+
+fn nested(x: (Option<u8>, &u8)) -> u8 {
+    let ((Some(ref n), _) | (None, n)) = x;
+    *n
+}
+
+fn main() {
+    assert_eq!(first(&Ok(1)), 1);
+    assert_eq!(first(&Err(&2)), 2);
+
+    let long = String::from("four");
+    assert_eq!(guarded(Ok(String::from("abc")), 2), 3);
+    assert_eq!(guarded(Ok(String::from("abc")), 5), 103);
+    assert_eq!(guarded(Err(&long), 3), 4);
+    assert_eq!(guarded(Err(&long), 5), 0);
+
+    assert_eq!(nested((Some(3), &4)), 3);
+    assert_eq!(nested((None, &4)), 4);
+}
@@ -0,0 +1,57 @@
+// Check that the patterns following an or-pattern whose alternatives bind different places, which
+// are tested once for all alternatives, bind the variables of the alternatives that matched.
+//@ run-pass
+
+fn bindings(x: (Result<u8, u8>, u8, Result<u8, u8>)) -> u32 {
+    match x {
+        (Ok(a) | Err(a), 1 | 2, Ok(b) | Err(b)) => a as u32 * 100 + b as u32,
+        (Ok(a), _, _) => a as u32,
+        _ => 0,
+    }
+}
+
+fn nested(x: (Option<(u8, u8)>, Option<(u8, u8)>)) -> u32 {
+    match x {
+        (Some((a, 0) | (0, a)), Some((b, 1) | (1, b))) => a as u32 * 100 + b as u32,
+        (None, Some((b, _))) | (Some((_, b)), None) => 1000 + b as u32,
+        _ => 0,
+    }
+}
+
+fn strings(x: (Result<String, String>, bool, Result<String, String>)) -> String {
+    match x {
+        (Ok(a) | Err(a), true, Ok(b) | Err(b)) => a + &b,
+        (Ok(a), false, _) => a,
+        _ => String::new(),
+    }
+}
+
+fn irrefutable(x: (Result<String, String>, Result<String, String>)) -> String {
+    let (Ok(a) | Err(a), Ok(b) | Err(b)) = x;
+    a + &b
+}
+
+fn main() {
+    assert_eq!(bindings((Ok(1), 1, Ok(2))), 102);
+    assert_eq!(bindings((Err(3), 2, Ok(4))), 304);
+    assert_eq!(bindings((Ok(5), 2, Err(6))), 506);
+    assert_eq!(bindings((Err(7), 1, Err(8))), 708);
+    assert_eq!(bindings((Ok(9), 3, Err(8))), 9);
+    assert_eq!(bindings((Err(9), 3, Err(8))), 0);
+
+    assert_eq!(nested((Some((5, 0)), Some((6, 1)))), 506);
+    assert_eq!(nested((Some((0, 5)), Some((1, 6)))), 506);
+    assert_eq!(nested((Some((0, 0)), Some((1, 1)))), 1);
+    assert_eq!(nested((Some((5, 0)), Some((6, 2)))), 0);
+    assert_eq!(nested((None, Some((6, 2)))), 1006);
+    assert_eq!(nested((Some((4, 7)), None)), 1007);
+
+    let s = |s: &str| s.to_string();
+    assert_eq!(strings((Ok(s("a")), true, Err(s("b")))), "ab");
+    assert_eq!(strings((Err(s("c")), true, Ok(s("d")))), "cd");
+    assert_eq!(strings((Ok(s("e")), false, Ok(s("f")))), "e");
+    assert_eq!(strings((Err(s("g")), false, Ok(s("h")))), "");
+
+    assert_eq!(irrefutable((Ok(s("i")), Err(s("j")))), "ij");
+    assert_eq!(irrefutable((Err(s("k")), Ok(s("l")))), "kl");
+}
@@ -0,0 +1,56 @@
+//@ run-pass
+// Test that the deref patterns of the same place share one call to `deref()` per path through the
+// decision tree, across alternatives of or-patterns and across arms.
+#![feature(deref_patterns, deref_pure_trait)]
+#![allow(incomplete_features)]
+
+use std::cell::Cell;
+use std::ops::{Deref, DerefPure};
+
+struct Counted<'a, T> {
+    value: T,
+    derefs: &'a Cell<u32>,
+}
+
+impl<T> Deref for Counted<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.derefs.set(self.derefs.get() + 1);
+        &self.value
+    }
+}
+
+unsafe impl<T> DerefPure for Counted<'_, T> {}
+
+fn or_pattern(x: Counted<'_, u32>) -> u32 {
+    match x {
+        deref!(1) | deref!(2) | deref!(3) => 1,
+        _ => 0,
+    }
+}
+
+fn arms(x: Counted<'_, Option<u32>>) -> u32 {
+    match x {
+        deref!(Some(0)) => 1,
+        deref!(Some(n)) if n > 10 => 2,
+        deref!(Some(n)) => n,
+        deref!(None) => 3,
+        _ => unreachable!(),
+    }
+}
+
+fn count(f: impl FnOnce(&Cell<u32>) -> u32) -> (u32, u32) {
+    let derefs = Cell::new(0);
+    let result = f(&derefs);
+    (result, derefs.get())
+}
+
+fn main() {
+    assert_eq!(count(|derefs| or_pattern(Counted { value: 3, derefs })), (1, 1));
+    assert_eq!(count(|derefs| or_pattern(Counted { value: 4, derefs })), (0, 1));
+
+    assert_eq!(count(|derefs| arms(Counted { value: Some(0), derefs })), (1, 1));
+    assert_eq!(count(|derefs| arms(Counted { value: Some(20), derefs })), (2, 1));
+    assert_eq!(count(|derefs| arms(Counted { value: Some(5), derefs })), (5, 1));
+    assert_eq!(count(|derefs| arms(Counted { value: None, derefs })), (3, 1));
+}
@@ -0,0 +1,59 @@
+//@ run-pass
+// Check that refutable deref patterns work in `let ... else`, and that the scrutinee is dropped
+// whether the pattern matches or not.
+#![feature(deref_patterns)]
+#![allow(incomplete_features)]
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+fn first(b: Box<Option<u32>>) -> u32 {
+    let deref!(Some(x)) = b else { return 0 };
+    x + 1
+}
+
+fn nested(v: Vec<Vec<u32>>) -> u32 {
+    let deref!([deref!([x]), deref!([_, y])]) = v else { return 0 };
+    x + y
+}
+
+fn by_ref(rc: &Rc<Option<String>>) -> usize {
+    let deref!(Some(ref s)) = *rc else { return 0 };
+    s.len()
+}
+
+struct Noisy<'a>(&'a Cell<u32>, Option<u32>);
+
+impl Drop for Noisy<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+fn drops(dropped: &Cell<u32>, value: Option<u32>) -> u32 {
+    let b = Box::new(Noisy(dropped, value));
+    let deref!(Noisy(_, Some(x))) = b else {
+        assert_eq!(dropped.get(), 0);
+        return 0;
+    };
+    assert_eq!(dropped.get(), 0);
+    x
+}
+
+fn main() {
+    assert_eq!(first(Box::new(Some(4))), 5);
+    assert_eq!(first(Box::new(None)), 0);
+
+    assert_eq!(nested(vec![vec![1], vec![2, 3]]), 4);
+    assert_eq!(nested(vec![vec![1], vec![2]]), 0);
+    assert_eq!(nested(vec![]), 0);
+
+    assert_eq!(by_ref(&Rc::new(Some("abc".to_string()))), 3);
+    assert_eq!(by_ref(&Rc::new(None)), 0);
+
+    let dropped = Cell::new(0);
+    assert_eq!(drops(&dropped, Some(7)), 7);
+    assert_eq!(dropped.get(), 1);
+    assert_eq!(drops(&dropped, None), 0);
+    assert_eq!(dropped.get(), 2);
+}
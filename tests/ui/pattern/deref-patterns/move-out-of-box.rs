@@ -0,0 +1,76 @@
+//@ run-pass
+// Test that by-value bindings of deref patterns on an owned `Box` move out of the box itself, like
+// `box` patterns, and that the box is then freed without dropping the moved-out parts again.
+#![feature(deref_patterns)]
+#![allow(incomplete_features)]
+
+use std::cell::RefCell;
+
+thread_local! {
+    static LOG: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+struct Noisy(&'static str);
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        LOG.with(|log| log.borrow_mut().push(self.0));
+    }
+}
+
+fn take_log() -> Vec<&'static str> {
+    LOG.with(|log| log.take())
+}
+
+fn whole(b: Box<Noisy>) -> Noisy {
+    match b {
+        deref!(x) => x,
+    }
+}
+
+fn partial(b: Box<(Noisy, Noisy)>) -> Noisy {
+    match b {
+        deref!((x, _)) => x,
+    }
+}
+
+fn nested(b: Box<Option<Box<Noisy>>>) -> Option<Noisy> {
+    match b {
+        deref!(Some(deref!(x))) => Some(x),
+        deref!(None) => None,
+    }
+}
+
+fn by_ref_then_move(b: Box<(u32, Noisy)>) -> Noisy {
+    match b {
+        deref!((0, ref x)) => Noisy(x.0),
+        deref!((_, x)) => x,
+    }
+}
+
+fn main() {
+    let x = whole(Box::new(Noisy("whole")));
+    assert_eq!(take_log(), [] as [&str; 0]);
+    drop(x);
+    assert_eq!(take_log(), ["whole"]);
+
+    let x = partial(Box::new((Noisy("kept"), Noisy("dropped"))));
+    assert_eq!(take_log(), ["dropped"]);
+    drop(x);
+    assert_eq!(take_log(), ["kept"]);
+
+    let x = nested(Box::new(Some(Box::new(Noisy("nested")))));
+    assert_eq!(take_log(), [] as [&str; 0]);
+    drop(x);
+    assert_eq!(take_log(), ["nested"]);
+    assert!(nested(Box::new(None)).is_none());
+
+    let x = by_ref_then_move(Box::new((1, Noisy("moved"))));
+    assert_eq!(take_log(), [] as [&str; 0]);
+    drop(x);
+    assert_eq!(take_log(), ["moved"]);
+    let x = by_ref_then_move(Box::new((0, Noisy("borrowed"))));
+    assert_eq!(take_log(), ["borrowed"]);
+    drop(x);
+    assert_eq!(take_log(), ["borrowed"]);
+}
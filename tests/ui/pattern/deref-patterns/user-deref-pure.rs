@@ -0,0 +1,109 @@
+//@ run-pass
+// Test the execution of deref patterns through a user type that implements `DerefPure`, and that
+// the scrutinee is only dropped after the arm that matched through it.
+#![feature(deref_patterns, deref_pure_trait)]
+#![allow(incomplete_features)]
+
+use std::cell::RefCell;
+use std::ops::{Deref, DerefMut, DerefPure};
+
+thread_local! {
+    static LOG: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn log(event: &'static str) {
+    LOG.with(|log| log.borrow_mut().push(event));
+}
+
+struct MyBox<T>(Box<T>);
+
+impl<T> Deref for MyBox<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for MyBox<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+unsafe impl<T> DerefPure for MyBox<T> {}
+
+impl<T> Drop for MyBox<T> {
+    fn drop(&mut self) {
+        log("drop");
+    }
+}
+
+enum Shape {
+    Circle { r: u32 },
+    Rect { w: u32, h: u32 },
+    Empty,
+}
+
+fn area(shape: MyBox<Shape>) -> u32 {
+    match shape {
+        deref!(Shape::Circle { r }) => {
+            log("circle");
+            3 * r * r
+        }
+        deref!(Shape::Rect { w, h }) if w == h => {
+            log("square");
+            w * w
+        }
+        deref!(Shape::Rect { w, h }) => {
+            log("rect");
+            w * h
+        }
+        deref!(Shape::Empty) => 0,
+        _ => unreachable!(),
+    }
+}
+
+fn grow(mut shape: MyBox<Shape>) -> MyBox<Shape> {
+    match shape {
+        deref!(Shape::Circle { ref mut r }) => *r += 1,
+        deref!(Shape::Rect { ref mut w, .. }) => *w += 1,
+        _ => {}
+    }
+    shape
+}
+
+fn nested(x: MyBox<Option<MyBox<(u32, u32)>>>) -> u32 {
+    match x {
+        deref!(Some(deref!((0, y)))) => y,
+        deref!(Some(deref!((x, _)))) => x + 100,
+        deref!(None) => 1000,
+        _ => unreachable!(),
+    }
+}
+
+fn take_log() -> Vec<&'static str> {
+    LOG.with(|log| log.take())
+}
+
+fn main() {
+    assert_eq!(area(MyBox(Box::new(Shape::Circle { r: 2 }))), 12);
+    assert_eq!(take_log(), ["circle", "drop"]);
+    assert_eq!(area(MyBox(Box::new(Shape::Rect { w: 3, h: 3 })))), 9);
+    assert_eq!(take_log(), ["square", "drop"]);
+    assert_eq!(area(MyBox(Box::new(Shape::Rect { w: 2, h: 5 })))), 10);
+    assert_eq!(take_log(), ["rect", "drop"]);
+    assert_eq!(area(MyBox(Box::new(Shape::Empty))), 0);
+    assert_eq!(take_log(), ["drop"]);
+
+    let shape = grow(grow(MyBox(Box::new(Shape::Rect { w: 1, h: 4 }))));
+    assert_eq!(take_log(), [] as [&str; 0]);
+    assert_eq!(area(shape), 12);
+    assert_eq!(take_log(), ["rect", "drop"]);
+
+    assert_eq!(nested(MyBox(Box::new(Some(MyBox(Box::new((0, 7))))))), 7);
+    assert_eq!(take_log(), ["drop", "drop"]);
+    assert_eq!(nested(MyBox(Box::new(Some(MyBox(Box::new((5, 7))))))), 105);
+    assert_eq!(take_log(), ["drop", "drop"]);
+    assert_eq!(nested(MyBox(Box::new(None))), 1000);
+    assert_eq!(take_log(), ["drop"]);
+}
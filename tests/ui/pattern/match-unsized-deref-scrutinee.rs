@@ -0,0 +1,44 @@
+//@ run-pass
+// Check that matching on the unsized place behind a reference doesn't need a temporary, so that
+// the arms can bind parts of it by reference, and mutate them through a mutable reference.
+
+fn classify(s: &str) -> u32 {
+    match *s {
+        "" => 0,
+        "one" => 1,
+        ref other => other.len() as u32 + 10,
+    }
+}
+
+fn rotate_first(v: &mut [u32]) {
+    match *v {
+        [] | [_] => {}
+        [ref mut first, ref mut rest @ ..] => {
+            for x in rest.iter_mut() {
+                std::mem::swap(first, x);
+            }
+        }
+    }
+}
+
+fn ends<'a>(v: &'a [String]) -> Option<(&'a str, &'a [String], &'a str)> {
+    match *v {
+        [ref first, ref middle @ .., ref last] => Some((first.as_str(), middle, last.as_str())),
+        _ => None,
+    }
+}
+
+fn main() {
+    assert_eq!(classify(""), 0);
+    assert_eq!(classify("one"), 1);
+    assert_eq!(classify("three"), 15);
+
+    let mut v = [1, 2, 3, 4];
+    rotate_first(&mut v);
+    assert_eq!(v, [4, 1, 2, 3]);
+
+    let strings = ["a".to_string(), "b".to_string(), "c".to_string()];
+    let (first, middle, last) = ends(&strings).unwrap();
+    assert_eq!((first, middle.len(), last), ("a", 1, "c"));
+    assert!(ends(&strings[..1]).is_none());
+}
@@ -0,0 +1,44 @@
+//@ check-pass
+// Check that never patterns in `let` statements and function parameters lower to an `Unreachable`
+// terminator, like never arms of a `match`: borrowck accepts uses of uninitialized or moved-out
+// variables after them, which are never reached.
+#![feature(never_patterns)]
+#![allow(incomplete_features)]
+#![allow(unreachable_code)]
+
+enum Void {}
+
+fn never_let(x: Void) -> String {
+    let s: String;
+    let ! = x;
+    s
+}
+
+fn never_let_after_move(x: Void, v: Vec<u8>) -> Vec<u8> {
+    drop(v);
+    let ! = x;
+    v
+}
+
+fn never_ref_let(x: &Void) -> String {
+    let s: String;
+    let &! = x;
+    s
+}
+
+fn never_param(!: Void) -> String {
+    let s: String;
+    s
+}
+
+fn never_tuple_param((_, !): (u8, Void)) -> String {
+    let s: String;
+    s
+}
+
+fn never_ref_param(&!: &Void) -> String {
+    let s: String;
+    s
+}
+
+fn main() {}
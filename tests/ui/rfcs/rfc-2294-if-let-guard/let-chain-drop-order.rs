@@ -0,0 +1,86 @@
+// Check the bindings and the drop order of temporaries of let chains in match guards, including
+// for or-patterns with several alternatives. The temporaries of the chain live until the end of
+// the arm, or are dropped before trying the next arms when a link of the chain fails.
+
+//@ run-pass
+
+#![feature(if_let_guard, let_chains)]
+#![allow(irrefutable_let_patterns)]
+
+use std::sync::Mutex;
+
+static A: Mutex<Vec<i32>> = Mutex::new(Vec::new());
+
+struct D(i32);
+
+fn make_d(x: i32) -> D {
+    A.lock().unwrap().push(x);
+    D(x)
+}
+
+impl Drop for D {
+    fn drop(&mut self) {
+        A.lock().unwrap().push(!self.0);
+    }
+}
+
+enum E {
+    A(i32),
+    B(i32),
+    C(i32),
+    D,
+}
+
+fn chain(x: Option<i32>, c: i32) -> i32 {
+    let _d = make_d(1);
+    match x {
+        Some(n) if let D(a) = make_d(n) && a > c && let D(b) = make_d(a + 10) => {
+            make_d(a + b);
+            a + b
+        }
+        Some(n) => n,
+        None => 0,
+    }
+}
+
+fn or_chain(e: E, c: i32) -> i32 {
+    let _d = make_d(1);
+    match e {
+        E::A(n) | E::B(n) | E::C(n)
+            if let D(a) = make_d(n)
+                && a > c
+                && let Some(b) = a.checked_mul(10) =>
+        {
+            a + b
+        }
+        E::B(n) => -n,
+        _ => 0,
+    }
+}
+
+fn main() {
+    assert_eq!(chain(Some(2), 0), 14);
+    assert_eq!(chain(Some(2), 5), 2);
+    assert_eq!(chain(None, 0), 0);
+    let expected = [
+        1, 2, 12, 14, !14, !12, !2, !1,
+        1, 2, !2, !1,
+        1, !1,
+    ];
+    assert_eq!(*A.lock().unwrap(), expected);
+    A.lock().unwrap().clear();
+
+    assert_eq!(or_chain(E::B(3), 0), 33);
+    assert_eq!(or_chain(E::B(3), 5), -3);
+    assert_eq!(or_chain(E::C(4), 5), 0);
+    assert_eq!(or_chain(E::A(6), 5), 66);
+    assert_eq!(or_chain(E::D, 0), 0);
+    let expected = [
+        1, 3, !3, !1,
+        1, 3, !3, !1,
+        1, 4, !4, !1,
+        1, 6, !6, !1,
+        1, !1,
+    ];
+    assert_eq!(*A.lock().unwrap(), expected);
+}
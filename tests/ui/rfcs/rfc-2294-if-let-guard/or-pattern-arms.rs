@@ -0,0 +1,75 @@
+// Check that the `if let` guards of or-pattern arms bind their variables once whichever
+// alternative matched, that the arm body sees them, and that a failing guard drops its
+// temporaries before trying the next arms.
+
+//@ run-pass
+
+#![feature(if_let_guard, let_chains)]
+
+use std::sync::Mutex;
+
+static A: Mutex<Vec<i32>> = Mutex::new(Vec::new());
+
+struct D(i32);
+
+fn make_d(x: i32) -> D {
+    A.lock().unwrap().push(x);
+    D(x)
+}
+
+impl Drop for D {
+    fn drop(&mut self) {
+        A.lock().unwrap().push(!self.0);
+    }
+}
+
+fn two(x: Result<i32, i32>) -> i32 {
+    match x {
+        Ok(n) | Err(n) if let Some(m) = n.checked_sub(1) => m,
+        Ok(_) => -1,
+        Err(_) => -2,
+    }
+}
+
+fn by_ref(x: &mut (Option<i32>, Option<i32>)) -> i32 {
+    match x {
+        (Some(n), None) | (None, Some(n)) if let Some(m) = n.checked_add(1) => {
+            *n = m;
+            m
+        }
+        _ => 0,
+    }
+}
+
+fn drops(x: (i32, i32), c: i32) -> i32 {
+    let _d = make_d(1);
+    match x {
+        (n, 0) | (0, n) if let D(a) = make_d(n) && a > c => a,
+        (n, _) => -n,
+    }
+}
+
+fn main() {
+    assert_eq!(two(Ok(3)), 2);
+    assert_eq!(two(Err(4)), 3);
+    assert_eq!(two(Ok(i32::MIN)), -1);
+    assert_eq!(two(Err(i32::MIN)), -2);
+
+    let mut x = (None, Some(5));
+    assert_eq!(by_ref(&mut x), 6);
+    assert_eq!(x, (None, Some(6)));
+    assert_eq!(by_ref(&mut (Some(i32::MAX), None)), 0);
+    assert_eq!(by_ref(&mut (Some(1), Some(2))), 0);
+
+    assert_eq!(drops((2, 0), 0), 2);
+    assert_eq!(drops((0, 3), 0), 3);
+    assert_eq!(drops((4, 0), 5), -4);
+    assert_eq!(drops((6, 7), 0), -6);
+    let expected = [
+        1, 2, !2, !1,
+        1, 3, !3, !1,
+        1, 4, !4, !1,
+        1, !1,
+    ];
+    assert_eq!(*A.lock().unwrap(), expected);
+}
@@ -0,0 +1,67 @@
+// Check that or-patterns in the `let` of an `if let` guard bind the variables of the alternative
+// that matched, whether or not the pattern of the arm is an or-pattern too, and that the arms after
+// the guard are tried when none of the alternatives matches.
+
+//@ run-pass
+
+#![feature(if_let_guard, let_chains)]
+
+fn g(n: i32) -> Result<i32, i32> {
+    if n >= 0 { Ok(n) } else { Err(-n) }
+}
+
+fn simple(x: Option<i32>) -> i32 {
+    match x {
+        Some(n) if let Ok(m) | Err(m) = g(n) && m > 1 => m,
+        Some(_) => -1,
+        None => 0,
+    }
+}
+
+fn both(x: Result<i32, i32>) -> i32 {
+    match x {
+        Ok(n) | Err(n) if let Ok(m) | Err(m) = g(n) && m % 2 == 0 => m,
+        Ok(n) => n + 100,
+        Err(n) => n + 200,
+    }
+}
+
+fn nested(x: (i32, i32)) -> i32 {
+    match x {
+        (a, b) if let (Ok(m) | Err(m), 0) | (Ok(0), m) = (g(a), b) => m,
+        (a, b) => a + b + 100,
+    }
+}
+
+fn by_value(x: Option<String>) -> String {
+    match x {
+        Some(s)
+            if let (Some(t), _) | (None, t) =
+                (s.strip_prefix("a").map(str::to_owned), s.clone()) =>
+        {
+            t
+        }
+        Some(_) => unreachable!(),
+        None => String::new(),
+    }
+}
+
+fn main() {
+    assert_eq!(simple(Some(5)), 5);
+    assert_eq!(simple(Some(-3)), 3);
+    assert_eq!(simple(Some(1)), -1);
+    assert_eq!(simple(None), 0);
+
+    assert_eq!(both(Ok(4)), 4);
+    assert_eq!(both(Err(-6)), 6);
+    assert_eq!(both(Ok(3)), 103);
+    assert_eq!(both(Err(5)), 205);
+
+    assert_eq!(nested((-4, 0)), 4);
+    assert_eq!(nested((0, 7)), 7);
+    assert_eq!(nested((2, 3)), 105);
+
+    assert_eq!(by_value(Some("abc".to_string())), "bc");
+    assert_eq!(by_value(Some("xyz".to_string())), "xyz");
+    assert_eq!(by_value(None), "");
+}